@@ -642,6 +642,7 @@ bar() -> ?FOO.
                                     1,
                                 ),
                             ),
+                            otp_release: None,
                             app_roots: AppRoots {
                                 otp: Some(
                                     AppRoots {
@@ -693,6 +694,7 @@ bar() -> ?FOO.
                                     1,
                                 ),
                             ),
+                            otp_release: None,
                             app_roots: AppRoots {
                                 otp: None,
                                 app_map: {
@@ -825,6 +827,7 @@ foo() -> ?BAR.
                                     1,
                                 ),
                             ),
+                            otp_release: None,
                             app_roots: AppRoots {
                                 otp: None,
                                 app_map: {
@@ -856,6 +859,7 @@ foo() -> ?BAR.
                                     1,
                                 ),
                             ),
+                            otp_release: None,
                             app_roots: AppRoots {
                                 otp: None,
                                 app_map: {},
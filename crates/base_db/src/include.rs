@@ -246,19 +246,23 @@ pub fn generated_file_include_lib(
     // We need to do the reverse here.
 
     let inc_app_data = db.file_app_data(included_file_id)?;
-    let candidate_path = inc_app_data
+    let include_path = include_path.as_path()?;
+    // Prefer the conventional `include/` directory of the app, since the
+    // other include dirs can overlap it, e.g. when the app root is one.
+    let app_include_dir = inc_app_data.dir.join("include");
+    let candidate_paths = std::iter::once(&app_include_dir)
         // Note: we use include_dirs here, as it keeps the include dirs local to the app only.
         // include_path is the search path including all dependency include_dir`s
-        .include_dirs
-        .iter()
-        .find_map(|dir| include_path.as_path()?.strip_prefix(dir))?;
-    let candidate = format!("{}/include/{}", inc_app_data.name, candidate_path.as_str());
-    let resolved_file_id =
-        IncludeCtx::new(db, Some(file_id), file_id).resolve_include_lib(&candidate)?;
-    if resolved_file_id == included_file_id {
-        // We have an equivalent include
-        Some(candidate)
-    } else {
-        None
+        .chain(inc_app_data.include_dirs.iter())
+        .filter_map(|dir| include_path.strip_prefix(dir));
+    for candidate_path in candidate_paths {
+        let candidate = format!("{}/include/{}", inc_app_data.name, candidate_path.as_str());
+        let resolved_file_id =
+            IncludeCtx::new(db, Some(file_id), file_id).resolve_include_lib(&candidate);
+        if resolved_file_id == Some(included_file_id) {
+            // We have an equivalent include
+            return Some(candidate);
+        }
     }
+    None
 }
@@ -114,6 +114,8 @@ pub struct ProjectData {
     pub root_dir: AbsPathBuf,
     pub deps_ebins: Vec<AbsPathBuf>,
     pub otp_project_id: Option<ProjectId>,
+    /// The release of the OTP used by the project, e.g. `"27"`, if known
+    pub otp_release: Option<String>,
     pub app_roots: AppRoots,
     pub eqwalizer_config: EqwalizerConfig,
     pub include_mapping: Option<Arc<IncludeMapping>>,
@@ -427,6 +429,7 @@ impl<'a> ProjectApps<'a> {
                 root_dir: project.root().into_owned(),
                 deps_ebins: project.deps_ebins(),
                 otp_project_id: self.otp_project_id,
+                otp_release: project.otp.release(),
                 app_roots,
                 eqwalizer_config: project.eqwalizer_config.clone(),
                 include_mapping: project.include_mapping.clone(),
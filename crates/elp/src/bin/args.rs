@@ -261,15 +261,15 @@ pub struct Lint {
     /// Do not print the full diagnostics for a file, just the count
     #[bpaf(external(parse_print_diags))]
     pub print_diags: bool,
-    /// Show diagnostics in JSON format
+    /// Show diagnostics in JSON format, either json or ndjson (one stable object per diagnostic)
     #[bpaf(
         argument("FORMAT"),
-        complete(format_completer),
+        complete(lint_format_completer),
         fallback(None),
-        guard(format_guard, "Please use json")
+        guard(lint_format_guard, "Please use json or ndjson")
     )]
     pub format: Option<String>,
-    /// Optional prefix to prepend to each diagnostic file path. Only used when --format=json is set
+    /// Optional prefix to prepend to each diagnostic file path. Only used when --format=json or --format=ndjson is set
     pub prefix: Option<String>,
 
     /// Include diagnostics produced by erlc
@@ -322,6 +322,12 @@ pub struct Lint {
     /// Apply to all matching diagnostic occurrences at once, rather
     /// than one at a time.
     pub one_shot: bool,
+    /// If applying fixes, keep recomputing the filtered diagnostics
+    /// and applying their fixes until none are left.
+    pub fixpoint: bool,
+    /// Maximum number of modules to lint in parallel (defaults to the number of CPUs)
+    #[bpaf(argument("JOBS"))]
+    pub jobs: Option<usize>,
 
     /// Rest of args are space separated list of apps to ignore
     #[bpaf(positional("IGNORED_APPS"))]
@@ -638,6 +644,18 @@ fn format_guard(format: &Option<String>) -> bool {
     }
 }
 
+fn lint_format_completer(_: &Option<String>) -> Vec<(String, Option<String>)> {
+    vec![("json".to_string(), None), ("ndjson".to_string(), None)]
+}
+
+fn lint_format_guard(format: &Option<String>) -> bool {
+    match format {
+        None => true,
+        Some(f) if f == "json" || f == "ndjson" => true,
+        _ => false,
+    }
+}
+
 #[allow(clippy::ptr_arg)] // This is needed in the BPAF macros
 fn at_least_1(data: &Vec<String>) -> bool {
     !data.is_empty()
@@ -718,6 +736,10 @@ impl Lint {
     pub fn is_format_json(&self) -> bool {
         self.format == Some("json".to_string())
     }
+
+    pub fn is_format_ndjson(&self) -> bool {
+        self.format == Some("ndjson".to_string())
+    }
 }
 
 impl ParseAllElp {
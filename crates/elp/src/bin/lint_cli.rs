@@ -129,26 +129,37 @@ fn do_parse_all(
     let pb = cli.progress(module_iter.len() as u64, "Parsing modules");
     let app_name = args.app.as_ref().map(|name| AppName(name.to_string()));
 
-    Ok(module_iter
-        .par_bridge()
-        .progress_with(pb)
-        .map_with(
-            analysis.clone(),
-            |db, (module_name, _file_source, file_id)| {
-                if !otp_file_to_ignore(db, file_id)
-                    && db.file_app_type(file_id).ok() != Some(Some(AppType::Dep))
-                    && !ignored_apps.contains(&db.file_app_name(file_id).ok())
-                    && (app_name.is_none()
-                        || db.file_app_name(file_id).ok().as_ref() == Some(&app_name))
-                {
-                    do_parse_one(db, config, file_id, module_name.as_str(), args).unwrap()
-                } else {
-                    None
-                }
-            },
-        )
-        .flatten()
-        .collect())
+    // Each module is linted against its own snapshot of the database, so
+    // they can be processed independently. A `num_threads` of 0 lets rayon
+    // pick the number of CPUs.
+    let pool = rayon::ThreadPoolBuilder::new()
+        .num_threads(args.jobs.unwrap_or(0))
+        .build()?;
+    let mut res: Vec<(String, FileId, DiagnosticCollection)> = pool.install(|| {
+        module_iter
+            .par_bridge()
+            .progress_with(pb)
+            .map_with(
+                analysis.clone(),
+                |db, (module_name, _file_source, file_id)| {
+                    if !otp_file_to_ignore(db, file_id)
+                        && db.file_app_type(file_id).ok() != Some(Some(AppType::Dep))
+                        && !ignored_apps.contains(&db.file_app_name(file_id).ok())
+                        && (app_name.is_none()
+                            || db.file_app_name(file_id).ok().as_ref() == Some(&app_name))
+                    {
+                        do_parse_one(db, config, file_id, module_name.as_str(), args).unwrap()
+                    } else {
+                        None
+                    }
+                },
+            )
+            .flatten()
+            .collect()
+    });
+    // The modules complete in any order, sort them so the output is stable
+    res.sort_by(|(a, a_id, _), (b, b_id, _)| (a, a_id).cmp(&(b, b_id)));
+    Ok(res)
 }
 
 fn do_parse_one(
@@ -290,7 +301,7 @@ pub fn do_codemod(
     } else {
         initial_diags.sort_by(|(a, _, _), (b, _, _)| a.cmp(b));
         let mut err_in_diag = false;
-        if args.is_format_json() {
+        if args.is_format_json() || args.is_format_ndjson() {
             for (_name, file_id, diags) in &initial_diags {
                 if args.print_diags {
                     for diag in diags {
@@ -312,6 +323,7 @@ pub fn do_codemod(
                             *file_id,
                             with_prefix(relative_path, prefix).as_path(),
                             args.use_cli_severity,
+                            args.is_format_ndjson(),
                             cli,
                         )?;
                     }
@@ -414,18 +426,24 @@ fn print_diagnostic_json(
     file_id: FileId,
     path: &Path,
     use_cli_severity: bool,
+    ndjson: bool,
     cli: &mut dyn Cli,
 ) -> Result<(), anyhow::Error> {
     let line_index = analysis.line_index(file_id)?;
-    let converted_diagnostic =
-        convert::ide_to_arc_diagnostic(&line_index, path, diagnostic, use_cli_severity);
-    writeln!(
-        cli,
-        "{}",
-        serde_json::to_string(&converted_diagnostic).unwrap_or_else(|err| panic!(
-            "print_diagnostics_json failed for '{converted_diagnostic:?}': {err}"
-        ))
-    )?;
+    let serialized = if ndjson {
+        let converted_diagnostic =
+            convert::ide_to_json_diagnostic(&line_index, path, diagnostic, use_cli_severity);
+        serde_json::to_string(&converted_diagnostic).unwrap_or_else(|err| {
+            panic!("print_diagnostics_json failed for '{converted_diagnostic:?}': {err}")
+        })
+    } else {
+        let converted_diagnostic =
+            convert::ide_to_arc_diagnostic(&line_index, path, diagnostic, use_cli_severity);
+        serde_json::to_string(&converted_diagnostic).unwrap_or_else(|err| {
+            panic!("print_diagnostics_json failed for '{converted_diagnostic:?}': {err}")
+        })
+    };
+    writeln!(cli, "{serialized}")?;
     Ok(())
 }
 
@@ -626,13 +644,28 @@ impl<'a> Lints<'a> {
                 .flatten()
                 .collect::<Vec<_>>();
 
-            let new_diagnostics = filter_diagnostics(
-                &self.analysis_host.analysis(),
-                &None,
-                None,
-                &new_diags,
-                &self.changed_forms,
-            )?;
+            // When running to a fixpoint we keep going with the
+            // diagnostics selected on the command line, wherever they
+            // are in the changed files. Otherwise only the
+            // simplification rules arising in the changed forms are
+            // considered.
+            let new_diagnostics = if self.args.fixpoint {
+                filter_diagnostics(
+                    &self.analysis_host.analysis(),
+                    &None,
+                    Some(&self.cfg.enabled),
+                    &new_diags,
+                    &FxHashSet::default(),
+                )?
+            } else {
+                filter_diagnostics(
+                    &self.analysis_host.analysis(),
+                    &None,
+                    None,
+                    &new_diags,
+                    &self.changed_forms,
+                )?
+            };
             self.diags = diagnostics_by_file_id(&new_diagnostics);
             if !self.diags.is_empty() {
                 writeln!(cli, "---------------------------------------------\n")?;
@@ -650,7 +683,7 @@ impl<'a> Lints<'a> {
                     }
                 }
             }
-            if !self.args.recursive {
+            if !self.args.recursive && !self.args.fixpoint {
                 break;
             }
         }
@@ -774,6 +807,7 @@ impl<'a> Lints<'a> {
                 }
             })
             .collect_vec();
+        let fixes = non_overlapping_fixes(fixes, file_id);
         if !fixes.is_empty() {
             let (diagnostics, assists): (Vec<diagnostics::Diagnostic>, Vec<Vec<Assist>>) =
                 fixes.iter().cloned().unzip();
@@ -873,6 +907,36 @@ impl<'a> Lints<'a> {
     }
 }
 
+/// Keep the fixes whose edits do not overlap, going backwards from the
+/// end of the file. The diagnostics of the dropped fixes are computed
+/// again once the kept ones are applied, so a later round picks them up.
+fn non_overlapping_fixes(
+    mut fixes: Vec<(diagnostics::Diagnostic, Vec<Assist>)>,
+    file_id: FileId,
+) -> Vec<(diagnostics::Diagnostic, Vec<Assist>)> {
+    let edit_range = |assists: &[Assist]| {
+        assists
+            .iter()
+            .filter_map(|assist| assist.source_change.as_ref()?.get_source_edit(file_id))
+            .flat_map(|edit| edit.iter().map(|indel| indel.delete))
+            .reduce(|acc, range| acc.cover(range))
+    };
+    fixes.sort_by_key(|(_, assists)| {
+        std::cmp::Reverse(edit_range(assists).map(|range| range.start()))
+    });
+    let mut kept_start: Option<TextSize> = None;
+    fixes
+        .into_iter()
+        .filter(|(_, assists)| match edit_range(assists) {
+            Some(range) if kept_start.is_none_or(|start| range.end() <= start) => {
+                kept_start = Some(range.start());
+                true
+            }
+            _ => false,
+        })
+        .collect()
+}
+
 fn diagnostics_by_file_id(
     diags: &Vec<(String, FileId, Vec<diagnostics::Diagnostic>)>,
 ) -> std::collections::HashMap<
@@ -936,6 +1000,7 @@ mod tests {
     use elp_ide::FunctionMatch;
     use elp_ide::diagnostics::DiagnosticCode;
     use elp_ide::diagnostics::ErlangServiceConfig;
+    use elp_ide::diagnostics::FunctionBudgetConfig;
     use elp_ide::diagnostics::Lint;
     use elp_ide::diagnostics::LintsFromConfig;
     use elp_ide::diagnostics::ReplaceCall;
@@ -972,6 +1037,10 @@ mod tests {
             erlang_service: ErlangServiceConfig {
                 warnings_as_errors: true,
             },
+            function_budget: FunctionBudgetConfig {
+                max_clauses: Some(10),
+                max_arity: None,
+            },
         })
         .unwrap();
 
@@ -981,6 +1050,9 @@ mod tests {
             
             [erlang_service]
             warnings_as_errors = true
+
+            [function_budget]
+            max_clauses = 10
             [[ad_hoc_lints.lints]]
             type = "ReplaceCall"
 
@@ -1017,6 +1089,10 @@ mod tests {
                 erlang_service: ErlangServiceConfig {
                     warnings_as_errors: false,
                 },
+                function_budget: FunctionBudgetConfig {
+                    max_clauses: None,
+                    max_arity: None,
+                },
                 ad_hoc_lints: LintsFromConfig {
                     lints: [],
                 },
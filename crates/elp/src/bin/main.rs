@@ -1270,6 +1270,37 @@ mod tests {
         .expect("bad test");
     }
 
+    #[test_case(false ; "rebar")]
+    #[test_case(true  ; "buck")]
+    fn lint_fixpoint_one_shot(buck: bool) {
+        let tmp_dir = make_tmp_dir();
+        let tmp_path = tmp_dir.path();
+        check_lint_fix(
+            args_vec![
+                "lint",
+                "--module",
+                "fix_all",
+                "--diagnostic-filter",
+                "W0029",
+                "--apply-fix",
+                "--one-shot",
+                "--fixpoint",
+                "--to",
+                tmp_path,
+            ],
+            "diagnostics",
+            expect_file!("../resources/test/diagnostics/parse_elp_lint_fixpoint.stdout"),
+            0,
+            buck,
+            None,
+            tmp_path,
+            Path::new("../resources/test/lint/fix_all"),
+            &[("app_a/src/fix_all.erl", "fix_all.erl")],
+            false,
+        )
+        .expect("bad test");
+    }
+
     #[test_case(false ; "rebar")]
     #[test_case(true  ; "buck")]
     fn lint_ignore_apps_a(buck: bool) {
@@ -13,14 +13,19 @@ pub mod load;
 pub mod types;
 
 use anyhow::Result;
-use elp_project_model::ProjectBuildData::Rebar;
+use elp_project_model::ProjectBuildData;
 
 use crate::build::types::LoadResult;
 use crate::cli::Cli;
 
 pub fn compile_deps(loaded: &LoadResult, cli: &dyn Cli) -> Result<()> {
-    if let Rebar(_) = loaded.project.project_build_data {
-        let pb = cli.spinner("Compiling dependencies");
+    let message = match loaded.project.project_build_data {
+        ProjectBuildData::Rebar(_) => Some("Compiling dependencies"),
+        ProjectBuildData::Mix(_) => Some("Compiling dependencies (mix)"),
+        _ => None,
+    };
+    if let Some(message) = message {
+        let pb = cli.spinner(message);
         loaded.project.compile_deps()?;
         loaded.update_erlang_service_paths();
         pb.finish();
@@ -35,6 +35,7 @@ use paths::Utf8Prefix;
 
 use crate::arc_types;
 use crate::from_proto;
+use crate::json_types;
 
 pub fn abs_path(url: &lsp_types::Url) -> Result<AbsPathBuf> {
     let path = url
@@ -260,3 +261,43 @@ pub fn ide_to_arc_diagnostic(
         None,
     )
 }
+
+fn ide_to_json_severity(severity: Severity) -> json_types::Severity {
+    match severity {
+        Severity::Error => json_types::Severity::Error,
+        Severity::Warning => json_types::Severity::Warning,
+        Severity::WeakWarning => json_types::Severity::WeakWarning,
+        Severity::Information => json_types::Severity::Information,
+    }
+}
+
+fn json_position(line_index: &LineIndex, offset: TextSize) -> json_types::Position {
+    let pos = position(line_index, offset);
+    json_types::Position::new(pos.line + 1, pos.character + 1)
+}
+
+pub fn ide_to_json_diagnostic(
+    line_index: &LineIndex,
+    path: &Path,
+    diagnostic: &Diagnostic,
+    use_cli_severity: bool,
+) -> json_types::Diagnostic {
+    let range = json_types::Range::new(
+        json_position(line_index, diagnostic.range.start()),
+        json_position(line_index, diagnostic.range.end()),
+    );
+    let has_fix = diagnostic
+        .fixes
+        .iter()
+        .flatten()
+        .any(|fix| fix.id.0 != "ignore_problem");
+    json_types::Diagnostic::new(
+        path,
+        diagnostic.code.as_code(),
+        diagnostic.code.as_label(),
+        ide_to_json_severity(diagnostic.severity(use_cli_severity)),
+        range,
+        diagnostic.message.clone(),
+        has_fix,
+    )
+}
@@ -17,8 +17,16 @@ use lsp_types::TextDocumentContentChangeEvent;
 use crate::from_proto::safe_text_range;
 use crate::line_endings::LineEndings;
 
+/// The UTF-8 encoding of U+FEFF, the byte order mark some editors put at
+/// the start of a file.
+const UTF8_BOM: &[u8] = b"\xEF\xBB\xBF";
+
 pub struct Document {
     pub content: String,
+    /// Whether the file started with a UTF-8 BOM. It is never part of
+    /// `content`, so that offsets are not shifted by it, and is put back by
+    /// `into_bytes` unless cleared.
+    pub has_bom: bool,
 }
 
 impl Document {
@@ -27,6 +35,10 @@ impl Document {
     }
 
     pub fn from_bytes(bytes: &[u8]) -> Document {
+        let (bytes, has_bom) = match bytes.strip_prefix(UTF8_BOM) {
+            Some(bytes) => (bytes, true),
+            None => (bytes, false),
+        };
         let content = match str::from_utf8(bytes) {
             Ok(text) => text.to_string(),
             Err(_err) => {
@@ -36,7 +48,7 @@ impl Document {
                 bytes.iter().map(|byte| (*byte) as char).collect()
             }
         };
-        Document { content }
+        Document { content, has_bom }
     }
 
     // From https://github.com/rust-lang/rust-analyzer/blob/607b9ea160149bacca41c0638f16d372c3b235cd/crates/rust-analyzer/src/lsp_utils.rs#L90
@@ -95,6 +107,42 @@ impl Document {
     }
 
     pub fn into_bytes(self) -> Vec<u8> {
-        self.content.into_bytes()
+        if self.has_bom {
+            [UTF8_BOM, self.content.as_bytes()].concat()
+        } else {
+            self.content.into_bytes()
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use elp_syntax::AstNode;
+    use elp_syntax::SourceFile;
+    use elp_syntax::TextSize;
+
+    use super::*;
+
+    #[test]
+    fn bom_is_stripped_from_content() {
+        let document = Document::from_bytes(b"\xEF\xBB\xBF-module(main).\nfoo() -> ok.\n");
+        assert!(document.has_bom);
+        let (text, _) = document.vfs_to_salsa();
+        let parse = SourceFile::parse_text(&text);
+        assert!(parse.errors().is_empty(), "{:?}", parse.errors());
+        let module = parse.tree().forms_only().next().unwrap();
+        assert_eq!(module.syntax().text_range().start(), TextSize::new(0));
+        assert_eq!(module.syntax().text(), "-module(main).");
+    }
+
+    #[test]
+    fn bom_is_preserved_on_round_trip() {
+        let bytes = b"\xEF\xBB\xBF-module(main).\n";
+        assert_eq!(Document::from_bytes(bytes).into_bytes(), bytes);
+
+        let bytes = b"-module(main).\n";
+        let document = Document::from_bytes(bytes);
+        assert!(!document.has_bom);
+        assert_eq!(document.into_bytes(), bytes);
     }
 }
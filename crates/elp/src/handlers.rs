@@ -19,6 +19,7 @@ use anyhow::bail;
 use elp_ide::Cancellable;
 use elp_ide::DocResult;
 use elp_ide::HighlightedRange;
+use elp_ide::HoverConfig;
 use elp_ide::NavigationTarget;
 use elp_ide::RangeInfo;
 use elp_ide::TextRange;
@@ -409,6 +410,27 @@ pub(crate) fn handle_goto_type_definition(
     Ok(Some(res))
 }
 
+pub(crate) fn handle_goto_spec(
+    snap: Snapshot,
+    params: lsp_types::TextDocumentPositionParams,
+) -> Result<Option<lsp_types::GotoDefinitionResponse>> {
+    let _p = tracing::info_span!("handle_goto_spec").entered();
+    let mut position = from_proto::file_position(&snap, params)?;
+    position.offset = snap
+        .analysis
+        .clamp_offset(position.file_id, position.offset)?;
+    let nav_info = match snap.analysis.goto_spec(position)? {
+        None => return Ok(None),
+        Some(it) => it,
+    };
+    let src = FileRange {
+        file_id: position.file_id,
+        range: nav_info.range,
+    };
+    let res = to_proto::goto_definition_response(&snap, Some(src), nav_info.info)?;
+    Ok(Some(res))
+}
+
 pub(crate) fn handle_references(
     snap: Snapshot,
     params: lsp_types::ReferenceParams,
@@ -465,9 +487,11 @@ pub(crate) fn handle_completion(
         .and_then(|ctx| ctx.trigger_character)
         .and_then(|s| s.chars().next());
 
-    let completions = snap
-        .analysis
-        .completions(position, completion_trigger_character)?;
+    let completions = snap.analysis.completions(
+        &snap.config.completion(),
+        position,
+        completion_trigger_character,
+    )?;
 
     Ok(Some(to_proto::completion_response(snap, completions)))
 }
@@ -575,23 +599,18 @@ pub(crate) fn handle_hover(snap: Snapshot, params: HoverParams) -> Result<Option
     position.offset = snap
         .analysis
         .clamp_offset(position.file_id, position.offset)?;
-    let query_range = FileRange {
-        file_id: position.file_id,
-        range: TextRange::empty(position.offset),
+    let hover_config = HoverConfig {
+        types: snap.config.types_on_hover(),
     };
-
-    let mut docs: Vec<(Doc, Option<FileRange>)> = Vec::default();
-
-    if snap.config.types_on_hover()
-        && let Some(type_info) = snap.analysis.type_at_position(query_range)?
-    {
-        let (ty, range) = &*type_info;
-        let text = &snap.analysis.file_text(range.file_id)?[range.range];
-        let type_doc = Doc::new(format!("```erlang\n{text} :: {ty}\n```\n"));
-        docs.push((type_doc, Some(range.to_owned())));
-        let refs = snap.analysis.type_references(range.file_id, ty)?;
-        if !refs.is_empty() {
-            let goto_list = refs
+    let mut doc: Option<(Doc, Option<FileRange>)> = None;
+    if let Some(hover) = snap.analysis.hover(position, &hover_config)? {
+        if let Some(doc_result) = &hover.doc_result {
+            send_hover_telemetry(doc_result);
+        }
+        let mut markdown = hover.markdown;
+        if !hover.type_references.is_empty() {
+            let goto_list = hover
+                .type_references
                 .into_iter()
                 .flat_map(|(name, range)| {
                     to_proto::location(&snap, range)
@@ -607,66 +626,23 @@ pub(crate) fn handle_hover(snap: Snapshot, params: HoverParams) -> Result<Option
                         .ok()
                 })
                 .join(" | ");
-            let goto_docs = Doc::new(format!("Go to: {goto_list}"));
-            docs.push((goto_docs, None));
+            markdown.push_str(&format!("\n\n---\n\nGo to: {goto_list}"));
         }
-    }
-
-    if let Some(doc_result) = snap.analysis.get_docs_at_position(position)? {
-        send_hover_telemetry(&doc_result);
-        let doc = doc_result.doc;
-        if let Some(doc) = doc {
-            let range = doc_result.token_range;
-            docs.push((doc, Some(range)));
+        if !markdown.is_empty() {
+            doc = Some((Doc::new(markdown), hover.range));
         }
     }
 
-    if let Some(macro_expansion) = snap.analysis.expand_macro(position)? {
-        let hover_info = (
-            Doc::new(format!(
-                "{}\n\n```erlang\n{}```\n",
-                macro_expansion.name, macro_expansion.expansion
-            )),
-            None,
-        );
-        docs.push(hover_info);
-    }
-
     let hover_actions_config = snap.config.hover_actions();
     let actions = snap
         .analysis
         .hover_actions(position, &hover_actions_config)?;
 
-    if docs.is_empty() && !actions.is_empty() {
-        let hover_info = (Doc::new("".to_string()), None);
-        docs.push(hover_info);
+    if doc.is_none() && !actions.is_empty() {
+        doc = Some((Doc::new("".to_string()), None));
     }
 
-    to_proto::hover_response(&snap, combine_docs(&docs), actions)
-}
-
-fn combine_docs(docs: &[(Doc, Option<FileRange>)]) -> Option<(Doc, Option<FileRange>)> {
-    match docs {
-        [] => None,
-        [(doc, src_range)] => Some((doc.clone(), *src_range)),
-        many => Some((
-            Doc::new(
-                many.iter()
-                    .map(|d| d.0.markdown_text())
-                    .collect_vec()
-                    .join("\n\n---\n\n"),
-            ),
-            many.iter().fold(None, |acc, (_, mr)| match (acc, mr) {
-                (None, None) => None,
-                (None, Some(_)) => *mr,
-                (Some(_), None) => acc,
-                (Some(a), Some(b)) => Some(FileRange {
-                    file_id: a.file_id,
-                    range: a.range.cover(b.range),
-                }),
-            }),
-        )),
-    }
+    to_proto::hover_response(&snap, doc, actions)
 }
 
 pub(crate) fn handle_folding_range(
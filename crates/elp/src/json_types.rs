@@ -0,0 +1,121 @@
+/*
+ * Copyright (c) Meta Platforms, Inc. and affiliates.
+ *
+ * This source code is dual-licensed under either the MIT license found in the
+ * LICENSE-MIT file in the root directory of this source tree or the Apache
+ * License, Version 2.0 found in the LICENSE-APACHE file in the root directory
+ * of this source tree. You may select, at your option, one of the
+ * above-listed licenses.
+ */
+
+//! Diagnostics as emitted by `elp lint --format ndjson`, one JSON object
+//! per line. The field names are part of the output format, and must stay
+//! stable for the tools consuming it.
+
+use std::path::Path;
+
+use serde::Serialize;
+
+#[derive(Debug, Serialize, PartialEq, Eq)]
+pub struct Diagnostic {
+    path: String,
+    // Diagnostic code, e.g. `W0001`
+    code: String,
+    // Diagnostic label, e.g. `module_mismatch`
+    name: String,
+    severity: Severity,
+    range: Range,
+    message: String,
+    // Whether the diagnostic comes with a fix, not counting the one
+    // adding an `elp:ignore` comment
+    has_fix: bool,
+}
+
+#[derive(Debug, Serialize, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum Severity {
+    Error,
+    Warning,
+    WeakWarning,
+    Information,
+}
+
+#[derive(Debug, Serialize, PartialEq, Eq)]
+pub struct Range {
+    start: Position,
+    end: Position,
+}
+
+/// A 1-based line and column.
+#[derive(Debug, Serialize, PartialEq, Eq)]
+pub struct Position {
+    line: u32,
+    column: u32,
+}
+
+impl Diagnostic {
+    pub fn new(
+        path: &Path,
+        code: String,
+        name: String,
+        severity: Severity,
+        range: Range,
+        message: String,
+        has_fix: bool,
+    ) -> Self {
+        Diagnostic {
+            path: path.display().to_string(), // lossy on Windows for unicode paths
+            code,
+            name,
+            severity,
+            range,
+            message,
+            has_fix,
+        }
+    }
+}
+
+impl Range {
+    pub fn new(start: Position, end: Position) -> Self {
+        Range { start, end }
+    }
+}
+
+impl Position {
+    pub fn new(line: u32, column: u32) -> Self {
+        Position { line, column }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::path::Path;
+
+    use elp_ide::TextRange;
+    use elp_ide::TextSize;
+    use elp_ide::diagnostics::Diagnostic;
+    use elp_ide::diagnostics::DiagnosticCode;
+    use elp_ide::elp_ide_db::LineIndex;
+    use expect_test::expect;
+
+    use crate::convert;
+
+    #[test]
+    fn module_mismatch_to_json() {
+        let text = "%% A comment\n-module(foo).\n";
+        let line_index = LineIndex::new(text);
+        let diagnostic = Diagnostic::new(
+            DiagnosticCode::ModuleMismatch,
+            "Module name (foo) does not match file name (bar)",
+            TextRange::new(TextSize::from(21), TextSize::from(24)),
+        );
+        let converted = convert::ide_to_json_diagnostic(
+            &line_index,
+            Path::new("src/bar.erl"),
+            &diagnostic,
+            false,
+        );
+        expect![[r#"{"path":"src/bar.erl","code":"W0001","name":"module_mismatch","severity":"error","range":{"start":{"line":2,"column":9},"end":{"line":2,"column":12}},"message":"Module name (foo) does not match file name (bar)","has_fix":false}"#]]
+            .assert_eq(&serde_json::to_string(&converted).unwrap());
+    }
+}
@@ -33,6 +33,7 @@ pub mod convert;
 pub mod document;
 mod from_proto;
 mod handlers;
+pub mod json_types;
 pub mod line_endings;
 pub mod lsp_ext;
 mod mem_docs;
@@ -160,6 +161,7 @@ mod tests {
     use elp_ide::FunctionMatch;
     use elp_ide::diagnostics::DiagnosticCode;
     use elp_ide::diagnostics::ErlangServiceConfig;
+    use elp_ide::diagnostics::FunctionBudgetConfig;
     use elp_ide::diagnostics::Lint;
     use elp_ide::diagnostics::LintsFromConfig;
     use elp_ide::diagnostics::ReplaceCall;
@@ -192,6 +194,7 @@ mod tests {
             },
             linters: FxHashMap::default(),
             erlang_service: ErlangServiceConfig::default(),
+            function_budget: FunctionBudgetConfig::default(),
         };
         expect![[r#"
             enabled_lints = ["W0011"]
@@ -223,6 +223,14 @@ impl Request for ExternalDocs {
     const METHOD: &'static str = "experimental/externalDocs";
 }
 
+pub enum GotoSpec {}
+
+impl Request for GotoSpec {
+    type Params = lsp_types::TextDocumentPositionParams;
+    type Result = Option<lsp_types::GotoDefinitionResponse>;
+    const METHOD: &'static str = "elp/gotoSpec";
+}
+
 pub enum HoverRequest {}
 
 impl Request for HoverRequest {
@@ -699,6 +699,7 @@ impl Server {
             .on::<lsp_ext::ExpandMacro>(handlers::handle_expand_macro)
             .on::<lsp_ext::Ping>(handlers::pong)
             .on::<lsp_ext::ExternalDocs>(handlers::handle_external_docs)
+            .on::<lsp_ext::GotoSpec>(handlers::handle_goto_spec)
             .finish();
 
         Ok(())
@@ -43,10 +43,12 @@ use elp_ide::elp_ide_db::ReferenceCategory;
 use elp_ide::elp_ide_db::SymbolKind;
 use elp_ide::elp_ide_db::assists::AssistUserInput;
 use elp_ide::elp_ide_db::docs::Doc;
+use elp_ide::elp_ide_db::elp_base_db::AnchoredPathBuf;
 use elp_ide::elp_ide_db::elp_base_db::FileId;
 use elp_ide::elp_ide_db::elp_base_db::FilePosition;
 use elp_ide::elp_ide_db::elp_base_db::FileRange;
 use elp_ide::elp_ide_db::rename::RenameError;
+use elp_ide::elp_ide_db::source_change::FileSystemEdit;
 use elp_ide::elp_ide_db::source_change::SourceChange;
 use elp_project_model::ProjectBuildData;
 use elp_text_edit::Indel;
@@ -155,7 +157,20 @@ pub(crate) fn workspace_edit(
             edits: edit.edits.into_iter().collect(),
         });
     }
-    let document_changes = lsp_types::DocumentChanges::Edits(edits);
+    let document_changes = if source_change.file_system_edits.is_empty() {
+        lsp_types::DocumentChanges::Edits(edits)
+    } else {
+        // Text edits refer to the original files, so they go before any
+        // file system operation.
+        let mut operations: Vec<lsp_types::DocumentChangeOperation> = edits
+            .into_iter()
+            .map(lsp_types::DocumentChangeOperation::Edit)
+            .collect();
+        for edit in source_change.file_system_edits {
+            operations.extend(document_change_operations(snap, edit)?);
+        }
+        lsp_types::DocumentChanges::Operations(operations)
+    };
     let workspace_edit = lsp_types::WorkspaceEdit {
         changes: None,
         document_changes: Some(document_changes),
@@ -164,6 +179,54 @@ pub(crate) fn workspace_edit(
     Ok(workspace_edit)
 }
 
+fn document_change_operations(
+    snap: &Snapshot,
+    edit: FileSystemEdit,
+) -> Result<Vec<lsp_types::DocumentChangeOperation>> {
+    let operations = match edit {
+        FileSystemEdit::CreateFile {
+            dst,
+            initial_contents,
+        } => {
+            let uri = anchored_url(snap, &dst)?;
+            let create = lsp_types::ResourceOp::Create(lsp_types::CreateFile {
+                uri: uri.clone(),
+                options: None,
+                annotation_id: None,
+            });
+            let contents = lsp_types::TextDocumentEdit {
+                text_document: lsp_types::OptionalVersionedTextDocumentIdentifier {
+                    uri,
+                    version: None,
+                },
+                edits: vec![lsp_types::OneOf::Left(lsp_types::TextEdit {
+                    range: lsp_types::Range::default(),
+                    new_text: initial_contents,
+                })],
+            };
+            vec![
+                lsp_types::DocumentChangeOperation::Op(create),
+                lsp_types::DocumentChangeOperation::Edit(contents),
+            ]
+        }
+        FileSystemEdit::MoveFile { src, dst } => {
+            let rename = lsp_types::ResourceOp::Rename(lsp_types::RenameFile {
+                old_uri: url(snap, src),
+                new_uri: anchored_url(snap, &dst)?,
+                options: None,
+                annotation_id: None,
+            });
+            vec![lsp_types::DocumentChangeOperation::Op(rename)]
+        }
+    };
+    Ok(operations)
+}
+
+/// The URL of `path`, relative to the directory of its anchor file.
+fn anchored_url(snap: &Snapshot, path: &AnchoredPathBuf) -> Result<lsp_types::Url> {
+    Ok(url(snap, path.anchor).join(&path.path)?)
+}
+
 pub(crate) fn code_action_kind(kind: AssistKind) -> lsp_types::CodeActionKind {
     match kind {
         AssistKind::None | AssistKind::Generate => lsp_types::CodeActionKind::EMPTY,
@@ -354,11 +417,44 @@ pub fn completion_response(
 ) -> lsp_types::CompletionResponse {
     let items = completions
         .into_iter()
-        .map(|it| completion_item(&snap, it))
+        .flat_map(|it| completion_items(&snap, it))
         .collect();
     lsp_types::CompletionResponse::Array(items)
 }
 
+/// A completion adding an include gets an item for each way of including
+/// the file, the most specific first. The others show their include form.
+fn completion_items(snap: &Snapshot, c: Completion) -> Vec<lsp_types::CompletionItem> {
+    let alternatives = match &c.additional_edit {
+        Some((pos, include)) => include
+            .alternatives
+            .iter()
+            .map(|alternative| (*pos, alternative.clone()))
+            .collect(),
+        None => Vec::new(),
+    };
+    let mut items = vec![completion_item(snap, c.clone())];
+    for (idx, (pos, alternative)) in alternatives.into_iter().enumerate() {
+        let description = alternative.as_attribute().trim_end().to_string();
+        let mut item = completion_item(
+            snap,
+            Completion {
+                additional_edit: Some((pos, alternative)),
+                ..c.clone()
+            },
+        );
+        // Sort right after the most specific include
+        let sort_text = item.sort_text.take().unwrap_or_else(|| item.label.clone());
+        item.sort_text = Some(format!("{sort_text}{}", idx + 1));
+        item.label_details = Some(lsp_types::CompletionItemLabelDetails {
+            detail: None,
+            description: Some(description),
+        });
+        items.push(item);
+    }
+    items
+}
+
 fn completion_item(snap: &Snapshot, c: Completion) -> lsp_types::CompletionItem {
     use Kind::*;
     use lsp_types::CompletionItemKind as K;
@@ -811,6 +907,7 @@ pub(crate) fn code_lens(
                     }
                 }
                 ProjectBuildData::Static(_) => {}
+                ProjectBuildData::Mix(_) => {}
                 ProjectBuildData::Otp => {}
             }
         }
@@ -16,8 +16,25 @@ use elp_types_db::eqwalizer::visitor::Visitor;
 
 use crate::ast::Pos;
 
+/// Number of eqWAlizer escape hatches used in a module.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct EscapeHatchCounts {
+    /// `%eqwalizer:fixme` comments
+    pub fixmes: usize,
+    /// `%eqwalizer:ignore` comments
+    pub ignores: usize,
+    /// `-eqwalizer({nowarn_function, ...})` attributes
+    pub nowarns: usize,
+}
+
+enum EscapeHatch<'a> {
+    Fixme(&'a TextRange),
+    Ignore(&'a TextRange),
+    Nowarn(&'a Pos),
+}
+
 struct EscapeHatchesVisitor<'a> {
-    diagnostics: &'a mut Vec<EqwalizerDiagnostic>,
+    escape_hatches: Vec<EscapeHatch<'a>>,
 }
 
 impl<'a> Visitor<'a, ()> for EscapeHatchesVisitor<'a> {
@@ -26,16 +43,15 @@ impl<'a> Visitor<'a, ()> for EscapeHatchesVisitor<'a> {
             ExternalForm::ElpMetadata(meta) => {
                 for fixme in &meta.fixmes {
                     if fixme.is_ignore {
-                        self.diagnostics.push(ignore_diagnostic(&fixme.comment))
+                        self.escape_hatches
+                            .push(EscapeHatch::Ignore(&fixme.comment))
                     } else {
-                        self.diagnostics.push(fixme_diagnostic(&fixme.comment))
+                        self.escape_hatches.push(EscapeHatch::Fixme(&fixme.comment))
                     }
                 }
             }
             ExternalForm::EqwalizerNowarnFunction(attr) => {
-                if let Some(d) = nowarn_diagnostic(&attr.pos) {
-                    self.diagnostics.push(d);
-                }
+                self.escape_hatches.push(EscapeHatch::Nowarn(&attr.pos))
             }
             _ => (),
         }
@@ -43,6 +59,14 @@ impl<'a> Visitor<'a, ()> for EscapeHatchesVisitor<'a> {
     }
 }
 
+fn collect_escape_hatches(ast: &AST) -> Vec<EscapeHatch<'_>> {
+    let mut visitor = EscapeHatchesVisitor {
+        escape_hatches: vec![],
+    };
+    let _ = visitor.visit_ast(ast);
+    visitor.escape_hatches
+}
+
 fn fixme_diagnostic(range: &TextRange) -> EqwalizerDiagnostic {
     EqwalizerDiagnostic {
         range: range.clone().into(),
@@ -84,6 +108,96 @@ fn nowarn_diagnostic(pos: &Pos) -> Option<EqwalizerDiagnostic> {
 }
 
 pub(crate) fn escape_hatches(diagnostics: &mut Vec<EqwalizerDiagnostic>, ast: &AST) {
-    let mut visitor = EscapeHatchesVisitor { diagnostics };
-    let _ = visitor.visit_ast(ast);
+    diagnostics.extend(
+        collect_escape_hatches(ast)
+            .into_iter()
+            .filter_map(|escape_hatch| match escape_hatch {
+                EscapeHatch::Fixme(range) => Some(fixme_diagnostic(range)),
+                EscapeHatch::Ignore(range) => Some(ignore_diagnostic(range)),
+                EscapeHatch::Nowarn(pos) => nowarn_diagnostic(pos),
+            }),
+    );
+}
+
+/// Count the escape hatches used in a module, so that tooling can report
+/// their density without going through the individual diagnostics.
+pub fn escape_hatch_summary(ast: &AST) -> EscapeHatchCounts {
+    collect_escape_hatches(ast).into_iter().fold(
+        EscapeHatchCounts::default(),
+        |mut counts, escape_hatch| {
+            match escape_hatch {
+                EscapeHatch::Fixme(_) => counts.fixmes += 1,
+                EscapeHatch::Ignore(_) => counts.ignores += 1,
+                EscapeHatch::Nowarn(_) => counts.nowarns += 1,
+            }
+            counts
+        },
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use elp_types_db::eqwalizer::Id;
+    use elp_types_db::eqwalizer::form::ElpMetadataAttr;
+    use elp_types_db::eqwalizer::form::EqwalizerNowarnFunctionAttr;
+    use elp_types_db::eqwalizer::form::Fixme;
+
+    use super::*;
+
+    fn range(start_byte: u32, end_byte: u32) -> TextRange {
+        TextRange {
+            start_byte,
+            end_byte,
+        }
+    }
+
+    fn fixme(start_byte: u32, is_ignore: bool) -> Fixme {
+        Fixme {
+            comment: range(start_byte, start_byte + 10),
+            suppression: range(start_byte + 11, start_byte + 20),
+            is_ignore,
+        }
+    }
+
+    fn nowarn(name: &str, start_byte: u32) -> ExternalForm {
+        ExternalForm::EqwalizerNowarnFunction(EqwalizerNowarnFunctionAttr {
+            pos: Pos::TextRange(range(start_byte, start_byte + 10)),
+            id: Id {
+                name: name.into(),
+                arity: 0,
+            },
+        })
+    }
+
+    #[test]
+    fn summary_counts_all_escape_hatches() {
+        let ast = AST {
+            from_beam: false,
+            forms: vec![
+                nowarn("foo", 0),
+                nowarn("bar", 20),
+                ExternalForm::ElpMetadata(ElpMetadataAttr {
+                    pos: Pos::TextRange(range(0, 0)),
+                    fixmes: vec![
+                        fixme(100, false),
+                        fixme(200, true),
+                        fixme(300, false),
+                        fixme(400, false),
+                    ],
+                }),
+            ],
+        };
+        assert_eq!(
+            escape_hatch_summary(&ast),
+            EscapeHatchCounts {
+                fixmes: 3,
+                ignores: 1,
+                nowarns: 2,
+            }
+        );
+
+        let mut diagnostics = vec![];
+        escape_hatches(&mut diagnostics, &ast);
+        assert_eq!(diagnostics.len(), 6);
+    }
 }
@@ -19,6 +19,9 @@ use crate::db::EqwalizerDiagnosticsDatabase;
 mod escape_hatches;
 mod overloaded_specs;
 
+pub use escape_hatches::EscapeHatchCounts;
+pub use escape_hatches::escape_hatch_summary;
+
 #[salsa::query_group(EqwalizerAnalysesDatabaseStorage)]
 pub trait EqwalizerAnalysesDatabase: EqwalizerDiagnosticsDatabase {
     fn compute_eqwalizer_stats(
@@ -1386,6 +1386,22 @@ fn call_type_erlang_bif() {
     );
 }
 
+#[test]
+fn call_type_erlang_integer_bifs() {
+    check(
+        r#"
+-type ints() :: pos_integer() | non_neg_integer() | neg_integer().
+"#,
+        expect![[r#"
+            -type ints() :: (
+                erlang:pos_integer() |
+                erlang:non_neg_integer() |
+                erlang:neg_integer()
+            ).
+        "#]],
+    );
+}
+
 #[test]
 fn record_type() {
     check(
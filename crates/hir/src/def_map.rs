@@ -206,6 +206,9 @@ impl DefMap {
                             type_alias,
                         },
                     );
+                    // A `-doc` attribute before a type documents the type
+                    last_doc_attribute = None;
+                    last_doc_metadata_attribute = None;
                 }
                 FormIdx::TypeExport(idx) => {
                     for export_id in form_list[idx].entries.clone() {
@@ -410,6 +413,11 @@ impl DefMap {
         self.exported_functions.contains(name)
     }
 
+    /// Whether the module is compiled with `export_all`
+    pub fn is_export_all(&self) -> bool {
+        self.export_all
+    }
+
     pub fn get_function(&self, name: &NameArity) -> Option<&FunctionDef> {
         self.functions_by_fa
             .get(name)
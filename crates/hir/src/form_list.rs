@@ -183,6 +183,11 @@ impl FormList {
         self.data.compile_options.iter()
     }
 
+    /// Returns the -record attributes in the file
+    pub fn records(&self) -> impl Iterator<Item = (RecordId, &Record)> {
+        self.data.records.iter()
+    }
+
     /// Returns an iterator over the -compile attributes in the file
     pub fn type_aliases(&self) -> impl Iterator<Item = (TypeAliasId, &TypeAlias)> {
         self.data.type_aliases.iter()
@@ -136,6 +136,7 @@ pub use intern::Atom;
 pub use intern::SsrSource;
 pub use intern::Var;
 pub use macro_exp::ResolvedMacro;
+pub use macro_exp::macro_definition_at;
 pub use module_data::CallbackDef;
 pub use module_data::DefineDef;
 pub use module_data::File;
@@ -9,6 +9,9 @@
  */
 
 use elp_base_db::FileId;
+use elp_base_db::FilePosition;
+use elp_syntax::AstNode;
+use elp_syntax::algo;
 use elp_syntax::ast;
 
 use crate::Define;
@@ -18,6 +21,7 @@ use crate::MacroName;
 use crate::ModuleAttribute;
 use crate::Name;
 use crate::PPDirective;
+use crate::Semantic;
 use crate::body::SSR_SOURCE_FILE_ID;
 use crate::db::DefDatabase;
 use crate::form_list::FormListData;
@@ -298,6 +302,23 @@ pub fn macro_name(macro_call: &ast::MacroCallExpr) -> Option<MacroName> {
     Some(MacroName::new(name, arity))
 }
 
+/// The `-define` a position inside a macro call comes from. When macro calls
+/// are nested, e.g. in `?FOO(?BAR)`, the outermost one wins, since it is the
+/// one whose expansion ends up in the code. Built-in macros have no `-define`.
+pub fn macro_definition_at(sema: &Semantic, position: FilePosition) -> Option<InFile<DefineId>> {
+    let source = sema.parse(position.file_id).value;
+    let macro_call = algo::ancestors_at_offset(source.syntax(), position.offset)?
+        .filter_map(ast::MacroCallExpr::cast)
+        .last()?;
+    match sema
+        .db
+        .resolve_macro(position.file_id, macro_name(&macro_call)?)?
+    {
+        ResolvedMacro::User(define) => Some(define),
+        ResolvedMacro::BuiltIn(_) => None,
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use elp_base_db::FileRange;
@@ -367,6 +388,30 @@ mod tests {
         assert_eq!(expected_range, found_range);
     }
 
+    #[track_caller]
+    fn check_definition_at(fixture: &str) {
+        let (db, fixture) = TestDB::with_fixture(fixture);
+        let sema = Semantic::new(&db);
+        let annos = fixture.annotations();
+        assert_eq!(annos.len(), 1);
+        let (expected_range, _) = annos[0];
+
+        let define = macro_definition_at(&sema, fixture.position())
+            .expect("failed to find macro definition");
+        let def = DefineDef {
+            file: File {
+                file_id: define.file_id,
+            },
+            define: db.file_form_list(define.file_id)[define.value].clone(),
+        };
+        let found_range = FileRange {
+            file_id: define.file_id,
+            range: def.source(&db).syntax().text_range(),
+        };
+
+        assert_eq!(expected_range, found_range);
+    }
+
     #[test]
     fn test_line() {
         check_built_in(
@@ -531,4 +576,56 @@ foo(X) ->
 "#,
         );
     }
+
+    #[test]
+    fn test_definition_at_expr() {
+        check_definition_at(
+            r#"
+   -define(VALUE(X), {value, X}).
+%% ^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^
+
+foo(Y) -> ?VALUE(Y~).
+"#,
+        );
+    }
+
+    #[test]
+    fn test_definition_at_type() {
+        check_definition_at(
+            r#"
+//- /src/types.hrl
+   -define(RESULT, ok | error).
+%% ^^^^^^^^^^^^^^^^^^^^^^^^^^^^
+//- /src/main.erl
+-module(main).
+-include("types.hrl").
+-spec foo() -> ?RE~SULT.
+foo() -> ok.
+"#,
+        );
+    }
+
+    #[test]
+    fn test_definition_at_nested() {
+        check_definition_at(
+            r#"
+   -define(OUTER(X), [X]).
+%% ^^^^^^^^^^^^^^^^^^^^^^^
+-define(INNER, inner).
+
+foo() -> ?OUTER(?IN~NER).
+"#,
+        );
+    }
+
+    #[test]
+    fn test_definition_at_built_in() {
+        let (db, fixture) = TestDB::with_fixture(
+            r#"
+foo() -> ?MOD~ULE.
+"#,
+        );
+        let sema = Semantic::new(&db);
+        assert_eq!(macro_definition_at(&sema, fixture.position()), None);
+    }
 }
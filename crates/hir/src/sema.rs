@@ -519,12 +519,21 @@ impl Semantic<'_> {
         ))))
     }
 
-    pub fn find_enclosing_spec(&self, file_id: FileId, syntax: &SyntaxNode) -> Option<SpecId> {
+    /// The spec the node belongs to, either because it is part of the spec
+    /// itself, or part of the function the spec is for.
+    pub fn find_enclosing_spec(
+        &self,
+        file_id: FileId,
+        syntax: &SyntaxNode,
+    ) -> Option<InFile<SpecId>> {
         let form = syntax.ancestors().find_map(ast::Form::cast)?;
         let form_list = self.db.file_form_list(file_id);
-        let form = form_list.find_form(&form)?;
-        match form {
-            FormIdx::Spec(fun) => Some(fun),
+        match form_list.find_form(&form)? {
+            FormIdx::Spec(spec_id) => Some(InFile::new(file_id, spec_id)),
+            FormIdx::FunctionClause(_) => {
+                let spec = self.find_enclosing_function_def(file_id, syntax)?.spec?;
+                Some(InFile::new(spec.file.file_id, spec.spec_id))
+            }
             _ => None,
         }
     }
@@ -946,6 +955,52 @@ impl Semantic<'_> {
         Arc::new(MacroDefineIndex { index })
     }
 
+    /// The headers of the project defining each record name.
+    pub fn record_define_index(&self, project_id: ProjectId) -> Arc<HeaderNameIndex> {
+        self.header_name_index(project_id, |form_list| {
+            form_list
+                .records()
+                .map(|(_, record)| record.name.as_str().to_string())
+                .collect()
+        })
+    }
+
+    /// The headers of the project defining each type name, whatever its
+    /// arity.
+    pub fn type_define_index(&self, project_id: ProjectId) -> Arc<HeaderNameIndex> {
+        self.header_name_index(project_id, |form_list| {
+            form_list
+                .type_aliases()
+                .map(|(_, alias)| alias.name().name().as_str().to_string())
+                .collect()
+        })
+    }
+
+    fn header_name_index(
+        &self,
+        project_id: ProjectId,
+        names: impl Fn(&FormList) -> Vec<String>,
+    ) -> Arc<HeaderNameIndex> {
+        let mut headers: FxHashMap<String, Vec<FileId>> = FxHashMap::default();
+        let include_file_index = self.db.include_file_index(project_id);
+        for file_id in include_file_index.path_to_file_id.values() {
+            let form_list = self.form_list(*file_id);
+            for name in names(&form_list) {
+                let files = headers.entry(name).or_default();
+                if !files.contains(file_id) {
+                    files.push(*file_id);
+                }
+            }
+        }
+
+        let mut builder = map::TrieBuilder::<char, Vec<FileId>>::new();
+        for (name, val) in headers {
+            builder.push(name.chars().collect::<Vec<_>>(), val)
+        }
+        let index = builder.build();
+        Arc::new(HeaderNameIndex { index })
+    }
+
     // -----------------------------------------------------------------
     // Folds
 
@@ -1132,6 +1187,23 @@ impl MacroDefineIndex {
     }
 }
 
+/// Names defined in the headers of a project, such as records or types,
+/// with the headers defining them.
+pub struct HeaderNameIndex {
+    pub index: map::Trie<char, Vec<FileId>>,
+}
+
+impl HeaderNameIndex {
+    /// The headers defining a name starting with `so_far`.
+    pub fn complete(&self, so_far: &str) -> FxHashSet<FileId> {
+        let chars: Vec<char> = so_far.chars().collect();
+        self.index
+            .postfix_search::<Vec<_>, _>(&chars)
+            .flat_map(|(_, files): (Vec<char>, &Vec<FileId>)| files.iter().copied())
+            .collect()
+    }
+}
+
 // ---------------------------------------------------------------------
 struct BoundVarsInPat<'a> {
     sema: &'a Semantic<'a>,
@@ -1956,6 +2028,39 @@ mod tests {
 
     // -----------------------------------------------------------------
 
+    #[test]
+    fn record_define_index() {
+        let fixture = r#"
+             //- /src/include.hrl
+             -record(person, {name}).
+
+             //- /src/include2.hrl
+             -record(pet, {name}).
+             -record(address, {street}).
+
+             //- /src/main.erl
+             -module(main).
+             -record(pen, {colour}).
+             "#;
+        let (db, _fixture) = TestDB::with_fixture(fixture);
+        let sema = Semantic::new(&db);
+        let index = sema.record_define_index(ProjectId(0));
+        let mut headers: Vec<FileId> = index.complete("pe").into_iter().collect();
+        headers.sort();
+        expect![[r#"
+            [
+                FileId(
+                    0,
+                ),
+                FileId(
+                    1,
+                ),
+            ]
+        "#]]
+        .assert_debug_eq(&headers);
+        assert!(index.complete("str").is_empty());
+    }
+
     #[test]
     fn macro_define_index() {
         let fixture = r#"
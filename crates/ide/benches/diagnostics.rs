@@ -0,0 +1,91 @@
+/*
+ * Copyright (c) Meta Platforms, Inc. and affiliates.
+ *
+ * This source code is dual-licensed under either the MIT license found in the
+ * LICENSE-MIT file in the root directory of this source tree or the Apache
+ * License, Version 2.0 found in the LICENSE-APACHE file in the root directory
+ * of this source tree. You may select, at your option, one of the
+ * above-listed licenses.
+ */
+
+//! Native diagnostics for every module of a project of a few hundred
+//! modules, computed one module at a time and in parallel, as done by
+//! `elp lint`.
+
+use criterion::BatchSize;
+use criterion::Criterion;
+use criterion::criterion_group;
+use criterion::criterion_main;
+use elp_ide::Analysis;
+use elp_ide::AnalysisHost;
+use elp_ide::diagnostics::DiagnosticsConfig;
+use elp_ide::elp_ide_db::RootDatabase;
+use elp_ide::elp_ide_db::elp_base_db::FileId;
+use elp_ide::elp_ide_db::elp_base_db::fixture::WithFixture;
+use rayon::prelude::IntoParallelRefIterator;
+use rayon::prelude::ParallelIterator;
+
+const MODULES: usize = 300;
+
+/// Modules of about 100 lines each, calling into the next module.
+fn project() -> String {
+    let mut text = String::new();
+    for m in 0..MODULES {
+        let next = (m + 1) % MODULES;
+        text.push_str(&format!(
+            "//- /src/mod_{m}.erl\n-module(mod_{m}).\n-export([main/1]).\n"
+        ));
+        text.push_str(&format!("main(X) -> mod_{next}:main(fun_0(X)).\n"));
+        for i in 0..20 {
+            text.push_str(&format!(
+                "fun_{i}(X) ->\n    Y = [X] ++ [{i}],\n    Z = lists:map(fun(E) -> E * {i} end, Y),\n    fun_{}(Z).\n\n",
+                i + 1
+            ));
+        }
+        text.push_str("fun_20(X) -> X.\n");
+    }
+    text
+}
+
+/// A fresh analysis for every iteration, so results are not memoized.
+fn setup(fixture: &str) -> (Analysis, Vec<FileId>) {
+    let (db, fixture) = RootDatabase::with_fixture(fixture);
+    (AnalysisHost::new(db).analysis(), fixture.files)
+}
+
+fn bench_diagnostics(c: &mut Criterion) {
+    let fixture = project();
+    let config = DiagnosticsConfig::default();
+    let mut group = c.benchmark_group(format!("native diagnostics for {MODULES} modules"));
+    group.sample_size(10);
+    group.bench_function("sequential", |b| {
+        b.iter_batched(
+            || setup(&fixture),
+            |(analysis, files)| {
+                files
+                    .iter()
+                    .map(|file_id| analysis.native_diagnostics(&config, &vec![], *file_id))
+                    .collect::<Vec<_>>()
+            },
+            BatchSize::PerIteration,
+        )
+    });
+    group.bench_function("parallel", |b| {
+        b.iter_batched(
+            || setup(&fixture),
+            |(analysis, files)| {
+                files
+                    .par_iter()
+                    .map_with(analysis.clone(), |db, file_id| {
+                        db.native_diagnostics(&config, &vec![], *file_id)
+                    })
+                    .collect::<Vec<_>>()
+            },
+            BatchSize::PerIteration,
+        )
+    });
+    group.finish();
+}
+
+criterion_group!(benches, bench_diagnostics);
+criterion_main!(benches);
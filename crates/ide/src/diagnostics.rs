@@ -91,23 +91,39 @@ use crate::common_test;
 mod application_env;
 mod atoms_exhaustion;
 mod binary_string_to_sigil;
+mod blocking_receive_in_callback;
 mod boolean_precedence;
 mod could_be_a_string_literal;
 mod cross_node_eval;
+mod dead_function;
 mod debugging_function;
 mod dependent_header;
 mod deprecated_function;
+mod duplicate_map_key;
 mod duplicate_module;
 mod edoc;
 mod effect_free_statement;
+mod element_index_out_of_range;
+mod empty_record_update;
 mod equality_check_with_unnecessary_operator;
 mod eqwalizer_assists;
+mod erlang_get_stacktrace;
+mod erlang_now;
 mod expression_can_be_simplified;
+mod float_equality;
+mod foldl_append_accumulator;
+mod format_args_mismatch;
 mod from_config;
+mod function_budget;
 mod head_mismatch;
+mod identity_list_operation;
+mod ignored_function_arg;
 mod inefficient_enumerate;
 mod inefficient_flatlength;
 mod inefficient_last;
+mod length_at_least;
+mod list_subtraction;
+mod literal_apply;
 mod macro_precedence_suprise;
 mod map_find_to_syntax;
 mod map_insertion_to_syntax;
@@ -118,6 +134,7 @@ mod missing_separator;
 mod misspelled_attribute;
 mod module_mismatch;
 mod mutable_variable;
+mod nested_case_to_maybe;
 mod no_catch;
 mod no_dialyzer_attribute;
 mod no_error_logger;
@@ -125,26 +142,42 @@ mod no_garbage_collect;
 mod no_nowarn_suppressions;
 mod no_size;
 mod nonstandard_integer_formatting;
+mod obsolete_string_function;
+mod record_missing_fields;
 mod record_tuple_match;
+mod recursive_append_accumulator;
 mod redundant_assignment;
+mod redundant_block;
+mod redundant_map_merge;
+mod redundant_try_after;
 mod replace_call;
 mod replace_in_spec;
+mod rethrow_without_stacktrace;
 mod sets_version_2;
 mod simplify_negation;
+mod specific_size_bif;
+mod timer_sleep_infinity;
 mod trivial_match;
+mod undefined_equality;
 mod undefined_function;
 mod undefined_macro;
+mod undefined_spawn_target;
 mod undocumented_function;
 mod undocumented_module;
 mod unexported_function;
 mod unnecessary_fold_to_build_map;
+mod unnecessary_list_append;
 mod unnecessary_map_from_list_around_comprehension;
 mod unnecessary_map_to_list_in_comprehension;
+mod unnecessary_try;
+mod unsafe_keyfind_element;
 mod unspecific_include;
+mod unused_comparison_result;
 mod unused_function_args;
 mod unused_include;
 mod unused_macro;
 mod unused_record_field;
+mod use_exact_equality;
 
 pub use elp_ide_db::DiagnosticCode;
 pub use from_config::Lint;
@@ -422,6 +455,7 @@ impl Diagnostic {
             &AssistConfig {
                 snippet_cap: None,
                 allowed: None,
+                disabled_ids: FxHashSet::default(),
             },
             AssistResolveStrategy::All,
             range,
@@ -495,6 +529,21 @@ impl<F> AdhocSemanticDiagnostics for F where
 {
 }
 
+/// The checker of a `DiagnosticDescriptor`, which can also read the
+/// `DiagnosticsConfig`, e.g. for thresholds set by the user.
+pub trait DescriptorChecker:
+    Fn(&mut Vec<Diagnostic>, &Semantic, FileId, FileKind, &DiagnosticsConfig)
+    + std::panic::RefUnwindSafe
+    + Sync
+{
+}
+impl<F> DescriptorChecker for F where
+    F: Fn(&mut Vec<Diagnostic>, &Semantic, FileId, FileKind, &DiagnosticsConfig)
+        + std::panic::RefUnwindSafe
+        + Sync
+{
+}
+
 // A base trait for linters
 pub(crate) trait Linter {
     // A unique identifier for the linter.
@@ -922,7 +971,35 @@ impl DiagnosticConditions {
 #[derive(Clone)]
 pub struct DiagnosticDescriptor<'a> {
     conditions: DiagnosticConditions,
-    checker: &'a dyn AdhocSemanticDiagnostics,
+    /// The codes of the diagnostics produced by the checker. Codes carrying
+    /// a value, like `DiagnosticCode::Missing`, are not listed.
+    codes: &'a [DiagnosticCode],
+    checker: &'a dyn DescriptorChecker,
+}
+
+/// Static metadata about a registered diagnostic, e.g. to let a client
+/// render a settings UI.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DiagnosticInfo {
+    pub code: DiagnosticCode,
+    pub label: String,
+    pub conditions: DiagnosticConditions,
+    /// Only known up front for diagnostics defined by a `Linter`
+    pub severity: Option<Severity>,
+    /// Only known up front for diagnostics defined by a `Linter`
+    pub description: Option<&'static str>,
+}
+
+impl DiagnosticInfo {
+    fn new(code: &DiagnosticCode, conditions: DiagnosticConditions) -> DiagnosticInfo {
+        DiagnosticInfo {
+            code: code.clone(),
+            label: code.as_label(),
+            conditions,
+            severity: None,
+            description: None,
+        }
+    }
 }
 
 // ---------------------------------------------------------------------
@@ -1000,9 +1077,16 @@ pub struct DiagnosticsConfig {
     pub include_otp: bool,
     pub include_edoc: bool,
     pub use_cli_severity: bool,
+    /// Severity to report for the given diagnostic codes, whatever the
+    /// diagnostic producing them chose.
+    pub severity_overrides: FxHashMap<DiagnosticCode, Severity>,
     /// Used in `elp lint` to request erlang service diagnostics if
     /// needed.
     pub request_erlang_service_diagnostics: bool,
+    /// Report functions with more clauses than this. Not checked if unset.
+    pub max_function_clauses: Option<usize>,
+    /// Report functions with a higher arity than this. Not checked if unset.
+    pub max_function_arity: Option<usize>,
 }
 
 impl DiagnosticsConfig {
@@ -1054,6 +1138,14 @@ impl DiagnosticsConfig {
             self.enabled = EnabledDiagnostics::from_set(allowed_diagnostics);
         }
         self.lints_from_config = lint_config.ad_hoc_lints.clone();
+        self.max_function_clauses = lint_config.function_budget.max_clauses;
+        self.max_function_arity = lint_config.function_budget.max_arity;
+        self.severity_overrides.extend(
+            lint_config
+                .linters
+                .iter()
+                .filter_map(|(code, linter)| Some((code.clone(), linter.severity?))),
+        );
         self.lint_config = Some(lint_config.clone());
         self.request_erlang_service_diagnostics = self.request_erlang_service_diagnostics();
         Ok(self)
@@ -1090,6 +1182,34 @@ impl DiagnosticsConfig {
         self
     }
 
+    pub fn set_severity_override(
+        mut self,
+        code: DiagnosticCode,
+        severity: Severity,
+    ) -> DiagnosticsConfig {
+        self.severity_overrides.insert(code, severity);
+        self
+    }
+
+    /// Replace the severity of `diagnostic` with the configured override
+    /// for its code, if any, both for the LSP and for the CLI.
+    pub fn apply_severity_override(&self, diagnostic: &mut Diagnostic) {
+        if let Some(severity) = self.severity_overrides.get(&diagnostic.code) {
+            diagnostic.severity = *severity;
+            diagnostic.cli_severity = None;
+        }
+    }
+
+    pub fn set_max_function_clauses(mut self, value: usize) -> DiagnosticsConfig {
+        self.max_function_clauses = Some(value);
+        self
+    }
+
+    pub fn set_max_function_arity(mut self, value: usize) -> DiagnosticsConfig {
+        self.max_function_arity = Some(value);
+        self
+    }
+
     pub fn enable(mut self, code: DiagnosticCode) -> DiagnosticsConfig {
         self.enabled.enable(code);
         self
@@ -1182,6 +1302,8 @@ pub struct LintConfig {
     pub disabled_lints: Vec<DiagnosticCode>,
     #[serde(default)]
     pub erlang_service: ErlangServiceConfig,
+    #[serde(default, skip_serializing_if = "FunctionBudgetConfig::is_empty")]
+    pub function_budget: FunctionBudgetConfig,
     #[serde(default)]
     pub ad_hoc_lints: LintsFromConfig,
     #[serde(default)]
@@ -1194,6 +1316,21 @@ pub struct ErlangServiceConfig {
     pub warnings_as_errors: bool,
 }
 
+/// Limits checked by the `function_budget` lint.
+#[derive(Deserialize, Serialize, Default, Debug, Clone)]
+pub struct FunctionBudgetConfig {
+    /// Report functions with more clauses than this
+    pub max_clauses: Option<usize>,
+    /// Report functions with a higher arity than this
+    pub max_arity: Option<usize>,
+}
+
+impl FunctionBudgetConfig {
+    fn is_empty(&self) -> bool {
+        self.max_clauses.is_none() && self.max_arity.is_none()
+    }
+}
+
 #[derive(Deserialize, Serialize, Default, Debug, Clone)]
 pub struct FunctionCallLinterConfig {
     include: Option<Vec<FunctionMatch>>,
@@ -1425,6 +1562,8 @@ pub fn native_diagnostics(
                 || !d.has_category(Category::Experimental))
             && !d.should_be_suppressed(&metadata, config)
     });
+    res.iter_mut()
+        .for_each(|d| config.apply_severity_override(d));
 
     LabeledDiagnostics {
         normal: res,
@@ -1468,6 +1607,27 @@ pub fn diagnostics_descriptors<'a>() -> Vec<&'a DiagnosticDescriptor<'a>> {
         &no_dialyzer_attribute::DESCRIPTOR,
         &no_catch::DESCRIPTOR,
         &no_nowarn_suppressions::DESCRIPTOR,
+        &use_exact_equality::DESCRIPTOR,
+        &unused_comparison_result::DESCRIPTOR,
+        &undefined_equality::DESCRIPTOR,
+        &record_missing_fields::DESCRIPTOR,
+        &redundant_try_after::DESCRIPTOR,
+        &dead_function::DESCRIPTOR,
+        &redundant_block::DESCRIPTOR,
+        &unnecessary_list_append::DESCRIPTOR,
+        &ignored_function_arg::DESCRIPTOR,
+        &unsafe_keyfind_element::DESCRIPTOR,
+        &nested_case_to_maybe::DESCRIPTOR,
+        &unnecessary_try::DESCRIPTOR,
+        &blocking_receive_in_callback::DESCRIPTOR,
+        &empty_record_update::DESCRIPTOR,
+        &recursive_append_accumulator::DESCRIPTOR,
+        &float_equality::DESCRIPTOR,
+        &list_subtraction::DESCRIPTOR,
+        &duplicate_map_key::DESCRIPTOR,
+        &length_at_least::DESCRIPTOR,
+        &rethrow_without_stacktrace::DESCRIPTOR,
+        &function_budget::DESCRIPTOR,
     ]
 }
 
@@ -1490,14 +1650,14 @@ pub fn diagnostics_from_descriptors(
                 // Filter the returned diagnostics to ensure they are
                 // enabled
                 let mut diags: Vec<Diagnostic> = Vec::default();
-                (descriptor.checker)(&mut diags, sema, file_id, file_kind);
+                (descriptor.checker)(&mut diags, sema, file_id, file_kind, config);
                 for diag in diags {
                     if config.enabled.contains(&diag.code) {
                         res.push(diag);
                     }
                 }
             } else {
-                (descriptor.checker)(res, sema, file_id, file_kind);
+                (descriptor.checker)(res, sema, file_id, file_kind, config);
             }
         }
     });
@@ -1525,12 +1685,18 @@ const FUNCTION_CALL_LINTERS: &[&dyn FunctionCallDiagnostics] = &[
     &sets_version_2::LINTER,
     &no_garbage_collect::LINTER,
     &no_size::LINTER,
+    &specific_size_bif::LINTER,
     &no_error_logger::LINTER,
     &debugging_function::LINTER,
     &atoms_exhaustion::LINTER,
     &undefined_function::LINTER,
     &unexported_function::LINTER,
     &cross_node_eval::LINTER,
+    &format_args_mismatch::LINTER,
+    &literal_apply::LINTER,
+    &undefined_spawn_target::LINTER,
+    &erlang_now::LINTER,
+    &element_index_out_of_range::LINTER,
 ];
 
 /// SSR pattern linters that use structural search and replace patterns
@@ -1539,6 +1705,14 @@ const SSR_PATTERN_LINTERS: &[&dyn SsrPatternsDiagnostics] = &[
     &binary_string_to_sigil::LINTER,
     &unnecessary_map_to_list_in_comprehension::LINTER,
     &could_be_a_string_literal::LINTER,
+    &redundant_map_merge::LINTER,
+    &obsolete_string_function::TOKENS_LINTER,
+    &obsolete_string_function::STRIP_LINTER,
+    &obsolete_string_function::SUBSTR_LINTER,
+    &foldl_append_accumulator::LINTER,
+    &erlang_get_stacktrace::LINTER,
+    &timer_sleep_infinity::LINTER,
+    &identity_list_operation::LINTER,
 ];
 
 /// Generic linters
@@ -1575,6 +1749,52 @@ pub(crate) fn linters() -> Vec<DiagnosticLinter> {
     all_linters
 }
 
+/// Diagnostics reported by `native_diagnostics` itself, rather than by a
+/// descriptor or a linter.
+const DIRECT_DIAGNOSTICS: &[DiagnosticCode] = &[
+    DiagnosticCode::SyntaxError,
+    DiagnosticCode::MissingModule,
+    DiagnosticCode::ModuleMismatch,
+    DiagnosticCode::MisspelledAttribute,
+    DiagnosticCode::UnusedInclude,
+];
+
+/// Metadata for every registered diagnostic, from both the
+/// `DiagnosticDescriptor`s and the linters.
+pub fn all_diagnostic_descriptors() -> Vec<DiagnosticInfo> {
+    let direct_conditions = DiagnosticConditions {
+        experimental: false,
+        include_generated: true,
+        include_tests: true,
+        default_disabled: false,
+    };
+    let mut res: Vec<DiagnosticInfo> = DIRECT_DIAGNOSTICS
+        .iter()
+        .map(|code| DiagnosticInfo::new(code, direct_conditions.clone()))
+        .collect();
+    res.extend(diagnostics_descriptors().iter().flat_map(|descriptor| {
+        descriptor
+            .codes
+            .iter()
+            .map(|code| DiagnosticInfo::new(code, descriptor.conditions.clone()))
+    }));
+    res.extend(linters().iter().map(|l| {
+        let linter = l.as_linter();
+        let conditions = DiagnosticConditions {
+            experimental: linter.is_experimental(),
+            include_generated: linter.should_process_generated_files(),
+            include_tests: linter.should_process_test_files(),
+            default_disabled: !linter.is_enabled(),
+        };
+        DiagnosticInfo {
+            severity: Some(linter.severity()),
+            description: Some(linter.description()),
+            ..DiagnosticInfo::new(&linter.id(), conditions)
+        }
+    }));
+    res
+}
+
 fn diagnostics_from_linters(
     res: &mut Vec<Diagnostic>,
     sema: &Semantic,
@@ -1968,7 +2188,8 @@ pub fn erlang_service_diagnostics(
             .filter(|(_file_id, d)| {
                 !d.should_be_suppressed(&metadata, config) && !config.disabled.contains(&d.code)
             })
-            .map(|(file_id, d)| {
+            .map(|(file_id, mut d)| {
+                config.apply_severity_override(&mut d);
                 (
                     file_id,
                     add_elp_assists_to_erlang_service_diagnostic(db, file_id, d),
@@ -2224,7 +2445,8 @@ pub fn edoc_diagnostics(
             .filter(|(_file_id, d)| {
                 !d.should_be_suppressed(&metadata, config) && !config.disabled.contains(&d.code)
             })
-            .for_each(|(file_id, diag)| {
+            .for_each(|(file_id, mut diag)| {
+                config.apply_severity_override(&mut diag);
                 diags_map
                     .entry(file_id)
                     .and_modify(|existing| existing.push(diag.clone()))
@@ -2270,6 +2492,10 @@ pub fn ct_diagnostics(
         .filter(|d| {
             !config.disabled.contains(&d.code) && !d.should_be_suppressed(&metadata, config)
         })
+        .map(|mut d| {
+            config.apply_severity_override(&mut d);
+            d
+        })
         .collect()
 }
 
@@ -3105,6 +3331,7 @@ baz(1)->4.
 
              -spec blah(Pred :: fun ((T) -> erlang:boolean), List :: [T]) -> erlang:boolean().
              blah(0, _Y) -> 1;
+          %% ^^^^ 💡 weak: Argument 2 of `blah/2` is ignored in every clause.
              blah(X, _Y) -> X + 1.
             "#,
         );
@@ -3521,6 +3748,64 @@ baz(1)->4.
         );
     }
 
+    #[test]
+    fn test_severity_override_for_descriptor() {
+        let config = DiagnosticsConfig::default()
+            .set_severity_override(DiagnosticCode::UnusedFunctionArg, Severity::WeakWarning);
+        check_diagnostics_with_config(
+            config,
+            r#"
+            //- /src/main.erl
+            -module(main).
+            -export([foo/1]).
+
+            foo(Arg) -> ok.
+            %%  ^^^ 💡 weak: this variable is unused
+            "#,
+        );
+    }
+
+    #[test]
+    fn test_severity_override_from_lint_config() {
+        let mut lint_config = LintConfig::default();
+        lint_config.linters.insert(
+            DiagnosticCode::UnusedFunctionArg,
+            LinterConfig {
+                severity: Some(Severity::Error),
+                include_tests: None,
+                include_generated: None,
+                experimental: None,
+                config: None,
+            },
+        );
+
+        let config = DiagnosticsConfig::default()
+            .configure_diagnostics(
+                &lint_config,
+                &Some("W0010".to_string()),
+                &None,
+                FallBackToAll::No,
+            )
+            .unwrap();
+        assert_eq!(
+            config
+                .severity_overrides
+                .get(&DiagnosticCode::UnusedFunctionArg),
+            Some(&Severity::Error)
+        );
+        check_diagnostics_with_config(
+            config,
+            r#"
+            //- /src/main.erl
+            -module(main).
+            -export([foo/1]).
+
+            foo(Arg) -> ok.
+            %%  ^^^ 💡 error: this variable is unused
+            "#,
+        );
+    }
+
     #[test]
     fn test_linter_include_tests_override() {
         let mut lint_config = LintConfig::default();
@@ -3644,4 +3929,60 @@ baz(1)->4.
             ),
         );
     }
+
+    #[test]
+    fn all_diagnostic_descriptors_lists_registered_diagnostics() {
+        let infos = all_diagnostic_descriptors();
+        let listed: FxHashSet<DiagnosticCode> =
+            infos.iter().map(|info| info.code.clone()).collect();
+        assert!(listed.contains(&DiagnosticCode::ModuleMismatch));
+
+        let (analysis, fixture) = crate::fixture::with_fixture(
+            r#"
+            //- /src/main.erl
+            -module(other).
+            -include("main.hrl").
+            -dyalizer({nowarn_function, f/1}).
+            -export([f/1]).
+
+            f(0) -> ok;
+            g(_) -> error.
+            //- /src/main.hrl
+            -define(UNUSED, unused).
+            "#,
+        );
+        let diagnostics = analysis
+            .native_diagnostics(&DiagnosticsConfig::default(), &vec![], fixture.files[0])
+            .unwrap();
+        let emitted: FxHashSet<DiagnosticCode> =
+            diagnostics.iter().map(|d| d.code.clone()).collect();
+        assert!(emitted.contains(&DiagnosticCode::ModuleMismatch));
+        assert!(emitted.contains(&DiagnosticCode::MisspelledAttribute));
+        assert!(emitted.contains(&DiagnosticCode::HeadMismatch));
+        for code in &emitted {
+            assert!(listed.contains(code), "{code:?} is not listed");
+        }
+
+        let trivial_match = infos
+            .iter()
+            .find(|info| info.code == DiagnosticCode::TrivialMatch)
+            .unwrap();
+        assert_eq!(trivial_match.label, "trivial_match");
+        assert!(!trivial_match.conditions.default_disabled);
+        assert_eq!(trivial_match.severity, None);
+
+        let nested_case = infos
+            .iter()
+            .find(|info| info.code == DiagnosticCode::NestedCaseToMaybe)
+            .unwrap();
+        assert!(nested_case.conditions.experimental);
+        assert!(nested_case.conditions.default_disabled);
+
+        let undefined_function = infos
+            .iter()
+            .find(|info| info.code == DiagnosticCode::UndefinedFunction)
+            .unwrap();
+        assert!(undefined_function.severity.is_some());
+        assert!(undefined_function.description.is_some());
+    }
 }
@@ -39,7 +39,8 @@ pub(crate) static DESCRIPTOR: DiagnosticDescriptor = DiagnosticDescriptor {
         include_tests: true,
         default_disabled: false,
     },
-    checker: &|diags, sema, file_id, _ext| {
+    codes: &[DiagnosticCode::ApplicationGetEnv],
+    checker: &|diags, sema, file_id, _ext, _config| {
         application_env(diags, sema, file_id);
     },
 };
@@ -8,15 +8,33 @@
  * above-listed licenses.
  */
 
+//! Lint: atoms_exhaustion
+//!
+//! Return a warning when `list_to_atom/1` or `binary_to_atom/1,2` creates an
+//! atom from a value that is not a compile-time constant. Atoms are never
+//! garbage collected, so doing this with untrusted input can exhaust the
+//! atom table.
+
+use elp_ide_assists::Assist;
 use elp_ide_db::elp_base_db::FileId;
+use elp_ide_db::source_change::SourceChangeBuilder;
+use elp_syntax::AstNode;
+use elp_syntax::algo;
+use elp_syntax::ast;
+use hir::Body;
+use hir::CallTarget;
+use hir::Expr;
+use hir::ExprId;
 use hir::Semantic;
 
 use crate::FunctionMatch;
 use crate::codemod_helpers::CheckCallCtx;
+use crate::codemod_helpers::MatchCtx;
 // @fb-only
 use crate::diagnostics::DiagnosticCode;
 use crate::diagnostics::FunctionCallLinter;
 use crate::diagnostics::Linter;
+use crate::fix;
 use crate::lazy_function_matches;
 
 pub(crate) struct AtomsExhaustionLinter;
@@ -41,7 +59,9 @@ impl Linter for AtomsExhaustionLinter {
 }
 
 impl FunctionCallLinter for AtomsExhaustionLinter {
-    type Context = ();
+    /// The `..._to_existing_atom` variant of the called function, if it
+    /// has one.
+    type Context = Option<String>;
 
     fn matches_functions(&self) -> Vec<FunctionMatch> {
         lazy_function_matches![
@@ -65,28 +85,86 @@ impl FunctionCallLinter for AtomsExhaustionLinter {
         // @fb-only
             // @fb-only
         let is_safe = false; // @oss-only
-        if !is_safe {
-            match context.args.as_vec()[..] {
-                [_, options] => {
-                    let body = context.in_clause.body();
-                    match &body[options].literal_list_contains_atom(context.in_clause, "safe") {
-                        Some(true) => None,
-                        _ => Some(()),
-                    }
-                }
-                _ => Some(()),
+        if is_safe {
+            return None;
+        }
+        let body = context.in_clause.body();
+        let args = context.args.as_vec();
+        // A constant can only ever create a single atom
+        if is_constant(&body, *args.first()?) {
+            return None;
+        }
+        if let [_, options] = args[..]
+            && body[options].literal_list_contains_atom(context.in_clause, "safe") == Some(true)
+        {
+            return None;
+        }
+        let name = match context.target {
+            CallTarget::Local { name } | CallTarget::Remote { name, .. } => {
+                body[*name].as_atom()?
             }
-        } else {
-            None
+        };
+        let name = context.in_clause.sema.db.lookup_atom(name);
+        let safe_name = name
+            .as_str()
+            .strip_suffix("_to_atom")
+            .map(|base| format!("{base}_to_existing_atom"));
+        Some(safe_name)
+    }
+
+    fn fixes(
+        &self,
+        match_context: &MatchCtx<Self::Context>,
+        sema: &Semantic,
+        file_id: FileId,
+    ) -> Option<Vec<Assist>> {
+        if match_context.range.file_id != file_id {
+            return None;
         }
+        let safe_name = match_context.extra.as_ref()?;
+        let source = sema.parse(file_id).value;
+        let call =
+            algo::find_node_at_range::<ast::Call>(source.syntax(), match_context.range.range)?;
+        let name = match call.expr()? {
+            ast::Expr::ExprMax(ast::ExprMax::Atom(name)) => name.syntax().text_range(),
+            ast::Expr::Remote(remote) => remote.fun()?.syntax().text_range(),
+            _ => return None,
+        };
+        let mut builder = SourceChangeBuilder::new(file_id);
+        builder.replace(name, safe_name.clone());
+        Some(vec![fix(
+            "use_existing_atom",
+            &format!("Use `{safe_name}` instead"),
+            builder.finish(),
+            match_context.range.range,
+        )])
     }
 }
 
 pub static LINTER: AtomsExhaustionLinter = AtomsExhaustionLinter;
 
+/// Whether the expression is known at compile time, e.g. `"foo"` or
+/// `<<"foo">>`, looking through macros.
+fn is_constant(body: &Body, expr_id: ExprId) -> bool {
+    match &body[expr_id] {
+        Expr::Literal(_) => true,
+        Expr::MacroCall { expansion, .. } => is_constant(body, *expansion),
+        Expr::List { exprs, tail } => {
+            exprs.iter().all(|expr| is_constant(body, *expr))
+                && tail.is_none_or(|tail| is_constant(body, tail))
+        }
+        Expr::Binary { segs } => segs.iter().all(|seg| {
+            is_constant(body, seg.elem) && seg.size.is_none_or(|size| is_constant(body, size))
+        }),
+        _ => false,
+    }
+}
+
 #[cfg(test)]
 mod tests {
 
+    use expect_test::expect;
+
     use crate::tests;
 
     #[test]
@@ -97,9 +175,10 @@ mod tests {
    -module(main).
    -export([main/0]).
    main() ->
-     erlang:list_to_atom(foo),
+     Foo = "foo",
+     erlang:list_to_atom(Foo),
 %%   ^^^^^^^^^^^^^^^^^^^ 💡 warning: Risk of atoms exhaustion.
-     list_to_atom(foo).
+     list_to_atom(Foo).
 %%   ^^^^^^^^^^^^ 💡 warning: Risk of atoms exhaustion.
 
 //- /opt/lib/stdlib-3.17/src/erlang.erl otp_app:/opt/lib/stdlib-3.17
@@ -118,9 +197,10 @@ mod tests {
    -module(main).
    -export([main/0]).
    main() ->
-     erlang:binary_to_atom(foo),
+     Foo = <<"foo">>,
+     erlang:binary_to_atom(Foo),
 %%   ^^^^^^^^^^^^^^^^^^^^^ 💡 warning: Risk of atoms exhaustion.
-     binary_to_atom(foo).
+     binary_to_atom(<<Foo/binary, "bar">>).
 %%   ^^^^^^^^^^^^^^ 💡 warning: Risk of atoms exhaustion.
 
 //- /opt/lib/stdlib-3.17/src/erlang.erl otp_app:/opt/lib/stdlib-3.17
@@ -131,6 +211,77 @@ mod tests {
         )
     }
 
+    #[test]
+    fn test_constant_argument() {
+        tests::check_diagnostics(
+            r#"
+//- /src/main.erl
+   -module(main).
+   -export([main/0]).
+   -define(NAME, "foo").
+   main() ->
+     list_to_atom("foo"),
+     list_to_atom([$f, $o, $o]),
+     list_to_atom(?NAME),
+     binary_to_atom(<<"foo">>),
+     binary_to_atom(<<"foo">>, utf8).
+
+//- /opt/lib/stdlib-3.17/src/erlang.erl otp_app:/opt/lib/stdlib-3.17
+   -module(erlang).
+   -export([list_to_atom/1, binary_to_atom/1, binary_to_atom/2]).
+   list_to_atom(_) -> ok.
+   binary_to_atom(_) -> ok.
+   binary_to_atom(_, _) -> ok.
+            "#,
+        )
+    }
+
+    #[test]
+    fn test_fix_local_call() {
+        tests::check_fix(
+            r#"
+//- /src/main.erl
+   -module(main).
+   -export([main/1]).
+   main(Name) ->
+     list_to_a~tom(Name).
+//- /opt/lib/stdlib-3.17/src/erlang.erl otp_app:/opt/lib/stdlib-3.17
+   -module(erlang).
+   -export([list_to_atom/1]).
+   list_to_atom(_) -> ok.
+            "#,
+            expect![[r#"
+   -module(main).
+   -export([main/1]).
+   main(Name) ->
+     list_to_existing_atom(Name).
+            "#]],
+        )
+    }
+
+    #[test]
+    fn test_fix_remote_call() {
+        tests::check_fix(
+            r#"
+//- /src/main.erl
+   -module(main).
+   -export([main/1]).
+   main(Name) ->
+     erlang:binary_to_a~tom(Name, utf8).
+//- /opt/lib/stdlib-3.17/src/erlang.erl otp_app:/opt/lib/stdlib-3.17
+   -module(erlang).
+   -export([binary_to_atom/2]).
+   binary_to_atom(_, _) -> ok.
+            "#,
+            expect![[r#"
+   -module(main).
+   -export([main/1]).
+   main(Name) ->
+     erlang:binary_to_existing_atom(Name, utf8).
+            "#]],
+        )
+    }
+
     #[test]
     fn test_binary_to_term() {
         tests::check_diagnostics(
@@ -0,0 +1,260 @@
+/*
+ * Copyright (c) Meta Platforms, Inc. and affiliates.
+ *
+ * This source code is dual-licensed under either the MIT license found in the
+ * LICENSE-MIT file in the root directory of this source tree or the Apache
+ * License, Version 2.0 found in the LICENSE-APACHE file in the root directory
+ * of this source tree. You may select, at your option, one of the
+ * above-listed licenses.
+ */
+
+//! Lint: blocking_receive_in_callback
+//!
+//! Return a warning for a `receive` without an `after 0` clause in a
+//! callback of `gen_server`, `gen_statem` or `gen_event`. It blocks the
+//! behaviour process, which then stops handling its own messages, and may
+//! even consume them.
+
+use elp_ide_db::DiagnosticCode;
+use elp_ide_db::elp_base_db::FileId;
+use elp_syntax::AstNode;
+use elp_syntax::SyntaxKind;
+use elp_syntax::ast;
+use hir::AnyExpr;
+use hir::AnyExprId;
+use hir::Body;
+use hir::Expr;
+use hir::ExprId;
+use hir::FunctionDef;
+use hir::Literal;
+use hir::Semantic;
+use hir::Strategy;
+use hir::fold::MacroStrategy;
+use hir::fold::ParenStrategy;
+use hir::fold::ParentId;
+
+use super::Diagnostic;
+use super::DiagnosticConditions;
+use super::DiagnosticDescriptor;
+use super::Severity;
+
+pub(crate) static DESCRIPTOR: DiagnosticDescriptor = DiagnosticDescriptor {
+    conditions: DiagnosticConditions {
+        experimental: false,
+        include_generated: false,
+        include_tests: true,
+        default_disabled: false,
+    },
+    codes: &[DiagnosticCode::BlockingReceiveInCallback],
+    checker: &|diagnostics, sema, file_id, _ext, _config| {
+        check_file(diagnostics, sema, file_id);
+    },
+};
+
+/// The callbacks of the OTP behaviours, which run in the behaviour process.
+/// The state callbacks of `gen_statem` can have any name, so they are not
+/// listed.
+const BEHAVIOUR_CALLBACKS: &[(&str, &[(&str, u32)])] = &[
+    (
+        "gen_server",
+        &[
+            ("init", 1),
+            ("handle_call", 3),
+            ("handle_cast", 2),
+            ("handle_info", 2),
+            ("handle_continue", 2),
+            ("terminate", 2),
+            ("code_change", 3),
+            ("format_status", 1),
+            ("format_status", 2),
+        ],
+    ),
+    (
+        "gen_statem",
+        &[
+            ("init", 1),
+            ("callback_mode", 0),
+            ("handle_event", 4),
+            ("terminate", 3),
+            ("code_change", 4),
+            ("format_status", 1),
+            ("format_status", 2),
+        ],
+    ),
+    (
+        "gen_event",
+        &[
+            ("init", 1),
+            ("handle_event", 2),
+            ("handle_call", 2),
+            ("handle_info", 2),
+            ("terminate", 2),
+            ("code_change", 3),
+            ("format_status", 1),
+            ("format_status", 2),
+        ],
+    ),
+];
+
+fn check_file(diagnostics: &mut Vec<Diagnostic>, sema: &Semantic, file_id: FileId) {
+    let form_list = sema.form_list(file_id);
+    let behaviours: Vec<(&str, &[(&str, u32)])> = form_list
+        .behaviour_attributes()
+        .filter_map(|(_, behaviour)| {
+            BEHAVIOUR_CALLBACKS
+                .iter()
+                .find(|(name, _)| behaviour.name.as_str() == *name)
+                .copied()
+        })
+        .collect();
+    if behaviours.is_empty() {
+        return;
+    }
+    sema.for_each_function(file_id, |def| {
+        let callback = behaviours.iter().find(|(_, callbacks)| {
+            callbacks.iter().any(|(name, arity)| {
+                def.name.name().as_str() == *name && def.name.arity() == *arity
+            })
+        });
+        if let Some((behaviour, _)) = callback {
+            check_function(diagnostics, sema, file_id, def, behaviour);
+        }
+    });
+}
+
+fn check_function(
+    diagnostics: &mut Vec<Diagnostic>,
+    sema: &Semantic,
+    file_id: FileId,
+    def: &FunctionDef,
+    behaviour: &str,
+) {
+    let def_fb = def.in_function_body(sema, def);
+    def_fb.fold_function(
+        Strategy {
+            macros: MacroStrategy::DoNotExpand,
+            parens: ParenStrategy::InvisibleParens,
+        },
+        (),
+        &mut |_acc, clause_id, ctx| {
+            if let AnyExpr::Expr(Expr::Receive { after, .. }) = &ctx.item {
+                let body = def_fb.body(clause_id);
+                if after
+                    .as_ref()
+                    .is_some_and(|after| is_zero(&body, after.timeout))
+                    // A fun may well run in another process
+                    || in_closure(&body, ctx.parents)
+                {
+                    return;
+                }
+                let map = def_fb.get_body_map(clause_id);
+                let range = ctx
+                    .item_id
+                    .as_expr_id()
+                    .and_then(|expr_id| map.expr(expr_id))
+                    .filter(|ptr| ptr.file_id() == file_id)
+                    .and_then(|ptr| match ptr.to_ast(sema.db) {
+                        ast::Expr::ExprMax(ast::ExprMax::ReceiveExpr(receive)) => receive
+                            .syntax()
+                            .children_with_tokens()
+                            .find(|element| element.kind() == SyntaxKind::ANON_RECEIVE)
+                            .map(|token| token.text_range()),
+                        _ => None,
+                    });
+                if let Some(range) = range {
+                    diagnostics.push(
+                        Diagnostic::new(
+                            DiagnosticCode::BlockingReceiveInCallback,
+                            format!(
+                                "Blocking `receive` in `{behaviour}` callback `{}`.",
+                                def.name
+                            ),
+                            range,
+                        )
+                        .with_severity(Severity::Warning)
+                        .with_ignore_fix(sema, file_id),
+                    );
+                }
+            }
+        },
+    )
+}
+
+fn is_zero(body: &Body, expr_id: ExprId) -> bool {
+    matches!(&body[expr_id], Expr::Literal(Literal::Integer(i)) if i.value == 0)
+}
+
+fn in_closure(body: &Body, parents: &[ParentId]) -> bool {
+    parents.iter().any(|parent_id| match parent_id {
+        ParentId::HirIdx(hir_idx) => match hir_idx.idx {
+            AnyExprId::Expr(idx) => matches!(body[idx], Expr::Closure { .. }),
+            _ => false,
+        },
+        _ => false,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+
+    use crate::diagnostics::Diagnostic;
+    use crate::diagnostics::DiagnosticCode;
+    use crate::tests;
+
+    fn filter(d: &Diagnostic) -> bool {
+        d.code == DiagnosticCode::BlockingReceiveInCallback
+    }
+
+    #[track_caller]
+    fn check_diagnostics(fixture: &str) {
+        tests::check_filtered_diagnostics(fixture, &filter)
+    }
+
+    #[test]
+    fn detects_receive_in_callback() {
+        check_diagnostics(
+            r#"
+         //- /src/main.erl
+         -module(main).
+         -behaviour(gen_server).
+         -export([handle_call/3]).
+
+         handle_call(get, _From, State) ->
+             receive
+         %%  ^^^^^^^ 💡 warning: Blocking `receive` in `gen_server` callback `handle_call/3`.
+                 {value, V} -> {reply, V, State}
+             end;
+         handle_call(poll, _From, State) ->
+             receive
+         %%  ^^^^^^^ 💡 warning: Blocking `receive` in `gen_server` callback `handle_call/3`.
+                 {value, V} -> {reply, V, State}
+             after 5000 -> {reply, timeout, State}
+             end.
+            "#,
+        )
+    }
+
+    #[test]
+    fn ignores_receive_outside_callbacks() {
+        check_diagnostics(
+            r#"
+         //- /src/main.erl
+         -module(main).
+         -behaviour(gen_server).
+         -export([handle_call/3, wait/0]).
+
+         handle_call(poll, _From, State) ->
+             spawn(fun() -> receive _ -> ok end end),
+             receive
+                 {value, V} -> {reply, V, State}
+             after 0 -> {reply, empty, State}
+             end.
+
+         wait() ->
+             receive
+                 {value, V} -> V
+             end.
+            "#,
+        )
+    }
+}
@@ -58,7 +58,8 @@ pub(crate) static DESCRIPTOR: DiagnosticDescriptor = DiagnosticDescriptor {
         include_tests: true,
         default_disabled: false,
     },
-    checker: &|diags, sema, file_id, _ext| {
+    codes: &[DiagnosticCode::BooleanPrecedence],
+    checker: &|diags, sema, file_id, _ext, _config| {
         boolean_precedence(diags, sema, file_id);
     },
 };
@@ -263,7 +264,8 @@ mod tests {
     pub(crate) fn check_diagnostics(fixture: &str) {
         let config = DiagnosticsConfig::default()
             .set_experimental(true)
-            .disable(DiagnosticCode::MacroPrecedenceEscape);
+            .disable(DiagnosticCode::MacroPrecedenceEscape)
+            .disable(DiagnosticCode::IgnoredFunctionArg);
         check_diagnostics_with_config(config, fixture)
     }
 
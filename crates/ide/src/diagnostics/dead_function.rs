@@ -0,0 +1,438 @@
+/*
+ * Copyright (c) Meta Platforms, Inc. and affiliates.
+ *
+ * This source code is dual-licensed under either the MIT license found in the
+ * LICENSE-MIT file in the root directory of this source tree or the Apache
+ * License, Version 2.0 found in the LICENSE-APACHE file in the root directory
+ * of this source tree. You may select, at your option, one of the
+ * above-listed licenses.
+ */
+
+//! Lint: dead_function
+//!
+//! Return a warning for a function of the module which cannot be reached
+//! from any exported function, nor from a module attribute such as
+//! `-on_load`. Mutually recursive helpers which are only called by each
+//! other are reported too.
+//!
+//! A function with a `-spec` is considered part of the API of the module,
+//! maybe called through a dynamic call we cannot see, and is treated as
+//! reachable.
+
+use elp_ide_assists::helpers::extend_form_range_for_delete;
+use elp_ide_db::DiagnosticCode;
+use elp_ide_db::elp_base_db::FileId;
+use elp_ide_db::source_change::SourceChangeBuilder;
+use elp_syntax::AstNode;
+use elp_syntax::TextRange;
+use fxhash::FxHashMap;
+use fxhash::FxHashSet;
+use hir::AnyExpr;
+use hir::CallTarget;
+use hir::Expr;
+use hir::FunctionDef;
+use hir::InFile;
+use hir::Literal;
+use hir::Name;
+use hir::NameArity;
+use hir::Semantic;
+use hir::Strategy;
+use hir::Term;
+use hir::fold::MacroStrategy;
+use hir::fold::ParenStrategy;
+
+use super::Diagnostic;
+use super::DiagnosticConditions;
+use super::DiagnosticDescriptor;
+use super::Severity;
+use crate::fix;
+
+pub(crate) static DESCRIPTOR: DiagnosticDescriptor = DiagnosticDescriptor {
+    conditions: DiagnosticConditions {
+        experimental: false,
+        include_generated: false,
+        include_tests: true,
+        default_disabled: true,
+    },
+    codes: &[DiagnosticCode::DeadFunction],
+    checker: &|diagnostics, sema, file_id, _ext, _config| {
+        dead_functions(diagnostics, sema, file_id);
+    },
+};
+
+/// Calls taking the function to run as a literal atom argument, with the
+/// position of that argument.
+const DYNAMIC_CALLS: &[(&str, usize)] = &[
+    ("erlang:apply/3", 1),
+    ("erlang:function_exported/3", 1),
+    ("erlang:hibernate/3", 1),
+    ("erlang:make_fun/3", 1),
+    ("erlang:spawn/3", 1),
+    ("erlang:spawn/4", 2),
+    ("erlang:spawn_link/3", 1),
+    ("erlang:spawn_link/4", 2),
+    ("erlang:spawn_monitor/3", 1),
+    ("erlang:spawn_monitor/4", 2),
+    ("erlang:spawn_opt/4", 1),
+    ("erlang:spawn_opt/5", 2),
+    ("erlang:spawn_request/3", 1),
+    ("erlang:spawn_request/4", 1),
+    ("erlang:spawn_request/5", 2),
+    ("erpc:call/4", 2),
+    ("erpc:call/5", 2),
+    ("erpc:cast/4", 2),
+    ("erpc:multicall/4", 2),
+    ("erpc:multicall/5", 2),
+    ("proc_lib:hibernate/3", 1),
+    ("proc_lib:spawn/3", 1),
+    ("proc_lib:spawn/4", 2),
+    ("proc_lib:spawn_link/3", 1),
+    ("proc_lib:spawn_link/4", 2),
+    ("proc_lib:spawn_opt/4", 1),
+    ("proc_lib:spawn_opt/5", 2),
+    ("proc_lib:start/3", 1),
+    ("proc_lib:start/4", 1),
+    ("proc_lib:start/5", 1),
+    ("proc_lib:start_link/3", 1),
+    ("proc_lib:start_link/4", 1),
+    ("proc_lib:start_link/5", 1),
+    ("proc_lib:start_monitor/3", 1),
+    ("proc_lib:start_monitor/4", 1),
+    ("proc_lib:start_monitor/5", 1),
+    ("rpc:async_call/4", 2),
+    ("rpc:call/4", 2),
+    ("rpc:call/5", 2),
+    ("rpc:cast/4", 2),
+    ("rpc:multicall/3", 1),
+    ("rpc:multicall/5", 2),
+    ("timer:apply_after/4", 2),
+    ("timer:apply_interval/4", 2),
+    ("timer:apply_repeatedly/4", 2),
+    ("timer:tc/3", 1),
+    ("timer:tc/4", 1),
+];
+
+/// What a function body, or a module attribute, refers to.
+#[derive(Default)]
+struct References {
+    functions: FxHashSet<NameArity>,
+    /// Function names used as atoms in dynamic calls, for any arity.
+    names: FxHashSet<Name>,
+}
+
+impl References {
+    fn refers_to(&self, name: &NameArity) -> bool {
+        self.functions.contains(name) || self.names.contains(name.name())
+    }
+}
+
+fn dead_functions(diagnostics: &mut Vec<Diagnostic>, sema: &Semantic, file_id: FileId) {
+    let def_map = sema.def_map(file_id);
+    if def_map.parse_transform {
+        // The functions may be called from generated code
+        return;
+    }
+    let functions: Vec<(&NameArity, &FunctionDef)> = def_map.get_functions().collect();
+    let references: FxHashMap<&NameArity, References> = functions
+        .iter()
+        .map(|(name, def)| (*name, function_references(sema, file_id, def)))
+        .collect();
+
+    let attribute_references = attribute_references(sema, file_id);
+    let mut reachable: FxHashSet<&NameArity> = FxHashSet::default();
+    let mut worklist: Vec<&NameArity> = functions
+        .iter()
+        .filter(|(name, def)| {
+            def.exported || def.spec.is_some() || attribute_references.refers_to(name)
+        })
+        .map(|(name, _)| *name)
+        .collect();
+    while let Some(name) = worklist.pop() {
+        if !reachable.insert(name) {
+            continue;
+        }
+        if let Some(refs) = references.get(name) {
+            worklist.extend(
+                functions
+                    .iter()
+                    .filter(|(callee, _)| !reachable.contains(callee) && refs.refers_to(callee))
+                    .map(|(callee, _)| *callee),
+            );
+        }
+    }
+
+    for (name, def) in &functions {
+        if def.file.file_id == file_id
+            && !reachable.contains(name)
+            && let Some(diagnostic) = make_diagnostic(sema, file_id, name, def)
+        {
+            diagnostics.push(diagnostic);
+        }
+    }
+}
+
+fn function_references(sema: &Semantic, file_id: FileId, def: &FunctionDef) -> References {
+    let def_fb = def.in_function_body(sema, def);
+    def_fb.fold_function(
+        Strategy {
+            macros: MacroStrategy::Expand,
+            parens: ParenStrategy::InvisibleParens,
+        },
+        References::default(),
+        &mut |mut acc, clause_id, ctx| {
+            let body = def_fb.body(clause_id);
+            match &ctx.item {
+                AnyExpr::Expr(Expr::Call { target, args }) => {
+                    let arity = args.len() as u32;
+                    if let Some(callee) = target.resolve_call(arity, sema, file_id, &body)
+                        && callee.file.file_id == file_id
+                    {
+                        acc.functions.insert(callee.name);
+                    }
+                    if let Some(label) = target.label(arity, sema, &body)
+                        && let Some((_, position)) =
+                            DYNAMIC_CALLS.iter().find(|(call, _)| *call == label)
+                        && let Some(arg) = args.get(*position)
+                        && let Some(atom) = body[*arg].as_atom()
+                    {
+                        acc.names.insert(sema.db.lookup_atom(atom));
+                    }
+                }
+                AnyExpr::Expr(Expr::CaptureFun { target, arity }) => {
+                    match &body[*arity] {
+                        Expr::Literal(Literal::Integer(arity)) => {
+                            if let Ok(arity) = arity.value.try_into()
+                                && let Some(callee) =
+                                    target.resolve_call(arity, sema, file_id, &body)
+                                && callee.file.file_id == file_id
+                            {
+                                acc.functions.insert(callee.name);
+                            }
+                        }
+                        // The arity is only known at runtime, so any
+                        // function of that name can be the target
+                        _ => {
+                            let name = match target {
+                                CallTarget::Local { name } => body[*name].as_atom(),
+                                CallTarget::Remote { .. } => None,
+                            };
+                            if let Some(name) = name {
+                                acc.names.insert(sema.db.lookup_atom(name));
+                            }
+                        }
+                    }
+                }
+                _ => {}
+            }
+            acc
+        },
+    )
+}
+
+/// Functions named in module attributes, as `name/arity` or `{name, arity}`,
+/// such as `-on_load(init/0).` or `-compile({inline, [f/1]}).`
+fn attribute_references(sema: &Semantic, file_id: FileId) -> References {
+    let form_list = sema.form_list(file_id);
+    let attribute_bodies = form_list
+        .attributes()
+        .map(|(id, _)| sema.db.attribute_body(InFile::new(file_id, id)))
+        .chain(
+            form_list
+                .compile_attributes()
+                .map(|(id, _)| sema.db.compile_body(InFile::new(file_id, id))),
+        );
+    let mut references = References::default();
+    for attribute in attribute_bodies {
+        let body = &attribute.body;
+        for (_, term) in body.terms.iter() {
+            if let Term::Tuple { exprs } = term
+                && let [name, arity] = exprs.as_slice()
+                && let Term::Literal(Literal::Atom(name)) = &body[*name]
+                && let Term::Literal(Literal::Integer(arity)) = &body[*arity]
+                && let Ok(arity) = arity.value.try_into()
+            {
+                references
+                    .functions
+                    .insert(NameArity::new(sema.db.lookup_atom(*name), arity));
+            }
+        }
+    }
+    references
+}
+
+fn make_diagnostic(
+    sema: &Semantic,
+    file_id: FileId,
+    name: &NameArity,
+    def: &FunctionDef,
+) -> Option<Diagnostic> {
+    let range = def.name_range(sema.db.upcast())?;
+    let decls = def.source(sema.db.upcast());
+    let first = decls.first()?.syntax().text_range();
+    let last = extend_form_range_for_delete(decls.last()?.syntax());
+
+    let mut builder = SourceChangeBuilder::new(file_id);
+    builder.delete(TextRange::new(first.start(), last.end()));
+    Some(
+        Diagnostic::new(
+            DiagnosticCode::DeadFunction,
+            format!("Function `{name}` is not reachable from any exported function."),
+            range,
+        )
+        .with_severity(Severity::WeakWarning)
+        .with_fixes(Some(vec![fix(
+            "dead_function",
+            &format!("Remove the function `{name}`"),
+            builder.finish(),
+            range,
+        )]))
+        .with_ignore_fix(sema, file_id),
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use elp_ide_db::DiagnosticCode;
+    use expect_test::Expect;
+    use expect_test::expect;
+
+    use crate::diagnostics::Diagnostic;
+    use crate::diagnostics::DiagnosticsConfig;
+    use crate::tests;
+
+    fn config() -> DiagnosticsConfig {
+        DiagnosticsConfig::default().enable(DiagnosticCode::DeadFunction)
+    }
+
+    #[track_caller]
+    fn check_diagnostics(fixture: &str) {
+        tests::check_diagnostics_with_config(config(), fixture);
+    }
+
+    /// For fixtures calling modules they do not define
+    #[track_caller]
+    fn check_filtered_diagnostics(fixture: &str) {
+        fn filter(d: &Diagnostic) -> bool {
+            d.code == DiagnosticCode::DeadFunction
+        }
+        tests::check_filtered_diagnostics_with_config(config(), &vec![], fixture, &filter);
+    }
+
+    #[track_caller]
+    fn check_fix(before: &str, after: Expect) {
+        tests::check_fix_with_config(config(), before, after);
+    }
+
+    #[test]
+    fn not_enabled_by_default() {
+        tests::check_diagnostics(
+            r#"
+              -module(main).
+              -export([foo/0]).
+              foo() -> ok.
+              bar() -> ok.
+            "#,
+        )
+    }
+
+    #[test]
+    fn flags_unreachable_functions() {
+        check_diagnostics(
+            r#"
+              -module(main).
+              -export([foo/0]).
+              foo() -> used().
+              used() -> ok.
+              ping(0) -> ok;
+          %%  ^^^^ 💡 weak: Function `ping/1` is not reachable from any exported function.
+              ping(N) -> pong(N - 1).
+              pong(N) -> ping(N).
+          %%  ^^^^ 💡 weak: Function `pong/1` is not reachable from any exported function.
+            "#,
+        )
+    }
+
+    #[test]
+    fn ignores_fun_references() {
+        check_diagnostics(
+            r#"
+              -module(main).
+              -export([foo/1, bar/1]).
+              foo(L) -> lists:map(fun double/1, L).
+              double(X) -> X * 2.
+              bar(N) ->
+                  F = fun ?MODULE:triple/1,
+                  F(N).
+              triple(X) -> X * 3.
+            "#,
+        )
+    }
+
+    #[test]
+    fn ignores_dynamic_calls_and_attributes() {
+        check_diagnostics(
+            r#"
+              -module(main).
+              -export([foo/0]).
+              -on_load(init/0).
+              -compile({inline, [inlined/1]}).
+              foo() ->
+                  spawn(?MODULE, loop, [0]),
+                  apply(main, helper, []).
+              loop(N) -> loop(N + 1).
+              helper() -> ok.
+              init() -> ok.
+              inlined(X) -> X.
+            "#,
+        )
+    }
+
+    #[test]
+    fn ignores_functions_with_spec() {
+        check_diagnostics(
+            r#"
+              -module(main).
+              -export([foo/0]).
+              foo() -> ok.
+              -spec api() -> ok.
+              api() -> helper().
+              helper() -> ok.
+            "#,
+        )
+    }
+
+    #[test]
+    fn ignores_remote_dynamic_calls() {
+        check_filtered_diagnostics(
+            r#"
+              -module(main).
+              -export([foo/1]).
+              foo(Node) ->
+                  rpc:call(Node, main, remote, []),
+                  timer:apply_after(100, main, later, []).
+              remote() -> ok.
+              later() -> ok.
+            "#,
+        )
+    }
+
+    #[test]
+    fn fix_removes_function() {
+        check_fix(
+            r#"
+-module(main).
+-export([foo/0]).
+foo() -> ok.
+b~ar(0) -> 0;
+bar(N) -> bar(N - 1).
+baz() -> foo().
+"#,
+            expect![[r#"
+                -module(main).
+                -export([foo/0]).
+                foo() -> ok.
+                baz() -> foo().
+            "#]],
+        )
+    }
+}
@@ -126,6 +126,7 @@ redbug() ->
     ok.
 
 start(_) ->
+%%<^^^^ 💡 weak: Argument 1 of `start/1` is ignored in every clause.
     ok.
  
 //- /src/redbug.erl
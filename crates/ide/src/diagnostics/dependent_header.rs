@@ -38,7 +38,8 @@ pub(crate) static DESCRIPTOR: DiagnosticDescriptor = DiagnosticDescriptor {
         include_tests: true,
         default_disabled: false,
     },
-    checker: &|diags, sema, file_id, file_kind| {
+    codes: &[DiagnosticCode::DependentHeader],
+    checker: &|diags, sema, file_id, file_kind, _config| {
         dependent_header(diags, sema, file_id, file_kind);
     },
 };
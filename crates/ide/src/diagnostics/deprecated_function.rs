@@ -51,7 +51,8 @@ pub(crate) static DESCRIPTOR: DiagnosticDescriptor = DiagnosticDescriptor {
         include_tests: true,
         default_disabled: false,
     },
-    checker: &|diags, sema, file_id, _ext| {
+    codes: &[DiagnosticCode::DeprecatedFunction],
+    checker: &|diags, sema, file_id, _ext, _config| {
         deprecated_function(diags, sema, file_id);
     },
 };
@@ -0,0 +1,232 @@
+/*
+ * Copyright (c) Meta Platforms, Inc. and affiliates.
+ *
+ * This source code is dual-licensed under either the MIT license found in the
+ * LICENSE-MIT file in the root directory of this source tree or the Apache
+ * License, Version 2.0 found in the LICENSE-APACHE file in the root directory
+ * of this source tree. You may select, at your option, one of the
+ * above-listed licenses.
+ */
+
+//! Lint/fix: duplicate_map_key
+//!
+//! Return a warning for a literal key appearing more than once in a map
+//! construction, e.g. `#{a => 1, a => 2}`, where only the last value is
+//! kept. Offer to remove the earlier association as a fix.
+
+use elp_ide_db::DiagnosticCode;
+use elp_ide_db::elp_base_db::FileId;
+use elp_ide_db::source_change::SourceChange;
+use elp_syntax::TextRange;
+use elp_text_edit::TextEdit;
+use fxhash::FxHashMap;
+use hir::AnyExpr;
+use hir::Atom;
+use hir::Body;
+use hir::Expr;
+use hir::ExprId;
+use hir::FunctionDef;
+use hir::Literal;
+use hir::Semantic;
+use hir::Strategy;
+use hir::fold::MacroStrategy;
+use hir::fold::ParenStrategy;
+
+use super::Diagnostic;
+use super::DiagnosticConditions;
+use super::DiagnosticDescriptor;
+use super::Severity;
+use crate::fix;
+
+pub(crate) static DESCRIPTOR: DiagnosticDescriptor = DiagnosticDescriptor {
+    conditions: DiagnosticConditions {
+        experimental: false,
+        include_generated: false,
+        include_tests: true,
+        default_disabled: false,
+    },
+    codes: &[DiagnosticCode::DuplicateMapKey],
+    checker: &|diagnostics, sema, file_id, _ext, _config| {
+        sema.for_each_function(file_id, |def| {
+            check_function(diagnostics, sema, file_id, def);
+        });
+    },
+};
+
+/// A map key which is statically known.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+enum LiteralKey {
+    Atom(Atom),
+    Integer(i128),
+    String(String),
+}
+
+fn literal_key(body: &Body, expr_id: ExprId) -> Option<LiteralKey> {
+    match &body[expr_id] {
+        Expr::Literal(Literal::Atom(atom)) => Some(LiteralKey::Atom(*atom)),
+        Expr::Literal(Literal::Integer(int)) => Some(LiteralKey::Integer(int.value)),
+        Expr::Literal(Literal::String(string)) => Some(LiteralKey::String(string.as_string())),
+        _ => None,
+    }
+}
+
+fn check_function(
+    diagnostics: &mut Vec<Diagnostic>,
+    sema: &Semantic,
+    file_id: FileId,
+    def: &FunctionDef,
+) {
+    let def_fb = def.in_function_body(sema, def);
+    def_fb.fold_function(
+        Strategy {
+            macros: MacroStrategy::DoNotExpand,
+            parens: ParenStrategy::InvisibleParens,
+        },
+        (),
+        &mut |_acc, clause_id, ctx| {
+            let AnyExpr::Expr(Expr::Map { fields }) = &ctx.item else {
+                return;
+            };
+            let body = def_fb.body(clause_id);
+            let key_range = |idx: usize| {
+                def_fb
+                    .range_for_expr(clause_id, fields[idx].0)
+                    .filter(|range| range.file_id == file_id)
+                    .map(|range| range.range)
+            };
+            let mut seen: FxHashMap<LiteralKey, usize> = FxHashMap::default();
+            for (idx, (key, _)) in fields.iter().enumerate() {
+                let Some(literal) = literal_key(&body, *key) else {
+                    continue;
+                };
+                if let Some(earlier) = seen.insert(literal, idx)
+                    && let Some(range) = key_range(idx)
+                    && let (Some(earlier_range), Some(next_range)) =
+                        (key_range(earlier), key_range(earlier + 1))
+                {
+                    let key_text = &sema.db.file_text(file_id)[range];
+                    // Removing the earlier value must not drop a side effect
+                    let fixes = matches!(body[fields[earlier].1], Expr::Literal(_) | Expr::Var(_))
+                        .then(|| {
+                            // Remove up to the next key, which exists since
+                            // the earlier association is not the last one
+                            let removed = TextRange::new(earlier_range.start(), next_range.start());
+                            vec![fix(
+                                "remove_duplicate_map_key",
+                                &format!("Remove the earlier `{key_text}` association"),
+                                SourceChange::from_text_edit(file_id, TextEdit::delete(removed)),
+                                range,
+                            )]
+                        });
+                    diagnostics.push(
+                        Diagnostic::new(
+                            DiagnosticCode::DuplicateMapKey,
+                            format!(
+                                "Duplicate key `{key_text}` in map, only the last value is kept."
+                            ),
+                            range,
+                        )
+                        .with_severity(Severity::Warning)
+                        .with_fixes(fixes)
+                        .with_ignore_fix(sema, file_id),
+                    );
+                }
+            }
+        },
+    )
+}
+
+#[cfg(test)]
+mod tests {
+
+    use expect_test::Expect;
+    use expect_test::expect;
+
+    use crate::diagnostics::Diagnostic;
+    use crate::diagnostics::DiagnosticCode;
+    use crate::tests;
+
+    fn filter(d: &Diagnostic) -> bool {
+        d.code == DiagnosticCode::DuplicateMapKey
+    }
+
+    #[track_caller]
+    fn check_diagnostics(fixture: &str) {
+        tests::check_filtered_diagnostics(fixture, &filter)
+    }
+
+    #[track_caller]
+    fn check_fix(fixture_before: &str, fixture_after: Expect) {
+        tests::check_fix(fixture_before, fixture_after)
+    }
+
+    #[test]
+    fn detects_duplicate_atom_keys() {
+        check_diagnostics(
+            r#"
+         //- /src/main.erl
+         -module(main).
+         -export([f/1]).
+
+         f(X) -> #{a => 1, b => X, a => 2}.
+         %%                        ^ 💡 warning: Duplicate key `a` in map, only the last value is kept.
+            "#,
+        )
+    }
+
+    #[test]
+    fn ignores_distinct_and_non_literal_keys() {
+        check_diagnostics(
+            r#"
+         //- /src/main.erl
+         -module(main).
+         -export([f/2]).
+
+         f(K1, K2) -> #{a => 1, "a" => 2, 1 => 3, K1 => 4, K2 => 5, K1 => 6}.
+            "#,
+        )
+    }
+
+    #[test]
+    fn fix_removes_earlier_association() {
+        check_fix(
+            r#"
+         //- /src/main.erl
+         -module(main).
+         -export([f/1]).
+
+         f(X) -> #{a => 1, b => X, ~a => 2}.
+            "#,
+            expect![[r#"
+                -module(main).
+                -export([f/1]).
+
+                f(X) -> #{b => X, a => 2}.
+            "#]],
+        )
+    }
+
+    #[test]
+    fn no_fix_when_earlier_value_has_side_effects() {
+        check_fix(
+            r#"
+         //- /src/main.erl
+         -module(main).
+         -export([f/0]).
+
+         f() -> #{a => g(), ~a => 2}.
+
+         g() -> ok.
+            "#,
+            expect![[r#"
+                -module(main).
+                -export([f/0]).
+
+                % elp:ignore W0087 (duplicate_map_key)
+                f() -> #{a => g(), a => 2}.
+
+                g() -> ok.
+            "#]],
+        )
+    }
+}
@@ -30,7 +30,8 @@ pub(crate) static DESCRIPTOR: DiagnosticDescriptor = DiagnosticDescriptor {
         include_tests: false, // Allow duplication in test fixtures
         default_disabled: false,
     },
-    checker: &|diags, sema, file_id, _file_kind| {
+    codes: &[DiagnosticCode::DuplicateModule],
+    checker: &|diags, sema, file_id, _file_kind, _config| {
         check_file(diags, sema, &file_id);
     },
 };
@@ -42,7 +42,8 @@ pub(crate) static DESCRIPTOR: DiagnosticDescriptor = DiagnosticDescriptor {
         include_tests: true,
         default_disabled: false,
     },
-    checker: &|diags, sema, file_id, _ext| {
+    codes: &[DiagnosticCode::OldEdocSyntax],
+    checker: &|diags, sema, file_id, _ext, _config| {
         check(diags, sema, file_id);
     },
 };
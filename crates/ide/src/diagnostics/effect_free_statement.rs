@@ -48,7 +48,8 @@ pub(crate) static DESCRIPTOR: DiagnosticDescriptor = DiagnosticDescriptor {
         include_tests: true,
         default_disabled: false,
     },
-    checker: &|diags, sema, file_id, _ext| {
+    codes: &[DiagnosticCode::StatementHasNoEffect],
+    checker: &|diags, sema, file_id, _ext, _config| {
         effect_free_statement(diags, sema, file_id);
     },
 };
@@ -159,7 +160,7 @@ fn has_no_effect(def_fb: &InFunctionClauseBody<&FunctionDef>, expr_id: &ExprId)
 }
 
 #[allow(clippy::match_like_matches_macro)]
-fn is_statement(expr: &ast::Expr) -> bool {
+pub(crate) fn is_statement(expr: &ast::Expr) -> bool {
     let syntax = expr.syntax();
     match syntax.parent() {
         Some(parent) => match parent.kind() {
@@ -179,7 +180,7 @@ fn is_macro_usage(expr: &ast::Expr) -> bool {
     syntax.kind() == SyntaxKind::MACRO_CALL_EXPR
 }
 
-fn is_followed_by(expected_kind: SyntaxKind, expr: &ast::Expr) -> bool {
+pub(crate) fn is_followed_by(expected_kind: SyntaxKind, expr: &ast::Expr) -> bool {
     let node = expr.syntax();
     let elements = iter::successors(node.next_sibling_or_token(), |n| {
         (*n).next_sibling_or_token()
@@ -240,7 +241,8 @@ mod tests {
         let config = DiagnosticsConfig::default()
             .disable(DiagnosticCode::BinaryStringToSigil)
             .disable(DiagnosticCode::UnspecificInclude)
-            .disable(DiagnosticCode::NoCatch);
+            .disable(DiagnosticCode::NoCatch)
+            .disable(DiagnosticCode::RedundantBlock);
         check_diagnostics_with_config(config, fixture)
     }
 
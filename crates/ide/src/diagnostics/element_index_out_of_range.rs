@@ -0,0 +1,156 @@
+/*
+ * Copyright (c) Meta Platforms, Inc. and affiliates.
+ *
+ * This source code is dual-licensed under either the MIT license found in the
+ * LICENSE-MIT file in the root directory of this source tree or the Apache
+ * License, Version 2.0 found in the LICENSE-APACHE file in the root directory
+ * of this source tree. You may select, at your option, one of the
+ * above-listed licenses.
+ */
+
+//! Lint: element_index_out_of_range
+//!
+//! Return an error for a call to `element/2` or `setelement/3` with a
+//! literal index outside of the tuple, which always raises `badarg`. The
+//! size of the tuple is known when it is a literal, or from its eqwalizer
+//! type. There is no fix.
+
+use std::borrow::Cow;
+
+use elp_types_db::eqwalizer::types::Type;
+use hir::Expr;
+use hir::Literal;
+
+use crate::FunctionMatch;
+use crate::codemod_helpers::CheckCallCtx;
+use crate::diagnostics::DiagnosticCode;
+use crate::diagnostics::FunctionCallLinter;
+use crate::diagnostics::Linter;
+use crate::diagnostics::Severity;
+use crate::lazy_function_matches;
+
+pub(crate) struct ElementIndexOutOfRangeLinter;
+
+impl Linter for ElementIndexOutOfRangeLinter {
+    fn id(&self) -> DiagnosticCode {
+        DiagnosticCode::ElementIndexOutOfRange
+    }
+
+    fn description(&self) -> &'static str {
+        "Tuple index out of range."
+    }
+
+    fn severity(&self) -> Severity {
+        Severity::Error
+    }
+}
+
+/// The literal index, and the size of the tuple it is out of range for.
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub(crate) struct OutOfRange {
+    index: i128,
+    size: usize,
+}
+
+impl FunctionCallLinter for ElementIndexOutOfRangeLinter {
+    type Context = OutOfRange;
+
+    fn match_description(&self, context: &Self::Context) -> Cow<'_, str> {
+        Cow::Owned(format!(
+            "Index {} is out of range for a tuple of size {}, this always raises `badarg`.",
+            context.index, context.size
+        ))
+    }
+
+    fn matches_functions(&self) -> Vec<FunctionMatch> {
+        lazy_function_matches![vec![
+            FunctionMatch::mfa("erlang", "element", 2),
+            FunctionMatch::mfa("erlang", "setelement", 3),
+        ]]
+    }
+
+    fn check_match(&self, context: &CheckCallCtx<'_, ()>) -> Option<Self::Context> {
+        let body = context.in_clause.body();
+        let args = context.args.as_vec();
+        let (index, tuple) = (*args.first()?, *args.get(1)?);
+        let Expr::Literal(Literal::Integer(index)) = &body[index] else {
+            return None;
+        };
+        let size = match &body[tuple] {
+            Expr::Tuple { exprs } => exprs.len(),
+            // Fall back on the eqwalizer type, if available
+            _ => match context.in_clause.sema.expr_type(&body, &tuple) {
+                Some(Type::TupleType(ty)) => ty.arg_tys.len(),
+                _ => return None,
+            },
+        };
+        let index = index.value;
+        if 1 <= index && index <= size as i128 {
+            return None;
+        }
+        Some(OutOfRange { index, size })
+    }
+}
+
+pub(crate) static LINTER: ElementIndexOutOfRangeLinter = ElementIndexOutOfRangeLinter;
+
+#[cfg(test)]
+mod tests {
+
+    use crate::diagnostics::Diagnostic;
+    use crate::diagnostics::DiagnosticCode;
+    use crate::tests;
+
+    fn filter(d: &Diagnostic) -> bool {
+        d.code == DiagnosticCode::ElementIndexOutOfRange
+    }
+
+    #[track_caller]
+    fn check_diagnostics(fixture: &str) {
+        tests::check_filtered_diagnostics(fixture, &filter)
+    }
+
+    #[test]
+    fn detects_out_of_range_index() {
+        check_diagnostics(
+            r#"
+  //- /src/main.erl
+  -module(main).
+
+  get() ->
+      element(3, {a, b}).
+  %%  ^^^^^^^ 💡 error: Index 3 is out of range for a tuple of size 2, this always raises `badarg`.
+
+  set(V) ->
+      erlang:setelement(0, {a, b}, V).
+  %%  ^^^^^^^^^^^^^^^^^ 💡 error: Index 0 is out of range for a tuple of size 2, this always raises `badarg`.
+  //- /opt/lib/erts-13.0/src/erlang.erl otp_app:/opt/lib/erts-13.0
+  -module(erlang).
+  -export([element/2, setelement/3]).
+  element(_, _) -> ok.
+  setelement(_, _, _) -> ok.
+            "#,
+        )
+    }
+
+    #[test]
+    fn in_range_index_not_flagged() {
+        check_diagnostics(
+            r#"
+  //- /src/main.erl
+  -module(main).
+
+  get() ->
+      element(2, {a, b}).
+
+  set(T, V) ->
+      setelement(3, T, V).
+  //- /opt/lib/erts-13.0/src/erlang.erl otp_app:/opt/lib/erts-13.0
+  -module(erlang).
+  -export([element/2, setelement/3]).
+  element(_, _) -> ok.
+  setelement(_, _, _) -> ok.
+            "#,
+        )
+    }
+}
@@ -0,0 +1,169 @@
+/*
+ * Copyright (c) Meta Platforms, Inc. and affiliates.
+ *
+ * This source code is dual-licensed under either the MIT license found in the
+ * LICENSE-MIT file in the root directory of this source tree or the Apache
+ * License, Version 2.0 found in the LICENSE-APACHE file in the root directory
+ * of this source tree. You may select, at your option, one of the
+ * above-listed licenses.
+ */
+
+//! Lint: empty_record_update
+//!
+//! Return a warning for a record update without any field, e.g.
+//! `R#rec{}`, which evaluates to `R` itself, only checking that it is a
+//! `rec` record. A record construction such as `#rec{}` is not affected.
+//!
+//! The fix replaces the update with `R`, and its label says that this drops
+//! the check.
+
+use elp_ide_db::DiagnosticCode;
+use elp_ide_db::elp_base_db::FileId;
+use elp_ide_db::source_change::SourceChangeBuilder;
+use elp_syntax::AstNode;
+use elp_syntax::ast;
+use hir::AnyExpr;
+use hir::Expr;
+use hir::FunctionDef;
+use hir::Semantic;
+use hir::Strategy;
+use hir::fold::MacroStrategy;
+use hir::fold::ParenStrategy;
+
+use super::Diagnostic;
+use super::DiagnosticConditions;
+use super::DiagnosticDescriptor;
+use super::Severity;
+use crate::fix;
+
+pub(crate) static DESCRIPTOR: DiagnosticDescriptor = DiagnosticDescriptor {
+    conditions: DiagnosticConditions {
+        experimental: false,
+        include_generated: false,
+        include_tests: true,
+        default_disabled: false,
+    },
+    codes: &[DiagnosticCode::EmptyRecordUpdate],
+    checker: &|diagnostics, sema, file_id, _ext, _config| {
+        sema.for_each_function(file_id, |def| {
+            check_function(diagnostics, sema, file_id, def)
+        });
+    },
+};
+
+fn check_function(
+    diagnostics: &mut Vec<Diagnostic>,
+    sema: &Semantic,
+    file_id: FileId,
+    def: &FunctionDef,
+) {
+    let def_fb = def.in_function_body(sema, def);
+    def_fb.fold_function(
+        Strategy {
+            macros: MacroStrategy::DoNotExpand,
+            parens: ParenStrategy::InvisibleParens,
+        },
+        (),
+        &mut |_acc, clause_id, ctx| {
+            if let AnyExpr::Expr(Expr::RecordUpdate { fields, .. }) = &ctx.item {
+                if !fields.is_empty() {
+                    return;
+                }
+                let map = def_fb.get_body_map(clause_id);
+                if let Some(diagnostic) = ctx
+                    .item_id
+                    .as_expr_id()
+                    .and_then(|expr_id| map.expr(expr_id))
+                    .filter(|ptr| ptr.file_id() == file_id)
+                    .and_then(|ptr| match ptr.to_ast(sema.db) {
+                        ast::Expr::RecordUpdateExpr(update) => Some(update),
+                        _ => None,
+                    })
+                    .and_then(|update| make_diagnostic(sema, file_id, &update))
+                {
+                    diagnostics.push(diagnostic);
+                }
+            }
+        },
+    )
+}
+
+fn make_diagnostic(
+    sema: &Semantic,
+    file_id: FileId,
+    update: &ast::RecordUpdateExpr,
+) -> Option<Diagnostic> {
+    let range = update.syntax().text_range();
+    let name = update.name()?.name()?.syntax().text().to_string();
+    let record = update.expr()?.syntax().text().to_string();
+    let mut builder = SourceChangeBuilder::new(file_id);
+    builder.replace(range, &record);
+    let fixes = vec![fix(
+        "empty_record_update",
+        &format!("Replace with `{record}`, dropping the `{name}` record check"),
+        builder.finish(),
+        range,
+    )];
+    Some(
+        Diagnostic::new(
+            DiagnosticCode::EmptyRecordUpdate,
+            format!(
+                "Record update without any field only checks that the value is a `{name}` record."
+            ),
+            range,
+        )
+        .with_severity(Severity::WeakWarning)
+        .with_ignore_fix(sema, file_id)
+        .with_fixes(Some(fixes)),
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use expect_test::expect;
+
+    use crate::diagnostics::Diagnostic;
+    use crate::diagnostics::DiagnosticCode;
+    use crate::tests;
+
+    fn filter(d: &Diagnostic) -> bool {
+        d.code == DiagnosticCode::EmptyRecordUpdate
+    }
+
+    #[track_caller]
+    fn check_diagnostics(fixture: &str) {
+        tests::check_filtered_diagnostics(fixture, &filter)
+    }
+
+    #[test]
+    fn flags_empty_update() {
+        check_diagnostics(
+            r#"
+              -module(main).
+              -record(rec, {a, b}).
+              foo(R) -> R#rec{}.
+              %%        ^^^^^^^ 💡 weak: Record update without any field only checks that the value is a `rec` record.
+              bar(R) -> {R#rec{a = 1}, #rec{}}.
+            "#,
+        )
+    }
+
+    #[test]
+    fn fix_keeps_record() {
+        tests::check_specific_fix(
+            "Replace with `R`, dropping the `rec` record check",
+            r#"
+              -module(main).
+              -record(rec, {a, b}).
+              foo(R) -> bar(R#r~ec{}).
+              bar(R) -> R.
+            "#,
+            expect![[r#"
+              -module(main).
+              -record(rec, {a, b}).
+              foo(R) -> bar(R).
+              bar(R) -> R.
+            "#]],
+        )
+    }
+}
@@ -74,7 +74,8 @@ pub(crate) static DESCRIPTOR: DiagnosticDescriptor = DiagnosticDescriptor {
         include_tests: true,
         default_disabled: false,
     },
-    checker: &|acc, sema, file_id, _ext| {
+    codes: &[DiagnosticCode::EqualityCheckWithUnnecessaryOperator],
+    checker: &|acc, sema, file_id, _ext, _config| {
         // N.B. we only apply when the operator is `=:=`/`=/=` (not `==`/`/=`)
         // as we can't be sure that the values with be safe to match otherwise
         // (e.g. floats are not safely matchable)
@@ -23,7 +23,6 @@ use elp_types_db::eqwalizer::tc_diagnostics::TypeError;
 use elp_types_db::eqwalizer::types::AtomLitType;
 use elp_types_db::eqwalizer::types::TupleType;
 use elp_types_db::eqwalizer::types::Type;
-use hir::FunctionBody;
 use hir::InFile;
 use hir::Literal;
 use hir::Semantic;
@@ -101,7 +100,7 @@ fn add_spec_fix(
     got: &Type,
     diagnostic: &mut Diagnostic,
 ) -> Option<()> {
-    let (spec_id, spec_body, _function_body) = get_spec(sema, file_id, diagnostic.range.start())?;
+    let (spec_id, spec_body) = get_spec(sema, file_id, diagnostic.range.start())?;
     if let [sig] = &spec_body.sigs[..] {
         match &spec_body.body[sig.result] {
             TypeExpr::Literal(Literal::Atom(_)) => {
@@ -165,12 +164,10 @@ fn get_spec(
     sema: &Semantic,
     file_id: FileId,
     offset: TextSize,
-) -> Option<(InFile<SpecId>, Arc<SpecBody>, Arc<FunctionBody>)> {
+) -> Option<(InFile<SpecId>, Arc<SpecBody>)> {
     let token = find_best_token(sema, FilePosition { file_id, offset })?;
-    let function = sema.find_enclosing_function_body(file_id, &token.value.parent()?)?;
-    let spec_body: Arc<SpecBody> = function.spec_body()?;
-    let spec_id = InFile::new(file_id, spec_body.spec_id()?);
-    Some((spec_id, spec_body, function))
+    let spec_id = sema.find_enclosing_spec(file_id, &token.value.parent()?)?;
+    Some((spec_id, sema.db.spec_body(spec_id)))
 }
 
 #[cfg(test)]
@@ -0,0 +1,328 @@
+/*
+ * Copyright (c) Meta Platforms, Inc. and affiliates.
+ *
+ * This source code is dual-licensed under either the MIT license found in the
+ * LICENSE-MIT file in the root directory of this source tree or the Apache
+ * License, Version 2.0 found in the LICENSE-APACHE file in the root directory
+ * of this source tree. You may select, at your option, one of the
+ * above-listed licenses.
+ */
+
+//! Lint: erlang_get_stacktrace
+//!
+//! Return an error for calls to `erlang:get_stacktrace/0`, which was removed
+//! in OTP 24, and suggest binding the stacktrace in the enclosing `catch`
+//! clause instead, e.g. `catch Class:Reason:Stacktrace -> ...`.
+
+use elp_ide_assists::helpers::freshen_variable_name;
+use elp_ide_db::DiagnosticCode;
+use elp_ide_db::elp_base_db::FileId;
+use elp_ide_db::source_change::SourceChangeBuilder;
+use elp_ide_ssr::Match;
+use elp_syntax::AstNode;
+use elp_syntax::algo;
+use elp_syntax::ast;
+use hir::InFile;
+use hir::NameArity;
+use hir::Semantic;
+use hir::known;
+
+use crate::diagnostics::Linter;
+use crate::diagnostics::Severity;
+use crate::diagnostics::SsrPatternsLinter;
+use crate::fix;
+
+pub(crate) struct ErlangGetStacktraceLinter;
+
+impl Linter for ErlangGetStacktraceLinter {
+    fn id(&self) -> DiagnosticCode {
+        DiagnosticCode::ErlangGetStacktrace
+    }
+
+    fn description(&self) -> &'static str {
+        "`erlang:get_stacktrace/0` was removed in OTP 24, bind the stacktrace in the `catch` clause instead."
+    }
+
+    fn severity(&self) -> Severity {
+        Severity::Error
+    }
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub(crate) enum Call {
+    Remote,
+    Local,
+}
+
+impl SsrPatternsLinter for ErlangGetStacktraceLinter {
+    type Context = Call;
+
+    fn patterns(&self) -> Vec<(String, Self::Context)> {
+        vec![
+            ("ssr: erlang:get_stacktrace().".to_string(), Call::Remote),
+            ("ssr: get_stacktrace().".to_string(), Call::Local),
+        ]
+    }
+
+    fn is_match_valid(
+        &self,
+        context: &Self::Context,
+        matched: &Match,
+        sema: &Semantic,
+        file_id: FileId,
+    ) -> Option<bool> {
+        if matched.range.file_id != file_id {
+            // We've somehow ended up with a match in a different file - this means we've
+            // accidentally expanded a macro from a different file, or some other complex case that
+            // gets hairy, so bail out.
+            return None;
+        }
+        // A local call may be to a function of the module itself
+        let local_definition = *context == Call::Local
+            && sema
+                .def_map(file_id)
+                .get_function(&NameArity::new(known::get_stacktrace, 0))
+                .is_some();
+        Some(!local_definition)
+    }
+
+    fn fixes(
+        &self,
+        _context: &Self::Context,
+        matched: &Match,
+        sema: &Semantic,
+        file_id: FileId,
+    ) -> Option<Vec<elp_ide_assists::Assist>> {
+        let source = sema.parse(file_id).value;
+        let call = algo::find_node_at_range::<ast::Call>(source.syntax(), matched.range.range)?;
+        let range = call.syntax().text_range();
+        let clause = call.syntax().ancestors().find_map(ast::CatchClause::cast)?;
+        if !clause.body()?.syntax().text_range().contains_range(range) {
+            return None;
+        }
+
+        let vars_in_clause =
+            sema.find_vars_in_clause_ast(&InFile::new(file_id, &ast::Expr::Call(call.clone())));
+        let mut builder = SourceChangeBuilder::new(file_id);
+        let stacktrace = match clause.stack().and_then(|stack| stack.class()) {
+            Some(var) => {
+                let text = var.syntax().text().to_string();
+                // An ignored variable such as `_` cannot be used in the body
+                if text.starts_with('_') {
+                    let fresh =
+                        freshen_variable_name(sema, "Stacktrace".to_string(), &vars_in_clause);
+                    builder.replace(var.syntax().text_range(), fresh.clone());
+                    fresh
+                } else {
+                    text
+                }
+            }
+            None => {
+                let pat = clause.pat()?.syntax().text_range();
+                let var = freshen_variable_name(sema, "Stacktrace".to_string(), &vars_in_clause);
+                // A pattern without a class only catches throws
+                if clause.class().is_none() {
+                    builder.insert(pat.start(), "throw:");
+                }
+                builder.insert(pat.end(), format!(":{var}"));
+                var
+            }
+        };
+        builder.replace(range, stacktrace);
+        Some(vec![fix(
+            "erlang_get_stacktrace",
+            "Bind the stacktrace in the `catch` clause",
+            builder.finish(),
+            range,
+        )])
+    }
+}
+
+pub(crate) static LINTER: ErlangGetStacktraceLinter = ErlangGetStacktraceLinter;
+
+#[cfg(test)]
+mod tests {
+
+    use expect_test::Expect;
+    use expect_test::expect;
+
+    use crate::diagnostics::Diagnostic;
+    use crate::diagnostics::DiagnosticCode;
+    use crate::tests;
+
+    fn filter(d: &Diagnostic) -> bool {
+        d.code == DiagnosticCode::ErlangGetStacktrace
+    }
+
+    #[track_caller]
+    fn check_diagnostics(fixture: &str) {
+        tests::check_filtered_diagnostics(fixture, &filter)
+    }
+
+    #[track_caller]
+    fn check_fix(fixture_before: &str, fixture_after: Expect) {
+        tests::check_fix(fixture_before, fixture_after)
+    }
+
+    #[test]
+    fn detects_get_stacktrace() {
+        check_diagnostics(
+            r#"
+         //- /src/main.erl
+         -module(main).
+
+         foo() ->
+             try bar()
+             catch error:Reason -> {Reason, erlang:get_stacktrace()}
+         %%                                 ^^^^^^^^^^^^^^^^^^^^^^^ 💡 error: `erlang:get_stacktrace/0` was removed in OTP 24, bind the stacktrace in the `catch` clause instead.
+             end.
+         baz() -> get_stacktrace().
+         %%       ^^^^^^^^^^^^^^^^ 💡 error: `erlang:get_stacktrace/0` was removed in OTP 24, bind the stacktrace in the `catch` clause instead.
+         bar() -> ok.
+            "#,
+        )
+    }
+
+    #[test]
+    fn ignores_local_get_stacktrace() {
+        check_diagnostics(
+            r#"
+         //- /src/main.erl
+         -module(main).
+
+         foo() -> get_stacktrace().
+         get_stacktrace() -> [].
+            "#,
+        )
+    }
+
+    #[test]
+    fn fixes_get_stacktrace_in_catch_clause() {
+        check_fix(
+            r#"
+         //- /src/main.erl
+         -module(main).
+
+         foo(Stacktrace) ->
+             try bar()
+             catch
+                 % elp:ignore W0017 (undefined_function)
+                 error:Reason -> {Reason, Stacktrace, erlang:get_stac~ktrace()}
+             end.
+         bar() -> ok.
+            "#,
+            expect![[r#"
+         -module(main).
+
+         foo(Stacktrace) ->
+             try bar()
+             catch
+                 % elp:ignore W0017 (undefined_function)
+                 error:Reason:Stacktrace0 -> {Reason, Stacktrace, Stacktrace0}
+             end.
+         bar() -> ok.
+            "#]],
+        )
+    }
+
+    #[test]
+    fn fixes_get_stacktrace_with_bound_stacktrace() {
+        check_fix(
+            r#"
+         //- /src/main.erl
+         -module(main).
+
+         foo() ->
+             try bar()
+             catch
+                 error:Reason:St -> {Reason, get_stac~ktrace()}
+             end.
+         bar() -> ok.
+            "#,
+            expect![[r#"
+         -module(main).
+
+         foo() ->
+             try bar()
+             catch
+                 error:Reason:St -> {Reason, St}
+             end.
+         bar() -> ok.
+            "#]],
+        )
+    }
+
+    #[test]
+    fn fixes_get_stacktrace_with_ignored_stacktrace() {
+        check_fix(
+            r#"
+         //- /src/main.erl
+         -module(main).
+
+         foo() ->
+             try bar()
+             catch
+                 error:Reason:_ -> {Reason, get_stac~ktrace()}
+             end.
+         bar() -> ok.
+            "#,
+            expect![[r#"
+         -module(main).
+
+         foo() ->
+             try bar()
+             catch
+                 error:Reason:Stacktrace -> {Reason, Stacktrace}
+             end.
+         bar() -> ok.
+            "#]],
+        )
+    }
+
+    #[test]
+    fn fixes_get_stacktrace_for_throw() {
+        check_fix(
+            r#"
+         //- /src/main.erl
+         -module(main).
+
+         foo() ->
+             try bar()
+             catch
+                 Reason -> {Reason, get_stac~ktrace()}
+             end.
+         bar() -> ok.
+            "#,
+            expect![[r#"
+         -module(main).
+
+         foo() ->
+             try bar()
+             catch
+                 throw:Reason:Stacktrace -> {Reason, Stacktrace}
+             end.
+         bar() -> ok.
+            "#]],
+        )
+    }
+
+    #[test]
+    fn only_ignore_fix_outside_catch_clause() {
+        check_fix(
+            r#"
+         //- /src/main.erl
+         -module(main).
+
+         % elp:ignore W0017 (undefined_function)
+         foo() -> erlang:get_stac~ktrace().
+            "#,
+            expect![[r#"
+         -module(main).
+
+         % elp:ignore W0017 (undefined_function)
+         % elp:ignore W0069 (erlang_get_stacktrace)
+         foo() -> erlang:get_stacktrace().
+            "#]],
+        )
+    }
+}
@@ -0,0 +1,146 @@
+/*
+ * Copyright (c) Meta Platforms, Inc. and affiliates.
+ *
+ * This source code is dual-licensed under either the MIT license found in the
+ * LICENSE-MIT file in the root directory of this source tree or the Apache
+ * License, Version 2.0 found in the LICENSE-APACHE file in the root directory
+ * of this source tree. You may select, at your option, one of the
+ * above-listed licenses.
+ */
+
+//! Lint: erlang_now
+//!
+//! Return a warning for calls to the deprecated `erlang:now/0`. There is no
+//! fix, since the replacement depends on what the value is used for.
+
+use hir::CallTarget;
+use hir::Name;
+use hir::NameArity;
+
+use crate::FunctionMatch;
+use crate::codemod_helpers::CheckCallCtx;
+use crate::diagnostics::DiagnosticCode;
+use crate::diagnostics::FunctionCallLinter;
+use crate::diagnostics::Linter;
+use crate::diagnostics::Severity;
+use crate::lazy_function_matches;
+
+pub(crate) struct ErlangNowLinter;
+
+impl Linter for ErlangNowLinter {
+    fn id(&self) -> DiagnosticCode {
+        DiagnosticCode::ErlangNow
+    }
+
+    fn description(&self) -> &'static str {
+        "`erlang:now/0` is deprecated. Use `erlang:system_time/1` for timestamps, `erlang:monotonic_time/0` to measure elapsed time, or `erlang:unique_integer/1` for unique values."
+    }
+
+    fn severity(&self) -> Severity {
+        Severity::WeakWarning
+    }
+}
+
+impl FunctionCallLinter for ErlangNowLinter {
+    type Context = ();
+
+    fn matches_functions(&self) -> Vec<FunctionMatch> {
+        lazy_function_matches![vec![FunctionMatch::mfa("erlang", "now", 0)]]
+    }
+
+    fn check_match(&self, context: &CheckCallCtx<'_, ()>) -> Option<Self::Context> {
+        // A local `now()` call is to the module's own function, or one from
+        // an included header, if there is one
+        if let CallTarget::Local { .. } = context.target {
+            let sema = context.in_clause.sema;
+            let file_id = context.in_clause.value.file.file_id;
+            let now = NameArity::new(Name::from_erlang_service("now"), 0);
+            if sema.def_map(file_id).get_function(&now).is_some() {
+                return None;
+            }
+        }
+        Some(())
+    }
+}
+
+pub(crate) static LINTER: ErlangNowLinter = ErlangNowLinter;
+
+#[cfg(test)]
+mod tests {
+
+    use crate::diagnostics::Diagnostic;
+    use crate::diagnostics::DiagnosticCode;
+    use crate::tests;
+
+    fn filter(d: &Diagnostic) -> bool {
+        d.code == DiagnosticCode::ErlangNow
+    }
+
+    #[track_caller]
+    fn check_diagnostics(fixture: &str) {
+        tests::check_filtered_diagnostics(fixture, &filter)
+    }
+
+    #[test]
+    fn detects_erlang_now() {
+        check_diagnostics(
+            r#"
+  //- /src/main.erl
+  -module(main).
+
+  remote() ->
+      erlang:now().
+  %%  ^^^^^^^^^^ 💡 weak: `erlang:now/0` is deprecated. Use `erlang:system_time/1` for timestamps, `erlang:monotonic_time/0` to measure elapsed time, or `erlang:unique_integer/1` for unique values.
+
+  local() ->
+      now().
+  %%  ^^^ 💡 weak: `erlang:now/0` is deprecated. Use `erlang:system_time/1` for timestamps, `erlang:monotonic_time/0` to measure elapsed time, or `erlang:unique_integer/1` for unique values.
+  //- /opt/lib/erts-13.0/src/erlang.erl otp_app:/opt/lib/erts-13.0
+  -module(erlang).
+  -export([now/0]).
+  now() -> {0, 0, 0}.
+            "#,
+        )
+    }
+
+    #[test]
+    fn local_now_not_flagged() {
+        check_diagnostics(
+            r#"
+  //- /src/main.erl
+  -module(main).
+  -compile({no_auto_import, [now/0]}).
+
+  foo() ->
+      now().
+
+  now() -> {0, 0, 0}.
+  //- /opt/lib/erts-13.0/src/erlang.erl otp_app:/opt/lib/erts-13.0
+  -module(erlang).
+  -export([now/0]).
+  now() -> {0, 0, 0}.
+            "#,
+        )
+    }
+
+    #[test]
+    fn header_now_not_flagged() {
+        check_diagnostics(
+            r#"
+  //- /src/main.erl
+  -module(main).
+  -compile({no_auto_import, [now/0]}).
+  -include("time.hrl").
+
+  foo() ->
+      now().
+  //- /src/time.hrl
+  now() -> {0, 0, 0}.
+  //- /opt/lib/erts-13.0/src/erlang.erl otp_app:/opt/lib/erts-13.0
+  -module(erlang).
+  -export([now/0]).
+  now() -> {0, 0, 0}.
+            "#,
+        )
+    }
+}
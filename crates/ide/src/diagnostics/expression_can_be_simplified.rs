@@ -14,17 +14,23 @@
 //! `[] ++ Xs`, `0 + X`, etc. This is typically useful as a simplification rule in codemods.
 
 use elp_ide_db::source_change::SourceChangeBuilder;
+use elp_syntax::ast::CompOp;
 use elp_syntax::ast::LogicOp;
+use elp_syntax::ast::Ordering;
 use elp_syntax::ast::UnaryOp;
 use hir::AnyExprId;
+use hir::AnyExprRef;
+use hir::Body;
 use hir::ClauseId;
 use hir::FunctionDef;
 use hir::InFunctionBody;
 use hir::Literal;
 use hir::Name;
 use hir::Strategy;
+use hir::fold::AnyCallBackCtx;
 use hir::fold::MacroStrategy;
 use hir::fold::ParenStrategy;
+use hir::fold::ParentId;
 use hir::known;
 
 use super::DiagnosticConditions;
@@ -47,7 +53,8 @@ pub(crate) static DESCRIPTOR: DiagnosticDescriptor = DiagnosticDescriptor {
         include_tests: true,
         default_disabled: false,
     },
-    checker: &|diags, sema, file_id, _ext| {
+    codes: &[DiagnosticCode::ExpressionCanBeSimplified],
+    checker: &|diags, sema, file_id, _ext, _config| {
         diagnostic(diags, sema, file_id);
     },
 };
@@ -70,7 +77,8 @@ fn diagnostic(diags: &mut Vec<Diagnostic>, sema: &Semantic, file_id: FileId) {
                             simplify_binary_op(op, lhs, rhs, clause_id, sema, &def_fb)
                         }
                         hir::AnyExpr::Expr(hir::Expr::UnaryOp { expr, op }) => {
-                            simplify_unary_op(op, expr, clause_id, sema, &def_fb)
+                            let in_operand = is_operand(&ctx, &def_fb.body(clause_id));
+                            simplify_unary_op(op, expr, in_operand, clause_id, sema, &def_fb)
                         }
                         _ => None,
                     };
@@ -189,6 +197,31 @@ fn simplify_binary_op(
             Some(rhs_str.to_string())
         }
 
+        // Constant results. The right operand is not evaluated after a
+        // constant left operand, while a left operand is only dropped if
+        // it is pure. This assumes it is a boolean, as otherwise the
+        // expression raises `badarg`.
+        (lhs, BinaryOp::LogicOp(LogicOp::And { lazy: true }), _rhs)
+            if is_literal_atom(sema, lhs, known::false_name) =>
+        {
+            Some("false".to_string())
+        }
+        (lhs, BinaryOp::LogicOp(LogicOp::And { lazy: true }), rhs)
+            if is_pure(sema, lhs) && is_literal_atom(sema, rhs, known::false_name) =>
+        {
+            Some("false".to_string())
+        }
+        (lhs, BinaryOp::LogicOp(LogicOp::Or { lazy: true }), _rhs)
+            if is_literal_atom(sema, lhs, known::true_name) =>
+        {
+            Some("true".to_string())
+        }
+        (lhs, BinaryOp::LogicOp(LogicOp::Or { lazy: true }), rhs)
+            if is_pure(sema, lhs) && is_literal_atom(sema, rhs, known::true_name) =>
+        {
+            Some("true".to_string())
+        }
+
         _ => None,
     }
 }
@@ -196,6 +229,7 @@ fn simplify_binary_op(
 fn simplify_unary_op(
     op: UnaryOp,
     expr_id: hir::ExprId,
+    in_operand: bool,
     clause_id: ClauseId,
     sema: &Semantic,
     def_fb: &InFunctionBody<&FunctionDef>,
@@ -214,11 +248,69 @@ fn simplify_unary_op(
         (expr, UnaryOp::Not) if is_literal_atom(sema, expr, known::false_name) => {
             Some("true".to_string())
         }
+        (hir::Expr::Paren { expr }, UnaryOp::Not) => match &body[*expr] {
+            hir::Expr::BinaryOp {
+                lhs,
+                rhs,
+                op: BinaryOp::CompOp(comp_op),
+            } => {
+                if body.is_macro(AnyExprId::Expr(*lhs)) || body.is_macro(AnyExprId::Expr(*rhs)) {
+                    return None;
+                }
+                let lhs_str = to_string(lhs, sema, clause_id, def_fb)?;
+                let rhs_str = to_string(rhs, sema, clause_id, def_fb)?;
+                let comparison = format!("{lhs_str} {} {rhs_str}", negate_comp_op(*comp_op));
+                if in_operand {
+                    Some(format!("({comparison})"))
+                } else {
+                    Some(comparison)
+                }
+            }
+            _ => None,
+        },
 
         _ => None,
     }
 }
 
+/// The comparison operator giving the opposite result. Erlang terms are
+/// totally ordered and floats have no NaN, so `not (X < Y)` is always
+/// `X >= Y`, unlike in languages following IEEE 754 comparisons.
+fn negate_comp_op(op: CompOp) -> CompOp {
+    match op {
+        CompOp::Eq { strict, negated } => CompOp::Eq {
+            strict,
+            negated: !negated,
+        },
+        CompOp::Ord { ordering, strict } => CompOp::Ord {
+            ordering: match ordering {
+                Ordering::Less => Ordering::Greater,
+                Ordering::Greater => Ordering::Less,
+            },
+            strict: !strict,
+        },
+    }
+}
+
+/// Whether the current expression is the operand of an operator binding
+/// tighter than a comparison, so that a comparison replacing it needs
+/// parentheses.
+fn is_operand(ctx: &AnyCallBackCtx, body: &Body) -> bool {
+    match ctx.parent() {
+        ParentId::HirIdx(hir_idx) => match body.get_any(hir_idx.idx) {
+            AnyExprRef::Expr(hir::Expr::UnaryOp { .. }) => true,
+            AnyExprRef::Expr(hir::Expr::BinaryOp { op, .. }) => !matches!(
+                op,
+                BinaryOp::LogicOp(LogicOp::And { lazy: true })
+                    | BinaryOp::LogicOp(LogicOp::Or { lazy: true })
+                    | BinaryOp::Send
+            ),
+            _ => false,
+        },
+        _ => false,
+    }
+}
+
 fn is_empty_list_expr(expr: &hir::Expr) -> bool {
     match expr {
         hir::Expr::List { exprs, tail } => exprs.is_empty() && tail.is_none(),
@@ -226,13 +318,21 @@ fn is_empty_list_expr(expr: &hir::Expr) -> bool {
     }
 }
 
-fn is_literal_atom(sema: &Semantic, expr: &hir::Expr, name: Name) -> bool {
+pub(crate) fn is_literal_atom(sema: &Semantic, expr: &hir::Expr, name: Name) -> bool {
     match expr {
         hir::Expr::Literal(Literal::Atom(atom)) => atom.as_name(sema.db.upcast()) == name,
         _ => false,
     }
 }
 
+/// Whether evaluating the expression has no effect, and cannot raise an
+/// exception in a boolean operator.
+fn is_pure(sema: &Semantic, expr: &hir::Expr) -> bool {
+    matches!(expr, hir::Expr::Var(_))
+        || is_literal_atom(sema, expr, known::true_name)
+        || is_literal_atom(sema, expr, known::false_name)
+}
+
 fn is_integer(n: i128, expr: &hir::Expr) -> bool {
     match expr {
         hir::Expr::Literal(hir::Literal::Integer(i)) => i.value == n,
@@ -275,6 +375,7 @@ mod tests {
     f([] ++ [1]),
    %% ^^^^^^^^^ 💡 warning: Can be simplified to `[1]`.
     f([2] ++ [1]),
+   %% ^^^^^^^^^^ 💡 weak: Prepend the elements with `|` instead of `++`.
     f(X ++ [1]),
     ok.
 
@@ -335,6 +436,84 @@ mod tests {
         check_fix("f(X) -> false~ orelse X.", expect![["f(X) -> X."]]);
         check_fix("f(X) -> not false~.", expect![["f(X) -> true."]]);
         check_fix("f(X) -> not true~.", expect![["f(X) -> false."]]);
+        check_fix("f(X) -> false~ andalso X.", expect![["f(X) -> false."]]);
+        check_fix("f(X) -> true~ orelse X.", expect![["f(X) -> true."]]);
+        check_fix("f(X) -> X andalso ~false.", expect![["f(X) -> false."]]);
+        check_fix("f(X) -> X orelse ~true.", expect![["f(X) -> true."]]);
+    }
+
+    #[test]
+    fn test_constant_short_circuit_bool_ops() {
+        check_diagnostics(
+            r#"
+  -module(main).
+  constant(X) ->
+    f(X orelse true),
+   %% ^^^^^^^^^^^^^ 💡 warning: Can be simplified to `true`.
+    f(X andalso false),
+   %% ^^^^^^^^^^^^^^^ 💡 warning: Can be simplified to `false`.
+    f(true orelse g()),
+   %% ^^^^^^^^^^^^^^^ 💡 warning: Can be simplified to `true`.
+    ok.
+
+  side_effects() ->
+    f(g() orelse true),
+    f(g() andalso false),
+    ok.
+
+  f(X) -> X.
+  g() -> true.
+            "#,
+        )
+    }
+
+    #[test]
+    fn test_negated_comparisons() {
+        check_diagnostics(
+            r#"
+  -module(main).
+  comparisons(X, Y) ->
+    f(not (X == Y)),
+   %% ^^^^^^^^^^^^ 💡 warning: Can be simplified to `X /= Y`.
+    f(not (X < Y)),
+   %% ^^^^^^^^^^^ 💡 warning: Can be simplified to `X >= Y`.
+    f(not X),
+    f(not (X andalso Y)),
+    ok.
+
+  f(X) -> X.
+            "#,
+        )
+    }
+
+    #[test]
+    fn test_fixes_negated_comparisons() {
+        check_fix("f(X, Y) -> not~ (X == Y).", expect![["f(X, Y) -> X /= Y."]]);
+        check_fix("f(X, Y) -> not~ (X /= Y).", expect![["f(X, Y) -> X == Y."]]);
+        check_fix(
+            "f(X, Y) -> not~ (X =:= Y).",
+            expect![["f(X, Y) -> X =/= Y."]],
+        );
+        check_fix(
+            "f(X, Y) -> not~ (X =/= Y).",
+            expect![["f(X, Y) -> X =:= Y."]],
+        );
+        check_fix("f(X, Y) -> not~ (X < Y).", expect![["f(X, Y) -> X >= Y."]]);
+        check_fix("f(X, Y) -> not~ (X =< Y).", expect![["f(X, Y) -> X > Y."]]);
+        check_fix("f(X, Y) -> not~ (X > Y).", expect![["f(X, Y) -> X =< Y."]]);
+        check_fix("f(X, Y) -> not~ (X >= Y).", expect![["f(X, Y) -> X < Y."]]);
+    }
+
+    #[test]
+    fn test_fixes_negated_comparison_in_operand() {
+        check_fix(
+            "f(X, Y) -> not~ (X == Y) and Y.",
+            expect![["f(X, Y) -> (X /= Y) and Y."]],
+        );
+        check_fix(
+            "f(X, Y) -> not~ (X == Y) andalso Y.",
+            expect![["f(X, Y) -> X /= Y andalso Y."]],
+        );
     }
 
     #[test]
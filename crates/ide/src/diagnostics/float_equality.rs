@@ -0,0 +1,168 @@
+/*
+ * Copyright (c) Meta Platforms, Inc. and affiliates.
+ *
+ * This source code is dual-licensed under either the MIT license found in the
+ * LICENSE-MIT file in the root directory of this source tree or the Apache
+ * License, Version 2.0 found in the LICENSE-APACHE file in the root directory
+ * of this source tree. You may select, at your option, one of the
+ * above-listed licenses.
+ */
+
+//! Lint: float_equality
+//!
+//! Return a warning for an exact (in)equality comparison with a float,
+//! e.g. `X == 0.1`, as rounding errors make it fragile. There is no fix,
+//! since the tolerance to compare with depends on the domain.
+//!
+//! eqwalizer does not tell floats from integers, so only operands which
+//! are floats by construction are considered: float literals and `/`.
+
+use elp_ide_db::DiagnosticCode;
+use elp_ide_db::elp_base_db::FileId;
+use elp_syntax::ast::ArithOp;
+use elp_syntax::ast::BinaryOp;
+use elp_syntax::ast::CompOp;
+use elp_syntax::ast::UnaryOp;
+use hir::AnyExpr;
+use hir::Body;
+use hir::Expr;
+use hir::ExprId;
+use hir::FunctionDef;
+use hir::Literal;
+use hir::Semantic;
+use hir::Strategy;
+use hir::fold::MacroStrategy;
+use hir::fold::ParenStrategy;
+
+use super::Diagnostic;
+use super::DiagnosticConditions;
+use super::DiagnosticDescriptor;
+use super::Severity;
+
+pub(crate) static DESCRIPTOR: DiagnosticDescriptor = DiagnosticDescriptor {
+    conditions: DiagnosticConditions {
+        experimental: false,
+        include_generated: false,
+        include_tests: true,
+        default_disabled: false,
+    },
+    codes: &[DiagnosticCode::FloatEquality],
+    checker: &|diagnostics, sema, file_id, _ext, _config| {
+        sema.for_each_function(file_id, |def| {
+            check_function(diagnostics, sema, file_id, def);
+        });
+    },
+};
+
+fn check_function(
+    diagnostics: &mut Vec<Diagnostic>,
+    sema: &Semantic,
+    file_id: FileId,
+    def: &FunctionDef,
+) {
+    let def_fb = def.in_function_body(sema, def);
+    def_fb.fold_function(
+        Strategy {
+            macros: MacroStrategy::DoNotExpand,
+            parens: ParenStrategy::InvisibleParens,
+        },
+        (),
+        &mut |_acc, clause_id, ctx| {
+            let AnyExpr::Expr(Expr::BinaryOp {
+                lhs,
+                rhs,
+                op: BinaryOp::CompOp(op @ CompOp::Eq { .. }),
+            }) = &ctx.item
+            else {
+                return;
+            };
+            let body = def_fb.body(clause_id);
+            if !is_float(&body, *lhs) && !is_float(&body, *rhs) {
+                return;
+            }
+            if let Some(range) = ctx
+                .item_id
+                .as_expr_id()
+                .and_then(|expr_id| def_fb.range_for_expr(clause_id, expr_id))
+                .filter(|range| range.file_id == file_id)
+            {
+                diagnostics.push(
+                    Diagnostic::new(
+                        DiagnosticCode::FloatEquality,
+                        format!(
+                            "Comparing floats with `{op}` is fragile, check that their difference is within a tolerance instead."
+                        ),
+                        range.range,
+                    )
+                    .with_severity(Severity::WeakWarning)
+                    .with_ignore_fix(sema, file_id),
+                );
+            }
+        },
+    )
+}
+
+/// Whether the expression always evaluates to a float.
+fn is_float(body: &Body, expr_id: ExprId) -> bool {
+    match &body[expr_id] {
+        Expr::Literal(Literal::Float(_)) => true,
+        Expr::UnaryOp {
+            expr,
+            op: UnaryOp::Plus | UnaryOp::Minus,
+        } => is_float(body, *expr),
+        Expr::BinaryOp {
+            op: BinaryOp::ArithOp(ArithOp::FloatDiv),
+            ..
+        } => true,
+        _ => false,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+
+    use crate::diagnostics::Diagnostic;
+    use crate::diagnostics::DiagnosticCode;
+    use crate::tests;
+
+    fn filter(d: &Diagnostic) -> bool {
+        d.code == DiagnosticCode::FloatEquality
+    }
+
+    #[track_caller]
+    fn check_diagnostics(fixture: &str) {
+        tests::check_filtered_diagnostics(fixture, &filter)
+    }
+
+    #[test]
+    fn detects_float_equality() {
+        check_diagnostics(
+            r#"
+         //- /src/main.erl
+         -module(main).
+         -export([f/1, g/2, h/1]).
+
+         f(X) -> X == 1.0.
+         %%      ^^^^^^^^ 💡 weak: Comparing floats with `==` is fragile, check that their difference is within a tolerance instead.
+         g(X, Y) -> X / Y =:= 0.5.
+         %%         ^^^^^^^^^^^^^ 💡 weak: Comparing floats with `=:=` is fragile, check that their difference is within a tolerance instead.
+         h(X) when X =/= -0.5 -> ok.
+         %%        ^^^^^^^^^^ 💡 weak: Comparing floats with `=/=` is fragile, check that their difference is within a tolerance instead.
+            "#,
+        )
+    }
+
+    #[test]
+    fn ignores_integer_equality() {
+        check_diagnostics(
+            r#"
+         //- /src/main.erl
+         -module(main).
+         -export([f/1, g/1]).
+
+         f(X) -> X == 1.
+         g(X) -> X < 1.0.
+            "#,
+        )
+    }
+}
@@ -0,0 +1,196 @@
+/*
+ * Copyright (c) Meta Platforms, Inc. and affiliates.
+ *
+ * This source code is dual-licensed under either the MIT license found in the
+ * LICENSE-MIT file in the root directory of this source tree or the Apache
+ * License, Version 2.0 found in the LICENSE-APACHE file in the root directory
+ * of this source tree. You may select, at your option, one of the
+ * above-listed licenses.
+ */
+
+//! Lint: foldl_append_accumulator
+//!
+//! warn on code of the form
+//! lists:foldl(fun(X, Acc) -> Acc ++ [E] end, [], List)
+//! since appending to the accumulator copies it on each step, and suggest
+//! lists:reverse(lists:foldl(fun(X, Acc) -> [E | Acc] end, [], List))
+//!
+//! Only a fun with a single clause whose whole body is the append is
+//! matched, so the fix can safely rewrite it.
+
+use elp_ide_db::DiagnosticCode;
+use elp_ide_db::elp_base_db::FileId;
+use elp_ide_db::source_change::SourceChangeBuilder;
+use elp_ide_ssr::Match;
+use elp_ide_ssr::is_placeholder_a_var_from_body;
+use elp_syntax::AstNode;
+use elp_syntax::TextRange;
+use elp_syntax::algo;
+use elp_syntax::ast;
+use hir::Semantic;
+
+use crate::diagnostics::Linter;
+use crate::diagnostics::Severity;
+use crate::diagnostics::SsrPatternsLinter;
+use crate::fix;
+
+pub(crate) struct FoldlAppendAccumulatorLinter;
+
+impl Linter for FoldlAppendAccumulatorLinter {
+    fn id(&self) -> DiagnosticCode {
+        DiagnosticCode::FoldlAppendAccumulator
+    }
+
+    fn description(&self) -> &'static str {
+        "Appending to the accumulator with `++` is quadratic, prepend and reverse the result instead."
+    }
+
+    fn severity(&self) -> Severity {
+        Severity::WeakWarning
+    }
+}
+
+impl SsrPatternsLinter for FoldlAppendAccumulatorLinter {
+    type Context = ();
+
+    fn patterns(&self) -> Vec<(String, Self::Context)> {
+        vec![(
+            format!(
+                "ssr: lists:foldl(fun({ELEM_VAR}, {ACC_VAR}) -> {ACC_VAR} ++ [{VALUE_VAR}] end, [], {LIST_VAR})."
+            ),
+            (),
+        )]
+    }
+
+    fn is_match_valid(
+        &self,
+        _context: &Self::Context,
+        matched: &Match,
+        sema: &Semantic,
+        file_id: FileId,
+    ) -> Option<bool> {
+        if matched.range.file_id != file_id {
+            // We've somehow ended up with a match in a different file - this means we've
+            // accidentally expanded a macro from a different file, or some other complex case that
+            // gets hairy, so bail out.
+            return None;
+        }
+        if let Some(comments) = matched.comments(sema) {
+            // Avoid clobbering comments in the original source code
+            if !comments.is_empty() {
+                return None;
+            }
+        }
+        let body_arc = matched.matched_node_body.get_body(sema)?;
+        let acc_matches = matched.get_placeholder_matches(sema, ACC_VAR)?;
+        let acc = acc_matches.first()?;
+        Some(is_placeholder_a_var_from_body(body_arc.as_ref(), acc))
+    }
+
+    fn range(&self, sema: &Semantic, matched: &Match) -> Option<TextRange> {
+        Some(append_expr(sema, matched)?.syntax().text_range())
+    }
+
+    fn fixes(
+        &self,
+        _context: &Self::Context,
+        matched: &Match,
+        sema: &Semantic,
+        file_id: FileId,
+    ) -> Option<Vec<elp_ide_assists::Assist>> {
+        let fold_range = matched.range.range;
+        let append_range = append_expr(sema, matched)?.syntax().text_range();
+        let acc = matched.placeholder_texts(sema, ACC_VAR)?.first()?.clone();
+        let value = matched.placeholder_text(sema, VALUE_VAR)?;
+
+        let mut builder = SourceChangeBuilder::new(file_id);
+        builder.insert(fold_range.start(), "lists:reverse(");
+        builder.replace(append_range, format!("[{value} | {acc}]"));
+        builder.insert(fold_range.end(), ")");
+        let fixes = vec![fix(
+            "foldl_append_accumulator",
+            "Prepend to the accumulator and reverse the result",
+            builder.finish(),
+            append_range,
+        )];
+        Some(fixes)
+    }
+}
+
+pub(crate) static LINTER: FoldlAppendAccumulatorLinter = FoldlAppendAccumulatorLinter;
+
+static ELEM_VAR: &str = "_@Elem";
+static ACC_VAR: &str = "_@Acc";
+static VALUE_VAR: &str = "_@Value";
+static LIST_VAR: &str = "_@List";
+
+/// The `Acc ++ [E]` expression making up the body of the fun.
+fn append_expr(sema: &Semantic, matched: &Match) -> Option<ast::Expr> {
+    let source = sema.parse(matched.range.file_id).value;
+    let call = algo::find_node_at_range::<ast::Call>(source.syntax(), matched.range.range)?;
+    let fun = match call.args()?.args().next()? {
+        ast::Expr::ExprMax(ast::ExprMax::AnonymousFun(fun)) => fun,
+        _ => return None,
+    };
+    fun.clauses().next()?.body()?.exprs().next()
+}
+
+#[cfg(test)]
+mod tests {
+
+    use expect_test::Expect;
+    use expect_test::expect;
+
+    use crate::diagnostics::Diagnostic;
+    use crate::diagnostics::DiagnosticCode;
+    use crate::tests;
+
+    fn filter(d: &Diagnostic) -> bool {
+        d.code == DiagnosticCode::FoldlAppendAccumulator
+    }
+
+    #[track_caller]
+    fn check_diagnostics(fixture: &str) {
+        tests::check_filtered_diagnostics(fixture, &filter)
+    }
+
+    #[track_caller]
+    fn check_fix(fixture_before: &str, fixture_after: Expect) {
+        tests::check_fix(fixture_before, fixture_after)
+    }
+
+    #[test]
+    fn detects_append_to_accumulator() {
+        check_diagnostics(
+            r#"
+         //- /src/foldl_append_accumulator.erl
+         -module(foldl_append_accumulator).
+
+         double(L) -> lists:foldl(fun(X, Acc) -> Acc ++ [X * 2] end, [], L).
+         %%                                      ^^^^^^^^^^^^^^ 💡 weak: Appending to the accumulator with `++` is quadratic, prepend and reverse the result instead.
+         prepend(L) -> lists:foldl(fun(X, Acc) -> [X | Acc] end, [], L).
+         non_empty(L) -> lists:foldl(fun(X, Acc) -> Acc ++ [X] end, [a], L).
+         other(L, Y) -> lists:foldl(fun(X, _Acc) -> Y ++ [X] end, [], L).
+            "#,
+        )
+    }
+
+    #[test]
+    fn fixes_append_to_accumulator() {
+        check_fix(
+            r#"
+         //- /src/foldl_append_accumulator.erl
+         -module(foldl_append_accumulator).
+
+         % elp:ignore W0017 (undefined_function)
+         double(L) -> lists:foldl(fun(X, Acc) -> Acc ~++ [X * 2] end, [], L).
+            "#,
+            expect![[r#"
+         -module(foldl_append_accumulator).
+
+         % elp:ignore W0017 (undefined_function)
+         double(L) -> lists:reverse(lists:foldl(fun(X, Acc) -> [X * 2 | Acc] end, [], L)).
+            "#]],
+        )
+    }
+}
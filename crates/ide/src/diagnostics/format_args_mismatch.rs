@@ -0,0 +1,208 @@
+/*
+ * Copyright (c) Meta Platforms, Inc. and affiliates.
+ *
+ * This source code is dual-licensed under either the MIT license found in the
+ * LICENSE-MIT file in the root directory of this source tree or the Apache
+ * License, Version 2.0 found in the LICENSE-APACHE file in the root directory
+ * of this source tree. You may select, at your option, one of the
+ * above-listed licenses.
+ */
+
+//! Lint: format_args_mismatch
+//!
+//! Return a diagnostic if the number of arguments passed to `io:format/1,2`,
+//! `io:fwrite/1,2` or `io_lib:format/2` does not match the number of
+//! arguments consumed by the control sequences of the format string.
+//! This fails at runtime with a `badarg` or `format` error.
+//!
+//! Only calls where both the format string and the argument list are
+//! literals are checked.
+
+use std::borrow::Cow;
+
+use hir::Expr;
+use hir::Literal;
+
+use crate::FunctionMatch;
+use crate::codemod_helpers::CheckCallCtx;
+use crate::diagnostics::DiagnosticCode;
+use crate::diagnostics::FunctionCallLinter;
+use crate::diagnostics::Linter;
+use crate::lazy_function_matches;
+
+pub(crate) struct FormatArgsMismatchLinter;
+
+impl Linter for FormatArgsMismatchLinter {
+    fn id(&self) -> DiagnosticCode {
+        DiagnosticCode::FormatArgsMismatch
+    }
+
+    fn description(&self) -> &'static str {
+        "The number of arguments does not match the format string."
+    }
+}
+
+#[derive(Debug, Default, Clone, PartialEq)]
+pub(crate) struct ArgsCount {
+    expected: usize,
+    actual: usize,
+}
+
+impl FunctionCallLinter for FormatArgsMismatchLinter {
+    type Context = ArgsCount;
+
+    fn match_description(&self, context: &Self::Context) -> Cow<'_, str> {
+        Cow::Owned(format!(
+            "The format string expects {} argument(s), but {} given.",
+            context.expected, context.actual
+        ))
+    }
+
+    fn matches_functions(&self) -> Vec<FunctionMatch> {
+        lazy_function_matches![vec![
+            FunctionMatch::mfa("io", "format", 1),
+            FunctionMatch::mfa("io", "format", 2),
+            FunctionMatch::mfa("io", "fwrite", 1),
+            FunctionMatch::mfa("io", "fwrite", 2),
+            FunctionMatch::mfa("io_lib", "format", 2),
+        ]]
+    }
+
+    fn check_match(&self, context: &CheckCallCtx<'_, ()>) -> Option<Self::Context> {
+        let body = context.in_clause.body();
+        let (format, actual) = match context.args.as_vec()[..] {
+            [format] => (format, 0),
+            [format, args] => match &body[args] {
+                Expr::List { exprs, tail: None } => (format, exprs.len()),
+                _ => return None,
+            },
+            _ => return None,
+        };
+        // A format string coming from a variable or a macro is not a
+        // string literal in the body, so it is skipped here.
+        let expected = match &body[format] {
+            Expr::Literal(Literal::String(format)) => count_format_args(&format.as_string())?,
+            _ => return None,
+        };
+        if expected != actual {
+            Some(ArgsCount { expected, actual })
+        } else {
+            None
+        }
+    }
+}
+
+pub static LINTER: FormatArgsMismatchLinter = FormatArgsMismatchLinter;
+
+/// Number of arguments consumed by the control sequences of `format`, of
+/// the form `~F.P.PadModC`. Returns `None` if `format` is not a valid
+/// format string.
+fn count_format_args(format: &str) -> Option<usize> {
+    let mut chars = format.chars().peekable();
+    let mut count = 0;
+    while let Some(c) = chars.next() {
+        if c != '~' {
+            continue;
+        }
+        // Field width and precision, each of which can be given as an
+        // argument with `*`, followed by the padding character
+        let mut dots = 0;
+        while let Some(&c) = chars.peek() {
+            match c {
+                '*' => count += 1,
+                '0'..='9' | '-' => {}
+                '.' if dots == 1 => {
+                    chars.next();
+                    chars.next()?;
+                    break;
+                }
+                '.' => dots += 1,
+                _ => break,
+            }
+            chars.next();
+        }
+        // Modifiers, of which only `K` takes an argument
+        while let Some(&c) = chars.peek() {
+            match c {
+                't' | 'l' | 'k' => {}
+                'K' => count += 1,
+                _ => break,
+            }
+            chars.next();
+        }
+        count += match chars.next()? {
+            '~' | 'n' => 0,
+            'c' | 'f' | 'e' | 'g' | 's' | 'w' | 'p' | 'B' | 'b' | '#' | '+' | 'i' => 1,
+            'W' | 'P' | 'X' | 'x' => 2,
+            _ => return None,
+        };
+    }
+    Some(count)
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::count_format_args;
+    use crate::diagnostics::Diagnostic;
+    use crate::diagnostics::DiagnosticCode;
+    use crate::tests;
+
+    fn filter(d: &Diagnostic) -> bool {
+        d.code == DiagnosticCode::FormatArgsMismatch
+    }
+
+    #[track_caller]
+    fn check_diagnostics(fixture: &str) {
+        tests::check_filtered_diagnostics(fixture, &filter)
+    }
+
+    #[test]
+    fn counts_control_sequences() {
+        assert_eq!(count_format_args("hello~n"), Some(0));
+        assert_eq!(count_format_args("~~ ~p ~tp ~s"), Some(3));
+        assert_eq!(count_format_args("~10.3.0f ~-10s"), Some(2));
+        assert_eq!(count_format_args("~*.*.xB ~W ~P"), Some(7));
+        assert_eq!(count_format_args("~Kp ~x"), Some(4));
+        assert_eq!(count_format_args("~q"), None);
+        assert_eq!(count_format_args("~"), None);
+    }
+
+    #[test]
+    fn detects_mismatched_args() {
+        check_diagnostics(
+            r#"
+         //- /src/main.erl
+         -module(main).
+
+         main(X, Y) ->
+             io:format("~p ~p~n", [X]),
+         %%  ^^^^^^^^^ 💡 warning: The format string expects 2 argument(s), but 1 given.
+             io:fwrite("~p~n"),
+         %%  ^^^^^^^^^ 💡 warning: The format string expects 1 argument(s), but 0 given.
+             io_lib:format("~s", [X, Y]).
+         %%  ^^^^^^^^^^^^^ 💡 warning: The format string expects 1 argument(s), but 2 given.
+            "#,
+        )
+    }
+
+    #[test]
+    fn ignores_matching_or_non_literal_args() {
+        check_diagnostics(
+            r#"
+         //- /src/main.erl
+         -module(main).
+         -define(FMT, "~p~n").
+
+         main(Fmt, X, Args) ->
+             io:format("~p ~p~n", [X, X]),
+             io:format("done~n"),
+             io:format(Fmt, [X]),
+             io:format(?FMT, []),
+             io:format("~p~n", Args),
+             io:format("~p~n", [X | Args]),
+             lists:flatten(io_lib:format("~w", [X])).
+            "#,
+        )
+    }
+}
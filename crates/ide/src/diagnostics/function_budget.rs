@@ -0,0 +1,156 @@
+/*
+ * Copyright (c) Meta Platforms, Inc. and affiliates.
+ *
+ * This source code is dual-licensed under either the MIT license found in the
+ * LICENSE-MIT file in the root directory of this source tree or the Apache
+ * License, Version 2.0 found in the LICENSE-APACHE file in the root directory
+ * of this source tree. You may select, at your option, one of the
+ * above-listed licenses.
+ */
+
+//! Lint: function_budget
+//!
+//! Return a warning for a function with more clauses, or a higher arity,
+//! than the maximum set in the `[function_budget]` section of the lint
+//! config. Nothing is reported unless a maximum is configured.
+
+use elp_ide_db::DiagnosticCode;
+use elp_ide_db::elp_base_db::FileId;
+use hir::Semantic;
+
+use super::Diagnostic;
+use super::DiagnosticConditions;
+use super::DiagnosticDescriptor;
+use super::DiagnosticsConfig;
+use super::Severity;
+
+pub(crate) static DESCRIPTOR: DiagnosticDescriptor = DiagnosticDescriptor {
+    conditions: DiagnosticConditions {
+        experimental: false,
+        include_generated: false,
+        include_tests: true,
+        default_disabled: false,
+    },
+    codes: &[DiagnosticCode::FunctionBudget],
+    checker: &|diagnostics, sema, file_id, _ext, config| {
+        function_budget(diagnostics, sema, file_id, config);
+    },
+};
+
+fn function_budget(
+    diagnostics: &mut Vec<Diagnostic>,
+    sema: &Semantic,
+    file_id: FileId,
+    config: &DiagnosticsConfig,
+) {
+    if config.max_function_clauses.is_none() && config.max_function_arity.is_none() {
+        return;
+    }
+    let def_map = sema.def_map_local(file_id);
+    for (name, def) in def_map.get_functions() {
+        let Some(range) = def.name_range(sema.db.upcast()) else {
+            continue;
+        };
+        let clauses = def.function_clauses.len();
+        if let Some(max) = config.max_function_clauses
+            && clauses > max
+        {
+            diagnostics.push(
+                Diagnostic::new(
+                    DiagnosticCode::FunctionBudget,
+                    format!(
+                        "Function `{name}` has {clauses} clauses, more than the maximum of {max}."
+                    ),
+                    range,
+                )
+                .with_severity(Severity::WeakWarning)
+                .with_ignore_fix(sema, file_id),
+            );
+        }
+        let arity = name.arity() as usize;
+        if let Some(max) = config.max_function_arity
+            && arity > max
+        {
+            diagnostics.push(
+                Diagnostic::new(
+                    DiagnosticCode::FunctionBudget,
+                    format!("Function `{name}` has arity {arity}, more than the maximum of {max}."),
+                    range,
+                )
+                .with_severity(Severity::WeakWarning)
+                .with_ignore_fix(sema, file_id),
+            );
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+
+    use crate::diagnostics::Diagnostic;
+    use crate::diagnostics::DiagnosticCode;
+    use crate::diagnostics::DiagnosticsConfig;
+    use crate::tests::check_filtered_diagnostics_with_config;
+
+    fn filter(d: &Diagnostic) -> bool {
+        d.code == DiagnosticCode::FunctionBudget
+    }
+
+    #[track_caller]
+    fn check_diagnostics(config: DiagnosticsConfig, fixture: &str) {
+        check_filtered_diagnostics_with_config(config, &vec![], fixture, &filter)
+    }
+
+    #[test]
+    fn too_many_clauses() {
+        check_diagnostics(
+            DiagnosticsConfig::default().set_max_function_clauses(2),
+            r#"
+            //- /src/main.erl
+            -module(main).
+            -export([over/1, under/1]).
+
+            over(1) -> one;
+         %% ^^^^ 💡 weak: Function `over/1` has 3 clauses, more than the maximum of 2.
+            over(2) -> two;
+            over(_) -> many.
+
+            under(1) -> one;
+            under(_) -> many.
+            "#,
+        )
+    }
+
+    #[test]
+    fn too_high_arity() {
+        check_diagnostics(
+            DiagnosticsConfig::default().set_max_function_arity(2),
+            r#"
+            //- /src/main.erl
+            -module(main).
+            -export([over/3, under/2]).
+
+            over(A, B, C) -> {A, B, C}.
+         %% ^^^^ 💡 weak: Function `over/3` has arity 3, more than the maximum of 2.
+
+            under(A, B) -> {A, B}.
+            "#,
+        )
+    }
+
+    #[test]
+    fn not_configured() {
+        check_diagnostics(
+            DiagnosticsConfig::default(),
+            r#"
+            //- /src/main.erl
+            -module(main).
+            -export([f/3]).
+
+            f(1, B, C) -> {B, C};
+            f(2, B, C) -> {C, B};
+            f(_, _, _) -> none.
+            "#,
+        )
+    }
+}
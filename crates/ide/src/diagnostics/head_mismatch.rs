@@ -43,7 +43,8 @@ pub(crate) static DESCRIPTOR_SEMANTIC: DiagnosticDescriptor = DiagnosticDescript
         include_tests: true,
         default_disabled: false,
     },
-    checker: &|diags, sema, file_id, _ext| {
+    codes: &[DiagnosticCode::HeadMismatch],
+    checker: &|diags, sema, file_id, _ext, _config| {
         head_mismatch_semantic(diags, sema, file_id);
     },
 };
@@ -365,7 +366,8 @@ mod tests {
     fn check_diagnostics(fixture: &str) {
         let config = DiagnosticsConfig::default()
             .disable(DiagnosticCode::Unexpected("unexpected_semi".to_string()))
-            .disable(DiagnosticCode::Unexpected("unexpected_dot".to_string()));
+            .disable(DiagnosticCode::Unexpected("unexpected_dot".to_string()))
+            .disable(DiagnosticCode::IgnoredFunctionArg);
         check_diagnostics_with_config(config, fixture)
     }
 
@@ -0,0 +1,264 @@
+/*
+ * Copyright (c) Meta Platforms, Inc. and affiliates.
+ *
+ * This source code is dual-licensed under either the MIT license found in the
+ * LICENSE-MIT file in the root directory of this source tree or the Apache
+ * License, Version 2.0 found in the LICENSE-APACHE file in the root directory
+ * of this source tree. You may select, at your option, one of the
+ * above-listed licenses.
+ */
+
+//! Lint: identity_list_operation
+//!
+//! warn on code of the form
+//! `lists:map(fun(X) -> X end, L)` or `lists:filter(fun(_) -> true end, L)`,
+//! which return `L` unchanged, and suggest `L`. Similarly,
+//! `lists:filter(fun(_) -> false end, L)` always returns `[]`.
+
+use elp_ide_db::DiagnosticCode;
+use elp_ide_db::elp_base_db::FileId;
+use elp_ide_db::source_change::SourceChangeBuilder;
+use elp_ide_ssr::Match;
+use elp_ide_ssr::is_placeholder_a_var_from_body;
+use hir::Semantic;
+
+use crate::diagnostics::Category;
+use crate::diagnostics::Linter;
+use crate::diagnostics::Severity;
+use crate::diagnostics::SsrPatternsLinter;
+use crate::fix;
+
+pub(crate) struct IdentityListOperationLinter;
+
+impl Linter for IdentityListOperationLinter {
+    fn id(&self) -> DiagnosticCode {
+        DiagnosticCode::IdentityListOperation
+    }
+
+    fn description(&self) -> &'static str {
+        "This call returns the list unchanged."
+    }
+
+    fn severity(&self) -> Severity {
+        Severity::WeakWarning
+    }
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub(crate) enum Operation {
+    /// `lists:map(fun(X) -> X end, L)`
+    MapIdentity,
+    /// `lists:filter(fun(_) -> true end, L)`
+    FilterTrue,
+    /// `lists:filter(fun(_) -> false end, L)`
+    FilterFalse,
+}
+
+impl SsrPatternsLinter for IdentityListOperationLinter {
+    type Context = Operation;
+
+    fn patterns(&self) -> Vec<(String, Self::Context)> {
+        vec![
+            (
+                format!("ssr: lists:map(fun({ELEM_VAR}) -> {ELEM_VAR} end, {LIST_VAR})."),
+                Operation::MapIdentity,
+            ),
+            (
+                format!("ssr: lists:filter(fun({ELEM_VAR}) -> true end, {LIST_VAR})."),
+                Operation::FilterTrue,
+            ),
+            (
+                format!("ssr: lists:filter(fun({ELEM_VAR}) -> false end, {LIST_VAR})."),
+                Operation::FilterFalse,
+            ),
+        ]
+    }
+
+    fn pattern_description(&self, context: &Self::Context) -> &'static str {
+        match context {
+            Operation::MapIdentity => "Mapping the identity function returns the list unchanged.",
+            Operation::FilterTrue => {
+                "Filtering with a predicate always returning `true` returns the list unchanged."
+            }
+            Operation::FilterFalse => {
+                "Filtering with a predicate always returning `false` returns an empty list."
+            }
+        }
+    }
+
+    fn is_match_valid(
+        &self,
+        _context: &Self::Context,
+        matched: &Match,
+        sema: &Semantic,
+        file_id: FileId,
+    ) -> Option<bool> {
+        if matched.range.file_id != file_id {
+            // We've somehow ended up with a match in a different file - this means we've
+            // accidentally expanded a macro from a different file, or some other complex case that
+            // gets hairy, so bail out.
+            return None;
+        }
+        if let Some(comments) = matched.comments(sema) {
+            // Avoid clobbering comments in the original source code
+            if !comments.is_empty() {
+                return None;
+            }
+        }
+        // The fun must accept any element, e.g. not `fun(ok) -> ok end`,
+        // and both occurrences in the identity fun must be the same variable
+        let body_arc = matched.matched_node_body.get_body(sema)?;
+        let elem_matches = matched.get_placeholder_matches(sema, ELEM_VAR)?;
+        Some(
+            elem_matches
+                .iter()
+                .all(|elem| is_placeholder_a_var_from_body(body_arc.as_ref(), elem)),
+        )
+    }
+
+    fn add_categories(&self, _context: &Self::Context) -> Vec<Category> {
+        vec![Category::SimplificationRule]
+    }
+
+    fn fixes(
+        &self,
+        context: &Self::Context,
+        matched: &Match,
+        sema: &Semantic,
+        file_id: FileId,
+    ) -> Option<Vec<elp_ide_assists::Assist>> {
+        let call_range = matched.range.range;
+        let replacement = match context {
+            Operation::MapIdentity | Operation::FilterTrue => {
+                matched.placeholder_text(sema, LIST_VAR)?
+            }
+            Operation::FilterFalse => {
+                // Dropping the list expression would also drop any side
+                // effect of evaluating it
+                let body_arc = matched.matched_node_body.get_body(sema)?;
+                let list = matched.get_placeholder_match(sema, LIST_VAR)?;
+                if !is_placeholder_a_var_from_body(body_arc.as_ref(), &list) {
+                    return None;
+                }
+                "[]".to_string()
+            }
+        };
+
+        let mut builder = SourceChangeBuilder::new(file_id);
+        builder.replace(call_range, &replacement);
+        let fixes = vec![fix(
+            "identity_list_operation",
+            &format!("Replace with `{replacement}`"),
+            builder.finish(),
+            call_range,
+        )];
+        Some(fixes)
+    }
+}
+
+pub(crate) static LINTER: IdentityListOperationLinter = IdentityListOperationLinter;
+
+static ELEM_VAR: &str = "_@Elem";
+static LIST_VAR: &str = "_@List";
+
+#[cfg(test)]
+mod tests {
+
+    use expect_test::Expect;
+    use expect_test::expect;
+
+    use crate::diagnostics::Diagnostic;
+    use crate::diagnostics::DiagnosticCode;
+    use crate::tests;
+
+    fn filter(d: &Diagnostic) -> bool {
+        d.code == DiagnosticCode::IdentityListOperation
+    }
+
+    #[track_caller]
+    fn check_diagnostics(fixture: &str) {
+        tests::check_filtered_diagnostics(fixture, &filter)
+    }
+
+    #[track_caller]
+    fn check_fix(fixture_before: &str, fixture_after: Expect) {
+        tests::check_fix(fixture_before, fixture_after)
+    }
+
+    #[test]
+    fn detects_identity_operations() {
+        check_diagnostics(
+            r#"
+         //- /src/identity_list_operation.erl
+         -module(identity_list_operation).
+
+         map(L) -> lists:map(fun(X) -> X end, L).
+         %%        ^^^^^^^^^^^^^^^^^^^^^^^^^^^^^ 💡 weak: Mapping the identity function returns the list unchanged.
+         keep(L) -> lists:filter(fun(_) -> true end, L).
+         %%         ^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^ 💡 weak: Filtering with a predicate always returning `true` returns the list unchanged.
+         drop(L) -> lists:filter(fun(_X) -> false end, L).
+         %%         ^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^ 💡 weak: Filtering with a predicate always returning `false` returns an empty list.
+         double(L) -> lists:map(fun(X) -> X * 2 end, L).
+         other(L, Y) -> lists:map(fun(_X) -> Y end, L).
+         only_ok(L) -> lists:filter(fun(ok) -> true end, L).
+            "#,
+        )
+    }
+
+    #[test]
+    fn fixes_identity_map() {
+        check_fix(
+            r#"
+         //- /src/identity_list_operation.erl
+         -module(identity_list_operation).
+
+         % elp:ignore W0017 (undefined_function)
+         fn(L) -> lists:m~ap(fun(X) -> X end, tl(L)).
+            "#,
+            expect![[r#"
+         -module(identity_list_operation).
+
+         % elp:ignore W0017 (undefined_function)
+         fn(L) -> tl(L).
+            "#]],
+        )
+    }
+
+    #[test]
+    fn fixes_filter_true() {
+        check_fix(
+            r#"
+         //- /src/identity_list_operation.erl
+         -module(identity_list_operation).
+
+         % elp:ignore W0017 (undefined_function)
+         fn(L) -> lists:fil~ter(fun(_) -> true end, L).
+            "#,
+            expect![[r#"
+         -module(identity_list_operation).
+
+         % elp:ignore W0017 (undefined_function)
+         fn(L) -> L.
+            "#]],
+        )
+    }
+
+    #[test]
+    fn fixes_filter_false() {
+        check_fix(
+            r#"
+         //- /src/identity_list_operation.erl
+         -module(identity_list_operation).
+
+         % elp:ignore W0017 (undefined_function)
+         fn(L) -> lists:fil~ter(fun(_) -> false end, L).
+            "#,
+            expect![[r#"
+         -module(identity_list_operation).
+
+         % elp:ignore W0017 (undefined_function)
+         fn(L) -> [].
+            "#]],
+        )
+    }
+}
@@ -0,0 +1,263 @@
+/*
+ * Copyright (c) Meta Platforms, Inc. and affiliates.
+ *
+ * This source code is dual-licensed under either the MIT license found in the
+ * LICENSE-MIT file in the root directory of this source tree or the Apache
+ * License, Version 2.0 found in the LICENSE-APACHE file in the root directory
+ * of this source tree. You may select, at your option, one of the
+ * above-listed licenses.
+ */
+
+//! Lint: ignored_function_arg
+//!
+//! Return a warning if an argument of a local function is ignored, i.e.
+//! matched by `_` or a `_`-prefixed variable, in every clause, since it can
+//! probably be removed.
+//!
+//! Functions whose arity is fixed elsewhere are skipped: exported functions,
+//! behaviour callbacks, functions captured with `fun foo/N`, and test
+//! functions and Common Test callbacks, which are called by the test
+//! framework.
+
+use elp_ide_db::DiagnosticCode;
+use elp_ide_db::elp_base_db::FileId;
+use elp_syntax::ast;
+use fxhash::FxHashSet;
+use hir::AnyExpr;
+use hir::CallTarget;
+use hir::Expr;
+use hir::FunctionDef;
+use hir::Name;
+use hir::NameArity;
+use hir::Semantic;
+use hir::Strategy;
+use hir::fold::MacroStrategy;
+use hir::fold::ParenStrategy;
+use hir::known;
+
+use super::Diagnostic;
+use super::DiagnosticConditions;
+use super::DiagnosticDescriptor;
+use super::Severity;
+
+pub(crate) static DESCRIPTOR: DiagnosticDescriptor = DiagnosticDescriptor {
+    conditions: DiagnosticConditions {
+        experimental: false,
+        include_generated: false,
+        include_tests: true,
+        default_disabled: false,
+    },
+    codes: &[DiagnosticCode::IgnoredFunctionArg],
+    checker: &|diagnostics, sema, file_id, _ext, _config| {
+        check(diagnostics, sema, file_id);
+    },
+};
+
+fn check(diagnostics: &mut Vec<Diagnostic>, sema: &Semantic, file_id: FileId) {
+    let callbacks = sema.resolve_callbacks(file_id);
+    let captured = captured_functions(sema, file_id);
+    sema.def_map_local(file_id)
+        .get_functions()
+        .for_each(|(_, def)| check_function(diagnostics, sema, def, &callbacks, &captured));
+}
+
+/// Local functions captured with `fun foo/N`, by name only, as the arity
+/// may not be a literal.
+fn captured_functions(sema: &Semantic, file_id: FileId) -> FxHashSet<Name> {
+    let mut res = FxHashSet::default();
+    sema.for_each_function(file_id, |def| {
+        let def_fb = def.in_function_body(sema, def);
+        def_fb.fold_function(
+            Strategy {
+                macros: MacroStrategy::Expand,
+                parens: ParenStrategy::InvisibleParens,
+            },
+            (),
+            &mut |_acc, clause_id, ctx| {
+                if let AnyExpr::Expr(Expr::CaptureFun {
+                    target: CallTarget::Local { name },
+                    ..
+                }) = &ctx.item
+                    && let Some(name) = def_fb.body(clause_id)[*name].as_atom()
+                {
+                    res.insert(sema.db.lookup_atom(name));
+                }
+            },
+        );
+    });
+    res
+}
+
+fn check_function(
+    diagnostics: &mut Vec<Diagnostic>,
+    sema: &Semantic,
+    def: &FunctionDef,
+    callbacks: &FxHashSet<NameArity>,
+    captured: &FxHashSet<Name>,
+) {
+    // The arity of these is fixed by their callers
+    if def.exported
+        || callbacks.contains(&def.name)
+        || captured.contains(def.name.name())
+        || is_test_function(def.name.name())
+    {
+        return;
+    }
+    let arity = def.name.arity() as usize;
+    let mut ignored = vec![true; arity];
+    for fun_decl in def.source(sema.db) {
+        let args: Vec<ast::Expr> = match fun_decl.clause() {
+            Some(ast::FunctionOrMacroClause::FunctionClause(clause)) => match clause.args() {
+                Some(args) => args.args().collect(),
+                None => return,
+            },
+            // We cannot tell what a macro clause does with its arguments
+            _ => return,
+        };
+        if args.len() != arity {
+            return;
+        }
+        for (ignored, arg) in ignored.iter_mut().zip(&args) {
+            *ignored &= is_ignored(arg);
+        }
+    }
+    let Some(range) = def.name_range(sema.db) else {
+        return;
+    };
+    for (i, _) in ignored.iter().enumerate().filter(|(_, ignored)| **ignored) {
+        diagnostics.push(
+            Diagnostic::new(
+                DiagnosticCode::IgnoredFunctionArg,
+                format!(
+                    "Argument {} of `{}` is ignored in every clause.",
+                    i + 1,
+                    def.name
+                ),
+                range,
+            )
+            .with_severity(Severity::WeakWarning)
+            .with_ignore_fix(sema, def.file.file_id),
+        );
+    }
+}
+
+/// Test cases and Common Test callbacks, whose arguments are fixed by the
+/// test framework.
+fn is_test_function(name: &Name) -> bool {
+    let ct_callbacks = [
+        known::init_per_suite,
+        known::end_per_suite,
+        known::init_per_group,
+        known::end_per_group,
+        known::init_per_testcase,
+        known::end_per_testcase,
+    ];
+    let text = name.as_str();
+    ct_callbacks.contains(name)
+        || text.starts_with("test_")
+        || text.ends_with("_test")
+        || text.ends_with("_test_")
+}
+
+fn is_ignored(arg: &ast::Expr) -> bool {
+    match arg {
+        ast::Expr::ExprMax(ast::ExprMax::Var(var)) => var.text().starts_with('_'),
+        _ => false,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+
+    use crate::diagnostics::Diagnostic;
+    use crate::diagnostics::DiagnosticCode;
+    use crate::tests;
+
+    fn filter(d: &Diagnostic) -> bool {
+        d.code == DiagnosticCode::IgnoredFunctionArg
+    }
+
+    #[track_caller]
+    fn check_diagnostics(fixture: &str) {
+        tests::check_filtered_diagnostics(fixture, &filter)
+    }
+
+    #[test]
+    fn detects_arg_ignored_in_all_clauses() {
+        check_diagnostics(
+            r#"
+         //- /src/main.erl
+         -module(main).
+         -export([main/1]).
+
+         main(X) -> foo(X, 1) + bar(X, 2).
+         foo(X, _) -> X.
+      %% ^^^ 💡 weak: Argument 2 of `foo/2` is ignored in every clause.
+         bar(0, _Y) -> 0;
+      %% ^^^ 💡 weak: Argument 2 of `bar/2` is ignored in every clause.
+         bar(X, _) -> X + 1.
+            "#,
+        )
+    }
+
+    #[test]
+    fn ignores_used_args() {
+        check_diagnostics(
+            r#"
+         //- /src/main.erl
+         -module(main).
+         -export([main/1, exported/2]).
+
+         main(X) -> foo(X, 1).
+         foo(X, 0) -> X;
+         foo(_X, Y) -> Y.
+         exported(X, _) -> X.
+            "#,
+        )
+    }
+
+    #[test]
+    fn ignores_captured_functions() {
+        check_diagnostics(
+            r#"
+         //- /src/main.erl
+         -module(main).
+         -export([main/1]).
+
+         main(L) -> lists:foldl(fun add/2, 0, L).
+         add(_X, Acc) -> Acc + 1.
+            "#,
+        )
+    }
+
+    #[test]
+    fn ignores_behaviour_callbacks() {
+        check_diagnostics(
+            r#"
+         //- /src/main.erl
+         -module(main).
+         -behaviour(my_behaviour).
+
+         handle(_Msg, State) -> State.
+         //- /src/my_behaviour.erl
+         -module(my_behaviour).
+         -callback handle(term(), term()) -> term().
+            "#,
+        )
+    }
+
+    #[test]
+    fn ignores_test_functions() {
+        check_diagnostics(
+            r#"
+         //- /src/main.erl
+         -module(main).
+
+         test_foo(_Config) -> ok.
+         foo_test() -> ok.
+         init_per_testcase(_TestCase, Config) -> Config.
+         end_per_suite(_Config) -> ok.
+            "#,
+        )
+    }
+}
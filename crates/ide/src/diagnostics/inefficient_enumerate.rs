@@ -41,7 +41,8 @@ pub(crate) static DESCRIPTOR: DiagnosticDescriptor = DiagnosticDescriptor {
         include_tests: true,
         default_disabled: false,
     },
-    checker: &|acc, sema, file_id, _ext| {
+    codes: &[DiagnosticCode::ListsZipWithSeqRatherThanEnumerate],
+    checker: &|acc, sema, file_id, _ext, _config| {
         inefficient_enumerate_custom_index_ssr(acc, sema, file_id);
         inefficient_enumerate_custom_index_and_step_ssr(acc, sema, file_id);
     },
@@ -35,7 +35,8 @@ pub(crate) static DESCRIPTOR: DiagnosticDescriptor = DiagnosticDescriptor {
         include_tests: true,
         default_disabled: false,
     },
-    checker: &|acc, sema, file_id, _ext| {
+    codes: &[DiagnosticCode::UnnecessaryFlatteningToFindFlatLength],
+    checker: &|acc, sema, file_id, _ext, _config| {
         inefficient_flatlength_ssr(acc, sema, file_id);
     },
 };
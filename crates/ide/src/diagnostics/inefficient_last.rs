@@ -36,7 +36,8 @@ pub(crate) static DESCRIPTOR: DiagnosticDescriptor = DiagnosticDescriptor {
         include_tests: true,
         default_disabled: false,
     },
-    checker: &|acc, sema, file_id, _ext| {
+    codes: &[DiagnosticCode::UnnecessaryReversalToFindLastElementOfList],
+    checker: &|acc, sema, file_id, _ext, _config| {
         inefficient_last_hd_ssr(acc, sema, file_id);
         inefficient_last_pat_ssr(acc, sema, file_id);
     },
@@ -0,0 +1,198 @@
+/*
+ * Copyright (c) Meta Platforms, Inc. and affiliates.
+ *
+ * This source code is dual-licensed under either the MIT license found in the
+ * LICENSE-MIT file in the root directory of this source tree or the Apache
+ * License, Version 2.0 found in the LICENSE-APACHE file in the root directory
+ * of this source tree. You may select, at your option, one of the
+ * above-listed licenses.
+ */
+
+//! Lint: length_at_least
+//!
+//! Return a warning for a comparison like `length(L) >= 2`, checking that
+//! a list has at least a few elements. `length/1` traverses the whole list,
+//! and raises an exception for an improper list, while matching
+//! `[_, _ | _]` takes constant time and never raises. There is no fix, as
+//! the comparison may not be in a place where a pattern can be used.
+
+use elp_ide_db::DiagnosticCode;
+use elp_ide_db::elp_base_db::FileId;
+use elp_syntax::ast::BinaryOp;
+use elp_syntax::ast::CompOp;
+use elp_syntax::ast::Ordering;
+use hir::AnyExpr;
+use hir::Body;
+use hir::CallTarget;
+use hir::Expr;
+use hir::ExprId;
+use hir::FunctionDef;
+use hir::Literal;
+use hir::Name;
+use hir::NameArity;
+use hir::Semantic;
+use hir::Strategy;
+use hir::fold::MacroStrategy;
+use hir::fold::ParenStrategy;
+
+use super::Diagnostic;
+use super::DiagnosticConditions;
+use super::DiagnosticDescriptor;
+use super::Severity;
+
+/// Larger minimum lengths give patterns too long to be more readable.
+const MAX_ELEMENTS: i128 = 4;
+
+pub(crate) static DESCRIPTOR: DiagnosticDescriptor = DiagnosticDescriptor {
+    conditions: DiagnosticConditions {
+        experimental: false,
+        include_generated: false,
+        include_tests: true,
+        default_disabled: false,
+    },
+    codes: &[DiagnosticCode::LengthAtLeast],
+    checker: &|diagnostics, sema, file_id, _ext, _config| {
+        sema.for_each_function(file_id, |def| {
+            check_function(diagnostics, sema, file_id, def);
+        });
+    },
+};
+
+fn check_function(
+    diagnostics: &mut Vec<Diagnostic>,
+    sema: &Semantic,
+    file_id: FileId,
+    def: &FunctionDef,
+) {
+    let def_fb = def.in_function_body(sema, def);
+    def_fb.fold_function(
+        Strategy {
+            macros: MacroStrategy::DoNotExpand,
+            parens: ParenStrategy::InvisibleParens,
+        },
+        (),
+        &mut |_acc, clause_id, ctx| {
+            let AnyExpr::Expr(Expr::BinaryOp {
+                lhs,
+                rhs,
+                op: BinaryOp::CompOp(CompOp::Ord { ordering, strict }),
+            }) = &ctx.item
+            else {
+                return;
+            };
+            let body = def_fb.body(clause_id);
+            // Normalise to `length(L) > N` or `length(L) >= N`
+            let (length, bound) = match ordering {
+                Ordering::Greater => (*lhs, *rhs),
+                Ordering::Less => (*rhs, *lhs),
+            };
+            if !is_length_call(sema, file_id, &body, length) {
+                return;
+            }
+            let Expr::Literal(Literal::Integer(bound)) = &body[bound] else {
+                return;
+            };
+            let elements = if *strict {
+                bound.value + 1
+            } else {
+                bound.value
+            };
+            if !(1..=MAX_ELEMENTS).contains(&elements) {
+                return;
+            }
+            if let Some(range) = ctx
+                .item_id
+                .as_expr_id()
+                .and_then(|expr_id| def_fb.range_for_expr(clause_id, expr_id))
+                .filter(|range| range.file_id == file_id)
+            {
+                let pattern = format!("[{} | _]", vec!["_"; elements as usize].join(", "));
+                diagnostics.push(
+                    Diagnostic::new(
+                        DiagnosticCode::LengthAtLeast,
+                        format!(
+                            "`length/1` traverses the whole list, match `{pattern}` to check for at least {elements} element{} instead.",
+                            if elements == 1 { "" } else { "s" }
+                        ),
+                        range.range,
+                    )
+                    .with_severity(Severity::WeakWarning)
+                    .with_ignore_fix(sema, file_id),
+                );
+            }
+        },
+    )
+}
+
+/// Whether the expression is a call to the `erlang:length/1` BIF.
+fn is_length_call(sema: &Semantic, file_id: FileId, body: &Body, expr_id: ExprId) -> bool {
+    let Expr::Call { target, args } = &body[expr_id] else {
+        return false;
+    };
+    if args.len() != 1 {
+        return false;
+    }
+    match target.label_short(sema, body).as_deref() {
+        Some("erlang:length") => true,
+        // A local `length(L)` call is to the module's own function, if
+        // it has one
+        Some("length") => {
+            matches!(target, CallTarget::Local { .. })
+                && sema
+                    .def_map_local(file_id)
+                    .get_function(&NameArity::new(Name::from_erlang_service("length"), 1))
+                    .is_none()
+        }
+        _ => false,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+
+    use crate::diagnostics::Diagnostic;
+    use crate::diagnostics::DiagnosticCode;
+    use crate::tests;
+
+    fn filter(d: &Diagnostic) -> bool {
+        d.code == DiagnosticCode::LengthAtLeast
+    }
+
+    #[track_caller]
+    fn check_diagnostics(fixture: &str) {
+        tests::check_filtered_diagnostics(fixture, &filter)
+    }
+
+    #[test]
+    fn detects_length_lower_bound() {
+        check_diagnostics(
+            r#"
+         //- /src/main.erl
+         -module(main).
+         -export([f/1, g/1]).
+
+         f(L) when length(L) >= 2 -> pair;
+         %%        ^^^^^^^^^^^^^^ 💡 weak: `length/1` traverses the whole list, match `[_, _ | _]` to check for at least 2 elements instead.
+         f(_) -> other.
+
+         g(L) -> 0 < erlang:length(L).
+         %%      ^^^^^^^^^^^^^^^^^^^^ 💡 weak: `length/1` traverses the whole list, match `[_ | _]` to check for at least 1 element instead.
+            "#,
+        )
+    }
+
+    #[test]
+    fn ignores_other_comparisons() {
+        check_diagnostics(
+            r#"
+         //- /src/main.erl
+         -module(main).
+         -export([f/2, g/1, h/1]).
+
+         f(L, N) -> length(L) >= N.
+         g(L) -> length(L) =< 2.
+         h(L) -> length(L) > 100.
+            "#,
+        )
+    }
+}
@@ -0,0 +1,158 @@
+/*
+ * Copyright (c) Meta Platforms, Inc. and affiliates.
+ *
+ * This source code is dual-licensed under either the MIT license found in the
+ * LICENSE-MIT file in the root directory of this source tree or the Apache
+ * License, Version 2.0 found in the LICENSE-APACHE file in the root directory
+ * of this source tree. You may select, at your option, one of the
+ * above-listed licenses.
+ */
+
+//! Lint: list_subtraction
+//!
+//! Return a warning for a list subtraction `A -- B`, which takes time
+//! proportional to the product of the lengths of its operands. Subtracting
+//! from, or subtracting, a short literal list is cheap, so those are not
+//! reported. Disabled by default, since most lists are short anyway.
+
+use elp_ide_db::DiagnosticCode;
+use elp_ide_db::elp_base_db::FileId;
+use elp_syntax::ast::BinaryOp;
+use elp_syntax::ast::ListOp;
+use hir::AnyExpr;
+use hir::Body;
+use hir::Expr;
+use hir::ExprId;
+use hir::FunctionDef;
+use hir::Literal;
+use hir::Semantic;
+use hir::Strategy;
+use hir::fold::MacroStrategy;
+use hir::fold::ParenStrategy;
+
+use super::Diagnostic;
+use super::DiagnosticConditions;
+use super::DiagnosticDescriptor;
+use super::Severity;
+
+/// Literal lists up to this length are considered small enough for `--`.
+const MAX_SMALL_LIST_LENGTH: usize = 8;
+
+pub(crate) static DESCRIPTOR: DiagnosticDescriptor = DiagnosticDescriptor {
+    conditions: DiagnosticConditions {
+        experimental: false,
+        include_generated: false,
+        include_tests: true,
+        default_disabled: true,
+    },
+    codes: &[DiagnosticCode::ListSubtraction],
+    checker: &|diagnostics, sema, file_id, _ext, _config| {
+        sema.for_each_function(file_id, |def| {
+            check_function(diagnostics, sema, file_id, def);
+        });
+    },
+};
+
+fn check_function(
+    diagnostics: &mut Vec<Diagnostic>,
+    sema: &Semantic,
+    file_id: FileId,
+    def: &FunctionDef,
+) {
+    let def_fb = def.in_function_body(sema, def);
+    def_fb.fold_function(
+        Strategy {
+            macros: MacroStrategy::DoNotExpand,
+            parens: ParenStrategy::InvisibleParens,
+        },
+        (),
+        &mut |_acc, clause_id, ctx| {
+            let AnyExpr::Expr(Expr::BinaryOp {
+                lhs,
+                rhs,
+                op: BinaryOp::ListOp(ListOp::Subtract),
+            }) = &ctx.item
+            else {
+                return;
+            };
+            let body = def_fb.body(clause_id);
+            if is_small_list(&body, *lhs) || is_small_list(&body, *rhs) {
+                return;
+            }
+            if let Some(range) = ctx
+                .item_id
+                .as_expr_id()
+                .and_then(|expr_id| def_fb.range_for_expr(clause_id, expr_id))
+                .filter(|range| range.file_id == file_id)
+            {
+                diagnostics.push(
+                    Diagnostic::new(
+                        DiagnosticCode::ListSubtraction,
+                        "List subtraction with `--` is slow for large lists, consider using `sets` or maps instead.",
+                        range.range,
+                    )
+                    .with_severity(Severity::WeakWarning)
+                    .with_ignore_fix(sema, file_id),
+                );
+            }
+        },
+    )
+}
+
+/// Whether the expression is a literal list, or string, with at most
+/// `MAX_SMALL_LIST_LENGTH` elements.
+fn is_small_list(body: &Body, expr_id: ExprId) -> bool {
+    match &body[expr_id] {
+        Expr::List { exprs, tail: None } => exprs.len() <= MAX_SMALL_LIST_LENGTH,
+        Expr::Literal(Literal::String(string)) => string.chars().count() <= MAX_SMALL_LIST_LENGTH,
+        Expr::Paren { expr } => is_small_list(body, *expr),
+        _ => false,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+
+    use crate::diagnostics::Diagnostic;
+    use crate::diagnostics::DiagnosticCode;
+    use crate::diagnostics::DiagnosticsConfig;
+    use crate::tests;
+
+    fn filter(d: &Diagnostic) -> bool {
+        d.code == DiagnosticCode::ListSubtraction
+    }
+
+    #[track_caller]
+    fn check_diagnostics(fixture: &str) {
+        let config = DiagnosticsConfig::default().enable(DiagnosticCode::ListSubtraction);
+        tests::check_filtered_diagnostics_with_config(config, &vec![], fixture, &filter)
+    }
+
+    #[test]
+    fn detects_variable_subtraction() {
+        check_diagnostics(
+            r#"
+         //- /src/main.erl
+         -module(main).
+         -export([f/2]).
+
+         f(Xs, Ys) -> Xs -- Ys.
+         %%           ^^^^^^^^ 💡 weak: List subtraction with `--` is slow for large lists, consider using `sets` or maps instead.
+            "#,
+        )
+    }
+
+    #[test]
+    fn ignores_small_literal_lists() {
+        check_diagnostics(
+            r#"
+         //- /src/main.erl
+         -module(main).
+         -export([f/0, g/1]).
+
+         f() -> [1, 2, 3] -- [2].
+         g(Xs) -> Xs -- [undefined].
+            "#,
+        )
+    }
+}
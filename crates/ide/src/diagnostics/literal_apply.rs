@@ -0,0 +1,232 @@
+/*
+ * Copyright (c) Meta Platforms, Inc. and affiliates.
+ *
+ * This source code is dual-licensed under either the MIT license found in the
+ * LICENSE-MIT file in the root directory of this source tree or the Apache
+ * License, Version 2.0 found in the LICENSE-APACHE file in the root directory
+ * of this source tree. You may select, at your option, one of the
+ * above-listed licenses.
+ */
+
+//! Lint: literal_apply
+//!
+//! Return a warning for `apply(Module, Function, Args)` where the module and
+//! the function are atoms and the arguments a literal list, e.g.
+//! `apply(foo, bar, [1, 2])`, which can be written as the direct call
+//! `foo:bar(1, 2)`.
+
+use std::borrow::Cow;
+
+use elp_ide_assists::Assist;
+use elp_ide_db::elp_base_db::FileId;
+use elp_ide_db::source_change::SourceChangeBuilder;
+use elp_syntax::AstNode;
+use elp_syntax::algo;
+use elp_syntax::ast;
+use hir::Atom;
+use hir::Body;
+use hir::Expr;
+use hir::ExprId;
+use hir::Semantic;
+
+use crate::FunctionMatch;
+use crate::codemod_helpers::CheckCallCtx;
+use crate::codemod_helpers::MatchCtx;
+use crate::diagnostics::DiagnosticCode;
+use crate::diagnostics::FunctionCallLinter;
+use crate::diagnostics::Linter;
+use crate::diagnostics::Severity;
+use crate::fix;
+use crate::lazy_function_matches;
+
+pub(crate) struct LiteralApplyLinter;
+
+impl Linter for LiteralApplyLinter {
+    fn id(&self) -> DiagnosticCode {
+        DiagnosticCode::LiteralApply
+    }
+
+    fn description(&self) -> &'static str {
+        "Call the function directly instead of through `apply/3`."
+    }
+
+    fn severity(&self) -> Severity {
+        Severity::WeakWarning
+    }
+}
+
+impl FunctionCallLinter for LiteralApplyLinter {
+    /// The `module:function/arity` being applied.
+    type Context = String;
+
+    fn match_description(&self, context: &Self::Context) -> Cow<'_, str> {
+        Cow::Owned(format!(
+            "Call `{context}` directly instead of through `apply/3`."
+        ))
+    }
+
+    fn matches_functions(&self) -> Vec<FunctionMatch> {
+        lazy_function_matches![vec![FunctionMatch::mfa("erlang", "apply", 3)]]
+    }
+
+    fn check_match(&self, context: &CheckCallCtx<'_, ()>) -> Option<Self::Context> {
+        let body = context.in_clause.body();
+        let [module, function, args] = context.args.as_vec()[..] else {
+            return None;
+        };
+        let module = literal_atom(&body, module)?;
+        let function = literal_atom(&body, function)?;
+        // The arity is only known for a proper list
+        let arity = match &body[args] {
+            Expr::List { exprs, tail: None } => exprs.len(),
+            _ => return None,
+        };
+        let db = context.in_clause.sema.db;
+        Some(format!(
+            "{}:{}/{arity}",
+            db.lookup_atom(module),
+            db.lookup_atom(function)
+        ))
+    }
+
+    fn fixes(
+        &self,
+        match_context: &MatchCtx<Self::Context>,
+        sema: &Semantic,
+        file_id: FileId,
+    ) -> Option<Vec<Assist>> {
+        if match_context.range.file_id != file_id {
+            return None;
+        }
+        let range = match_context.range.range;
+        let source = sema.parse(file_id).value;
+        let call = algo::find_node_at_range::<ast::Call>(source.syntax(), range)?;
+        let [module, function, args]: [ast::Expr; 3] =
+            call.args()?.args().collect::<Vec<_>>().try_into().ok()?;
+        let args = match args {
+            ast::Expr::ExprMax(ast::ExprMax::List(list)) => list
+                .exprs()
+                .map(|arg| arg.syntax().text().to_string())
+                .collect::<Vec<_>>()
+                .join(", "),
+            _ => return None,
+        };
+        let mut builder = SourceChangeBuilder::new(file_id);
+        builder.replace(
+            range,
+            format!(
+                "{}:{}({args})",
+                module.syntax().text(),
+                function.syntax().text()
+            ),
+        );
+        Some(vec![fix(
+            "literal_apply",
+            "Rewrite to a direct call",
+            builder.finish(),
+            range,
+        )])
+    }
+}
+
+pub(crate) static LINTER: LiteralApplyLinter = LiteralApplyLinter;
+
+/// The atom an expression evaluates to, looking through macros such as
+/// `?MODULE`.
+fn literal_atom(body: &Body, expr_id: ExprId) -> Option<Atom> {
+    match &body[expr_id] {
+        Expr::MacroCall { expansion, .. } => literal_atom(body, *expansion),
+        expr => expr.as_atom(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+
+    use expect_test::Expect;
+    use expect_test::expect;
+
+    use crate::diagnostics::Diagnostic;
+    use crate::diagnostics::DiagnosticCode;
+    use crate::tests;
+
+    fn filter(d: &Diagnostic) -> bool {
+        d.code == DiagnosticCode::LiteralApply
+    }
+
+    #[track_caller]
+    fn check_diagnostics(fixture: &str) {
+        tests::check_filtered_diagnostics(fixture, &filter)
+    }
+
+    #[track_caller]
+    fn check_fix(fixture_before: &str, fixture_after: Expect) {
+        tests::check_fix(fixture_before, fixture_after)
+    }
+
+    #[test]
+    fn detects_literal_apply() {
+        check_diagnostics(
+            r#"
+         //- /src/main.erl
+         -module(main).
+
+         foo(X) ->
+             apply(lists, reverse, [X]),
+         %%  ^^^^^ 💡 weak: Call `lists:reverse/1` directly instead of through `apply/3`.
+             erlang:apply(main, bar, []).
+         %%  ^^^^^^^^^^^^ 💡 weak: Call `main:bar/0` directly instead of through `apply/3`.
+         bar() -> ok.
+         //- /opt/lib/stdlib-3.17/src/erlang.erl otp_app:/opt/lib/stdlib-3.17
+         -module(erlang).
+         -export([apply/3]).
+         apply(_, _, _) -> ok.
+            "#,
+        )
+    }
+
+    #[test]
+    fn ignores_unknown_arguments() {
+        check_diagnostics(
+            r#"
+         //- /src/main.erl
+         -module(main).
+
+         foo(M, F, Args, T) ->
+             apply(M, bar, []),
+             apply(main, F, []),
+             apply(main, bar, Args),
+             apply(main, bar, [1 | T]),
+             apply(fun bar/0, []).
+         bar() -> ok.
+         //- /opt/lib/stdlib-3.17/src/erlang.erl otp_app:/opt/lib/stdlib-3.17
+         -module(erlang).
+         -export([apply/3]).
+         apply(_, _, _) -> ok.
+            "#,
+        )
+    }
+
+    #[test]
+    fn fixes_literal_apply() {
+        check_fix(
+            r#"
+         //- /src/main.erl
+         -module(main).
+
+         foo(X) -> ap~ply(?MODULE, bar, [X, {X, 2}]).
+         bar(A, B) -> {A, B}.
+         //- /opt/lib/stdlib-3.17/src/erlang.erl otp_app:/opt/lib/stdlib-3.17
+         -module(erlang).
+         -export([apply/3]).
+         apply(_, _, _) -> ok.
+            "#,
+            expect![[r#"
+         -module(main).
+
+         foo(X) -> ?MODULE:bar(X, {X, 2}).
+         bar(A, B) -> {A, B}.
+            "#]],
+        )
+    }
+}
@@ -43,7 +43,8 @@ pub(crate) static DESCRIPTOR: DiagnosticDescriptor = DiagnosticDescriptor {
         include_tests: true,
         default_disabled: false,
     },
-    checker: &|diags, sema, file_id, _file_kind| {
+    codes: &[DiagnosticCode::MacroPrecedenceEscape],
+    checker: &|diags, sema, file_id, _file_kind, _config| {
         check_file(diags, sema, &file_id);
     },
 };
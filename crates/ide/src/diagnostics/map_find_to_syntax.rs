@@ -61,7 +61,8 @@ pub(crate) static DESCRIPTOR: DiagnosticDescriptor = DiagnosticDescriptor {
         include_tests: true,
         default_disabled: false,
     },
-    checker: &|diags, sema, file_id, _ext| {
+    codes: &[DiagnosticCode::MapsFindFunctionRatherThanSyntax],
+    checker: &|diags, sema, file_id, _ext, _config| {
         from_ssr(
             diags,
             sema,
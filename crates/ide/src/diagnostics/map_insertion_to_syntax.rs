@@ -39,7 +39,11 @@ pub(crate) static DESCRIPTOR: DiagnosticDescriptor = DiagnosticDescriptor {
         include_tests: true,
         default_disabled: false,
     },
-    checker: &|acc, sema, file_id, _ext| {
+    codes: &[
+        DiagnosticCode::MapsPutFunctionRatherThanSyntax,
+        DiagnosticCode::MapsUpdateFunctionRatherThanSyntax,
+    ],
+    checker: &|acc, sema, file_id, _ext, _config| {
         map_put_to_syntax_ssr(acc, sema, file_id);
         map_update_to_syntax_ssr(acc, sema, file_id);
     },
@@ -48,7 +48,8 @@ pub(crate) static DESCRIPTOR: DiagnosticDescriptor = DiagnosticDescriptor {
         include_tests: false,
         default_disabled: true,
     },
-    checker: &|diags, sema, file_id, file_kind| {
+    codes: &[crate::diagnostics::DiagnosticCode::MissingCompileWarnMissingSpec],
+    checker: &|diags, sema, file_id, file_kind, _config| {
         missing_compile_warn_missing_spec(diags, sema, file_id, file_kind);
     },
 };
@@ -584,4 +585,86 @@ mod tests {
             "#,
         )
     }
+
+    #[test]
+    fn ignore_range_is_honoured() {
+        check_diagnostics(
+            r#"
+         //- /erl/my_app/src/main.erl
+            -module(main).
+            % elp:ignore-start W0012
+            -compile(warn_missing_spec).
+            % elp:ignore-end
+            "#,
+        )
+    }
+
+    #[test]
+    fn ignore_range_only_covers_enclosed_lines() {
+        check_diagnostics(
+            r#"
+         //- /erl/my_app/src/main.erl
+            -module(main).
+            % elp:ignore-start W0012
+            % elp:ignore-end
+            -compile(warn_missing_spec).
+         %% ^^^^^^^^^^^^^^^^^^^^^^^^^^^^ 💡 error: Please add "-compile(warn_missing_spec_all)." to the module. If exported functions are not all specced, they need to be specced.
+            "#,
+        )
+    }
+
+    #[test]
+    fn ignore_range_unmatched_start_covers_rest_of_file() {
+        check_diagnostics(
+            r#"
+         //- /erl/my_app/src/main.erl
+            -module(main).
+            % elp:ignore-start W0012
+
+            -compile(warn_missing_spec).
+            "#,
+        )
+    }
+
+    #[test]
+    fn ignore_range_unmatched_end_is_ignored() {
+        check_diagnostics(
+            r#"
+         //- /erl/my_app/src/main.erl
+            -module(main).
+            % elp:ignore-end
+            -compile(warn_missing_spec).
+         %% ^^^^^^^^^^^^^^^^^^^^^^^^^^^^ 💡 error: Please add "-compile(warn_missing_spec_all)." to the module. If exported functions are not all specced, they need to be specced.
+            "#,
+        )
+    }
+
+    #[test]
+    fn ignore_range_nested() {
+        check_diagnostics(
+            r#"
+         //- /erl/my_app/src/main.erl
+            -module(main).
+            % elp:ignore-start W0012
+            % elp:ignore-start W0007
+            % elp:ignore-end
+            -compile(warn_missing_spec).
+            % elp:ignore-end
+            "#,
+        )
+    }
+
+    #[test]
+    fn ignore_range_end_for_other_code() {
+        check_diagnostics(
+            r#"
+         //- /erl/my_app/src/main.erl
+            -module(main).
+            % elp:ignore-start W0012
+            % elp:ignore-end W0007
+            -compile(warn_missing_spec).
+            % elp:ignore-end W0012
+            "#,
+        )
+    }
 }
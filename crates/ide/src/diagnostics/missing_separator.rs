@@ -32,7 +32,8 @@ pub(crate) static DESCRIPTOR: DiagnosticDescriptor = DiagnosticDescriptor {
         include_tests: true,
         default_disabled: false,
     },
-    checker: &|diags, sema, file_id, _ext| {
+    codes: &[],
+    checker: &|diags, sema, file_id, _ext, _config| {
         missing_separator_semantic(diags, sema, file_id);
     },
 };
@@ -13,7 +13,9 @@
 // Diagnostic for mismatches between the module attribute name and the path of the given file
 
 use elp_ide_assists::Assist;
+use elp_ide_db::elp_base_db::AnchoredPathBuf;
 use elp_ide_db::elp_base_db::FileId;
+use elp_ide_db::source_change::FileSystemEdit;
 use elp_ide_db::source_change::SourceChange;
 use elp_syntax::AstNode;
 use elp_syntax::SyntaxNode;
@@ -37,17 +39,19 @@ pub(crate) fn module_mismatch(
     let root_id = sema.db.file_source_root(file_id);
     let root = sema.db.source_root(root_id);
     let path = root.path_for_file(&file_id).unwrap();
-    let filename = path.name_and_extension().unwrap_or_default().0;
+    let (filename, extension) = path.name_and_extension().unwrap_or_default();
     let loc = module_name.syntax().text_range();
-    if module_name.text()? != filename {
+    let module_name_text = module_name.text()?;
+    if module_name_text != filename {
         let d = Diagnostic::new(
             crate::diagnostics::DiagnosticCode::ModuleMismatch,
             format!("Module name ({module_name}) does not match file name ({filename})"),
             loc,
         )
-        .with_fixes(Some(vec![rename_module_to_match_filename(
-            file_id, loc, filename,
-        )]));
+        .with_fixes(Some(vec![
+            rename_module_to_match_filename(file_id, loc, filename),
+            rename_file_to_match_module(file_id, loc, &module_name_text, extension),
+        ]));
         acc.push(d);
     };
     Some(())
@@ -65,11 +69,40 @@ fn rename_module_to_match_filename(file_id: FileId, loc: TextRange, filename: &s
     )
 }
 
+fn rename_file_to_match_module(
+    file_id: FileId,
+    loc: TextRange,
+    module_name: &str,
+    extension: Option<&str>,
+) -> Assist {
+    let filename = match extension {
+        Some(extension) => format!("{module_name}.{extension}"),
+        None => module_name.to_string(),
+    };
+    let edit = FileSystemEdit::MoveFile {
+        src: file_id,
+        dst: AnchoredPathBuf {
+            anchor: file_id,
+            path: filename.clone(),
+        },
+    };
+    fix(
+        "rename_file_to_match_module",
+        &format!("Rename file to: {filename}"),
+        SourceChange::from(edit),
+        loc,
+    )
+}
+
 #[cfg(test)]
 mod tests {
 
+    use elp_ide_db::source_change::FileSystemEdit;
     use expect_test::expect;
 
+    use crate::diagnostics::DiagnosticCode;
+    use crate::diagnostics::DiagnosticsConfig;
+    use crate::fixture;
     use crate::tests::check_diagnostics;
     use crate::tests::check_fix;
 
@@ -102,4 +135,42 @@ mod tests {
             "#,
         );
     }
+
+    #[test]
+    fn test_module_mismatch_offers_both_renames() {
+        let (analysis, position, diagnostics_enabled) = fixture::position(
+            r#"
+//- /src/foo.erl
+-module(b~ar).
+"#,
+        );
+        let diagnostics = fixture::diagnostics_for(
+            &analysis,
+            position.file_id,
+            &DiagnosticsConfig::default(),
+            &vec![],
+            &diagnostics_enabled,
+        );
+        let fixes: Vec<_> = diagnostics
+            .diagnostics_for(position.file_id)
+            .into_iter()
+            .filter(|d| d.code == DiagnosticCode::ModuleMismatch)
+            .flat_map(|d| d.fixes.clone().unwrap_or_default())
+            .collect();
+        let labels: Vec<String> = fixes.iter().map(|fix| fix.label.to_string()).collect();
+        assert_eq!(
+            labels,
+            vec!["Rename module to: foo", "Rename file to: bar.erl"]
+        );
+
+        let source_change = fixes[1].source_change.as_ref().unwrap();
+        match &source_change.file_system_edits[..] {
+            [FileSystemEdit::MoveFile { src, dst }] => {
+                assert_eq!(*src, position.file_id);
+                assert_eq!(dst.anchor, position.file_id);
+                assert_eq!(dst.path, "bar.erl");
+            }
+            edits => panic!("unexpected file system edits: {edits:?}"),
+        }
+    }
 }
@@ -50,7 +50,8 @@ pub(crate) static DESCRIPTOR: DiagnosticDescriptor = DiagnosticDescriptor {
         include_tests: true,
         default_disabled: false,
     },
-    checker: &|diags, sema, file_id, _ext| {
+    codes: &[DiagnosticCode::MutableVarBug],
+    checker: &|diags, sema, file_id, _ext, _config| {
         mutable_variable_bug(diags, sema, file_id);
     },
 };
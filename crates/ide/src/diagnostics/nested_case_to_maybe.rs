@@ -0,0 +1,379 @@
+/*
+ * Copyright (c) Meta Platforms, Inc. and affiliates.
+ *
+ * This source code is dual-licensed under either the MIT license found in the
+ * LICENSE-MIT file in the root directory of this source tree or the Apache
+ * License, Version 2.0 found in the LICENSE-APACHE file in the root directory
+ * of this source tree. You may select, at your option, one of the
+ * above-listed licenses.
+ */
+
+//! Lint: nested_case_to_maybe
+//!
+//! Return a warning for a pyramid of nested `case` expressions, each matching
+//! `{ok, _}` and continuing in its success branch while passing errors
+//! through unchanged, which reads better as a `maybe ... else ... end`
+//! expression.
+
+use elp_ide_db::DiagnosticCode;
+use elp_ide_db::elp_base_db::FileId;
+use elp_syntax::AstNode;
+use elp_syntax::SyntaxKind;
+use elp_syntax::TextRange;
+use elp_syntax::ast;
+use fxhash::FxHashSet;
+use hir::AnyExpr;
+use hir::Body;
+use hir::CRClause;
+use hir::Expr;
+use hir::ExprId;
+use hir::FunctionDef;
+use hir::Literal;
+use hir::Name;
+use hir::Pat;
+use hir::PatId;
+use hir::Semantic;
+use hir::Strategy;
+use hir::Var;
+use hir::fold::MacroStrategy;
+use hir::fold::ParenStrategy;
+use hir::known;
+
+use super::Diagnostic;
+use super::DiagnosticConditions;
+use super::DiagnosticDescriptor;
+use super::Severity;
+
+/// The number of nested cases from which the lint is reported
+const MIN_DEPTH: usize = 3;
+
+pub(crate) static DESCRIPTOR: DiagnosticDescriptor = DiagnosticDescriptor {
+    conditions: DiagnosticConditions {
+        experimental: true,
+        include_generated: false,
+        include_tests: true,
+        default_disabled: true,
+    },
+    codes: &[DiagnosticCode::NestedCaseToMaybe],
+    checker: &|diagnostics, sema, file_id, _ext, _config| {
+        sema.for_each_function(file_id, |def| {
+            check_function(diagnostics, sema, file_id, def)
+        });
+    },
+};
+
+fn check_function(
+    diagnostics: &mut Vec<Diagnostic>,
+    sema: &Semantic,
+    file_id: FileId,
+    def: &FunctionDef,
+) {
+    let def_fb = def.in_function_body(sema, def);
+    let cases = def_fb.fold_function(
+        Strategy {
+            macros: MacroStrategy::Expand,
+            parens: ParenStrategy::InvisibleParens,
+        },
+        Vec::new(),
+        &mut |mut acc, clause_id, ctx| {
+            if let AnyExpr::Expr(Expr::Case { .. }) = ctx.item
+                && let Some(expr_id) = ctx.item_id.as_expr_id()
+            {
+                acc.push((clause_id, expr_id));
+            }
+            acc
+        },
+    );
+    // Only the outermost case of a pyramid is reported
+    let mut nested = FxHashSet::default();
+    let mut chains = Vec::new();
+    for (clause_id, expr_id) in cases {
+        let body = def_fb.body(clause_id);
+        let mut depth = 1;
+        let mut current = expr_id;
+        while let Some(inner) = ok_branch_case(sema, &body, current) {
+            nested.insert((clause_id, inner));
+            depth += 1;
+            current = inner;
+        }
+        chains.push((clause_id, expr_id, depth));
+    }
+    for (clause_id, expr_id, depth) in chains {
+        if depth < MIN_DEPTH || nested.contains(&(clause_id, expr_id)) {
+            continue;
+        }
+        let map = def_fb.get_body_map(clause_id);
+        let Some(range) = map
+            .expr(expr_id)
+            .filter(|ptr| ptr.file_id() == file_id)
+            .and_then(|ptr| match ptr.to_ast(sema.db) {
+                ast::Expr::ExprMax(ast::ExprMax::CaseExpr(case)) => case_head_range(&case),
+                _ => None,
+            })
+        else {
+            continue;
+        };
+        diagnostics.push(
+            Diagnostic::new(
+                DiagnosticCode::NestedCaseToMaybe,
+                format!(
+                    "{depth} nested `case` expressions matching `{{ok, _}}`, consider a `maybe` expression."
+                ),
+                range,
+            )
+            .with_severity(Severity::WeakWarning)
+            .experimental()
+            .with_ignore_fix(sema, file_id),
+        );
+    }
+}
+
+/// The range of `case ... of`, the whole expression is usually too large
+/// to highlight.
+fn case_head_range(case: &ast::CaseExpr) -> Option<TextRange> {
+    let of = case
+        .syntax()
+        .children_with_tokens()
+        .filter_map(|element| element.into_token())
+        .find(|token| token.kind() == SyntaxKind::ANON_OF)?;
+    Some(TextRange::new(
+        case.syntax().text_range().start(),
+        of.text_range().end(),
+    ))
+}
+
+/// If `expr_id` is a case with a single `{ok, _}` clause, all other clauses
+/// passing errors through, and the success branch ending with another such
+/// case, that inner case.
+fn ok_branch_case(sema: &Semantic, body: &Body, expr_id: ExprId) -> Option<ExprId> {
+    let last = *ok_clause(sema, body, expr_id)?.exprs.last()?;
+    ok_clause(sema, body, last)?;
+    Some(last)
+}
+
+/// If `expr_id` is a case with a single `{ok, _}` clause, all other clauses
+/// passing errors through unchanged, that `{ok, _}` clause.
+fn ok_clause<'a>(sema: &Semantic, body: &'a Body, expr_id: ExprId) -> Option<&'a CRClause> {
+    let Expr::Case { clauses, .. } = &body[expr_id] else {
+        return None;
+    };
+    let (ok_clauses, error_clauses): (Vec<_>, Vec<_>) = clauses
+        .iter()
+        .partition(|clause| is_tagged_tuple(sema, body, clause.pat, known::ok));
+    let [ok_clause] = ok_clauses.as_slice() else {
+        return None;
+    };
+    if error_clauses.is_empty()
+        || !error_clauses
+            .iter()
+            .all(|clause| passes_error_through(sema, body, clause))
+    {
+        return None;
+    }
+    Some(ok_clause)
+}
+
+/// Whether the clause matches an error and evaluates to it unchanged, as
+/// the `else` of a `maybe` expression would, e.g. `{error, _} = E -> E`,
+/// `E -> E` or `{error, R} -> {error, R}`.
+fn passes_error_through(sema: &Semantic, body: &Body, clause: &CRClause) -> bool {
+    if !clause.guards.is_empty() {
+        return false;
+    }
+    let [expr] = clause.exprs.as_slice() else {
+        return false;
+    };
+    match &body[*expr] {
+        Expr::Var(var) => binds_whole_error(sema, body, clause.pat, *var),
+        Expr::Tuple { exprs } => match (&body[clause.pat], exprs.as_slice()) {
+            (Pat::Tuple { pats }, [tag, reason]) => {
+                is_tagged_tuple(sema, body, clause.pat, known::error)
+                    && matches!(
+                        &body[*tag],
+                        Expr::Literal(Literal::Atom(atom)) if atom.as_name(sema.db.upcast()) == known::error
+                    )
+                    && matches!(
+                        (&body[pats[1]], &body[*reason]),
+                        (Pat::Var(pat_var), Expr::Var(var)) if pat_var == var
+                    )
+            }
+            _ => false,
+        },
+        _ => false,
+    }
+}
+
+/// Whether the pattern binds `var` to the whole value, and only matches
+/// errors or anything.
+fn binds_whole_error(sema: &Semantic, body: &Body, pat_id: PatId, var: Var) -> bool {
+    match &body[pat_id] {
+        Pat::Var(pat_var) => *pat_var == var,
+        Pat::Match { lhs, rhs } => {
+            (matches!(&body[*lhs], Pat::Var(pat_var) if *pat_var == var)
+                && is_tagged_tuple(sema, body, *rhs, known::error))
+                || (matches!(&body[*rhs], Pat::Var(pat_var) if *pat_var == var)
+                    && is_tagged_tuple(sema, body, *lhs, known::error))
+        }
+        _ => false,
+    }
+}
+
+/// Whether the pattern is a two element tuple tagged with `tag`
+fn is_tagged_tuple(sema: &Semantic, body: &Body, pat_id: PatId, tag: Name) -> bool {
+    match &body[pat_id] {
+        Pat::Tuple { pats } => match pats.as_slice() {
+            [first, _] => matches!(
+                &body[*first],
+                Pat::Literal(Literal::Atom(atom)) if atom.as_name(sema.db.upcast()) == tag
+            ),
+            _ => false,
+        },
+        Pat::Match { lhs, rhs } => {
+            is_tagged_tuple(sema, body, *lhs, tag.clone()) || is_tagged_tuple(sema, body, *rhs, tag)
+        }
+        _ => false,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+
+    use crate::diagnostics::Diagnostic;
+    use crate::diagnostics::DiagnosticCode;
+    use crate::diagnostics::DiagnosticsConfig;
+    use crate::tests;
+
+    fn filter(d: &Diagnostic) -> bool {
+        d.code == DiagnosticCode::NestedCaseToMaybe
+    }
+
+    #[track_caller]
+    fn check_diagnostics(fixture: &str) {
+        let config = DiagnosticsConfig::default()
+            .set_experimental(true)
+            .enable(DiagnosticCode::NestedCaseToMaybe);
+        tests::check_filtered_diagnostics_with_config(config, &vec![], fixture, &filter)
+    }
+
+    #[test]
+    fn detects_three_nested_cases() {
+        check_diagnostics(
+            r#"
+         //- /src/main.erl
+         -module(main).
+
+         foo(X) ->
+             case a(X) of
+         %%  ^^^^^^^^^^^^ 💡 weak: 3 nested `case` expressions matching `{ok, _}`, consider a `maybe` expression.
+                 {ok, A} ->
+                     case b(A) of
+                         {ok, B} ->
+                             case c(B) of
+                                 {ok, C} -> {ok, C};
+                                 {error, _} = Error -> Error
+                             end;
+                         {error, _} = Error -> Error
+                     end;
+                 Error -> Error
+             end.
+
+         a(X) -> {ok, X}.
+         b(X) -> {ok, X}.
+         c(X) -> {ok, X}.
+            "#,
+        )
+    }
+
+    #[test]
+    fn ignores_shallow_or_unrelated_cases() {
+        check_diagnostics(
+            r#"
+         //- /src/main.erl
+         -module(main).
+
+         foo(X) ->
+             case a(X) of
+                 {ok, A} ->
+                     case b(A) of
+                         {ok, B} -> B;
+                         {error, _} = Error -> Error
+                     end;
+                 {error, _} = Error -> Error
+             end.
+
+         bar(X) ->
+             case a(X) of
+                 {ok, A} ->
+                     case b(A) of
+                         {ok, B} ->
+                             case c(B) of
+                                 {ok, C} -> C;
+                                 {error, _} -> undefined
+                             end;
+                         other -> other
+                     end;
+                 Error -> Error
+             end.
+
+         a(X) -> {ok, X}.
+         b(X) -> {ok, X}.
+         c(X) -> {ok, X}.
+            "#,
+        )
+    }
+
+    #[test]
+    fn ignores_innermost_case_not_matching_ok() {
+        check_diagnostics(
+            r#"
+         //- /src/main.erl
+         -module(main).
+
+         foo(X) ->
+             case a(X) of
+                 {ok, A} ->
+                     case b(A) of
+                         {ok, B} ->
+                             case B of
+                                 true -> yes;
+                                 false -> no
+                             end;
+                         {error, _} = Error -> Error
+                     end;
+                 {error, _} = Error -> Error
+             end.
+
+         a(X) -> {ok, X}.
+         b(X) -> {ok, X}.
+            "#,
+        )
+    }
+
+    #[test]
+    fn ignores_error_clause_changing_the_error() {
+        check_diagnostics(
+            r#"
+         //- /src/main.erl
+         -module(main).
+
+         foo(X) ->
+             case a(X) of
+                 {ok, A} ->
+                     case b(A) of
+                         {ok, B} ->
+                             case c(B) of
+                                 {ok, C} -> {ok, C};
+                                 {error, Reason} -> {error, {c_failed, Reason}}
+                             end;
+                         {error, Reason} -> {error, Reason}
+                     end;
+                 Error -> Error
+             end.
+
+         a(X) -> {ok, X}.
+         b(X) -> {ok, X}.
+         c(X) -> {ok, X}.
+            "#,
+        )
+    }
+}
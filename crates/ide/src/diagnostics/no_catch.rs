@@ -8,13 +8,20 @@
  * above-listed licenses.
  */
 
+use elp_ide_assists::Assist;
+use elp_ide_assists::helpers::freshen_variable_name;
 use elp_ide_db::DiagnosticCode;
+use elp_ide_db::elp_base_db::FileId;
+use elp_ide_db::source_change::SourceChange;
 use elp_syntax::AstNode;
+use elp_syntax::ast;
+use elp_text_edit::TextEdit;
 use hir::AnyExpr;
 use hir::AnyExprId;
 use hir::BodySourceMap;
 use hir::Expr;
 use hir::FunctionDef;
+use hir::InFile;
 use hir::Semantic;
 use hir::Strategy;
 use hir::fold::MacroStrategy;
@@ -24,24 +31,33 @@ use super::DiagnosticConditions;
 use super::DiagnosticDescriptor;
 use crate::diagnostics::Diagnostic;
 use crate::diagnostics::Severity;
+use crate::fix;
 
 const DIAGNOSTIC_CODE: DiagnosticCode = DiagnosticCode::NoCatch;
 const DIAGNOSTIC_MESSAGE: &str = "Avoid `catch`.";
-const DIAGNOSTIC_SEVERITY: Severity = Severity::Warning;
+const DIAGNOSTIC_SEVERITY: Severity = Severity::WeakWarning;
 
 pub(crate) static DESCRIPTOR: DiagnosticDescriptor = DiagnosticDescriptor {
     conditions: DiagnosticConditions {
         experimental: false,
         include_generated: false,
         include_tests: true,
-        default_disabled: false,
+        default_disabled: true,
     },
-    checker: &|diagnostics, sema, file_id, _ext| {
-        sema.for_each_function(file_id, |def| check_function(diagnostics, sema, def));
+    codes: &[DiagnosticCode::NoCatch],
+    checker: &|diagnostics, sema, file_id, _ext, _config| {
+        sema.for_each_function(file_id, |def| {
+            check_function(diagnostics, sema, file_id, def)
+        });
     },
 };
 
-fn check_function(diagnostics: &mut Vec<Diagnostic>, sema: &Semantic, def: &FunctionDef) {
+fn check_function(
+    diagnostics: &mut Vec<Diagnostic>,
+    sema: &Semantic,
+    file_id: FileId,
+    def: &FunctionDef,
+) {
     let def_fb = def.in_function_body(sema, def);
     def_fb.fold_function(
         Strategy {
@@ -52,7 +68,7 @@ fn check_function(diagnostics: &mut Vec<Diagnostic>, sema: &Semantic, def: &Func
         &mut |_acc, clause_id, ctx| {
             if let AnyExpr::Expr(Expr::Catch { expr: _ }) = ctx.item {
                 let map = def_fb.get_body_map(clause_id);
-                if let Some(diagnostic) = make_diagnostic(sema, &map, ctx.item_id) {
+                if let Some(diagnostic) = make_diagnostic(sema, file_id, &map, ctx.item_id) {
                     diagnostics.push(diagnostic);
                 }
             };
@@ -60,16 +76,35 @@ fn check_function(diagnostics: &mut Vec<Diagnostic>, sema: &Semantic, def: &Func
     )
 }
 
-fn make_diagnostic(sema: &Semantic, map: &BodySourceMap, item_id: AnyExprId) -> Option<Diagnostic> {
+fn make_diagnostic(
+    sema: &Semantic,
+    file_id: FileId,
+    map: &BodySourceMap,
+    item_id: AnyExprId,
+) -> Option<Diagnostic> {
     match item_id {
         AnyExprId::Expr(expr_id) => {
             let ast_ptr = map.expr(expr_id)?;
+            if ast_ptr.file_id() != file_id {
+                return None;
+            }
             match &ast_ptr.to_ast(sema.db) {
-                elp_syntax::ast::Expr::CatchExpr(catch_expr) => {
+                ast::Expr::CatchExpr(catch_expr) => {
+                    // Leave alone a `catch` already guarded by a `try`, which
+                    // is usually deliberate
+                    if catch_expr
+                        .syntax()
+                        .ancestors()
+                        .any(|node| ast::TryExpr::can_cast(node.kind()))
+                    {
+                        return None;
+                    }
                     let catch_keyword = catch_expr.syntax().first_token()?;
                     let range = catch_keyword.text_range();
                     let diagnostic = Diagnostic::new(DIAGNOSTIC_CODE, DIAGNOSTIC_MESSAGE, range)
-                        .with_severity(DIAGNOSTIC_SEVERITY);
+                        .with_severity(DIAGNOSTIC_SEVERITY)
+                        .with_fixes(rewrite_as_try(sema, file_id, catch_expr))
+                        .with_ignore_fix(sema, file_id);
                     Some(diagnostic)
                 }
                 _ => None,
@@ -79,10 +114,60 @@ fn make_diagnostic(sema: &Semantic, map: &BodySourceMap, item_id: AnyExprId) ->
     }
 }
 
+/// Rewrite `catch Expr` as a `try` returning the class and reason of the
+/// exception. The variables of the `catch` clause must not be bound
+/// already, or they would be matched instead.
+fn rewrite_as_try(
+    sema: &Semantic,
+    file_id: FileId,
+    catch_expr: &ast::CatchExpr,
+) -> Option<Vec<Assist>> {
+    let expr = catch_expr.expr()?;
+    let range = catch_expr.syntax().text_range();
+    let vars_in_clause = sema.find_vars_in_clause_ast(&InFile::new(
+        file_id,
+        &ast::Expr::CatchExpr(catch_expr.clone()),
+    ));
+    let class = freshen_variable_name(sema, "Class".to_string(), &vars_in_clause);
+    let reason = freshen_variable_name(sema, "Reason".to_string(), &vars_in_clause);
+    let stk = freshen_variable_name(sema, "_Stk".to_string(), &vars_in_clause);
+    let edit = TextEdit::replace(
+        range,
+        format!(
+            "try {} catch {class}:{reason}:{stk} -> {{{class}, {reason}}} end",
+            expr.syntax().text()
+        ),
+    );
+    Some(vec![fix(
+        "rewrite_catch_as_try",
+        "Rewrite `catch` as `try`",
+        SourceChange::from_text_edit(file_id, edit),
+        range,
+    )])
+}
+
 #[cfg(test)]
 mod tests {
+    use elp_ide_db::DiagnosticCode;
+    use expect_test::Expect;
+    use expect_test::expect;
+
+    use crate::diagnostics::DiagnosticsConfig;
+    use crate::tests;
+
+    fn config() -> DiagnosticsConfig {
+        DiagnosticsConfig::default().enable(DiagnosticCode::NoCatch)
+    }
 
-    use crate::tests::check_diagnostics;
+    #[track_caller]
+    fn check_diagnostics(fixture: &str) {
+        tests::check_diagnostics_with_config(config(), fixture);
+    }
+
+    #[track_caller]
+    fn check_fix(before: &str, after: Expect) {
+        tests::check_fix_with_config(config(), before, after);
+    }
 
     #[test]
     fn basic() {
@@ -93,11 +178,72 @@ mod tests {
 
               catcher(X,Y) ->
               case catch X/Y of
-              %%   ^^^^^ warning: Avoid `catch`.
+              %%   ^^^^^ 💡 weak: Avoid `catch`.
                 {'EXIT', {badarith,_}} -> "uh oh";
                 N -> N
               end.
               "#,
         )
     }
+
+    #[test]
+    fn not_enabled_by_default() {
+        tests::check_diagnostics(
+            r#"
+              -module(main).
+              catcher(X) -> catch X().
+              "#,
+        )
+    }
+
+    #[test]
+    fn ignored_inside_try() {
+        check_diagnostics(
+            r#"
+              -module(main).
+              catcher(X) ->
+                  try
+                      catch X()
+                  after
+                      ok
+                  end.
+              "#,
+        )
+    }
+
+    #[test]
+    fn rewrite_as_try() {
+        check_fix(
+            r#"
+              -module(main).
+              catcher(X) ->
+                  Res = (ca~tch X()),
+                  Res.
+              "#,
+            expect![[r#"
+              -module(main).
+              catcher(X) ->
+                  Res = (try X() catch Class:Reason:_Stk -> {Class, Reason} end),
+                  Res.
+              "#]],
+        )
+    }
+
+    #[test]
+    fn rewrite_as_try_fresh_variables() {
+        check_fix(
+            r#"
+              -module(main).
+              catcher(X, Class) ->
+                  Reason = (ca~tch X()),
+                  {Class, Reason}.
+              "#,
+            expect![[r#"
+              -module(main).
+              catcher(X, Class) ->
+                  Reason = (try X() catch Class0:Reason0:_Stk -> {Class0, Reason0} end),
+                  {Class, Reason}.
+              "#]],
+        )
+    }
 }
@@ -29,7 +29,8 @@ pub(crate) static DESCRIPTOR: DiagnosticDescriptor = DiagnosticDescriptor {
         include_tests: false,
         default_disabled: false,
     },
-    checker: &|diags, sema, file_id, _ext| {
+    codes: &[DiagnosticCode::NoDialyzerAttribute],
+    checker: &|diags, sema, file_id, _ext, _config| {
         no_dialyzer_attribute(diags, sema, file_id);
     },
 };
@@ -33,7 +33,8 @@ pub(crate) static DESCRIPTOR: DiagnosticDescriptor = DiagnosticDescriptor {
         include_tests: true,
         default_disabled: false,
     },
-    checker: &|diags, sema, file_id, _ext| {
+    codes: &[DiagnosticCode::NoNoWarnSuppressions],
+    checker: &|diags, sema, file_id, _ext, _config| {
         no_warn_suppression(diags, sema, file_id);
     },
 };
@@ -41,7 +41,8 @@ pub(crate) static DESCRIPTOR: DiagnosticDescriptor = DiagnosticDescriptor {
         include_tests: true,
         default_disabled: false,
     },
-    checker: &|diags, sema, file_id, _ext| {
+    codes: &[DiagnosticCode::NonStandardIntegerFormatting],
+    checker: &|diags, sema, file_id, _ext, _config| {
         integer_literal_format(diags, sema, file_id);
     },
 };
@@ -0,0 +1,407 @@
+/*
+ * Copyright (c) Meta Platforms, Inc. and affiliates.
+ *
+ * This source code is dual-licensed under either the MIT license found in the
+ * LICENSE-MIT file in the root directory of this source tree or the Apache
+ * License, Version 2.0 found in the LICENSE-APACHE file in the root directory
+ * of this source tree. You may select, at your option, one of the
+ * above-listed licenses.
+ */
+
+//! Lints: string_tokens, string_strip, string_substr
+//!
+//! warn on calls to the obsolete functions of the `string` module that do
+//! not handle Unicode strings, and suggest their documented replacement:
+//!
+//! - `string:tokens(Str, Seps)` becomes `string:lexemes(Str, Seps)`
+//! - `string:strip(Str, Dir, Char)` becomes `string:trim(Str, Dir, [Char])`
+//! - `string:substr(Str, Start, Len)` becomes `string:slice(Str, Start - 1, Len)`
+
+use elp_ide_db::DiagnosticCode;
+use elp_ide_db::elp_base_db::FileId;
+use elp_ide_db::source_change::SourceChangeBuilder;
+use hir::Semantic;
+
+use crate::diagnostics::Linter;
+use crate::diagnostics::Severity;
+use crate::diagnostics::SsrPatternsLinter;
+use crate::fix;
+
+static STR_VAR: &str = "_@Str";
+static SEPS_VAR: &str = "_@Seps";
+static DIR_VAR: &str = "_@Dir";
+static CHAR_VAR: &str = "_@Char";
+static START_VAR: &str = "_@Start";
+static LEN_VAR: &str = "_@Len";
+
+// ---------------------------------------------------------------------
+
+pub(crate) struct StringTokensLinter;
+
+impl Linter for StringTokensLinter {
+    fn id(&self) -> DiagnosticCode {
+        DiagnosticCode::StringTokens
+    }
+
+    fn description(&self) -> &'static str {
+        "`string:tokens/2` is obsolete, use `string:lexemes/2` instead."
+    }
+
+    fn severity(&self) -> Severity {
+        Severity::WeakWarning
+    }
+}
+
+impl SsrPatternsLinter for StringTokensLinter {
+    type Context = ();
+
+    fn patterns(&self) -> Vec<(String, Self::Context)> {
+        vec![(format!("ssr: string:tokens({STR_VAR}, {SEPS_VAR})."), ())]
+    }
+
+    fn fixes(
+        &self,
+        _context: &Self::Context,
+        matched: &elp_ide_ssr::Match,
+        sema: &Semantic,
+        file_id: FileId,
+    ) -> Option<Vec<elp_ide_assists::Assist>> {
+        let string = matched.placeholder_text(sema, STR_VAR)?;
+        let seps = matched.placeholder_text(sema, SEPS_VAR)?;
+        replace_call(
+            matched,
+            file_id,
+            "string_tokens",
+            "string:lexemes/2",
+            format!("string:lexemes({string}, {seps})"),
+        )
+    }
+}
+
+pub(crate) static TOKENS_LINTER: StringTokensLinter = StringTokensLinter;
+
+// ---------------------------------------------------------------------
+
+pub(crate) struct StringStripLinter;
+
+impl Linter for StringStripLinter {
+    fn id(&self) -> DiagnosticCode {
+        DiagnosticCode::StringStrip
+    }
+
+    fn description(&self) -> &'static str {
+        "`string:strip` is obsolete, use `string:trim/3` instead."
+    }
+
+    fn severity(&self) -> Severity {
+        Severity::WeakWarning
+    }
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub(crate) enum StripArity {
+    One,
+    Two,
+    Three,
+}
+
+impl SsrPatternsLinter for StringStripLinter {
+    type Context = StripArity;
+
+    fn patterns(&self) -> Vec<(String, Self::Context)> {
+        vec![
+            (format!("ssr: string:strip({STR_VAR})."), StripArity::One),
+            (
+                format!("ssr: string:strip({STR_VAR}, {DIR_VAR})."),
+                StripArity::Two,
+            ),
+            (
+                format!("ssr: string:strip({STR_VAR}, {DIR_VAR}, {CHAR_VAR})."),
+                StripArity::Three,
+            ),
+        ]
+    }
+
+    fn fixes(
+        &self,
+        context: &Self::Context,
+        matched: &elp_ide_ssr::Match,
+        sema: &Semantic,
+        file_id: FileId,
+    ) -> Option<Vec<elp_ide_assists::Assist>> {
+        let string = matched.placeholder_text(sema, STR_VAR)?;
+        // `string:strip/1,2` only remove spaces
+        let (dir, chars) = match context {
+            StripArity::One => ("both", "\" \"".to_string()),
+            StripArity::Two => (trim_direction(matched, sema)?, "\" \"".to_string()),
+            StripArity::Three => (
+                trim_direction(matched, sema)?,
+                format!("[{}]", matched.placeholder_text(sema, CHAR_VAR)?),
+            ),
+        };
+        replace_call(
+            matched,
+            file_id,
+            "string_strip",
+            "string:trim/3",
+            format!("string:trim({string}, {dir}, {chars})"),
+        )
+    }
+}
+
+/// The `string:trim/3` direction equivalent to the literal `string:strip`
+/// direction of the match.
+fn trim_direction(matched: &elp_ide_ssr::Match, sema: &Semantic) -> Option<&'static str> {
+    match matched.placeholder_text(sema, DIR_VAR)?.as_str() {
+        "left" => Some("leading"),
+        "right" => Some("trailing"),
+        "both" => Some("both"),
+        _ => None,
+    }
+}
+
+pub(crate) static STRIP_LINTER: StringStripLinter = StringStripLinter;
+
+// ---------------------------------------------------------------------
+
+pub(crate) struct StringSubstrLinter;
+
+impl Linter for StringSubstrLinter {
+    fn id(&self) -> DiagnosticCode {
+        DiagnosticCode::StringSubstr
+    }
+
+    fn description(&self) -> &'static str {
+        "`string:substr` is obsolete, use `string:slice` instead."
+    }
+
+    fn severity(&self) -> Severity {
+        Severity::WeakWarning
+    }
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub(crate) enum SubstrArity {
+    Two,
+    Three,
+}
+
+impl SsrPatternsLinter for StringSubstrLinter {
+    type Context = SubstrArity;
+
+    fn patterns(&self) -> Vec<(String, Self::Context)> {
+        vec![
+            (
+                format!("ssr: string:substr({STR_VAR}, {START_VAR})."),
+                SubstrArity::Two,
+            ),
+            (
+                format!("ssr: string:substr({STR_VAR}, {START_VAR}, {LEN_VAR})."),
+                SubstrArity::Three,
+            ),
+        ]
+    }
+
+    fn fixes(
+        &self,
+        context: &Self::Context,
+        matched: &elp_ide_ssr::Match,
+        sema: &Semantic,
+        file_id: FileId,
+    ) -> Option<Vec<elp_ide_assists::Assist>> {
+        let string = matched.placeholder_text(sema, STR_VAR)?;
+        // `string:substr` counts from 1, `string:slice` from 0
+        let start = matched.placeholder_text(sema, START_VAR)?;
+        let start = match start.parse::<u64>() {
+            Ok(start) if start > 0 => (start - 1).to_string(),
+            _ if start.chars().all(|c| c.is_alphanumeric() || c == '_') => format!("{start} - 1"),
+            _ => format!("({start}) - 1"),
+        };
+        let replacement = match context {
+            SubstrArity::Two => format!("string:slice({string}, {start})"),
+            SubstrArity::Three => {
+                let len = matched.placeholder_text(sema, LEN_VAR)?;
+                format!("string:slice({string}, {start}, {len})")
+            }
+        };
+        replace_call(
+            matched,
+            file_id,
+            "string_substr",
+            "string:slice",
+            replacement,
+        )
+    }
+}
+
+pub(crate) static SUBSTR_LINTER: StringSubstrLinter = StringSubstrLinter;
+
+// ---------------------------------------------------------------------
+
+fn replace_call(
+    matched: &elp_ide_ssr::Match,
+    file_id: FileId,
+    id: &'static str,
+    replacement_name: &str,
+    replacement: String,
+) -> Option<Vec<elp_ide_assists::Assist>> {
+    let range = matched.range.range;
+    let mut builder = SourceChangeBuilder::new(file_id);
+    builder.replace(range, replacement);
+    Some(vec![fix(
+        id,
+        &format!("Replace with `{replacement_name}`"),
+        builder.finish(),
+        range,
+    )])
+}
+
+#[cfg(test)]
+mod tests {
+
+    use expect_test::Expect;
+    use expect_test::expect;
+
+    use crate::diagnostics::Diagnostic;
+    use crate::diagnostics::DiagnosticCode;
+    use crate::tests;
+
+    fn filter(d: &Diagnostic) -> bool {
+        matches!(
+            d.code,
+            DiagnosticCode::StringTokens
+                | DiagnosticCode::StringStrip
+                | DiagnosticCode::StringSubstr
+        )
+    }
+
+    #[track_caller]
+    fn check_diagnostics(fixture: &str) {
+        tests::check_filtered_diagnostics(fixture, &filter)
+    }
+
+    #[track_caller]
+    fn check_fix(fixture_before: &str, fixture_after: Expect) {
+        tests::check_fix(fixture_before, fixture_after)
+    }
+
+    #[test]
+    fn detects_obsolete_string_functions() {
+        check_diagnostics(
+            r#"
+         //- /src/main.erl
+         -module(main).
+
+         tokens(S) -> string:tokens(S, ",").
+         %%           ^^^^^^^^^^^^^^^^^^^^^ 💡 weak: `string:tokens/2` is obsolete, use `string:lexemes/2` instead.
+         strip(S) -> string:strip(S, left).
+         %%          ^^^^^^^^^^^^^^^^^^^^^ 💡 weak: `string:strip` is obsolete, use `string:trim/3` instead.
+         substr(S) -> string:substr(S, 2, 3).
+         %%           ^^^^^^^^^^^^^^^^^^^^^^ 💡 weak: `string:substr` is obsolete, use `string:slice` instead.
+         lexemes(S) -> string:lexemes(S, ",").
+         //- /opt/lib/stdlib-3.17/src/string.erl otp_app:/opt/lib/stdlib-3.17
+         -module(string).
+         -export([tokens/2, strip/2, substr/3, lexemes/2]).
+         tokens(_, _) -> [].
+         strip(S, _) -> S.
+         substr(S, _, _) -> S.
+         lexemes(_, _) -> [].
+            "#,
+        )
+    }
+
+    #[test]
+    fn fixes_string_tokens() {
+        check_fix(
+            r#"
+         //- /src/main.erl
+         -module(main).
+
+         % elp:ignore W0017 (undefined_function)
+         fn(S) -> string:tok~ens(S, [$,, $;]).
+            "#,
+            expect![[r#"
+         -module(main).
+
+         % elp:ignore W0017 (undefined_function)
+         fn(S) -> string:lexemes(S, [$,, $;]).
+            "#]],
+        )
+    }
+
+    #[test]
+    fn fixes_string_strip() {
+        check_fix(
+            r#"
+         //- /src/main.erl
+         -module(main).
+
+         % elp:ignore W0017 (undefined_function)
+         fn(S) -> string:st~rip(S, right, $\n).
+            "#,
+            expect![[r#"
+         -module(main).
+
+         % elp:ignore W0017 (undefined_function)
+         fn(S) -> string:trim(S, trailing, [$\n]).
+            "#]],
+        )
+    }
+
+    #[test]
+    fn fixes_string_strip_default() {
+        check_fix(
+            r#"
+         //- /src/main.erl
+         -module(main).
+
+         % elp:ignore W0017 (undefined_function)
+         fn(S) -> string:st~rip(S).
+            "#,
+            expect![[r#"
+         -module(main).
+
+         % elp:ignore W0017 (undefined_function)
+         fn(S) -> string:trim(S, both, " ").
+            "#]],
+        )
+    }
+
+    #[test]
+    fn fixes_string_substr() {
+        check_fix(
+            r#"
+         //- /src/main.erl
+         -module(main).
+
+         % elp:ignore W0017 (undefined_function)
+         fn(S, N) -> {string:sub~str(S, 3, N), string:substr(S, N + 1)}.
+            "#,
+            expect![[r#"
+         -module(main).
+
+         % elp:ignore W0017 (undefined_function)
+         fn(S, N) -> {string:slice(S, 2, N), string:substr(S, N + 1)}.
+            "#]],
+        )
+    }
+
+    #[test]
+    fn fixes_string_substr_expression_start() {
+        check_fix(
+            r#"
+         //- /src/main.erl
+         -module(main).
+
+         % elp:ignore W0017 (undefined_function)
+         fn(S, N) -> string:sub~str(S, N + 1).
+            "#,
+            expect![[r#"
+         -module(main).
+
+         % elp:ignore W0017 (undefined_function)
+         fn(S, N) -> string:slice(S, (N + 1) - 1).
+            "#]],
+        )
+    }
+}
@@ -0,0 +1,253 @@
+/*
+ * Copyright (c) Meta Platforms, Inc. and affiliates.
+ *
+ * This source code is dual-licensed under either the MIT license found in the
+ * LICENSE-MIT file in the root directory of this source tree or the Apache
+ * License, Version 2.0 found in the LICENSE-APACHE file in the root directory
+ * of this source tree. You may select, at your option, one of the
+ * above-listed licenses.
+ */
+
+//! Lint: record_missing_fields
+//!
+//! Return a warning when a record is constructed without setting some of its
+//! fields that have no default value, since they silently get the value
+//! `undefined`.
+
+use elp_ide_db::DiagnosticCode;
+use elp_ide_db::elp_base_db::FileId;
+use elp_ide_db::source_change::SourceChangeBuilder;
+use elp_syntax::AstNode;
+use elp_syntax::SyntaxKind;
+use elp_syntax::ast;
+use hir::AnyExpr;
+use hir::Expr;
+use hir::FunctionDef;
+use hir::Name;
+use hir::Semantic;
+use hir::Strategy;
+use hir::fold::MacroStrategy;
+use hir::fold::ParenStrategy;
+
+use super::Diagnostic;
+use super::DiagnosticConditions;
+use super::DiagnosticDescriptor;
+use super::Severity;
+use crate::fix;
+
+pub(crate) static DESCRIPTOR: DiagnosticDescriptor = DiagnosticDescriptor {
+    conditions: DiagnosticConditions {
+        experimental: false,
+        include_generated: false,
+        include_tests: true,
+        default_disabled: true,
+    },
+    codes: &[DiagnosticCode::RecordMissingFields],
+    checker: &|diagnostics, sema, file_id, _ext, _config| {
+        sema.for_each_function(file_id, |def| {
+            check_function(diagnostics, sema, file_id, def)
+        });
+    },
+};
+
+fn check_function(
+    diagnostics: &mut Vec<Diagnostic>,
+    sema: &Semantic,
+    file_id: FileId,
+    def: &FunctionDef,
+) {
+    let def_map = sema.def_map(file_id);
+    let def_fb = def.in_function_body(sema, def);
+    def_fb.fold_function(
+        Strategy {
+            macros: MacroStrategy::DoNotExpand,
+            parens: ParenStrategy::InvisibleParens,
+        },
+        (),
+        &mut |_acc, clause_id, ctx| {
+            if let AnyExpr::Expr(Expr::Record { name, fields }) = &ctx.item {
+                let name = sema.db.lookup_atom(*name);
+                let Some(record) = def_map.get_record(&name) else {
+                    return;
+                };
+                let assigned: Vec<Name> = fields
+                    .iter()
+                    .map(|(field, _)| sema.db.lookup_atom(*field))
+                    .collect();
+                let missing: Vec<Name> = record
+                    .fields(sema.db.upcast())
+                    .filter(|(field, field_def)| {
+                        !assigned.contains(field)
+                            && field_def.source(sema.db.upcast()).expr().is_none()
+                    })
+                    .map(|(field, _)| field)
+                    .collect();
+                if missing.is_empty() {
+                    return;
+                }
+                let map = def_fb.get_body_map(clause_id);
+                if let Some(diagnostic) = ctx
+                    .item_id
+                    .as_expr_id()
+                    .and_then(|expr_id| map.expr(expr_id))
+                    .filter(|ptr| ptr.file_id() == file_id)
+                    .and_then(|ptr| match ptr.to_ast(sema.db) {
+                        ast::Expr::RecordExpr(record_expr) => Some(record_expr),
+                        _ => None,
+                    })
+                    .and_then(|record_expr| {
+                        make_diagnostic(sema, file_id, &record_expr, &name, &missing)
+                    })
+                {
+                    diagnostics.push(diagnostic);
+                }
+            }
+        },
+    )
+}
+
+fn make_diagnostic(
+    sema: &Semantic,
+    file_id: FileId,
+    record_expr: &ast::RecordExpr,
+    record_name: &Name,
+    missing: &[Name],
+) -> Option<Diagnostic> {
+    // `_ = Expr` initializes all the fields not listed
+    if record_expr
+        .fields()
+        .any(|field| field.name().is_some_and(|name| name.syntax().text() == "_"))
+    {
+        return None;
+    }
+    let range = record_expr.name()?.syntax().text_range();
+    let closing = record_expr
+        .syntax()
+        .children_with_tokens()
+        .filter_map(|element| element.into_token())
+        .find(|token| token.kind() == SyntaxKind::ANON_RRACE)?;
+    let assignments = missing
+        .iter()
+        .map(|field| format!("{field} = undefined"))
+        .collect::<Vec<_>>()
+        .join(", ");
+    let insert = if record_expr.fields().next().is_some() {
+        format!(", {assignments}")
+    } else {
+        assignments
+    };
+    let mut builder = SourceChangeBuilder::new(file_id);
+    builder.insert(closing.text_range().start(), insert);
+    let fields = missing
+        .iter()
+        .map(|field| format!("`{field}`"))
+        .collect::<Vec<_>>()
+        .join(", ");
+    let message = format!("Record '{record_name}' constructed without setting {fields}.");
+    Some(
+        Diagnostic::new(DiagnosticCode::RecordMissingFields, message, range)
+            .with_severity(Severity::WeakWarning)
+            .with_fixes(Some(vec![fix(
+                "record_missing_fields",
+                "Set the missing fields to `undefined`",
+                builder.finish(),
+                range,
+            )]))
+            .with_ignore_fix(sema, file_id),
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use elp_ide_db::DiagnosticCode;
+    use expect_test::Expect;
+    use expect_test::expect;
+
+    use crate::diagnostics::DiagnosticsConfig;
+    use crate::tests;
+
+    fn config() -> DiagnosticsConfig {
+        DiagnosticsConfig::default().enable(DiagnosticCode::RecordMissingFields)
+    }
+
+    #[track_caller]
+    fn check_diagnostics(fixture: &str) {
+        tests::check_diagnostics_with_config(config(), fixture);
+    }
+
+    #[track_caller]
+    fn check_fix(before: &str, after: Expect) {
+        tests::check_fix_with_config(config(), before, after);
+    }
+
+    #[test]
+    fn not_enabled_by_default() {
+        tests::check_diagnostics(
+            r#"
+              -module(main).
+              -record(rec, {a, b = 1}).
+              foo() -> #rec{}.
+              get(R) -> {R#rec.a, R#rec.b}.
+            "#,
+        )
+    }
+
+    #[test]
+    fn flags_missing_fields_without_default() {
+        check_diagnostics(
+            r#"
+              -module(main).
+              -record(rec, {a, b = 1, c :: integer(), d}).
+              foo() -> #rec{d = 2}.
+              %%       ^^^^ 💡 weak: Record 'rec' constructed without setting `a`, `c`.
+              get(R) -> {R#rec.a, R#rec.b, R#rec.c}.
+            "#,
+        )
+    }
+
+    #[test]
+    fn ignores_complete_records() {
+        check_diagnostics(
+            r#"
+              -module(main).
+              -record(rec, {a, b = 1}).
+              all() -> #rec{a = 1, b = 2}.
+              defaults() -> #rec{a = 1}.
+              catch_all() -> #rec{_ = 0}.
+              update(R) -> R#rec{b = 3}.
+            "#,
+        )
+    }
+
+    #[test]
+    fn fix_missing_fields() {
+        check_fix(
+            r#"
+              -module(main).
+              -record(rec, {a, b = 1, c}).
+              foo() -> #r~ec{b = 2}.
+            "#,
+            expect![[r#"
+              -module(main).
+              -record(rec, {a, b = 1, c}).
+              foo() -> #rec{b = 2, a = undefined, c = undefined}.
+            "#]],
+        )
+    }
+
+    #[test]
+    fn fix_empty_record() {
+        check_fix(
+            r#"
+              -module(main).
+              -record(rec, {a}).
+              foo() -> #r~ec{}.
+            "#,
+            expect![[r#"
+              -module(main).
+              -record(rec, {a}).
+              foo() -> #rec{a = undefined}.
+            "#]],
+        )
+    }
+}
@@ -38,7 +38,8 @@ pub(crate) static DESCRIPTOR: DiagnosticDescriptor = DiagnosticDescriptor {
         include_tests: true,
         default_disabled: false,
     },
-    checker: &|diags, sema, file_id, _file_kind| {
+    codes: &[DiagnosticCode::RecordTupleMatch],
+    checker: &|diags, sema, file_id, _file_kind, _config| {
         record_tuple_match(diags, sema, file_id);
     },
 };
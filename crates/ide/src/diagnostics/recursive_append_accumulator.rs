@@ -0,0 +1,183 @@
+/*
+ * Copyright (c) Meta Platforms, Inc. and affiliates.
+ *
+ * This source code is dual-licensed under either the MIT license found in the
+ * LICENSE-MIT file in the root directory of this source tree or the Apache
+ * License, Version 2.0 found in the LICENSE-APACHE file in the root directory
+ * of this source tree. You may select, at your option, one of the
+ * above-listed licenses.
+ */
+
+//! Lint: recursive_append_accumulator
+//!
+//! Return a warning for a self-recursive call passing `Acc ++ Expr` as
+//! argument, where `Acc` is the parameter in the same position. Appending
+//! copies the accumulator on each call, which makes building the result
+//! quadratic.
+
+use elp_ide_db::DiagnosticCode;
+use elp_ide_db::elp_base_db::FileId;
+use elp_syntax::ast::BinaryOp;
+use elp_syntax::ast::ListOp;
+use hir::AnyExpr;
+use hir::Expr;
+use hir::FunctionDef;
+use hir::Pat;
+use hir::Semantic;
+use hir::Strategy;
+use hir::fold::MacroStrategy;
+use hir::fold::ParenStrategy;
+
+use super::Diagnostic;
+use super::DiagnosticConditions;
+use super::DiagnosticDescriptor;
+use super::Severity;
+
+pub(crate) static DESCRIPTOR: DiagnosticDescriptor = DiagnosticDescriptor {
+    conditions: DiagnosticConditions {
+        experimental: false,
+        include_generated: false,
+        include_tests: true,
+        default_disabled: false,
+    },
+    codes: &[DiagnosticCode::RecursiveAppendAccumulator],
+    checker: &|diagnostics, sema, file_id, _ext, _config| {
+        sema.for_each_function(file_id, |def| {
+            check_function(diagnostics, sema, file_id, def);
+        });
+    },
+};
+
+fn check_function(
+    diagnostics: &mut Vec<Diagnostic>,
+    sema: &Semantic,
+    file_id: FileId,
+    def: &FunctionDef,
+) {
+    let def_fb = def.in_function_body(sema, def);
+    def_fb.fold_function(
+        Strategy {
+            macros: MacroStrategy::DoNotExpand,
+            parens: ParenStrategy::InvisibleParens,
+        },
+        (),
+        &mut |_acc, clause_id, ctx| {
+            let AnyExpr::Expr(Expr::Call { target, args }) = &ctx.item else {
+                return;
+            };
+            let body = def_fb.body(clause_id);
+            let is_recursive = target
+                .resolve_call(args.len() as u32, sema, file_id, &body)
+                .is_some_and(|callee| {
+                    callee.file.file_id == def.file.file_id && callee.function_id == def.function_id
+                });
+            if !is_recursive {
+                return;
+            }
+            let pats = &def_fb[clause_id].clause.pats;
+            for (arg, pat) in args.iter().zip(pats) {
+                let Expr::BinaryOp {
+                    lhs,
+                    op: BinaryOp::ListOp(ListOp::Append),
+                    ..
+                } = &body[*arg]
+                else {
+                    continue;
+                };
+                let (Expr::Var(acc), Pat::Var(param)) = (&body[*lhs], &body[*pat]) else {
+                    continue;
+                };
+                if acc != param {
+                    continue;
+                }
+                if let Some(range) = def_fb
+                    .range_for_expr(clause_id, *arg)
+                    .filter(|range| range.file_id == file_id)
+                {
+                    diagnostics.push(
+                        Diagnostic::new(
+                            DiagnosticCode::RecursiveAppendAccumulator,
+                            format!(
+                                "Appending to `{}` with `++` on each recursive call is quadratic, prepend and reverse the result instead.",
+                                acc.as_string(sema.db.upcast())
+                            ),
+                            range.range,
+                        )
+                        .with_severity(Severity::Warning)
+                        .with_ignore_fix(sema, file_id),
+                    );
+                }
+            }
+        },
+    )
+}
+
+#[cfg(test)]
+mod tests {
+
+    use crate::diagnostics::Diagnostic;
+    use crate::diagnostics::DiagnosticCode;
+    use crate::tests;
+
+    fn filter(d: &Diagnostic) -> bool {
+        d.code == DiagnosticCode::RecursiveAppendAccumulator
+    }
+
+    #[track_caller]
+    fn check_diagnostics(fixture: &str) {
+        tests::check_filtered_diagnostics(fixture, &filter)
+    }
+
+    #[test]
+    fn detects_quadratic_string_builder() {
+        check_diagnostics(
+            r#"
+         //- /src/main.erl
+         -module(main).
+         -export([join/1]).
+
+         join(Words) -> join(Words, "").
+
+         join([], Acc) -> Acc;
+         join([Word | Rest], Acc) ->
+             join(Rest, Acc ++ Word ++ " ").
+         %%             ^^^^^^^^^^^^^^^^^^ 💡 warning: Appending to `Acc` with `++` on each recursive call is quadratic, prepend and reverse the result instead.
+            "#,
+        )
+    }
+
+    #[test]
+    fn ignores_prepending_accumulator() {
+        check_diagnostics(
+            r#"
+         //- /src/main.erl
+         -module(main).
+         -export([double/1]).
+
+         double(L) -> double(L, []).
+
+         double([], Acc) -> lists:reverse(Acc);
+         double([X | Rest], Acc) ->
+             double(Rest, [X * 2 | Acc]).
+            "#,
+        )
+    }
+
+    #[test]
+    fn ignores_other_calls() {
+        check_diagnostics(
+            r#"
+         //- /src/main.erl
+         -module(main).
+         -export([f/2, g/2]).
+
+         %% Not recursive
+         f(X, Acc) -> g(X, Acc ++ [X]).
+
+         %% Not appending to the parameter in the same position
+         g([], Acc) -> Acc;
+         g([X | Rest], Acc) -> g(Rest, [X] ++ Acc).
+            "#,
+        )
+    }
+}
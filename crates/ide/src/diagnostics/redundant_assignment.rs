@@ -49,7 +49,8 @@ pub(crate) static DESCRIPTOR: DiagnosticDescriptor = DiagnosticDescriptor {
         include_tests: true,
         default_disabled: false,
     },
-    checker: &|diags, sema, file_id, _ext| {
+    codes: &[DiagnosticCode::RedundantAssignment],
+    checker: &|diags, sema, file_id, _ext, _config| {
         redundant_assignment(diags, sema, file_id);
     },
 };
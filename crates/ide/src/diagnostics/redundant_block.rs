@@ -0,0 +1,263 @@
+/*
+ * Copyright (c) Meta Platforms, Inc. and affiliates.
+ *
+ * This source code is dual-licensed under either the MIT license found in the
+ * LICENSE-MIT file in the root directory of this source tree or the Apache
+ * License, Version 2.0 found in the LICENSE-APACHE file in the root directory
+ * of this source tree. You may select, at your option, one of the
+ * above-listed licenses.
+ */
+
+//! Lint: redundant_block
+//!
+//! Return a warning for a `begin ... end` block which adds nothing, either
+//! because it wraps a single expression, e.g. `X = begin foo() end`, or
+//! because it is itself a statement of a sequence, e.g. a clause body.
+
+use elp_ide_assists::helpers::expr_needs_parens_ast;
+use elp_ide_db::DiagnosticCode;
+use elp_ide_db::elp_base_db::FileId;
+use elp_ide_db::source_change::SourceChangeBuilder;
+use elp_syntax::AstNode;
+use elp_syntax::SyntaxKind;
+use elp_syntax::TextRange;
+use elp_syntax::TextSize;
+use elp_syntax::ast;
+use hir::AnyExpr;
+use hir::Expr;
+use hir::FunctionDef;
+use hir::Semantic;
+use hir::Strategy;
+use hir::fold::MacroStrategy;
+use hir::fold::ParenStrategy;
+
+use super::Diagnostic;
+use super::DiagnosticConditions;
+use super::DiagnosticDescriptor;
+use super::Severity;
+use crate::fix;
+
+pub(crate) static DESCRIPTOR: DiagnosticDescriptor = DiagnosticDescriptor {
+    conditions: DiagnosticConditions {
+        experimental: false,
+        include_generated: false,
+        include_tests: true,
+        default_disabled: false,
+    },
+    codes: &[DiagnosticCode::RedundantBlock],
+    checker: &|diagnostics, sema, file_id, _ext, _config| {
+        sema.for_each_function(file_id, |def| {
+            check_function(diagnostics, sema, file_id, def)
+        });
+    },
+};
+
+fn check_function(
+    diagnostics: &mut Vec<Diagnostic>,
+    sema: &Semantic,
+    file_id: FileId,
+    def: &FunctionDef,
+) {
+    let def_fb = def.in_function_body(sema, def);
+    def_fb.fold_function(
+        Strategy {
+            macros: MacroStrategy::DoNotExpand,
+            parens: ParenStrategy::InvisibleParens,
+        },
+        (),
+        &mut |_acc, clause_id, ctx| {
+            if let AnyExpr::Expr(Expr::Block { .. }) = &ctx.item {
+                let map = def_fb.get_body_map(clause_id);
+                if let Some(diagnostic) = ctx
+                    .item_id
+                    .as_expr_id()
+                    .and_then(|expr_id| map.expr(expr_id))
+                    .filter(|ptr| ptr.file_id() == file_id)
+                    .and_then(|ptr| match ptr.to_ast(sema.db) {
+                        ast::Expr::ExprMax(ast::ExprMax::BlockExpr(block)) => Some(block),
+                        _ => None,
+                    })
+                    .and_then(|block| make_diagnostic(sema, file_id, &block))
+                {
+                    diagnostics.push(diagnostic);
+                }
+            }
+        },
+    )
+}
+
+fn make_diagnostic(sema: &Semantic, file_id: FileId, block: &ast::BlockExpr) -> Option<Diagnostic> {
+    let exprs: Vec<ast::Expr> = block.exprs().collect();
+    let parent = block.syntax().parent()?;
+    if exprs.is_empty()
+        || block
+            .syntax()
+            .ancestors()
+            .any(|node| ast::Guard::can_cast(node.kind()))
+    {
+        return None;
+    }
+    let in_sequence = matches!(
+        parent.kind(),
+        SyntaxKind::CLAUSE_BODY | SyntaxKind::BLOCK_EXPR
+    );
+    // Unwrapping a single expression is fine where it cannot bind to a
+    // surrounding operator
+    let single_expr = match exprs.as_slice() {
+        [expr] => {
+            !expr_needs_parens_ast(expr)
+                || matches!(
+                    parent.kind(),
+                    SyntaxKind::EXPR_ARGS | SyntaxKind::LIST | SyntaxKind::TUPLE
+                )
+        }
+        _ => false,
+    };
+    if !(in_sequence || single_expr) {
+        return None;
+    }
+
+    let range = block.syntax().text_range();
+    let first = exprs.first()?.syntax().text_range();
+    let last = exprs.last()?.syntax().text_range();
+    let file_text = sema.db.file_text(file_id);
+    let inner = &file_text[TextRange::new(first.start(), last.end())];
+    // Keep the statements aligned with the `begin` they replace
+    let extra_indent =
+        column(&file_text, first.start()).saturating_sub(column(&file_text, range.start()));
+    let replacement = inner
+        .split('\n')
+        .enumerate()
+        .map(|(i, line)| {
+            let indent = line.len() - line.trim_start_matches(' ').len();
+            if i == 0 {
+                line
+            } else {
+                &line[indent.min(extra_indent)..]
+            }
+        })
+        .collect::<Vec<_>>()
+        .join("\n");
+
+    let mut builder = SourceChangeBuilder::new(file_id);
+    builder.replace(range, replacement);
+    Some(
+        Diagnostic::new(
+            DiagnosticCode::RedundantBlock,
+            "Redundant `begin ... end` block.",
+            range,
+        )
+        .with_severity(Severity::WeakWarning)
+        .with_fixes(Some(vec![fix(
+            "redundant_block",
+            "Unwrap the `begin ... end` block",
+            builder.finish(),
+            range,
+        )]))
+        .with_ignore_fix(sema, file_id),
+    )
+}
+
+/// The column of the offset in its line.
+fn column(text: &str, offset: TextSize) -> usize {
+    let before = &text[..usize::from(offset)];
+    before.len() - before.rfind('\n').map_or(0, |newline| newline + 1)
+}
+
+#[cfg(test)]
+mod tests {
+    use expect_test::expect;
+
+    use crate::tests::check_diagnostics;
+    use crate::tests::check_fix;
+
+    #[test]
+    fn flags_single_expression_blocks() {
+        check_diagnostics(
+            r#"
+              -module(main).
+              foo() ->
+                  X = begin bar() end,
+              %%      ^^^^^^^^^^^^^^^ 💡 weak: Redundant `begin ... end` block.
+                  {X, [begin 1 + 2 end]}.
+              %%       ^^^^^^^^^^^^^^^ 💡 weak: Redundant `begin ... end` block.
+              bar() -> ok.
+            "#,
+        )
+    }
+
+    #[test]
+    fn flags_blocks_in_sequences() {
+        check_diagnostics(
+            r#"
+              -module(main).
+              foo() ->
+                  begin bar(), bar() end.
+              %%  ^^^^^^^^^^^^^^^^^^^^^^ 💡 weak: Redundant `begin ... end` block.
+              baz(X) ->
+                  case X of
+                      a -> ok;
+                      _ -> begin bar(), X end
+              %%           ^^^^^^^^^^^^^^^^^^^ 💡 weak: Redundant `begin ... end` block.
+                  end.
+              bar() -> ok.
+            "#,
+        )
+    }
+
+    #[test]
+    fn ignores_needed_blocks() {
+        check_diagnostics(
+            r#"
+              -module(main).
+              foo(A, B) ->
+                  X = begin bar(), A end,
+                  Y = 2 * begin A + B end,
+                  {X, Y}.
+              bar() -> ok.
+            "#,
+        )
+    }
+
+    #[test]
+    fn fix_splices_statements() {
+        check_fix(
+            r#"
+-module(main).
+foo() ->
+    be~gin
+        bar(),
+        bar()
+    end.
+bar() -> ok.
+"#,
+            expect![[r#"
+                -module(main).
+                foo() ->
+                    bar(),
+                    bar().
+                bar() -> ok.
+            "#]],
+        )
+    }
+
+    #[test]
+    fn fix_unwraps_single_expression() {
+        check_fix(
+            r#"
+-module(main).
+foo() ->
+    X = be~gin bar() end,
+    X.
+bar() -> ok.
+"#,
+            expect![[r#"
+                -module(main).
+                foo() ->
+                    X = bar(),
+                    X.
+                bar() -> ok.
+            "#]],
+        )
+    }
+}
@@ -0,0 +1,253 @@
+/*
+ * Copyright (c) Meta Platforms, Inc. and affiliates.
+ *
+ * This source code is dual-licensed under either the MIT license found in the
+ * LICENSE-MIT file in the root directory of this source tree or the Apache
+ * License, Version 2.0 found in the LICENSE-APACHE file in the root directory
+ * of this source tree. You may select, at your option, one of the
+ * above-listed licenses.
+ */
+
+//! Lint: redundant_map_merge
+//!
+//! warn on code of the form `maps:merge(M, #{})` or `maps:merge(#{}, M)`
+//! and suggest `M`.
+//!
+//! Note that `maps:merge/2` raises `{badmap, M}` if `M` is not a map, so the
+//! fix is only equivalent when `M` is syntactically a map. Otherwise the fix
+//! label calls out that the `badmap` error is no longer raised.
+
+use elp_ide_db::DiagnosticCode;
+use elp_ide_db::elp_base_db::FileId;
+use elp_ide_db::source_change::SourceChangeBuilder;
+use elp_ide_ssr::Match;
+use elp_ide_ssr::SubId;
+use hir::AnyExprId;
+use hir::ComprehensionBuilder;
+use hir::Expr;
+use hir::Semantic;
+
+use crate::diagnostics::Linter;
+use crate::diagnostics::Severity;
+use crate::diagnostics::SsrPatternsLinter;
+use crate::fix;
+
+pub(crate) struct RedundantMapMergeLinter;
+
+impl Linter for RedundantMapMergeLinter {
+    fn id(&self) -> DiagnosticCode {
+        DiagnosticCode::RedundantMapMerge
+    }
+
+    fn description(&self) -> &'static str {
+        "Merging with an empty map has no effect."
+    }
+
+    fn severity(&self) -> Severity {
+        Severity::WeakWarning
+    }
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub(crate) enum EmptyArg {
+    First,
+    Second,
+}
+
+impl SsrPatternsLinter for RedundantMapMergeLinter {
+    type Context = EmptyArg;
+
+    fn patterns(&self) -> Vec<(String, Self::Context)> {
+        vec![
+            (
+                format!("ssr: maps:merge(#{{}}, {MAP_VAR})."),
+                EmptyArg::First,
+            ),
+            (
+                format!("ssr: maps:merge({MAP_VAR}, #{{}})."),
+                EmptyArg::Second,
+            ),
+        ]
+    }
+
+    fn is_match_valid(
+        &self,
+        context: &Self::Context,
+        matched: &elp_ide_ssr::Match,
+        sema: &Semantic,
+        file_id: FileId,
+    ) -> Option<bool> {
+        if matched.range.file_id != file_id {
+            // We've somehow ended up with a match in a different file - this means we've
+            // accidentally expanded a macro from a different file, or some other complex case that
+            // gets hairy, so bail out.
+            return None;
+        }
+        if let Some(comments) = matched.comments(sema) {
+            // Avoid clobbering comments in the original source code
+            if !comments.is_empty() {
+                return None;
+            }
+        }
+        match context {
+            // `maps:merge(#{}, #{})` matches both patterns, only report it once
+            EmptyArg::First => Some(matched.placeholder_text(sema, MAP_VAR)? != "#{}"),
+            EmptyArg::Second => Some(true),
+        }
+    }
+
+    fn fixes(
+        &self,
+        _context: &Self::Context,
+        matched: &elp_ide_ssr::Match,
+        sema: &Semantic,
+        file_id: FileId,
+    ) -> Option<Vec<elp_ide_assists::Assist>> {
+        let merge_range = matched.range.range;
+        let map = matched.placeholder_text(sema, MAP_VAR)?;
+
+        let label = if is_known_map(sema, matched).unwrap_or(false) {
+            "Remove redundant `maps:merge/2`"
+        } else {
+            "Remove redundant `maps:merge/2` (no `badmap` error if not a map)"
+        };
+
+        let mut builder = SourceChangeBuilder::new(file_id);
+        builder.replace(merge_range, map);
+        let fixes = vec![fix(
+            "redundant_map_merge",
+            label,
+            builder.finish(),
+            merge_range,
+        )];
+        Some(fixes)
+    }
+}
+
+/// Whether the non-empty argument is a map literal, map update or map
+/// comprehension, so that dropping the `maps:merge/2` call cannot hide a
+/// `badmap` error.
+fn is_known_map(sema: &Semantic, matched: &Match) -> Option<bool> {
+    let map = matched.get_placeholder_match(sema, MAP_VAR)?;
+    let body = matched.matched_node_body.get_body(sema)?;
+    match map.code_id {
+        SubId::AnyExprId(AnyExprId::Expr(expr_id)) => Some(matches!(
+            &body[expr_id],
+            Expr::Map { .. }
+                | Expr::MapUpdate { .. }
+                | Expr::Comprehension {
+                    builder: ComprehensionBuilder::Map(..),
+                    ..
+                }
+        )),
+        _ => Some(false),
+    }
+}
+
+pub(crate) static LINTER: RedundantMapMergeLinter = RedundantMapMergeLinter;
+
+static MAP_VAR: &str = "_@Map";
+
+#[cfg(test)]
+mod tests {
+
+    use expect_test::Expect;
+    use expect_test::expect;
+
+    use crate::diagnostics::Diagnostic;
+    use crate::diagnostics::DiagnosticCode;
+    use crate::tests;
+
+    fn filter(d: &Diagnostic) -> bool {
+        d.code == DiagnosticCode::RedundantMapMerge
+    }
+
+    #[track_caller]
+    fn check_diagnostics(fixture: &str) {
+        tests::check_filtered_diagnostics(fixture, &filter)
+    }
+
+    #[track_caller]
+    fn check_specific_fix(assist_label: &str, fixture_before: &str, fixture_after: Expect) {
+        tests::check_specific_fix(assist_label, fixture_before, fixture_after)
+    }
+
+    #[test]
+    fn detects_redundant_map_merge() {
+        check_diagnostics(
+            r#"
+         //- /src/redundant_map_merge.erl
+         -module(redundant_map_merge).
+
+         first(M) -> maps:merge(#{}, M).
+         %%          ^^^^^^^^^^^^^^^^^^ 💡 weak: Merging with an empty map has no effect.
+         second(M) -> maps:merge(M#{a => 1}, #{}).
+         %%           ^^^^^^^^^^^^^^^^^^^^^^^^^^^ 💡 weak: Merging with an empty map has no effect.
+         both() -> maps:merge(#{}, #{}).
+         %%        ^^^^^^^^^^^^^^^^^^^^ 💡 weak: Merging with an empty map has no effect.
+         neither(M1, M2) -> maps:merge(M1, M2).
+         non_empty(M) -> maps:merge(M, #{a => 1}).
+            "#,
+        )
+    }
+
+    #[test]
+    fn fixes_redundant_map_merge_first_arg() {
+        check_specific_fix(
+            "Remove redundant `maps:merge/2` (no `badmap` error if not a map)",
+            r#"
+         //- /src/redundant_map_merge.erl
+         -module(redundant_map_merge).
+
+         % elp:ignore W0017 (undefined_function)
+         fn(M) -> maps:me~rge(#{}, M).
+            "#,
+            expect![[r#"
+         -module(redundant_map_merge).
+
+         % elp:ignore W0017 (undefined_function)
+         fn(M) -> M.
+            "#]],
+        )
+    }
+
+    #[test]
+    fn fixes_redundant_map_merge_second_arg() {
+        check_specific_fix(
+            "Remove redundant `maps:merge/2`",
+            r#"
+         //- /src/redundant_map_merge.erl
+         -module(redundant_map_merge).
+
+         % elp:ignore W0017 (undefined_function)
+         fn(M) -> maps:me~rge(M#{a => 1}, #{}).
+            "#,
+            expect![[r#"
+         -module(redundant_map_merge).
+
+         % elp:ignore W0017 (undefined_function)
+         fn(M) -> M#{a => 1}.
+            "#]],
+        )
+    }
+
+    #[test]
+    fn fixes_redundant_map_merge_map_comprehension() {
+        check_specific_fix(
+            "Remove redundant `maps:merge/2`",
+            r#"
+         //- /src/redundant_map_merge.erl
+         -module(redundant_map_merge).
+
+         % elp:ignore W0017 (undefined_function)
+         fn(L) -> maps:me~rge(#{}, #{K => V || {K, V} <- L}).
+            "#,
+            expect![[r#"
+         -module(redundant_map_merge).
+
+         % elp:ignore W0017 (undefined_function)
+         fn(L) -> #{K => V || {K, V} <- L}.
+            "#]],
+        )
+    }
+}
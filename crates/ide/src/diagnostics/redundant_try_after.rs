@@ -0,0 +1,238 @@
+/*
+ * Copyright (c) Meta Platforms, Inc. and affiliates.
+ *
+ * This source code is dual-licensed under either the MIT license found in the
+ * LICENSE-MIT file in the root directory of this source tree or the Apache
+ * License, Version 2.0 found in the LICENSE-APACHE file in the root directory
+ * of this source tree. You may select, at your option, one of the
+ * above-listed licenses.
+ */
+
+//! Lint: redundant_try_after
+//!
+//! Return a warning when the `after` block of a `try` has no side effects,
+//! e.g. `try foo() catch _:_ -> error after ok end`, since its value is
+//! always discarded.
+
+use elp_ide_db::DiagnosticCode;
+use elp_ide_db::elp_base_db::FileId;
+use elp_ide_db::source_change::SourceChangeBuilder;
+use elp_syntax::AstNode;
+use elp_syntax::SyntaxKind;
+use elp_syntax::TextRange;
+use elp_syntax::ast;
+use hir::AnyExpr;
+use hir::Body;
+use hir::Expr;
+use hir::ExprId;
+use hir::FunctionDef;
+use hir::Semantic;
+use hir::Strategy;
+use hir::fold::MacroStrategy;
+use hir::fold::ParenStrategy;
+
+use super::Diagnostic;
+use super::DiagnosticConditions;
+use super::DiagnosticDescriptor;
+use super::Severity;
+use crate::fix;
+
+pub(crate) static DESCRIPTOR: DiagnosticDescriptor = DiagnosticDescriptor {
+    conditions: DiagnosticConditions {
+        experimental: false,
+        include_generated: false,
+        include_tests: true,
+        default_disabled: false,
+    },
+    codes: &[DiagnosticCode::RedundantTryAfter],
+    checker: &|diagnostics, sema, file_id, _ext, _config| {
+        sema.for_each_function(file_id, |def| {
+            check_function(diagnostics, sema, file_id, def)
+        });
+    },
+};
+
+/// BIFs which can neither fail nor have side effects.
+const PURE_BIFS: &[&str] = &[
+    "erlang:self/0",
+    "erlang:node/0",
+    "erlang:is_atom/1",
+    "erlang:is_binary/1",
+    "erlang:is_bitstring/1",
+    "erlang:is_boolean/1",
+    "erlang:is_float/1",
+    "erlang:is_function/1",
+    "erlang:is_integer/1",
+    "erlang:is_list/1",
+    "erlang:is_map/1",
+    "erlang:is_number/1",
+    "erlang:is_pid/1",
+    "erlang:is_port/1",
+    "erlang:is_reference/1",
+    "erlang:is_tuple/1",
+];
+
+fn check_function(
+    diagnostics: &mut Vec<Diagnostic>,
+    sema: &Semantic,
+    file_id: FileId,
+    def: &FunctionDef,
+) {
+    let def_fb = def.in_function_body(sema, def);
+    def_fb.fold_function(
+        Strategy {
+            macros: MacroStrategy::DoNotExpand,
+            parens: ParenStrategy::InvisibleParens,
+        },
+        (),
+        &mut |_acc, clause_id, ctx| {
+            if let AnyExpr::Expr(Expr::Try { after, .. }) = &ctx.item {
+                let body = def_fb.body(clause_id);
+                if after.is_empty() || !after.iter().all(|expr| is_pure(sema, &body, *expr)) {
+                    return;
+                }
+                let map = def_fb.get_body_map(clause_id);
+                if let Some(diagnostic) = ctx
+                    .item_id
+                    .as_expr_id()
+                    .and_then(|expr_id| map.expr(expr_id))
+                    .filter(|ptr| ptr.file_id() == file_id)
+                    .and_then(|ptr| match ptr.to_ast(sema.db) {
+                        ast::Expr::ExprMax(ast::ExprMax::TryExpr(try_expr)) => Some(try_expr),
+                        _ => None,
+                    })
+                    .and_then(|try_expr| make_diagnostic(sema, file_id, &try_expr))
+                {
+                    diagnostics.push(diagnostic);
+                }
+            }
+        },
+    )
+}
+
+/// Conservatively check that evaluating the expression has no observable
+/// effect: only literals, variables, data built from them and pure BIFs.
+fn is_pure(sema: &Semantic, body: &Body, expr_id: ExprId) -> bool {
+    match &body[expr_id] {
+        Expr::Literal(_) | Expr::Var(_) => true,
+        Expr::Tuple { exprs } => exprs.iter().all(|expr| is_pure(sema, body, *expr)),
+        Expr::List { exprs, tail } => {
+            exprs.iter().all(|expr| is_pure(sema, body, *expr))
+                && tail.is_none_or(|tail| is_pure(sema, body, tail))
+        }
+        Expr::Paren { expr } => is_pure(sema, body, *expr),
+        Expr::Call { target, args } => {
+            target
+                .label(args.len() as u32, sema, body)
+                .is_some_and(|label| PURE_BIFS.contains(&label.as_str()))
+                && args.iter().all(|expr| is_pure(sema, body, *expr))
+        }
+        _ => false,
+    }
+}
+
+fn make_diagnostic(
+    sema: &Semantic,
+    file_id: FileId,
+    try_expr: &ast::TryExpr,
+) -> Option<Diagnostic> {
+    let after = try_expr.after()?;
+    let range = after.syntax().text_range();
+    let diagnostic = Diagnostic::new(
+        DiagnosticCode::RedundantTryAfter,
+        "Redundant `after` block: it has no side effects.",
+        range,
+    )
+    .with_severity(Severity::WeakWarning);
+    // Without a `catch`, the `try` needs the `after` block to be valid
+    let diagnostic = if try_expr.catch().next().is_some() {
+        let start = match after.syntax().prev_sibling_or_token() {
+            Some(prev) if prev.kind() == SyntaxKind::WHITESPACE => prev.text_range().start(),
+            _ => range.start(),
+        };
+        let mut builder = SourceChangeBuilder::new(file_id);
+        builder.delete(TextRange::new(start, range.end()));
+        diagnostic.with_fixes(Some(vec![fix(
+            "redundant_try_after",
+            "Remove the `after` block",
+            builder.finish(),
+            range,
+        )]))
+    } else {
+        diagnostic
+    };
+    Some(diagnostic.with_ignore_fix(sema, file_id))
+}
+
+#[cfg(test)]
+mod tests {
+    use expect_test::expect;
+
+    use crate::tests::check_diagnostics;
+    use crate::tests::check_fix;
+
+    #[test]
+    fn flags_pure_after_block() {
+        check_diagnostics(
+            r#"
+              -module(main).
+              foo() ->
+                  try bar()
+                  catch _:_ -> error
+                  after ok
+              %%  ^^^^^^^^ 💡 weak: Redundant `after` block: it has no side effects.
+                  end.
+              baz(X) ->
+                  try bar()
+                  after {X, [self()]}
+              %%  ^^^^^^^^^^^^^^^^^^^ 💡 weak: Redundant `after` block: it has no side effects.
+                  end.
+              bar() -> ok.
+            "#,
+        )
+    }
+
+    #[test]
+    fn ignores_after_block_with_side_effects() {
+        check_diagnostics(
+            r#"
+              -module(main).
+              foo(Fd) ->
+                  try bar()
+                  catch _:_ -> error
+                  after file:close(Fd)
+                  end.
+              baz(Pid) ->
+                  try bar()
+                  after
+                      Pid ! done,
+                      ok
+                  end.
+              bar() -> ok.
+            "#,
+        )
+    }
+
+    #[test]
+    fn fix_removes_after_block() {
+        check_fix(
+            r#"
+              -module(main).
+              foo() ->
+                  try bar()
+                  catch _:_ -> error
+                  af~ter ok
+                  end.
+              bar() -> ok.
+            "#,
+            expect![[r#"
+              -module(main).
+              foo() ->
+                  try bar()
+                  catch _:_ -> error
+                  end.
+              bar() -> ok.
+            "#]],
+        )
+    }
+}
@@ -0,0 +1,362 @@
+/*
+ * Copyright (c) Meta Platforms, Inc. and affiliates.
+ *
+ * This source code is dual-licensed under either the MIT license found in the
+ * LICENSE-MIT file in the root directory of this source tree or the Apache
+ * License, Version 2.0 found in the LICENSE-APACHE file in the root directory
+ * of this source tree. You may select, at your option, one of the
+ * above-listed licenses.
+ */
+
+//! Lint: rethrow_without_stacktrace
+//!
+//! Return a warning for a `catch` clause whose body only re-raises the
+//! caught reason with `throw/1`, `error/1` or `exit/1`, e.g.
+//! `catch Class:Reason -> throw(Reason)`. This loses the original
+//! stacktrace, and possibly the class, so suggest `erlang:raise/3` instead.
+//!
+//! A clause catching a specific class and raising the reason with another
+//! one, e.g. `catch error:Reason -> exit(Reason)`, is left alone, as the
+//! conversion is likely deliberate.
+
+use elp_ide_assists::helpers::freshen_variable_name;
+use elp_ide_db::DiagnosticCode;
+use elp_ide_db::elp_base_db::FileId;
+use elp_ide_db::source_change::SourceChangeBuilder;
+use elp_syntax::AstNode;
+use elp_syntax::TextRange;
+use elp_syntax::algo;
+use elp_syntax::ast;
+use hir::AnyExpr;
+use hir::Body;
+use hir::CallTarget;
+use hir::CatchClause;
+use hir::Expr;
+use hir::ExprId;
+use hir::FunctionDef;
+use hir::InFile;
+use hir::Literal;
+use hir::Name;
+use hir::NameArity;
+use hir::Pat;
+use hir::Semantic;
+use hir::Strategy;
+use hir::fold::MacroStrategy;
+use hir::fold::ParenStrategy;
+
+use super::Diagnostic;
+use super::DiagnosticConditions;
+use super::DiagnosticDescriptor;
+use super::Severity;
+use crate::fix;
+
+pub(crate) static DESCRIPTOR: DiagnosticDescriptor = DiagnosticDescriptor {
+    conditions: DiagnosticConditions {
+        experimental: false,
+        include_generated: false,
+        include_tests: true,
+        default_disabled: false,
+    },
+    codes: &[DiagnosticCode::RethrowWithoutStacktrace],
+    checker: &|diagnostics, sema, file_id, _ext, _config| {
+        sema.for_each_function(file_id, |def| {
+            check_function(diagnostics, sema, file_id, def);
+        });
+    },
+};
+
+fn check_function(
+    diagnostics: &mut Vec<Diagnostic>,
+    sema: &Semantic,
+    file_id: FileId,
+    def: &FunctionDef,
+) {
+    let def_fb = def.in_function_body(sema, def);
+    def_fb.fold_function(
+        Strategy {
+            macros: MacroStrategy::DoNotExpand,
+            parens: ParenStrategy::InvisibleParens,
+        },
+        (),
+        &mut |_acc, clause_id, ctx| {
+            let AnyExpr::Expr(Expr::Try { catch_clauses, .. }) = &ctx.item else {
+                return;
+            };
+            let body = def_fb.body(clause_id);
+            for clause in catch_clauses {
+                let Some((call, function)) = rethrow_call(sema, file_id, &body, clause) else {
+                    continue;
+                };
+                let Some(range) = def_fb
+                    .range_for_expr(clause_id, call)
+                    .filter(|range| range.file_id == file_id)
+                else {
+                    continue;
+                };
+                let range = range.range;
+                let mut diagnostic = Diagnostic::new(
+                    DiagnosticCode::RethrowWithoutStacktrace,
+                    format!(
+                        "Re-raising the caught reason with `{function}/1` loses the stacktrace, use `erlang:raise/3` instead."
+                    ),
+                    range,
+                )
+                .with_severity(Severity::WeakWarning);
+                if let Some(fixes) = fixes(sema, file_id, range) {
+                    diagnostic = diagnostic.with_fixes(Some(fixes));
+                }
+                diagnostics.push(diagnostic.with_ignore_fix(sema, file_id));
+            }
+        },
+    )
+}
+
+/// The call in the clause body re-raising the caught reason, if that is
+/// all the clause does, with the name of the function called.
+fn rethrow_call(
+    sema: &Semantic,
+    file_id: FileId,
+    body: &Body,
+    clause: &CatchClause,
+) -> Option<(ExprId, &'static str)> {
+    let [call] = clause.exprs[..] else {
+        return None;
+    };
+    let Expr::Call { target, args } = &body[call] else {
+        return None;
+    };
+    let [arg] = args[..] else {
+        return None;
+    };
+    let (Expr::Var(reason), Pat::Var(caught)) = (&body[arg], &body[clause.reason]) else {
+        return None;
+    };
+    if reason != caught {
+        return None;
+    }
+    let label = target.label_short(sema, body)?;
+    let function = match label.strip_prefix("erlang:").unwrap_or(&label) {
+        "throw" => "throw",
+        "error" => "error",
+        "exit" => "exit",
+        _ => return None,
+    };
+    // A local call is to the module's own function, if it has one
+    if let CallTarget::Local { .. } = target
+        && sema
+            .def_map_local(file_id)
+            .get_function(&NameArity::new(Name::from_erlang_service(function), 1))
+            .is_some()
+    {
+        return None;
+    }
+    // A clause without a class only catches throws
+    let class = match clause.class.map(|class| &body[class]) {
+        None => Some("throw".to_string()),
+        Some(Pat::Literal(Literal::Atom(atom))) => Some(atom.as_string(sema.db.upcast())),
+        _ => None,
+    };
+    if class.is_some_and(|class| class != function) {
+        return None;
+    }
+    Some((call, function))
+}
+
+fn fixes(
+    sema: &Semantic,
+    file_id: FileId,
+    range: TextRange,
+) -> Option<Vec<elp_ide_assists::Assist>> {
+    let source = sema.parse(file_id).value;
+    let call = algo::find_node_at_range::<ast::Call>(source.syntax(), range)?;
+    let reason = call.args()?.args().next()?.syntax().text().to_string();
+    let clause = call.syntax().ancestors().find_map(ast::CatchClause::cast)?;
+    let vars_in_clause =
+        sema.find_vars_in_clause_ast(&InFile::new(file_id, &ast::Expr::Call(call.clone())));
+
+    let mut builder = SourceChangeBuilder::new(file_id);
+    let class = match clause.class().and_then(|class| class.class()) {
+        Some(class) => {
+            let text = class.syntax().text().to_string();
+            if text.starts_with('_') {
+                let var = freshen_variable_name(sema, "Class".to_string(), &vars_in_clause);
+                builder.replace(class.syntax().text_range(), var.clone());
+                var
+            } else {
+                text
+            }
+        }
+        None => "throw".to_string(),
+    };
+    let stacktrace = match clause.stack().and_then(|stack| stack.class()) {
+        Some(var) => {
+            let text = var.syntax().text().to_string();
+            if text.starts_with('_') {
+                let fresh = freshen_variable_name(sema, "Stacktrace".to_string(), &vars_in_clause);
+                builder.replace(var.syntax().text_range(), fresh.clone());
+                fresh
+            } else {
+                text
+            }
+        }
+        None => {
+            let pat = clause.pat()?.syntax().text_range();
+            let var = freshen_variable_name(sema, "Stacktrace".to_string(), &vars_in_clause);
+            if clause.class().is_none() {
+                builder.insert(pat.start(), "throw:");
+            }
+            builder.insert(pat.end(), format!(":{var}"));
+            var
+        }
+    };
+    builder.replace(
+        range,
+        format!("erlang:raise({class}, {reason}, {stacktrace})"),
+    );
+    Some(vec![fix(
+        "rethrow_without_stacktrace",
+        "Re-raise with `erlang:raise/3`",
+        builder.finish(),
+        range,
+    )])
+}
+
+#[cfg(test)]
+mod tests {
+
+    use expect_test::Expect;
+    use expect_test::expect;
+
+    use crate::diagnostics::Diagnostic;
+    use crate::diagnostics::DiagnosticCode;
+    use crate::tests;
+
+    fn filter(d: &Diagnostic) -> bool {
+        d.code == DiagnosticCode::RethrowWithoutStacktrace
+    }
+
+    #[track_caller]
+    fn check_diagnostics(fixture: &str) {
+        tests::check_filtered_diagnostics(fixture, &filter)
+    }
+
+    #[track_caller]
+    fn check_fix(fixture_before: &str, fixture_after: Expect) {
+        tests::check_fix(fixture_before, fixture_after)
+    }
+
+    #[test]
+    fn detects_rethrow() {
+        check_diagnostics(
+            r#"
+         //- /src/main.erl
+         -module(main).
+         -export([f/0, g/0]).
+
+         f() ->
+             try bar()
+             catch
+                 _:Reason -> throw(Reason)
+         %%                  ^^^^^^^^^^^^^ 💡 weak: Re-raising the caught reason with `throw/1` loses the stacktrace, use `erlang:raise/3` instead.
+             end.
+
+         g() ->
+             try bar()
+             catch
+                 error:Reason -> erlang:error(Reason)
+         %%                      ^^^^^^^^^^^^^^^^^^^^ 💡 weak: Re-raising the caught reason with `error/1` loses the stacktrace, use `erlang:raise/3` instead.
+             end.
+
+         bar() -> ok.
+            "#,
+        )
+    }
+
+    #[test]
+    fn ignores_transformed_reason() {
+        check_diagnostics(
+            r#"
+         //- /src/main.erl
+         -module(main).
+         -export([f/0, g/0]).
+
+         f() ->
+             try bar()
+             catch
+                 error:Reason -> error({bar_failed, Reason})
+             end.
+
+         %% Converting an error to an exit is deliberate
+         g() ->
+             try bar()
+             catch
+                 error:Reason -> exit(Reason)
+             end.
+
+         bar() -> ok.
+            "#,
+        )
+    }
+
+    #[test]
+    fn fixes_rethrow() {
+        check_fix(
+            r#"
+         //- /src/main.erl
+         -module(main).
+         -export([f/0]).
+
+         f() ->
+             try bar()
+             catch
+                 Class:Reason -> thr~ow(Reason)
+             end.
+
+         bar() -> ok.
+            "#,
+            expect![[r#"
+         -module(main).
+         -export([f/0]).
+
+         f() ->
+             try bar()
+             catch
+                 Class:Reason:Stacktrace -> erlang:raise(Class, Reason, Stacktrace)
+             end.
+
+         bar() -> ok.
+            "#]],
+        )
+    }
+
+    #[test]
+    fn fixes_rethrow_without_class() {
+        check_fix(
+            r#"
+         //- /src/main.erl
+         -module(main).
+         -export([f/0]).
+
+         f() ->
+             try bar()
+             catch
+                 Reason -> thr~ow(Reason)
+             end.
+
+         bar() -> ok.
+            "#,
+            expect![[r#"
+         -module(main).
+         -export([f/0]).
+
+         f() ->
+             try bar()
+             catch
+                 throw:Reason:Stacktrace -> erlang:raise(throw, Reason, Stacktrace)
+             end.
+
+         bar() -> ok.
+            "#]],
+        )
+    }
+}
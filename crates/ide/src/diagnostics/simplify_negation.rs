@@ -64,7 +64,8 @@ pub(crate) static DESCRIPTOR: DiagnosticDescriptor = DiagnosticDescriptor {
         include_tests: true,
         default_disabled: false,
     },
-    checker: &|acc, sema, file_id, _ext| {
+    codes: &[DiagnosticCode::SimplifyNegation],
+    checker: &|acc, sema, file_id, _ext, _config| {
         from_ssr(
             acc,
             sema,
@@ -0,0 +1,318 @@
+/*
+ * Copyright (c) Meta Platforms, Inc. and affiliates.
+ *
+ * This source code is dual-licensed under either the MIT license found in the
+ * LICENSE-MIT file in the root directory of this source tree or the Apache
+ * License, Version 2.0 found in the LICENSE-APACHE file in the root directory
+ * of this source tree. You may select, at your option, one of the
+ * above-listed licenses.
+ */
+
+//! Lint: specific_size_bif
+//!
+//! Return a warning for calls to the polymorphic `size/1` BIF, and offer a
+//! fix to `tuple_size/1` or `byte_size/1` when the argument is known to be a
+//! tuple or a binary.
+//!
+//! `byte_size/1` rounds up the size of a bitstring, where `size/1` rounds
+//! it down, so it is only suggested for binary expressions whose segments
+//! are all a whole number of bytes.
+
+use std::borrow::Cow;
+
+use elp_ide_assists::Assist;
+use elp_ide_db::elp_base_db::FileId;
+use elp_ide_db::source_change::SourceChangeBuilder;
+use elp_types_db::eqwalizer::types::Type;
+use hir::BinarySeg;
+use hir::Body;
+use hir::Expr;
+use hir::ExprId;
+use hir::Literal;
+use hir::Semantic;
+
+use crate::codemod_helpers::CheckCallCtx;
+use crate::codemod_helpers::FunctionMatch;
+use crate::codemod_helpers::MatchCtx;
+use crate::diagnostics::DiagnosticCode;
+use crate::diagnostics::FunctionCallLinter;
+use crate::diagnostics::Linter;
+use crate::diagnostics::Severity;
+use crate::fix;
+use crate::lazy_function_matches;
+
+pub(crate) struct SpecificSizeBifLinter;
+
+impl Linter for SpecificSizeBifLinter {
+    fn id(&self) -> DiagnosticCode {
+        DiagnosticCode::SpecificSizeBif
+    }
+    fn description(&self) -> &'static str {
+        "Use a specific BIF instead of `size/1`."
+    }
+    fn severity(&self) -> Severity {
+        Severity::WeakWarning
+    }
+    fn is_enabled(&self) -> bool {
+        false
+    }
+    fn should_process_test_files(&self) -> bool {
+        false
+    }
+}
+
+/// The specific BIF to use instead of `size/1`, when the argument type is
+/// known.
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub(crate) enum SizeBif {
+    #[default]
+    Unknown,
+    TupleSize,
+    ByteSize,
+}
+
+impl SizeBif {
+    fn name(&self) -> Option<&'static str> {
+        match self {
+            SizeBif::Unknown => None,
+            SizeBif::TupleSize => Some("tuple_size"),
+            SizeBif::ByteSize => Some("byte_size"),
+        }
+    }
+}
+
+impl FunctionCallLinter for SpecificSizeBifLinter {
+    type Context = SizeBif;
+
+    fn match_description(&self, context: &Self::Context) -> Cow<'_, str> {
+        match context.name() {
+            Some(bif) => Cow::Owned(format!("Use `{bif}/1` instead of the `size/1` BIF.")),
+            None => {
+                Cow::Borrowed("Use `byte_size/1` or `tuple_size/1` instead of the `size/1` BIF.")
+            }
+        }
+    }
+
+    fn matches_functions(&self) -> Vec<FunctionMatch> {
+        lazy_function_matches![vec![FunctionMatch::mfa("erlang", "size", 1)]]
+    }
+
+    fn check_match(&self, context: &CheckCallCtx<'_, ()>) -> Option<Self::Context> {
+        let [arg] = context.args.as_vec()[..] else {
+            return Some(SizeBif::Unknown);
+        };
+        Some(specific_bif(
+            context.in_clause.sema,
+            &context.in_clause.body(),
+            arg,
+        ))
+    }
+
+    fn fixes(
+        &self,
+        match_context: &MatchCtx<Self::Context>,
+        sema: &Semantic,
+        file_id: FileId,
+    ) -> Option<Vec<Assist>> {
+        let bif = match_context.extra.name()?;
+        let range = match_context.range_surface_mf?;
+        if range.file_id != file_id {
+            return None;
+        }
+        let file_text = sema.db.file_text(file_id);
+        // Keep the `erlang:` qualifier, if any
+        let module = file_text[range.range].strip_suffix("size")?;
+        let mut builder = SourceChangeBuilder::new(file_id);
+        builder.replace(range.range, format!("{module}{bif}"));
+        Some(vec![fix(
+            "replace_size",
+            &format!("Replace with `{bif}/1`"),
+            builder.finish(),
+            range.range,
+        )])
+    }
+}
+
+pub static LINTER: SpecificSizeBifLinter = SpecificSizeBifLinter;
+
+/// The BIF to use instead of `size/1` for the given argument.
+fn specific_bif(sema: &Semantic, body: &Body, arg: ExprId) -> SizeBif {
+    match &body[arg] {
+        Expr::Tuple { .. } | Expr::Record { .. } => SizeBif::TupleSize,
+        Expr::Binary { segs } if segs.iter().all(|seg| is_whole_bytes(sema, body, seg)) => {
+            SizeBif::ByteSize
+        }
+        Expr::Binary { .. } => SizeBif::Unknown,
+        // The eqwalizer type of a binary does not tell it from a
+        // bitstring, so it is only used for tuples
+        _ => match sema.expr_type(body, &arg) {
+            Some(
+                Type::TupleType(_)
+                | Type::AnyTupleType
+                | Type::RecordType(_)
+                | Type::RefinedRecordType(_),
+            ) => SizeBif::TupleSize,
+            _ => SizeBif::Unknown,
+        },
+    }
+}
+
+/// Whether the segment is known to be a whole number of bytes long.
+fn is_whole_bytes(sema: &Semantic, body: &Body, seg: &BinarySeg<ExprId>) -> bool {
+    let tys: Vec<String> = seg
+        .tys
+        .iter()
+        .map(|ty| ty.as_string(sema.db.upcast()))
+        .collect();
+    let has_ty = |names: &[&str]| tys.iter().any(|ty| names.contains(&ty.as_str()));
+    if has_ty(&["bits", "bitstring"]) {
+        return false;
+    }
+    if has_ty(&["utf8", "utf16", "utf32"]) {
+        return true;
+    }
+    let unit = seg
+        .unit
+        .unwrap_or(if has_ty(&["binary", "bytes"]) { 8 } else { 1 });
+    match seg.size {
+        // A binary without a size is a whole binary, an integer defaults
+        // to 8 bits and a float to 64
+        None => unit % 8 == 0 || !has_ty(&["binary", "bytes"]),
+        Some(size) => match &body[size] {
+            Expr::Literal(Literal::Integer(size)) => (size.value * unit) % 8 == 0,
+            _ => unit % 8 == 0,
+        },
+    }
+}
+
+#[cfg(test)]
+mod tests {
+
+    use expect_test::expect;
+
+    use expect_test::Expect;
+
+    use crate::diagnostics::Diagnostic;
+    use crate::diagnostics::DiagnosticCode;
+    use crate::diagnostics::DiagnosticsConfig;
+    use crate::tests;
+
+    fn config() -> DiagnosticsConfig {
+        // no_size reports the same calls
+        DiagnosticsConfig::default()
+            .enable(DiagnosticCode::SpecificSizeBif)
+            .disable(DiagnosticCode::NoSize)
+    }
+
+    fn filter(d: &Diagnostic) -> bool {
+        d.code == DiagnosticCode::SpecificSizeBif
+    }
+
+    #[track_caller]
+    fn check_filtered_diagnostics(fixture: &str, filter: &dyn Fn(&Diagnostic) -> bool) {
+        tests::check_filtered_diagnostics_with_config(config(), &vec![], fixture, filter)
+    }
+
+    #[track_caller]
+    fn check_fix(fixture_before: &str, fixture_after: Expect) {
+        tests::check_fix_with_config(config(), fixture_before, fixture_after)
+    }
+
+    #[test]
+    fn basic() {
+        check_filtered_diagnostics(
+            r#"
+              //- /src/main.erl
+              -module(main).
+              foo() -> erlang:size({}).
+              %%       ^^^^^^^^^^^ 💡 weak: Use `tuple_size/1` instead of the `size/1` BIF.
+
+              bar(A) -> size(<<A/binary, "x">>).
+              %%        ^^^^ 💡 weak: Use `byte_size/1` instead of the `size/1` BIF.
+
+              baz(X) -> size(X).
+              %%        ^^^^ 💡 weak: Use `byte_size/1` or `tuple_size/1` instead of the `size/1` BIF.
+              //- /opt/lib/stdlib-3.17/src/erlang.erl otp_app:/opt/lib/stdlib-3.17
+              -module(erlang).
+              -export([size/1]).
+              size(_) -> 42.
+             "#,
+            &filter,
+        )
+    }
+
+    #[test]
+    fn not_enabled_by_default() {
+        tests::check_filtered_diagnostics(
+            r#"
+              //- /src/main.erl
+              -module(main).
+              foo() -> erlang:size({}).
+              //- /opt/lib/stdlib-3.17/src/erlang.erl otp_app:/opt/lib/stdlib-3.17
+              -module(erlang).
+              -export([size/1]).
+              size(_) -> 42.
+             "#,
+            &filter,
+        )
+    }
+
+    #[test]
+    fn no_fix_for_bitstrings() {
+        check_filtered_diagnostics(
+            r#"
+              //- /src/main.erl
+              -module(main).
+              foo(A) -> size(<<A:3>>).
+              %%        ^^^^ 💡 weak: Use `byte_size/1` or `tuple_size/1` instead of the `size/1` BIF.
+              bar(A) -> size(<<A/bits>>).
+              %%        ^^^^ 💡 weak: Use `byte_size/1` or `tuple_size/1` instead of the `size/1` BIF.
+              //- /opt/lib/stdlib-3.17/src/erlang.erl otp_app:/opt/lib/stdlib-3.17
+              -module(erlang).
+              -export([size/1]).
+              size(_) -> 42.
+             "#,
+            &filter,
+        )
+    }
+
+    #[test]
+    fn fix_tuple_size() {
+        check_fix(
+            r#"
+              //- /src/main.erl
+              -module(main).
+              -record(rec, {a}).
+              foo(A) -> er~lang:size(#rec{a = A}).
+              //- /opt/lib/stdlib-3.17/src/erlang.erl otp_app:/opt/lib/stdlib-3.17
+              -module(erlang).
+              -export([size/1]).
+              size(_) -> 42.
+             "#,
+            expect![[r#"
+                -module(main).
+                -record(rec, {a}).
+                foo(A) -> erlang:tuple_size(#rec{a = A}).
+            "#]],
+        );
+    }
+
+    #[test]
+    fn fix_byte_size() {
+        check_fix(
+            r#"
+              //- /src/main.erl
+              -module(main).
+              foo(A) -> si~ze(<<A/binary, "x">>).
+              //- /opt/lib/stdlib-3.17/src/erlang.erl otp_app:/opt/lib/stdlib-3.17
+              -module(erlang).
+              -export([size/1]).
+              size(_) -> 42.
+             "#,
+            expect![[r#"
+                -module(main).
+                foo(A) -> byte_size(<<A/binary, "x">>).
+            "#]],
+        );
+    }
+}
@@ -0,0 +1,167 @@
+/*
+ * Copyright (c) Meta Platforms, Inc. and affiliates.
+ *
+ * This source code is dual-licensed under either the MIT license found in the
+ * LICENSE-MIT file in the root directory of this source tree or the Apache
+ * License, Version 2.0 found in the LICENSE-APACHE file in the root directory
+ * of this source tree. You may select, at your option, one of the
+ * above-listed licenses.
+ */
+
+//! Lint: timer_sleep_infinity
+//!
+//! Return a warning for `timer:sleep(infinity)`, and suggest
+//! `receive after infinity -> ok end`, which blocks forever without going
+//! through the `timer` module.
+
+use elp_ide_assists::Assist;
+use elp_ide_db::DiagnosticCode;
+use elp_ide_db::elp_base_db::FileId;
+use elp_ide_db::source_change::SourceChangeBuilder;
+use elp_ide_ssr::Match;
+use hir::Semantic;
+
+use crate::diagnostics::Linter;
+use crate::diagnostics::Severity;
+use crate::diagnostics::SsrPatternsLinter;
+use crate::fix;
+
+pub(crate) struct TimerSleepInfinityLinter;
+
+impl Linter for TimerSleepInfinityLinter {
+    fn id(&self) -> DiagnosticCode {
+        DiagnosticCode::TimerSleepInfinity
+    }
+
+    fn description(&self) -> &'static str {
+        "Use `receive after infinity -> ok end` to block forever."
+    }
+
+    fn severity(&self) -> Severity {
+        Severity::WeakWarning
+    }
+
+    fn is_enabled(&self) -> bool {
+        false
+    }
+}
+
+impl SsrPatternsLinter for TimerSleepInfinityLinter {
+    type Context = ();
+
+    fn patterns(&self) -> Vec<(String, Self::Context)> {
+        // The atom in the pattern only matches the literal `infinity`, not
+        // a timeout held in a variable
+        vec![("ssr: timer:sleep(infinity).".to_string(), ())]
+    }
+
+    fn is_match_valid(
+        &self,
+        _context: &Self::Context,
+        matched: &Match,
+        _sema: &Semantic,
+        file_id: FileId,
+    ) -> Option<bool> {
+        Some(matched.range.file_id == file_id)
+    }
+
+    fn fixes(
+        &self,
+        _context: &Self::Context,
+        matched: &Match,
+        _sema: &Semantic,
+        file_id: FileId,
+    ) -> Option<Vec<Assist>> {
+        let range = matched.range.range;
+        let mut builder = SourceChangeBuilder::new(file_id);
+        builder.replace(range, "receive after infinity -> ok end");
+        Some(vec![fix(
+            "timer_sleep_infinity",
+            "Replace with `receive after infinity -> ok end`",
+            builder.finish(),
+            range,
+        )])
+    }
+}
+
+pub(crate) static LINTER: TimerSleepInfinityLinter = TimerSleepInfinityLinter;
+
+#[cfg(test)]
+mod tests {
+
+    use expect_test::Expect;
+    use expect_test::expect;
+
+    use crate::diagnostics::Diagnostic;
+    use crate::diagnostics::DiagnosticCode;
+    use crate::diagnostics::DiagnosticsConfig;
+    use crate::tests;
+
+    fn config() -> DiagnosticsConfig {
+        DiagnosticsConfig::default().enable(DiagnosticCode::TimerSleepInfinity)
+    }
+
+    fn filter(d: &Diagnostic) -> bool {
+        d.code == DiagnosticCode::TimerSleepInfinity
+    }
+
+    #[track_caller]
+    fn check_diagnostics(fixture: &str) {
+        tests::check_filtered_diagnostics_with_config(config(), &vec![], fixture, &filter)
+    }
+
+    #[track_caller]
+    fn check_fix(fixture_before: &str, fixture_after: Expect) {
+        tests::check_fix_with_config(config(), fixture_before, fixture_after)
+    }
+
+    #[test]
+    fn detects_sleep_infinity() {
+        check_diagnostics(
+            r#"
+         //- /src/main.erl
+         -module(main).
+
+         foo(N) ->
+             timer:sleep(N),
+             timer:sleep(1000),
+             timer:sleep(infinity).
+         %%  ^^^^^^^^^^^^^^^^^^^^^ 💡 weak: Use `receive after infinity -> ok end` to block forever.
+            "#,
+        )
+    }
+
+    #[test]
+    fn not_enabled_by_default() {
+        tests::check_filtered_diagnostics(
+            r#"
+         //- /src/main.erl
+         -module(main).
+
+         foo() -> timer:sleep(infinity).
+            "#,
+            &filter,
+        )
+    }
+
+    #[test]
+    fn fixes_sleep_infinity() {
+        check_fix(
+            r#"
+         //- /src/main.erl
+         -module(main).
+
+         foo() ->
+             % elp:ignore W0017 (undefined_function)
+             timer:sle~ep(infinity).
+            "#,
+            expect![[r#"
+         -module(main).
+
+         foo() ->
+             % elp:ignore W0017 (undefined_function)
+             receive after infinity -> ok end.
+            "#]],
+        )
+    }
+}
@@ -54,7 +54,8 @@ pub(crate) static DESCRIPTOR: DiagnosticDescriptor = DiagnosticDescriptor {
         include_tests: true,
         default_disabled: false,
     },
-    checker: &|diags, sema, file_id, _ext| {
+    codes: &[DiagnosticCode::TrivialMatch],
+    checker: &|diags, sema, file_id, _ext, _config| {
         trivial_match(diags, sema, file_id);
     },
 };
@@ -0,0 +1,218 @@
+/*
+ * Copyright (c) Meta Platforms, Inc. and affiliates.
+ *
+ * This source code is dual-licensed under either the MIT license found in the
+ * LICENSE-MIT file in the root directory of this source tree or the Apache
+ * License, Version 2.0 found in the LICENSE-APACHE file in the root directory
+ * of this source tree. You may select, at your option, one of the
+ * above-listed licenses.
+ */
+
+//! Lint: undefined_equality
+//!
+//! Suggest `=:=`/`=/=` instead of `==`/`/=` when comparing a term with the
+//! atom `undefined`. An atom is never equal to a number, so both operators
+//! behave the same, but the exact one states the intent.
+//!
+//! e.g.
+//!
+//! ```ignore
+//! X == undefined
+//! ```
+//!
+//! becomes
+//!
+//! ```ignore
+//! X =:= undefined
+//! ```
+
+use elp_ide_db::DiagnosticCode;
+use elp_ide_db::elp_base_db::FileId;
+use elp_ide_db::source_change::SourceChange;
+use elp_syntax::ast;
+use elp_syntax::ast::BinaryOp;
+use elp_syntax::ast::CompOp;
+use elp_text_edit::TextEdit;
+use hir::AnyExpr;
+use hir::Expr;
+use hir::FunctionDef;
+use hir::Semantic;
+use hir::Strategy;
+use hir::fold::MacroStrategy;
+use hir::fold::ParenStrategy;
+use hir::known;
+
+use super::Diagnostic;
+use super::DiagnosticConditions;
+use super::DiagnosticDescriptor;
+use super::Severity;
+use super::expression_can_be_simplified::is_literal_atom;
+use super::use_exact_equality::is_non_numeric;
+use crate::fix;
+
+pub(crate) static DESCRIPTOR: DiagnosticDescriptor = DiagnosticDescriptor {
+    conditions: DiagnosticConditions {
+        experimental: false,
+        include_generated: false,
+        include_tests: true,
+        default_disabled: false,
+    },
+    codes: &[DiagnosticCode::UndefinedEquality],
+    checker: &|diagnostics, sema, file_id, _ext, _config| {
+        sema.for_each_function(file_id, |def| {
+            check_function(diagnostics, sema, file_id, def)
+        });
+    },
+};
+
+fn check_function(
+    diagnostics: &mut Vec<Diagnostic>,
+    sema: &Semantic,
+    file_id: FileId,
+    def: &FunctionDef,
+) {
+    let def_fb = def.in_function_body(sema, def);
+    def_fb.fold_function(
+        Strategy {
+            macros: MacroStrategy::DoNotExpand,
+            parens: ParenStrategy::InvisibleParens,
+        },
+        (),
+        &mut |_acc, clause_id, ctx| {
+            if let AnyExpr::Expr(Expr::BinaryOp {
+                lhs,
+                rhs,
+                op:
+                    BinaryOp::CompOp(CompOp::Eq {
+                        strict: false,
+                        negated,
+                    }),
+            }) = ctx.item
+            {
+                let body = def_fb.body(clause_id);
+                let is_undefined =
+                    |expr_id| is_literal_atom(sema, &body[expr_id], known::undefined);
+                // When both sides are non-numeric, `use_exact_equality` already applies
+                let other = if is_undefined(lhs) {
+                    rhs
+                } else if is_undefined(rhs) {
+                    lhs
+                } else {
+                    return;
+                };
+                if is_non_numeric(&body, other) {
+                    return;
+                }
+                let map = def_fb.get_body_map(clause_id);
+                if let Some(diagnostic) = ctx
+                    .item_id
+                    .as_expr_id()
+                    .and_then(|expr_id| map.expr(expr_id))
+                    .filter(|ptr| ptr.file_id() == file_id)
+                    .and_then(|ptr| make_diagnostic(sema, file_id, &ptr.to_ast(sema.db), negated))
+                {
+                    diagnostics.push(diagnostic);
+                }
+            };
+        },
+    )
+}
+
+fn make_diagnostic(
+    sema: &Semantic,
+    file_id: FileId,
+    expr: &ast::Expr,
+    negated: bool,
+) -> Option<Diagnostic> {
+    let ast::Expr::BinaryOpExpr(binop) = expr else {
+        return None;
+    };
+    // Only edit the operator token, leaving the operands untouched
+    let (_op, token) = binop.op()?;
+    let range = token.text_range();
+    let (from, to) = if negated {
+        ("/=", "=/=")
+    } else {
+        ("==", "=:=")
+    };
+    let edit = TextEdit::replace(range, to.to_string());
+    let message = format!("Compare with `undefined` using `{to}` instead of `{from}`.");
+    Some(
+        Diagnostic::new(DiagnosticCode::UndefinedEquality, message, range)
+            .with_severity(Severity::WeakWarning)
+            .with_fixes(Some(vec![fix(
+                "undefined_equality",
+                &format!("Replace `{from}` with `{to}`"),
+                SourceChange::from_text_edit(file_id, edit),
+                range,
+            )]))
+            .with_ignore_fix(sema, file_id),
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use expect_test::expect;
+
+    use crate::tests::check_diagnostics;
+    use crate::tests::check_fix;
+
+    #[test]
+    fn flags_non_exact_comparison_with_undefined() {
+        check_diagnostics(
+            r#"
+              -module(main).
+              eq(X) -> X == undefined.
+              %%         ^^ 💡 weak: Compare with `undefined` using `=:=` instead of `==`.
+              neq(X) when undefined /= X -> X.
+              %%                    ^^ 💡 weak: Compare with `undefined` using `=/=` instead of `/=`.
+            "#,
+        )
+    }
+
+    #[test]
+    fn ignores_exact_and_other_comparisons() {
+        check_diagnostics(
+            r#"
+              -module(main).
+              exact(X) -> X =:= undefined.
+              other(X) -> X == other.
+              matched(X) ->
+                  case X of
+                      undefined -> a;
+                      _ -> b
+                  end.
+            "#,
+        )
+    }
+
+    #[test]
+    fn fix_equal() {
+        check_fix(
+            r#"
+              -module(main).
+              foo(X) -> X =~= undefined.
+            "#,
+            expect![[r#"
+              -module(main).
+              foo(X) -> X =:= undefined.
+            "#]],
+        )
+    }
+
+    #[test]
+    fn fix_not_equal() {
+        check_fix(
+            r#"
+              -module(main).
+              foo(X) -> X ~/= undefined andalso bar(X).
+              bar(_) -> true.
+            "#,
+            expect![[r#"
+              -module(main).
+              foo(X) -> X =/= undefined andalso bar(X).
+              bar(_) -> true.
+            "#]],
+        )
+    }
+}
@@ -267,7 +267,12 @@ mod tests {
     #[test]
     fn test_do_not_exclude_get_stacktrace() {
         // erlang:get_stacktrace/0 last existed in OTP20. Do not special-case it
-        check_diagnostics(
+        // here, erlang_get_stacktrace reports it separately
+        let config = DiagnosticsConfig::default()
+            .disable(elp_ide_db::DiagnosticCode::NoSize)
+            .disable(elp_ide_db::DiagnosticCode::ErlangGetStacktrace);
+        check_diagnostics_with_config(
+            config,
             r#"
 //- /src/main.erl
   -module(main).
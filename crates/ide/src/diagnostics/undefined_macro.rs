@@ -54,20 +54,28 @@ pub(crate) fn add_assist(
     let multiple = includes.len() > 1;
     for include in includes {
         if let Some(pos) = include.insert_position_if_needed(sema, file_id) {
-            let mut builder = TextEdit::builder();
-            builder.insert(pos.offset, include.as_attribute());
-            let edit = builder.finish();
-            let label = if multiple {
-                format!("Add required include for '{name}' ({})", include.app_name)
-            } else {
-                format!("Add required include for '{name}'")
-            };
-            diagnostic.add_fix(fix(
-                "add_macro_include",
-                label.as_str(),
-                SourceChange::from_text_edit(file_id, edit),
-                diagnostic.range,
-            ));
+            // The most specific include comes first
+            for (i, candidate) in include.candidates().enumerate() {
+                let mut builder = TextEdit::builder();
+                builder.insert(pos.offset, candidate.as_attribute());
+                let edit = builder.finish();
+                let label = match (multiple, i) {
+                    (true, 0) => {
+                        format!("Add required include for '{name}' ({})", include.app_name)
+                    }
+                    (false, 0) => format!("Add required include for '{name}'"),
+                    _ => format!(
+                        "Add required include for '{name}' ({})",
+                        candidate.as_attribute().trim_end()
+                    ),
+                };
+                diagnostic.add_fix(fix(
+                    "add_macro_include",
+                    label.as_str(),
+                    SourceChange::from_text_edit(file_id, edit),
+                    diagnostic.range,
+                ));
+            }
         }
     }
     Some(())
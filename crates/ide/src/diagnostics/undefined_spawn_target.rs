@@ -0,0 +1,215 @@
+/*
+ * Copyright (c) Meta Platforms, Inc. and affiliates.
+ *
+ * This source code is dual-licensed under either the MIT license found in the
+ * LICENSE-MIT file in the root directory of this source tree or the Apache
+ * License, Version 2.0 found in the LICENSE-APACHE file in the root directory
+ * of this source tree. You may select, at your option, one of the
+ * above-listed licenses.
+ */
+
+//! Lint: undefined_spawn_target
+//!
+//! Return a warning when `spawn/3`, `spawn_link/3`, `spawn_monitor/3` or
+//! `apply/3` is given a literal module, function and argument list that do
+//! not resolve to an exported function, e.g. `spawn(foo, loop, [])` where
+//! `foo:loop/0` is missing or private. Such a call fails at runtime.
+
+use std::borrow::Cow;
+
+use hir::Atom;
+use hir::Body;
+use hir::Expr;
+use hir::ExprId;
+use hir::NameArity;
+use hir::known;
+
+use crate::FunctionMatch;
+use crate::codemod_helpers::CheckCallCtx;
+use crate::diagnostics::DiagnosticCode;
+use crate::diagnostics::FunctionCallLinter;
+use crate::diagnostics::Linter;
+use crate::lazy_function_matches;
+
+pub(crate) struct UndefinedSpawnTargetLinter;
+
+impl Linter for UndefinedSpawnTargetLinter {
+    fn id(&self) -> DiagnosticCode {
+        DiagnosticCode::UndefinedSpawnTarget
+    }
+
+    fn description(&self) -> &'static str {
+        "Target function is undefined or not exported."
+    }
+}
+
+pub(crate) enum Target {
+    Undefined(String),
+    Unexported(String),
+}
+
+impl FunctionCallLinter for UndefinedSpawnTargetLinter {
+    type Context = Target;
+
+    fn match_description(&self, context: &Self::Context) -> Cow<'_, str> {
+        match context {
+            Target::Undefined(label) => {
+                Cow::Owned(format!("Target function '{label}' is undefined."))
+            }
+            Target::Unexported(label) => {
+                Cow::Owned(format!("Target function '{label}' is not exported."))
+            }
+        }
+    }
+
+    fn matches_functions(&self) -> Vec<FunctionMatch> {
+        lazy_function_matches![vec![
+            FunctionMatch::mfa("erlang", "spawn", 3),
+            FunctionMatch::mfa("erlang", "spawn_link", 3),
+            FunctionMatch::mfa("erlang", "spawn_monitor", 3),
+            FunctionMatch::mfa("erlang", "apply", 3),
+        ]]
+    }
+
+    fn check_match(&self, context: &CheckCallCtx<'_, ()>) -> Option<Self::Context> {
+        let def_fb = context.in_clause;
+        let sema = def_fb.sema;
+        let body = def_fb.body();
+        let [module, function, args] = context.args.as_vec()[..] else {
+            return None;
+        };
+        // Variables can point anywhere, so only literal targets are checked
+        let module = literal_atom(&body, module)?;
+        let function = literal_atom(&body, function)?;
+        let arity = match &body[args] {
+            Expr::List { exprs, tail: None } => exprs.len() as u32,
+            _ => return None,
+        };
+        if sema.db.lookup_atom(function) == known::module_info && arity <= 1 {
+            return None;
+        }
+        let module = sema.db.lookup_atom(module);
+        let name = NameArity::new(sema.db.lookup_atom(function), arity);
+        let label = format!("{module}:{name}");
+        let Some(target) = sema.resolve_module_name(def_fb.file_id(), &module) else {
+            return Some(Target::Undefined(label));
+        };
+        let def_map = sema.def_map(target.file.file_id);
+        if def_map.get_function(&name).is_none() {
+            Some(Target::Undefined(label))
+        } else if !def_map.is_function_exported(&name) {
+            Some(Target::Unexported(label))
+        } else {
+            None
+        }
+    }
+}
+
+pub(crate) static LINTER: UndefinedSpawnTargetLinter = UndefinedSpawnTargetLinter;
+
+/// The atom an expression evaluates to, looking through macros such as
+/// `?MODULE`.
+fn literal_atom(body: &Body, expr_id: ExprId) -> Option<Atom> {
+    match &body[expr_id] {
+        Expr::MacroCall { expansion, .. } => literal_atom(body, *expansion),
+        expr => expr.as_atom(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+
+    use crate::diagnostics::Diagnostic;
+    use crate::diagnostics::DiagnosticCode;
+    use crate::tests;
+
+    fn filter(d: &Diagnostic) -> bool {
+        d.code == DiagnosticCode::UndefinedSpawnTarget
+    }
+
+    #[track_caller]
+    fn check_diagnostics(fixture: &str) {
+        tests::check_filtered_diagnostics(fixture, &filter)
+    }
+
+    #[test]
+    fn detects_undefined_target() {
+        check_diagnostics(
+            r#"
+         //- /src/main.erl
+         -module(main).
+
+         foo() ->
+             spawn(worker, missing, []),
+         %%  ^^^^^ 💡 warning: Target function 'worker:missing/0' is undefined.
+             erlang:spawn_link(worker, loop, [1, 2]),
+         %%  ^^^^^^^^^^^^^^^^^ 💡 warning: Target function 'worker:loop/2' is undefined.
+             apply(no_such_module, loop, [1]).
+         %%  ^^^^^ 💡 warning: Target function 'no_such_module:loop/1' is undefined.
+         //- /src/worker.erl
+         -module(worker).
+         -export([loop/1]).
+         loop(_) -> ok.
+         //- /opt/lib/stdlib-3.17/src/erlang.erl otp_app:/opt/lib/stdlib-3.17
+         -module(erlang).
+         -export([spawn/3, spawn_link/3, spawn_monitor/3, apply/3]).
+         spawn(_, _, _) -> ok.
+         spawn_link(_, _, _) -> ok.
+         spawn_monitor(_, _, _) -> ok.
+         apply(_, _, _) -> ok.
+            "#,
+        )
+    }
+
+    #[test]
+    fn detects_unexported_target() {
+        check_diagnostics(
+            r#"
+         //- /src/main.erl
+         -module(main).
+
+         foo() ->
+             spawn_monitor(worker, private, []),
+         %%  ^^^^^^^^^^^^^ 💡 warning: Target function 'worker:private/0' is not exported.
+             spawn(?MODULE, bar, []).
+         %%  ^^^^^ 💡 warning: Target function 'main:bar/0' is not exported.
+         bar() -> ok.
+         //- /src/worker.erl
+         -module(worker).
+         -export([loop/1]).
+         loop(_) -> ok.
+         private() -> ok.
+         //- /opt/lib/stdlib-3.17/src/erlang.erl otp_app:/opt/lib/stdlib-3.17
+         -module(erlang).
+         -export([spawn/3, spawn_monitor/3]).
+         spawn(_, _, _) -> ok.
+         spawn_monitor(_, _, _) -> ok.
+            "#,
+        )
+    }
+
+    #[test]
+    fn ignores_exported_and_unknown_targets() {
+        check_diagnostics(
+            r#"
+         //- /src/main.erl
+         -module(main).
+
+         foo(M, F, Args) ->
+             spawn(worker, loop, [1]),
+             spawn(M, loop, []),
+             spawn(worker, F, []),
+             spawn(worker, loop, Args),
+             spawn(worker, module_info, []).
+         //- /src/worker.erl
+         -module(worker).
+         -export([loop/1]).
+         loop(_) -> ok.
+         //- /opt/lib/stdlib-3.17/src/erlang.erl otp_app:/opt/lib/stdlib-3.17
+         -module(erlang).
+         -export([spawn/3]).
+         spawn(_, _, _) -> ok.
+            "#,
+        )
+    }
+}
@@ -35,7 +35,8 @@ pub(crate) static DESCRIPTOR: DiagnosticDescriptor = DiagnosticDescriptor {
         include_tests: false,
         default_disabled: true,
     },
-    checker: &|diags, sema, file_id, _ext| {
+    codes: &[DiagnosticCode::UndocumentedModule],
+    checker: &|diags, sema, file_id, _ext, _config| {
         check(diags, sema, file_id);
     },
 };
@@ -0,0 +1,217 @@
+/*
+ * Copyright (c) Meta Platforms, Inc. and affiliates.
+ *
+ * This source code is dual-licensed under either the MIT license found in the
+ * LICENSE-MIT file in the root directory of this source tree or the Apache
+ * License, Version 2.0 found in the LICENSE-APACHE file in the root directory
+ * of this source tree. You may select, at your option, one of the
+ * above-listed licenses.
+ */
+
+//! Lint: unnecessary_list_append
+//!
+//! warn on code of the form `[X] ++ L` or `[A, B] ++ L` and suggest `[X | L]`
+//! or `[A, B | L]`
+
+use elp_ide_db::DiagnosticCode;
+use elp_ide_db::elp_base_db::FileId;
+use elp_ide_db::source_change::SourceChangeBuilder;
+use elp_ide_ssr::Match;
+use elp_ide_ssr::match_pattern_in_file_functions;
+use elp_syntax::AstNode;
+use elp_syntax::algo;
+use elp_syntax::ast;
+use hir::Semantic;
+use hir::fold::MacroStrategy;
+use hir::fold::ParenStrategy;
+use hir::fold::Strategy;
+
+use crate::diagnostics::Category;
+use crate::diagnostics::Diagnostic;
+use crate::diagnostics::DiagnosticConditions;
+use crate::diagnostics::DiagnosticDescriptor;
+use crate::diagnostics::Severity;
+use crate::fix;
+
+pub(crate) static DESCRIPTOR: DiagnosticDescriptor = DiagnosticDescriptor {
+    conditions: DiagnosticConditions {
+        experimental: false,
+        include_generated: false,
+        include_tests: true,
+        default_disabled: false,
+    },
+    codes: &[DiagnosticCode::UnnecessaryListAppend],
+    checker: &|acc, sema, file_id, _ext, _config| {
+        unnecessary_list_append_ssr(acc, sema, file_id);
+    },
+};
+
+static PREFIX_VAR: &str = "_@Prefix";
+static LIST_VAR: &str = "_@List";
+
+fn unnecessary_list_append_ssr(diags: &mut Vec<Diagnostic>, sema: &Semantic, file_id: FileId) {
+    let matches = match_pattern_in_file_functions(
+        sema,
+        Strategy {
+            macros: MacroStrategy::Expand,
+            parens: ParenStrategy::InvisibleParens,
+        },
+        file_id,
+        format!("ssr: {PREFIX_VAR} ++ {LIST_VAR}.").as_str(),
+    );
+    matches.matches.iter().for_each(|m| {
+        if let Some(diagnostic) = make_diagnostic(sema, file_id, m) {
+            diags.push(diagnostic);
+        }
+    });
+}
+
+fn make_diagnostic(
+    sema: &Semantic,
+    original_file_id: FileId,
+    matched: &Match,
+) -> Option<Diagnostic> {
+    sensibility_check(sema, original_file_id, matched)?;
+    let file_id = matched.range.file_id;
+    let range = matched.range.range;
+    let source = sema.parse(file_id).value;
+    let append = algo::find_node_at_range::<ast::BinaryOpExpr>(source.syntax(), range)?;
+    // Only a proper list literal can take the tail directly
+    let elements = match append.lhs()? {
+        ast::Expr::ExprMax(ast::ExprMax::List(list)) => list
+            .exprs()
+            .map(|expr| match expr {
+                ast::Expr::Pipe(_) => None,
+                expr => Some(expr.syntax().text().to_string()),
+            })
+            .collect::<Option<Vec<_>>>()?,
+        _ => return None,
+    };
+    if elements.is_empty() {
+        // `[] ++ L` is handled by `expression_can_be_simplified`
+        return None;
+    }
+    let list = append.rhs()?.syntax().text().to_string();
+    let replacement = format!("[{} | {list}]", elements.join(", "));
+    let mut builder = SourceChangeBuilder::new(file_id);
+    builder.replace(range, &replacement);
+    let fixes = vec![fix(
+        "unnecessary_list_append",
+        format!("Rewrite to `{replacement}`").as_str(),
+        builder.finish(),
+        range,
+    )];
+    Some(
+        Diagnostic::new(
+            DiagnosticCode::UnnecessaryListAppend,
+            "Prepend the elements with `|` instead of `++`.",
+            range,
+        )
+        .with_severity(Severity::WeakWarning)
+        .add_categories([Category::SimplificationRule])
+        .with_ignore_fix(sema, file_id)
+        .with_fixes(Some(fixes)),
+    )
+}
+
+fn sensibility_check(sema: &Semantic<'_>, original_file_id: FileId, matched: &Match) -> Option<()> {
+    if let Some(comments) = matched.comments(sema) {
+        // Avoid clobbering comments in the original source code
+        if !comments.is_empty() {
+            return None;
+        }
+    }
+    if matched.range.file_id != original_file_id {
+        // We've somehow ended up with a match in a different file - this means we've
+        // accidentally expanded a macro from a different file, or some other complex case that
+        // gets hairy, so bail out.
+        return None;
+    }
+    Some(())
+}
+
+#[cfg(test)]
+mod tests {
+
+    use expect_test::Expect;
+    use expect_test::expect;
+
+    use crate::diagnostics::Diagnostic;
+    use crate::diagnostics::DiagnosticCode;
+    use crate::tests;
+
+    fn filter(d: &Diagnostic) -> bool {
+        d.code == DiagnosticCode::UnnecessaryListAppend
+    }
+
+    #[track_caller]
+    fn check_diagnostics(fixture: &str) {
+        tests::check_filtered_diagnostics(fixture, &filter)
+    }
+
+    #[track_caller]
+    fn check_fix(fixture_before: &str, fixture_after: Expect) {
+        tests::check_fix(fixture_before, fixture_after)
+    }
+
+    #[test]
+    fn detects_unnecessary_list_append() {
+        check_diagnostics(
+            r#"
+         //- /src/main.erl
+         -module(main).
+
+         foo(X, L) -> [X] ++ L.
+         %%           ^^^^^^^^ 💡 weak: Prepend the elements with `|` instead of `++`.
+         bar(A, B, L) -> [A, B] ++ L.
+         %%              ^^^^^^^^^^^ 💡 weak: Prepend the elements with `|` instead of `++`.
+            "#,
+        )
+    }
+
+    #[test]
+    fn ignores_other_appends() {
+        check_diagnostics(
+            r#"
+         //- /src/main.erl
+         -module(main).
+
+         foo(X, L, T) -> {[] ++ L, X ++ L, "ab" ++ L, [X | T] ++ L, [E || E <- X] ++ L}.
+            "#,
+        )
+    }
+
+    #[test]
+    fn fixes_single_element() {
+        check_fix(
+            r#"
+         //- /src/main.erl
+         -module(main).
+
+         foo(X, L) -> [X]~ ++ L.
+            "#,
+            expect![[r#"
+         -module(main).
+
+         foo(X, L) -> [X | L].
+            "#]],
+        )
+    }
+
+    #[test]
+    fn fixes_multiple_elements() {
+        check_fix(
+            r#"
+         //- /src/main.erl
+         -module(main).
+
+         foo(A, B, L) -> [A, {B, 1}] +~+ lists:reverse(L).
+            "#,
+            expect![[r#"
+         -module(main).
+
+         foo(A, B, L) -> [A, {B, 1} | lists:reverse(L)].
+            "#]],
+        )
+    }
+}
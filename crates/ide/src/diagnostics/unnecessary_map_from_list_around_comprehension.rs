@@ -35,7 +35,8 @@ pub(crate) static DESCRIPTOR: DiagnosticDescriptor = DiagnosticDescriptor {
         include_tests: true,
         default_disabled: false,
     },
-    checker: &|acc, sema, file_id, _ext| {
+    codes: &[DiagnosticCode::UnnecessaryMapFromListAroundComprehension],
+    checker: &|acc, sema, file_id, _ext, _config| {
         unnecessary_maps_from_list_around_comprehension_ssr(acc, sema, file_id);
     },
 };
@@ -0,0 +1,209 @@
+/*
+ * Copyright (c) Meta Platforms, Inc. and affiliates.
+ *
+ * This source code is dual-licensed under either the MIT license found in the
+ * LICENSE-MIT file in the root directory of this source tree or the Apache
+ * License, Version 2.0 found in the LICENSE-APACHE file in the root directory
+ * of this source tree. You may select, at your option, one of the
+ * above-listed licenses.
+ */
+
+//! Lint: unnecessary_try
+//!
+//! Return a warning when a `try` with `catch` clauses evaluates only a
+//! literal or a variable, e.g. `try X catch throw:_ -> error end`, since the
+//! body can never raise and the `catch` clauses are dead code.
+//!
+//! Deciding whether an arbitrary expression can raise is hard, so the
+//! detector is deliberately conservative.
+
+use elp_ide_db::DiagnosticCode;
+use elp_ide_db::elp_base_db::FileId;
+use elp_ide_db::source_change::SourceChangeBuilder;
+use elp_syntax::AstNode;
+use elp_syntax::SyntaxKind;
+use elp_syntax::ast;
+use hir::AnyExpr;
+use hir::Expr;
+use hir::FunctionDef;
+use hir::Semantic;
+use hir::Strategy;
+use hir::fold::MacroStrategy;
+use hir::fold::ParenStrategy;
+
+use super::Diagnostic;
+use super::DiagnosticConditions;
+use super::DiagnosticDescriptor;
+use super::Severity;
+use crate::fix;
+
+pub(crate) static DESCRIPTOR: DiagnosticDescriptor = DiagnosticDescriptor {
+    conditions: DiagnosticConditions {
+        experimental: false,
+        include_generated: false,
+        include_tests: true,
+        default_disabled: false,
+    },
+    codes: &[DiagnosticCode::UnnecessaryTry],
+    checker: &|diagnostics, sema, file_id, _ext, _config| {
+        sema.for_each_function(file_id, |def| {
+            check_function(diagnostics, sema, file_id, def)
+        });
+    },
+};
+
+fn check_function(
+    diagnostics: &mut Vec<Diagnostic>,
+    sema: &Semantic,
+    file_id: FileId,
+    def: &FunctionDef,
+) {
+    let def_fb = def.in_function_body(sema, def);
+    def_fb.fold_function(
+        Strategy {
+            macros: MacroStrategy::DoNotExpand,
+            parens: ParenStrategy::InvisibleParens,
+        },
+        (),
+        &mut |_acc, clause_id, ctx| {
+            if let AnyExpr::Expr(Expr::Try {
+                exprs,
+                of_clauses,
+                catch_clauses,
+                after,
+            }) = &ctx.item
+            {
+                // The `of` clauses and the `after` block can both raise,
+                // so only a bare `try ... catch ... end` is considered
+                if catch_clauses.is_empty() || !of_clauses.is_empty() || !after.is_empty() {
+                    return;
+                }
+                let body = def_fb.body(clause_id);
+                let [expr] = exprs[..] else {
+                    return;
+                };
+                if !matches!(body[expr], Expr::Literal(_) | Expr::Var(_)) {
+                    return;
+                }
+                let map = def_fb.get_body_map(clause_id);
+                if let Some(diagnostic) = ctx
+                    .item_id
+                    .as_expr_id()
+                    .and_then(|expr_id| map.expr(expr_id))
+                    .filter(|ptr| ptr.file_id() == file_id)
+                    .and_then(|ptr| match ptr.to_ast(sema.db) {
+                        ast::Expr::ExprMax(ast::ExprMax::TryExpr(try_expr)) => Some(try_expr),
+                        _ => None,
+                    })
+                    .and_then(|try_expr| make_diagnostic(sema, file_id, &try_expr))
+                {
+                    diagnostics.push(diagnostic);
+                }
+            }
+        },
+    )
+}
+
+fn make_diagnostic(
+    sema: &Semantic,
+    file_id: FileId,
+    try_expr: &ast::TryExpr,
+) -> Option<Diagnostic> {
+    let try_token = try_expr
+        .syntax()
+        .children_with_tokens()
+        .find(|element| element.kind() == SyntaxKind::ANON_TRY)?;
+    let range = try_token.text_range();
+    let body = try_expr.exprs().next()?;
+    let mut builder = SourceChangeBuilder::new(file_id);
+    builder.replace(
+        try_expr.syntax().text_range(),
+        body.syntax().text().to_string(),
+    );
+    let fixes = vec![fix(
+        "unnecessary_try",
+        "Unwrap the `try` body",
+        builder.finish(),
+        range,
+    )];
+    Some(
+        Diagnostic::new(
+            DiagnosticCode::UnnecessaryTry,
+            "Unnecessary `try`: its body cannot raise an exception.",
+            range,
+        )
+        .with_severity(Severity::WeakWarning)
+        .with_ignore_fix(sema, file_id)
+        .with_fixes(Some(fixes)),
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use expect_test::expect;
+
+    use crate::tests::check_diagnostics;
+    use crate::tests::check_fix;
+
+    #[test]
+    fn flags_literal_and_variable_bodies() {
+        check_diagnostics(
+            r#"
+              -module(main).
+              foo() ->
+                  try ok
+              %%  ^^^ 💡 weak: Unnecessary `try`: its body cannot raise an exception.
+                  catch throw:_ -> error
+                  end.
+              bar(X) ->
+                  try X
+              %%  ^^^ 💡 weak: Unnecessary `try`: its body cannot raise an exception.
+                  catch _:_ -> error
+                  end.
+            "#,
+        )
+    }
+
+    #[test]
+    fn ignores_bodies_which_can_raise() {
+        check_diagnostics(
+            r#"
+              -module(main).
+              foo(X) ->
+                  try bar(X)
+                  catch throw:_ -> error
+                  end.
+              baz(X) ->
+                  try X of
+                      ok -> bar(X)
+                  catch _:_ -> error
+                  end.
+              qux(X) ->
+                  try X
+                  after bar(X)
+                  end.
+              bar(X) -> X.
+            "#,
+        )
+    }
+
+    #[test]
+    fn fix_unwraps_body() {
+        check_fix(
+            r#"
+              -module(main).
+              foo(X) ->
+                  bar(t~ry X
+                      catch throw:_ -> error
+                      end).
+              bar(X) -> X.
+            "#,
+            expect![[r#"
+              -module(main).
+              foo(X) ->
+                  bar(X).
+              bar(X) -> X.
+            "#]],
+        )
+    }
+}
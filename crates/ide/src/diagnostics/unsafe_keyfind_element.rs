@@ -0,0 +1,136 @@
+/*
+ * Copyright (c) Meta Platforms, Inc. and affiliates.
+ *
+ * This source code is dual-licensed under either the MIT license found in the
+ * LICENSE-MIT file in the root directory of this source tree or the Apache
+ * License, Version 2.0 found in the LICENSE-APACHE file in the root directory
+ * of this source tree. You may select, at your option, one of the
+ * above-listed licenses.
+ */
+
+//! Lint: unsafe_keyfind_element
+//!
+//! warn on code of the form `element(N, lists:keyfind(Key, Pos, List))`, which
+//! crashes with `badarg` when the key is not found and `keyfind` returns `false`
+
+use elp_ide_db::DiagnosticCode;
+use elp_ide_db::elp_base_db::FileId;
+use elp_ide_ssr::Match;
+use elp_ide_ssr::match_pattern_in_file_functions;
+use hir::Semantic;
+use hir::fold::MacroStrategy;
+use hir::fold::ParenStrategy;
+use hir::fold::Strategy;
+
+use crate::diagnostics::Diagnostic;
+use crate::diagnostics::DiagnosticConditions;
+use crate::diagnostics::DiagnosticDescriptor;
+use crate::diagnostics::Severity;
+
+pub(crate) static DESCRIPTOR: DiagnosticDescriptor = DiagnosticDescriptor {
+    conditions: DiagnosticConditions {
+        experimental: false,
+        include_generated: false,
+        include_tests: true,
+        default_disabled: false,
+    },
+    codes: &[DiagnosticCode::UnsafeKeyfindElement],
+    checker: &|acc, sema, file_id, _ext, _config| {
+        unsafe_keyfind_element_ssr(acc, sema, file_id);
+    },
+};
+
+static INDEX_VAR: &str = "_@Index";
+static KEY_VAR: &str = "_@Key";
+static POS_VAR: &str = "_@Pos";
+static LIST_VAR: &str = "_@List";
+
+fn unsafe_keyfind_element_ssr(diags: &mut Vec<Diagnostic>, sema: &Semantic, file_id: FileId) {
+    for element in ["element", "erlang:element"] {
+        let matches = match_pattern_in_file_functions(
+            sema,
+            Strategy {
+                macros: MacroStrategy::Expand,
+                parens: ParenStrategy::InvisibleParens,
+            },
+            file_id,
+            format!(
+                "ssr: {element}({INDEX_VAR}, lists:keyfind({KEY_VAR}, {POS_VAR}, {LIST_VAR}))."
+            )
+            .as_str(),
+        );
+        matches.matches.iter().for_each(|m| {
+            if let Some(diagnostic) = make_diagnostic(sema, file_id, m) {
+                diags.push(diagnostic);
+            }
+        });
+    }
+}
+
+fn make_diagnostic(
+    sema: &Semantic,
+    original_file_id: FileId,
+    matched: &Match,
+) -> Option<Diagnostic> {
+    if matched.range.file_id != original_file_id {
+        // The match comes from a macro defined in another file, so we
+        // cannot report it here
+        return None;
+    }
+    Some(Diagnostic::new(
+        DiagnosticCode::UnsafeKeyfindElement,
+        "`lists:keyfind/3` returns `false` when the key is not found, so `element/2` may crash. Match on the result with a `case` instead.",
+        matched.range.range,
+    )
+    .with_severity(Severity::Warning)
+    .with_ignore_fix(sema, original_file_id))
+}
+
+#[cfg(test)]
+mod tests {
+
+    use crate::diagnostics::Diagnostic;
+    use crate::diagnostics::DiagnosticCode;
+    use crate::tests;
+
+    fn filter(d: &Diagnostic) -> bool {
+        d.code == DiagnosticCode::UnsafeKeyfindElement
+    }
+
+    #[track_caller]
+    fn check_diagnostics(fixture: &str) {
+        tests::check_filtered_diagnostics(fixture, &filter)
+    }
+
+    #[test]
+    fn detects_element_of_keyfind() {
+        check_diagnostics(
+            r#"
+         //- /src/main.erl
+         -module(main).
+
+         foo(Key, List) -> element(2, lists:keyfind(Key, 1, List)).
+         %%                ^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^ 💡 warning: `lists:keyfind/3` returns `false` when the key is not found, so `element/2` may crash. Match on the result with a `case` instead.
+         bar(Key, List) -> erlang:element(3, lists:keyfind(Key, 2, List)).
+         %%                ^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^ 💡 warning: `lists:keyfind/3` returns `false` when the key is not found, so `element/2` may crash. Match on the result with a `case` instead.
+            "#,
+        )
+    }
+
+    #[test]
+    fn ignores_matched_keyfind() {
+        check_diagnostics(
+            r#"
+         //- /src/main.erl
+         -module(main).
+
+         foo(Key, List) ->
+             case lists:keyfind(Key, 1, List) of
+                 {Key, Value} -> Value;
+                 false -> undefined
+             end.
+         bar(Key, List) -> element(2, lists:keysearch(Key, 1, List)).
+            "#,
+        )
+    }
+}
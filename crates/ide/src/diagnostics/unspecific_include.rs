@@ -45,7 +45,8 @@ pub(crate) static DESCRIPTOR: DiagnosticDescriptor = DiagnosticDescriptor {
         include_tests: true,
         default_disabled: false,
     },
-    checker: &|diags, sema, file_id, _file_kind| {
+    codes: &[DiagnosticCode::UnspecificInclude],
+    checker: &|diags, sema, file_id, _file_kind, _config| {
         check_includes(diags, sema, file_id);
     },
 };
@@ -75,6 +76,8 @@ fn check_includes(acc: &mut Vec<Diagnostic>, sema: &Semantic, file_id: FileId) {
 
                 let include_path = path_for_file(sema.db.upcast(), included_file_id)?;
                 if !include_path.to_string().contains("/src/") {
+                    // Only report the include when there is a valid
+                    // `include_lib` form to replace it with
                     let replacement = generated_file_include_lib(
                         sema.db.upcast(),
                         file_id,
@@ -234,4 +237,21 @@ mod tests {
             "#]],
         )
     }
+
+    #[test]
+    fn fixes_unspecific_include_from_other_app() {
+        check_fix(
+            r#"
+           //- /app_a/src/unspecific_include.erl app:app_a include_path:/app_b/include
+           -module(unspecific_include).
+           -include("some~_header_from_app_b.hrl").
+           %%       ^^^^^^^^^^^^^^^^^^^^^^^^^^^^ 💡 weak: Unspecific include.
+           //- /app_b/include/some_header_from_app_b.hrl include_path:/app_b include_path:/app_b/include app:app_b
+           -define(B,3)."#,
+            expect![[r#"
+                -module(unspecific_include).
+                -include_lib("app_b/include/some_header_from_app_b.hrl").
+            "#]],
+        )
+    }
 }
@@ -0,0 +1,184 @@
+/*
+ * Copyright (c) Meta Platforms, Inc. and affiliates.
+ *
+ * This source code is dual-licensed under either the MIT license found in the
+ * LICENSE-MIT file in the root directory of this source tree or the Apache
+ * License, Version 2.0 found in the LICENSE-APACHE file in the root directory
+ * of this source tree. You may select, at your option, one of the
+ * above-listed licenses.
+ */
+
+//! Lint: unused_comparison_result
+//!
+//! Return a diagnostic if a comparison is used as a statement, so that its
+//! result is discarded. This is almost always a bug, where a match or a
+//! guard was intended.
+//!
+//! e.g.
+//!
+//! ```ignore
+//! foo(X, Y) ->
+//!     X == Y,
+//!     ok.
+//! ```
+
+use elp_ide_db::DiagnosticCode;
+use elp_ide_db::elp_base_db::FileId;
+use elp_ide_db::source_change::SourceChange;
+use elp_syntax::AstNode;
+use elp_syntax::SyntaxKind;
+use elp_syntax::ast;
+use elp_syntax::ast::BinaryOp;
+use elp_syntax::ast::CompOp;
+use elp_text_edit::TextEdit;
+use hir::AnyExpr;
+use hir::AnyExprId;
+use hir::Expr;
+use hir::FunctionDef;
+use hir::Semantic;
+use hir::Strategy;
+use hir::fold::MacroStrategy;
+use hir::fold::ParenStrategy;
+
+use super::Diagnostic;
+use super::DiagnosticConditions;
+use super::DiagnosticDescriptor;
+use super::Severity;
+use super::effect_free_statement::is_followed_by;
+use super::effect_free_statement::is_statement;
+use crate::fix;
+
+pub(crate) static DESCRIPTOR: DiagnosticDescriptor = DiagnosticDescriptor {
+    conditions: DiagnosticConditions {
+        experimental: false,
+        include_generated: false,
+        include_tests: true,
+        default_disabled: false,
+    },
+    codes: &[DiagnosticCode::UnusedComparisonResult],
+    checker: &|diagnostics, sema, file_id, _ext, _config| {
+        sema.for_each_function(file_id, |def| {
+            check_function(diagnostics, sema, file_id, def)
+        });
+    },
+};
+
+fn check_function(
+    diagnostics: &mut Vec<Diagnostic>,
+    sema: &Semantic,
+    file_id: FileId,
+    def: &FunctionDef,
+) {
+    let source_file = sema.parse(file_id);
+    let def_fb = def.in_function_body(sema, def);
+    def_fb.fold_function(
+        Strategy {
+            macros: MacroStrategy::DoNotExpand,
+            parens: ParenStrategy::InvisibleParens,
+        },
+        (),
+        &mut |_acc, clause_id, ctx| {
+            if let AnyExpr::Expr(Expr::BinaryOp {
+                op: BinaryOp::CompOp(op),
+                ..
+            }) = ctx.item
+                && let AnyExprId::Expr(expr_id) = ctx.item_id
+            {
+                let body_map = def_fb.get_body_map(clause_id);
+                if let Some(in_file_ast_ptr) = body_map.expr(expr_id)
+                    && in_file_ast_ptr.file_id() == file_id
+                    && let Some(expr_ast) = in_file_ast_ptr.to_node(&source_file)
+                    && is_statement(&expr_ast)
+                    && is_followed_by(SyntaxKind::ANON_COMMA, &expr_ast)
+                {
+                    diagnostics.push(make_diagnostic(sema, file_id, &expr_ast, op));
+                }
+            }
+        },
+    )
+}
+
+fn make_diagnostic(sema: &Semantic, file_id: FileId, expr: &ast::Expr, op: CompOp) -> Diagnostic {
+    let range = expr.syntax().text_range();
+    let diagnostic = Diagnostic::new(
+        DiagnosticCode::UnusedComparisonResult,
+        "The result of this comparison is unused. Did you mean to use a match or a guard?",
+        range,
+    )
+    .with_severity(Severity::Warning);
+    let diagnostic = match (op, expr) {
+        // An equality check in statement position is most likely a typo for a match
+        (CompOp::Eq { negated: false, .. }, ast::Expr::BinaryOpExpr(binop)) => match binop.op() {
+            Some((_, token)) => {
+                let edit = TextEdit::replace(token.text_range(), "=".to_string());
+                diagnostic.with_fixes(Some(vec![fix(
+                    "replace_comparison_with_match",
+                    &format!("Replace `{}` with `=`", token.text()),
+                    SourceChange::from_text_edit(file_id, edit),
+                    range,
+                )]))
+            }
+            None => diagnostic,
+        },
+        _ => diagnostic,
+    };
+    diagnostic.with_ignore_fix(sema, file_id)
+}
+
+#[cfg(test)]
+mod tests {
+    use expect_test::expect;
+
+    use crate::tests::check_diagnostics;
+    use crate::tests::check_fix;
+
+    #[test]
+    fn flags_comparison_statement() {
+        check_diagnostics(
+            r#"
+              -module(main).
+              foo(X, Y) ->
+                  X == Y,
+              %%  ^^^^^^ 💡 warning: The result of this comparison is unused. Did you mean to use a match or a guard?
+                  X < Y,
+              %%  ^^^^^ 💡 warning: The result of this comparison is unused. Did you mean to use a match or a guard?
+                  ok.
+            "#,
+        )
+    }
+
+    #[test]
+    fn ignores_used_comparison() {
+        check_diagnostics(
+            r#"
+              -module(main).
+              foo(X, Y) ->
+                  R = X == Y,
+                  case X =:= Y of
+                      true -> R;
+                      false -> X /= Y
+                  end.
+              bar(X, Y) ->
+                  X == Y.
+            "#,
+        )
+    }
+
+    #[test]
+    fn fix_replaces_with_match() {
+        check_fix(
+            r#"
+              -module(main).
+              foo(X, Y) ->
+                  X =~:= Y,
+                  ok.
+            "#,
+            expect![[r#"
+              -module(main).
+              foo(X, Y) ->
+                  X = Y,
+                  ok.
+            "#]],
+        )
+    }
+}
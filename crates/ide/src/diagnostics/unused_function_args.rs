@@ -48,7 +48,8 @@ pub(crate) static DESCRIPTOR: DiagnosticDescriptor = DiagnosticDescriptor {
         include_tests: true,
         default_disabled: false,
     },
-    checker: &|diags, sema, file_id, _ext| {
+    codes: &[DiagnosticCode::UnusedFunctionArg],
+    checker: &|diags, sema, file_id, _ext, _config| {
         unused_function_args(diags, sema, file_id);
     },
 };
@@ -201,9 +202,17 @@ mod tests {
 
     use expect_test::expect;
 
-    use crate::tests::check_diagnostics;
+    use crate::diagnostics::DiagnosticCode;
+    use crate::diagnostics::DiagnosticsConfig;
+    use crate::tests::check_diagnostics_with_config;
     use crate::tests::check_fix;
 
+    #[track_caller]
+    fn check_diagnostics(fixture: &str) {
+        let config = DiagnosticsConfig::default().disable(DiagnosticCode::IgnoredFunctionArg);
+        check_diagnostics_with_config(config, fixture)
+    }
+
     #[test]
     fn check_diagnostic_unused_unprefixed_variables() {
         check_diagnostics(
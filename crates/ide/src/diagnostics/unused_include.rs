@@ -10,7 +10,11 @@
 
 // Diagnostic: unused include
 //
-// Return a warning if nothing is used from an include file
+// Return a warning if nothing is used from an include file.
+//
+// To stay conservative, headers that are also included through another
+// header of the module are not reported, and macros are regarded as used
+// if they are referenced anywhere in the module text.
 
 use elp_ide_assists::helpers::extend_range;
 use elp_ide_db::SearchScope;
@@ -52,10 +56,16 @@ pub(crate) fn unused_includes(
     let form_list = db.file_form_list(file_id);
     let mut cache = Default::default();
     let source_file = db.parse(file_id);
+    let nested_includes = nested_includes(db, file_id);
     for (include_idx, attr) in form_list.includes() {
         if !EXCLUDES.contains(attr.path()) {
             let in_file = InFile::new(file_id, include_idx);
             if let Some(include_file_id) = db.resolve_include(in_file) {
+                // Removing the include would not remove the header,
+                // since it is also included by another header.
+                if nested_includes.contains(&include_file_id) {
+                    continue;
+                }
                 if is_file_used(sema, db, include_file_id, file_id, &mut cache) {
                     continue;
                 }
@@ -77,7 +87,7 @@ pub(crate) fn unused_includes(
                     format!("Unused file: {path}"),
                     attribute_range,
                 )
-                .with_severity(Severity::Warning)
+                .with_severity(Severity::WeakWarning)
                 .with_fixes(Some(vec![fix(
                     "remove_unused_include",
                     "Remove unused include",
@@ -93,6 +103,27 @@ pub(crate) fn unused_includes(
     }
 }
 
+/// The files included by the headers included in `file_id`, transitively.
+fn nested_includes(db: &dyn DefDatabase, file_id: FileId) -> FxHashSet<FileId> {
+    let mut res = FxHashSet::default();
+    let mut todo = direct_includes(db, file_id);
+    while let Some(header) = todo.pop() {
+        for nested in direct_includes(db, header) {
+            if res.insert(nested) {
+                todo.push(nested);
+            }
+        }
+    }
+    res
+}
+
+fn direct_includes(db: &dyn DefDatabase, file_id: FileId) -> Vec<FileId> {
+    db.file_form_list(file_id)
+        .includes()
+        .filter_map(|(include_idx, _)| db.resolve_include(InFile::new(file_id, include_idx)))
+        .collect()
+}
+
 fn is_file_used(
     sema: &Semantic,
     db: &dyn DefDatabase,
@@ -181,6 +212,18 @@ fn is_file_used(
             }
         }
 
+        // Macro usages are not found in all positions, e.g. in some
+        // guards, so look for a textual reference as well.
+        let target_text = db.file_text(target);
+        if def_map
+            .get_macros()
+            .keys()
+            .any(|name| target_text.contains(&format!("?{}", name.name())))
+        {
+            cache.insert(file_id, true);
+            return true;
+        }
+
         for macro_def in def_map.get_macros().values() {
             if SymbolDefinition::Define(macro_def.clone())
                 .usages(sema)
@@ -252,7 +295,8 @@ mod tests {
         let config = DiagnosticsConfig::default()
             .disable(DiagnosticCode::UndefinedFunction)
             .disable(DiagnosticCode::UnspecificInclude)
-            .disable(DiagnosticCode::NoDialyzerAttribute);
+            .disable(DiagnosticCode::NoDialyzerAttribute)
+            .disable(DiagnosticCode::IgnoredFunctionArg);
         check_diagnostics_with_config(config, fixture)
     }
 
@@ -265,7 +309,7 @@ mod tests {
 //- /src/foo.erl
   -module(foo).
   -include("foo.hrl").
-%%^^^^^^^^^^^^^^^^^^^^ 💡 warning: Unused file: foo.hrl
+%%^^^^^^^^^^^^^^^^^^^^ 💡 weak: Unused file: foo.hrl
         "#,
         );
     }
@@ -293,7 +337,7 @@ mod tests {
 //- /src/foo.erl
   -module(foo).
   -include("foo.hrl").
-%%^^^^^^^^^^^^^^^^^^^^ 💡 warning: Unused file: foo.hrl
+%%^^^^^^^^^^^^^^^^^^^^ 💡 weak: Unused file: foo.hrl
         "#,
         );
     }
@@ -321,7 +365,7 @@ mod tests {
 //- /src/foo.erl
   -module(foo).
   -include("foo.hrl").
-%%^^^^^^^^^^^^^^^^^^^^ 💡 warning: Unused file: foo.hrl
+%%^^^^^^^^^^^^^^^^^^^^ 💡 weak: Unused file: foo.hrl
         "#,
         );
     }
@@ -350,7 +394,7 @@ mod tests {
 //- /src/foo.erl
   -module(foo).
   -include("foo.hrl").
-%%^^^^^^^^^^^^^^^^^^^^ 💡 warning: Unused file: foo.hrl
+%%^^^^^^^^^^^^^^^^^^^^ 💡 weak: Unused file: foo.hrl
         "#,
         );
     }
@@ -594,7 +638,7 @@ foo() -> ok.
 //- /src/main.erl
 -module(main).
   -include("header.hrl").
-%%^^^^^^^^^^^^^^^^^^^^^^^ 💡 warning: Unused file: header.hrl
+%%^^^^^^^^^^^^^^^^^^^^^^^ 💡 weak: Unused file: header.hrl
 
 foo() -> ok.
 
@@ -611,7 +655,7 @@ foo() -> ok.
 //- /src/main.erl
 -module(main).
 -incl~ude("header.hrl").
-%%<^^^^^^^^^^^^^^^^^^^^^ 💡 warning: Unused file: header.hrl
+%%<^^^^^^^^^^^^^^^^^^^^^ 💡 weak: Unused file: header.hrl
 
 foo() -> ok.
 
@@ -654,6 +698,43 @@ foo(?RECORD_NAME) -> ok.
 
 //- /opt/lib/common_test-1.27.1/include/ct.hrl otp_app:/opt/lib/common_test-1.27.1
 //- /src/header.hrl
+"#,
+        )
+    }
+
+    #[test]
+    fn not_reported_when_included_by_another_header() {
+        check_diagnostics(
+            r#"
+//- /src/main.erl
+-module(main).
+-include("outer.hrl").
+-include("inner.hrl").
+
+foo() -> ?OUTER.
+
+//- /src/outer.hrl
+-include("inner.hrl").
+-define(OUTER, outer).
+
+//- /src/inner.hrl
+-define(INNER, inner).
+"#,
+        )
+    }
+
+    #[test]
+    fn macro_used_in_guard() {
+        check_diagnostics(
+            r#"
+//- /src/main.erl
+-module(main).
+-include("header.hrl").
+
+foo(X) when ?IS_OK(X) -> ok.
+
+//- /src/header.hrl
+-define(IS_OK(X), X =:= ok).
 "#,
         )
     }
@@ -31,7 +31,8 @@ pub(crate) static DESCRIPTOR: DiagnosticDescriptor = DiagnosticDescriptor {
         include_tests: true,
         default_disabled: false,
     },
-    checker: &|diags, sema, file_id, file_kind| {
+    codes: &[DiagnosticCode::UnusedRecordField],
+    checker: &|diags, sema, file_id, file_kind, _config| {
         unused_record_field(diags, sema, file_id, file_kind);
     },
 };
@@ -0,0 +1,256 @@
+/*
+ * Copyright (c) Meta Platforms, Inc. and affiliates.
+ *
+ * This source code is dual-licensed under either the MIT license found in the
+ * LICENSE-MIT file in the root directory of this source tree or the Apache
+ * License, Version 2.0 found in the LICENSE-APACHE file in the root directory
+ * of this source tree. You may select, at your option, one of the
+ * above-listed licenses.
+ */
+
+//! Lint: use_exact_equality
+//!
+//! Suggest `=:=`/`=/=` instead of `==`/`/=` when both operands are statically
+//! known to be non-numeric terms, such as atoms, tuples or lists of atoms.
+//!
+//! e.g.
+//!
+//! ```ignore
+//! Status == {error, not_found}
+//! ```
+//!
+//! becomes
+//!
+//! ```ignore
+//! Status =:= {error, not_found}
+//! ```
+
+use elp_ide_db::DiagnosticCode;
+use elp_ide_db::elp_base_db::FileId;
+use elp_ide_db::source_change::SourceChange;
+use elp_syntax::ast;
+use elp_syntax::ast::BinaryOp;
+use elp_syntax::ast::CompOp;
+use elp_text_edit::TextEdit;
+use hir::AnyExpr;
+use hir::AnyExprId;
+use hir::Body;
+use hir::BodySourceMap;
+use hir::Expr;
+use hir::ExprId;
+use hir::FunctionDef;
+use hir::Literal;
+use hir::Semantic;
+use hir::Strategy;
+use hir::fold::MacroStrategy;
+use hir::fold::ParenStrategy;
+
+use super::Diagnostic;
+use super::DiagnosticConditions;
+use super::DiagnosticDescriptor;
+use super::Severity;
+use crate::fix;
+
+const DIAGNOSTIC_CODE: DiagnosticCode = DiagnosticCode::UseExactEquality;
+const DIAGNOSTIC_SEVERITY: Severity = Severity::Warning;
+
+pub(crate) static DESCRIPTOR: DiagnosticDescriptor = DiagnosticDescriptor {
+    conditions: DiagnosticConditions {
+        experimental: false,
+        include_generated: false,
+        include_tests: true,
+        default_disabled: true,
+    },
+    codes: &[DiagnosticCode::UseExactEquality],
+    checker: &|diagnostics, sema, file_id, _ext, _config| {
+        sema.for_each_function(file_id, |def| {
+            check_function(diagnostics, sema, file_id, def)
+        });
+    },
+};
+
+fn check_function(
+    diagnostics: &mut Vec<Diagnostic>,
+    sema: &Semantic,
+    file_id: FileId,
+    def: &FunctionDef,
+) {
+    let def_fb = def.in_function_body(sema, def);
+    def_fb.fold_function(
+        Strategy {
+            macros: MacroStrategy::DoNotExpand,
+            parens: ParenStrategy::InvisibleParens,
+        },
+        (),
+        &mut |_acc, clause_id, ctx| {
+            if let AnyExpr::Expr(Expr::BinaryOp {
+                lhs,
+                rhs,
+                op:
+                    BinaryOp::CompOp(CompOp::Eq {
+                        strict: false,
+                        negated,
+                    }),
+            }) = ctx.item
+            {
+                let body = def_fb.body(clause_id);
+                if is_non_numeric(&body, lhs) && is_non_numeric(&body, rhs) {
+                    let map = def_fb.get_body_map(clause_id);
+                    if let Some(diagnostic) =
+                        make_diagnostic(sema, file_id, &map, ctx.item_id, negated)
+                    {
+                        diagnostics.push(diagnostic);
+                    }
+                }
+            };
+        },
+    )
+}
+
+/// A term is non-numeric if it cannot contain an integer or a float, so
+/// comparing it with `==` is always equivalent to comparing it with `=:=`.
+pub(crate) fn is_non_numeric(body: &Body, expr_id: ExprId) -> bool {
+    match &body[expr_id] {
+        Expr::Literal(Literal::Atom(_)) => true,
+        Expr::Tuple { exprs } => exprs.iter().all(|e| is_non_numeric(body, *e)),
+        Expr::List { exprs, tail } => {
+            exprs.iter().all(|e| is_non_numeric(body, *e))
+                && tail.is_none_or(|t| is_non_numeric(body, t))
+        }
+        Expr::Record { fields, .. } => fields.iter().all(|(_, e)| is_non_numeric(body, *e)),
+        _ => false,
+    }
+}
+
+fn make_diagnostic(
+    sema: &Semantic,
+    file_id: FileId,
+    map: &BodySourceMap,
+    item_id: AnyExprId,
+    negated: bool,
+) -> Option<Diagnostic> {
+    let expr_id = match item_id {
+        AnyExprId::Expr(expr_id) => Some(expr_id),
+        _ => None,
+    }?;
+    let ast_ptr = map.expr(expr_id)?;
+    if ast_ptr.file_id() != file_id {
+        return None;
+    }
+    match &ast_ptr.to_ast(sema.db) {
+        ast::Expr::BinaryOpExpr(binop) => {
+            let (_op, token) = binop.op()?;
+            let range = token.text_range();
+            let (from, to) = if negated {
+                ("/=", "=/=")
+            } else {
+                ("==", "=:=")
+            };
+            let edit = TextEdit::replace(range, to.to_string());
+            let message = format!("Use the exact comparison `{to}` instead of `{from}`.");
+            let diagnostic = Diagnostic::new(DIAGNOSTIC_CODE, message, range)
+                .with_severity(DIAGNOSTIC_SEVERITY)
+                .with_fixes(Some(vec![fix(
+                    "use_exact_equality",
+                    &format!("Replace `{from}` with `{to}`"),
+                    SourceChange::from_text_edit(file_id, edit),
+                    range,
+                )]))
+                .with_ignore_fix(sema, file_id);
+            Some(diagnostic)
+        }
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use elp_ide_db::DiagnosticCode;
+    use expect_test::Expect;
+    use expect_test::expect;
+
+    use crate::diagnostics::DiagnosticsConfig;
+    use crate::tests;
+
+    fn config() -> DiagnosticsConfig {
+        DiagnosticsConfig::default().enable(DiagnosticCode::UseExactEquality)
+    }
+
+    #[track_caller]
+    fn check_diagnostics(fixture: &str) {
+        tests::check_diagnostics_with_config(config(), fixture);
+    }
+
+    #[track_caller]
+    fn check_fix(before: &str, after: Expect) {
+        tests::check_fix_with_config(config(), before, after);
+    }
+
+    #[test]
+    fn not_enabled_by_default() {
+        tests::check_diagnostics(
+            r#"
+              -module(main).
+              foo() -> a == b.
+            "#,
+        )
+    }
+
+    #[test]
+    fn flags_non_numeric_operands() {
+        check_diagnostics(
+            r#"
+              -module(main).
+              -record(rec, {a, b}).
+              atoms() -> a == b.
+              %%           ^^ 💡 warning: Use the exact comparison `=:=` instead of `==`.
+              tuples() -> {ok, a} /= {error, [b, c]}.
+              %%                  ^^ 💡 warning: Use the exact comparison `=/=` instead of `/=`.
+              records() -> #rec{a = x} == #rec{a = y, b = z}.
+              %%                       ^^ 💡 warning: Use the exact comparison `=:=` instead of `==`.
+            "#,
+        )
+    }
+
+    #[test]
+    fn ignores_possibly_numeric_operands() {
+        check_diagnostics(
+            r#"
+              -module(main).
+              vars(X, Y) -> X == Y.
+              var_atom(X) -> X == a.
+              numbers() -> 1 == 1.0.
+              tuples() -> {ok, 1} == {ok, a}.
+              exact() -> a =:= b.
+            "#,
+        )
+    }
+
+    #[test]
+    fn fix_equal() {
+        check_fix(
+            r#"
+              -module(main).
+              foo() -> {a, b} =~= {a, c}.
+            "#,
+            expect![[r#"
+              -module(main).
+              foo() -> {a, b} =:= {a, c}.
+            "#]],
+        )
+    }
+
+    #[test]
+    fn fix_not_equal() {
+        check_fix(
+            r#"
+              -module(main).
+              foo() -> [a, b] ~/= [].
+            "#,
+            expect![[r#"
+              -module(main).
+              foo() -> [a, b] =/= [].
+            "#]],
+        )
+    }
+}
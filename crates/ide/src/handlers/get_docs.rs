@@ -22,3 +22,100 @@ pub(crate) fn get_doc_for_token(
     let docs = elp_ide_db::docs::Documentation::new(db, sema);
     Doc::from_reference(&docs, token)
 }
+
+#[cfg(test)]
+mod tests {
+    use elp_ide_completion::CompletionConfig;
+    use expect_test::Expect;
+    use expect_test::expect;
+
+    use crate::fixture;
+
+    /// Resolve the documentation of a completion, as done by the language
+    /// server when the client resolves a completion item.
+    #[track_caller]
+    fn check_completion_doc(fixture: &str, label: &str, expect: Expect) {
+        let (analysis, position, _) = fixture::position(fixture);
+        let completion = analysis
+            .completions(&CompletionConfig::default(), position, None)
+            .unwrap()
+            .into_iter()
+            .find(|c| c.label == label)
+            .unwrap_or_else(|| panic!("no completion for {label}"));
+        let doc = analysis
+            .get_docs_at_position(completion.position.expect("no position"))
+            .unwrap()
+            .and_then(|result| result.doc)
+            .expect("no documentation");
+        expect.assert_eq(doc.markdown_text());
+    }
+
+    #[test]
+    fn completion_doc_from_doc_attribute() {
+        check_completion_doc(
+            r#"
+-module(main).
+-doc """
+Add `This` to `That`.
+
+Returns the sum.
+""".
+-spec add(integer(), integer()) -> integer().
+add(This, That) -> This + That.
+
+main() -> ad~.
+"#,
+            "add/2",
+            expect![[r#"
+                ```erlang
+                -spec add(integer(), integer()) -> integer().
+                ```
+
+                -----
+
+                Add `This` to `That`.
+
+                Returns the sum."#]],
+        );
+    }
+
+    #[test]
+    fn completion_doc_from_doc_attribute_string() {
+        check_completion_doc(
+            r#"
+-module(main).
+-doc("Greet \"World\".").
+hello() -> ok.
+
+main() -> hel~.
+"#,
+            "hello/0",
+            expect![[r#"Greet "World"."#]],
+        );
+    }
+
+    #[test]
+    fn completion_doc_of_type_not_on_next_function() {
+        let (analysis, position, _) = fixture::position(
+            r#"
+-module(main).
+-doc("A colour.").
+-type colour() :: red | green.
+hello() -> ok.
+
+main() -> hel~.
+"#,
+        );
+        let completion = analysis
+            .completions(&CompletionConfig::default(), position, None)
+            .unwrap()
+            .into_iter()
+            .find(|c| c.label == "hello/0")
+            .expect("no completion for hello/0");
+        let doc = analysis
+            .get_docs_at_position(completion.position.expect("no position"))
+            .unwrap()
+            .and_then(|result| result.doc);
+        assert!(doc.is_none());
+    }
+}
@@ -0,0 +1,153 @@
+/*
+ * Copyright (c) Meta Platforms, Inc. and affiliates.
+ *
+ * This source code is dual-licensed under either the MIT license found in the
+ * LICENSE-MIT file in the root directory of this source tree or the Apache
+ * License, Version 2.0 found in the LICENSE-APACHE file in the root directory
+ * of this source tree. You may select, at your option, one of the
+ * above-listed licenses.
+ */
+
+use elp_ide_db::ReferenceClass;
+use elp_ide_db::RootDatabase;
+use elp_ide_db::SymbolClass;
+use elp_ide_db::SymbolDefinition;
+use elp_ide_db::SymbolKind;
+use elp_ide_db::elp_base_db::FilePosition;
+use elp_ide_db::find_best_token;
+use elp_syntax::AstNode;
+use elp_syntax::SmolStr;
+use hir::InFile;
+use hir::Semantic;
+use hir::SpecId;
+
+use crate::RangeInfo;
+use crate::navigation_target::NavigationTarget;
+
+/// Navigate to the `-spec` of the called function when on a call, or else
+/// of the function being defined.
+pub(crate) fn goto_spec(
+    db: &RootDatabase,
+    position: FilePosition,
+) -> Option<RangeInfo<Vec<NavigationTarget>>> {
+    let sema = Semantic::new(db);
+    let token = find_best_token(&sema, position)?;
+    let spec_id = match SymbolClass::classify(&sema, token.clone()) {
+        Some(SymbolClass::Reference {
+            refs: ReferenceClass::Definition(SymbolDefinition::Function(def)),
+            ..
+        }) => {
+            let spec = def.spec?;
+            InFile::new(spec.file.file_id, spec.spec_id)
+        }
+        _ => sema.find_enclosing_spec(position.file_id, &token.value.parent()?)?,
+    };
+    let targets = vec![spec_nav(&sema, spec_id)];
+    Some(RangeInfo::new(token.value.text_range(), targets))
+}
+
+fn spec_nav(sema: &Semantic, spec_id: InFile<SpecId>) -> NavigationTarget {
+    let form_list = sema.form_list(spec_id.file_id);
+    let spec = &form_list[spec_id.value];
+    let source = sema.parse(spec_id.file_id).value;
+    let ast_spec = spec.form_id.get(&source);
+    let full_range = ast_spec.syntax().text_range();
+    let focus_range = ast_spec.fun().map(|name| name.syntax().text_range());
+    NavigationTarget {
+        file_id: spec_id.file_id,
+        full_range,
+        focus_range,
+        highlight_ranges: Some(vec![focus_range.unwrap_or(full_range)]),
+        name: SmolStr::new(format!("{}/{}", spec.name.name().raw(), spec.name.arity())),
+        kind: SymbolKind::Function,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::fixture;
+    use crate::tests::check_navs;
+    use crate::tests::check_no_parse_errors;
+
+    #[track_caller]
+    fn check(fixture: &str) {
+        let (analysis, fixture) = fixture::with_fixture(fixture);
+        let expected = fixture.annotations();
+        check_no_parse_errors(&analysis, fixture.file_id());
+
+        let navs = analysis
+            .goto_spec(fixture.position())
+            .unwrap()
+            .expect("no spec found")
+            .info;
+        check_navs(navs, expected);
+    }
+
+    #[track_caller]
+    fn check_unresolved(fixture: &str) {
+        let (analysis, position, _) = fixture::position(fixture);
+        check_no_parse_errors(&analysis, position.file_id);
+
+        if let Some(navs) = analysis.goto_spec(position).unwrap() {
+            panic!("didn't expect this to resolve anywhere: {navs:?}")
+        }
+    }
+
+    #[test]
+    fn from_function_definition() {
+        check(
+            r#"
+//- /src/main.erl
+-module(main).
+
+-spec foo(integer()) -> integer().
+%%    ^^^
+foo(X) ->
+    X + ~1.
+"#,
+        );
+    }
+
+    #[test]
+    fn from_call() {
+        check(
+            r#"
+//- /src/main.erl
+-module(main).
+
+bar() -> other:f~oo(1).
+
+//- /src/other.erl
+-module(other).
+-export([foo/1]).
+-spec foo(integer()) -> integer().
+%%    ^^^
+foo(X) -> X.
+"#,
+        );
+    }
+
+    #[test]
+    fn without_spec() {
+        check_unresolved(
+            r#"
+//- /src/main.erl
+-module(main).
+
+foo(X) ->
+    X + ~1.
+"#,
+        );
+        check_unresolved(
+            r#"
+//- /src/main.erl
+-module(main).
+
+bar() -> ok.
+
+-spec foo() -> ok.
+foo() -> b~ar().
+"#,
+        );
+    }
+}
@@ -10,5 +10,6 @@
 
 pub mod get_docs;
 pub mod goto_definition;
+pub mod goto_spec;
 pub mod goto_type_definition;
 pub mod references;
@@ -8,11 +8,27 @@
  * above-listed licenses.
  */
 
+use elp_ide_db::EqwalizerDatabase;
 use elp_ide_db::RootDatabase;
 use elp_ide_db::elp_base_db::FilePosition;
+use elp_ide_db::elp_base_db::FileRange;
+use elp_ide_db::elp_base_db::SourceDatabaseExt;
+use elp_ide_db::eqwalizer::type_references;
+use elp_ide_db::find_best_token;
+use elp_syntax::AstNode;
+use elp_syntax::SmolStr;
+use elp_syntax::SyntaxToken;
+use elp_syntax::TextRange;
+use elp_syntax::ast;
+use hir::InFile;
+use hir::MacroCallDef;
+use hir::Semantic;
 
+use crate::DocResult;
 use crate::doc_links::DocLink;
 use crate::doc_links::external_docs;
+use crate::expand_macro::expand_macro;
+use crate::handlers::get_docs::get_doc_for_token;
 
 #[derive(Clone, Debug, PartialEq, Eq)]
 pub struct HoverActionsConfig {
@@ -36,6 +52,149 @@ pub(crate) fn actions(
     res
 }
 
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct HoverConfig {
+    /// Include the eqwalizer type of the expression under the cursor.
+    pub types: bool,
+}
+
+/// Everything known about the symbol under the cursor, ready to be shown
+/// in a hover popup.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct HoverResult {
+    /// The eqwalizer type, macro definition, `-spec` and documentation,
+    /// as separate markdown sections.
+    pub markdown: String,
+    /// The range of the hovered expression or token.
+    pub range: Option<FileRange>,
+    /// The types referenced by the eqwalizer type, with their definitions.
+    pub type_references: Vec<(SmolStr, FileRange)>,
+    /// Links to external documentation, e.g. for OTP functions.
+    pub links: Vec<DocLink>,
+    /// The documentation lookup for the token under the cursor.
+    pub doc_result: Option<DocResult>,
+}
+
+pub(crate) fn hover(
+    db: &RootDatabase,
+    position: FilePosition,
+    config: &HoverConfig,
+) -> Option<HoverResult> {
+    let sema = Semantic::new(db);
+    let mut sections = Vec::new();
+    let mut range: Option<FileRange> = None;
+    let mut type_references = Vec::new();
+    if config.types
+        && let Some((type_section, type_range, refs)) = eqwalizer_type(db, position)
+    {
+        sections.push(type_section);
+        range = Some(type_range);
+        type_references = refs;
+    }
+    let mut doc_result = None;
+    if let Some(token) = find_best_token(&sema, position) {
+        let token_range = FileRange {
+            file_id: token.file_id,
+            range: token.value.text_range(),
+        };
+        match macro_definition(db, &sema, position, &token) {
+            Some(macro_section) => sections.push(macro_section),
+            // e.g. a predefined macro such as `?MODULE`
+            None => {
+                if let Some(expanded) = expand_macro(db, position)
+                    && !POSITION_DEPENDENT_MACROS.contains(&expanded.name.as_str())
+                {
+                    sections.push(format!(
+                        "{}\n\n```erlang\n{}\n```",
+                        expanded.name,
+                        expanded.expansion.trim()
+                    ));
+                }
+            }
+        }
+        let doc = get_doc_for_token(db, &sema, &token);
+        if let Some(doc) = &doc {
+            sections.push(doc.markdown_text().to_string());
+            range = Some(match range {
+                Some(range) if range.file_id == token_range.file_id => FileRange {
+                    file_id: range.file_id,
+                    range: range.range.cover(token_range.range),
+                },
+                _ => token_range,
+            });
+        }
+        doc_result = Some(DocResult {
+            doc,
+            token_kind: token.value.kind(),
+            token_text: token.value.text().to_string(),
+            token_range,
+        });
+    }
+    let links = external_docs(db, &position).unwrap_or_default();
+    if sections.is_empty() && links.is_empty() {
+        return None;
+    }
+    Some(HoverResult {
+        markdown: sections.join("\n\n---\n\n"),
+        range,
+        type_references,
+        links,
+        doc_result,
+    })
+}
+
+/// Predefined macros whose expansion depends on where they are used. We only
+/// lower them to a dummy value, so showing it on hover would be misleading.
+const POSITION_DEPENDENT_MACROS: &[&str] = &["LINE"];
+
+/// The eqwalizer type of the expression at the cursor, with its range and
+/// the types it references.
+fn eqwalizer_type(
+    db: &RootDatabase,
+    position: FilePosition,
+) -> Option<(String, FileRange, Vec<(SmolStr, FileRange)>)> {
+    let query_range = FileRange {
+        file_id: position.file_id,
+        range: TextRange::empty(position.offset),
+    };
+    let type_info = db.type_at_position(query_range)?;
+    let (ty, range) = &*type_info;
+    let text = &db.file_text(range.file_id)[range.range];
+    let refs = type_references(db, range.file_id, ty);
+    Some((format!("```erlang\n{text} :: {ty}\n```"), *range, refs))
+}
+
+/// The definition of the macro called at the cursor and, for a macro
+/// taking arguments, its expansion with the actual arguments.
+fn macro_definition(
+    db: &RootDatabase,
+    sema: &Semantic,
+    position: FilePosition,
+    token: &InFile<SyntaxToken>,
+) -> Option<String> {
+    let call = token
+        .value
+        .parent_ancestors()
+        .find_map(ast::MacroCallExpr::cast)?;
+    if !call.name()?.syntax().text_range().contains(position.offset) {
+        return None;
+    }
+    let MacroCallDef::Macro(define) = sema.to_def(token.with_value(&call))? else {
+        return None;
+    };
+    let definition = define.source(db.upcast()).syntax().text().to_string();
+    let mut section = format!("```erlang\n{definition}\n```");
+    if call.args().is_some()
+        && let Some(expanded) = expand_macro(db, position)
+    {
+        section.push_str(&format!(
+            "\n\nExpands to:\n\n```erlang\n{}\n```",
+            expanded.expansion.trim()
+        ));
+    }
+    Some(section)
+}
+
 fn doc_links_actions(res: &mut Vec<HoverAction>, db: &RootDatabase, position: FilePosition) {
     let doc_links = external_docs(db, &position).unwrap_or_default();
     for doc_link in doc_links {
@@ -46,8 +205,14 @@ fn doc_links_actions(res: &mut Vec<HoverAction>, db: &RootDatabase, position: Fi
 #[cfg(test)]
 mod tests {
 
+    use elp_project_model::otp::otp_supported_by_eqwalizer;
+    use expect_test::Expect;
+    use expect_test::expect;
+
+    use crate::DocResult;
     use crate::HoverAction;
     use crate::HoverActionsConfig;
+    use crate::HoverConfig;
     use crate::doc_links::DocLink;
     use crate::fixture;
 
@@ -83,4 +248,172 @@ main() ->
             })],
         );
     }
+
+    #[track_caller]
+    fn check_hover(fixture: &str, expect: Expect) {
+        let (analysis, position, _) = fixture::position(fixture);
+        let hover = analysis
+            .hover(position, &HoverConfig { types: true })
+            .unwrap()
+            .unwrap();
+        let links = hover
+            .links
+            .iter()
+            .map(|link| format!("[{}]({})", link.title, link.uri))
+            .collect::<Vec<_>>()
+            .join("\n");
+        expect.assert_eq(&format!("{}\n\nLinks:\n{}", hover.markdown, links));
+    }
+
+    #[test]
+    fn hover_local_function_with_spec() {
+        check_hover(
+            r#"
+//- /src/main.erl
+-module(main).
+-spec foo(integer()) -> ok.
+foo(_) -> ok.
+main() ->
+  f~oo(1).
+    "#,
+            expect![[r#"
+                ```erlang
+                -spec foo(integer()) -> ok.
+                ```
+
+                Links:
+            "#]],
+        );
+    }
+
+    #[test]
+    fn hover_otp_function() {
+        check_hover(
+            r#"
+//- /opt/lib/stdlib-3.17/src/lists.erl otp_app:/opt/lib/stdlib-3.17
+-module(lists).
+-export([reverse/1]).
+-spec reverse(list()) -> list().
+reverse([]) -> [].
+
+//- /src/main.erl
+-module(main).
+main() ->
+  lists:rev~erse([]).
+    "#,
+            expect![[r#"
+                ```erlang
+                -spec reverse(list()) -> list().
+                ```
+
+                Links:
+                [lists:reverse/1](https://erlang.org/doc/man/lists.html#reverse/1)"#]],
+        );
+    }
+
+    #[test]
+    fn hover_object_like_macro() {
+        check_hover(
+            r#"
+//- /src/main.erl
+-module(main).
+-define(TIMEOUT, 5000).
+main() ->
+  ?TIME~OUT.
+    "#,
+            expect![[r#"
+                ```erlang
+                -define(TIMEOUT, 5000).
+                ```
+
+                Links:
+            "#]],
+        );
+    }
+
+    #[test]
+    fn hover_predefined_macro() {
+        check_hover(
+            r#"
+//- /src/main.erl
+-module(main).
+main() ->
+  ?MOD~ULE.
+    "#,
+            expect![[r#"
+                MODULE
+
+                ```erlang
+                main
+                ```
+
+                Links:
+            "#]],
+        );
+    }
+
+    #[test]
+    fn hover_line_macro_not_expanded() {
+        let (analysis, position, _) = fixture::position(
+            r#"
+//- /src/main.erl
+-module(main).
+main() ->
+  ?LI~NE.
+    "#,
+        );
+        let hover = analysis
+            .hover(position, &HoverConfig { types: true })
+            .unwrap();
+        assert!(hover.is_none());
+    }
+
+    #[test]
+    fn hover_function_like_macro() {
+        check_hover(
+            r#"
+//- /src/main.erl
+-module(main).
+-define(FOO(X, Y), foo + X + Y + 1).
+main() ->
+  ?F~OO(4, 5).
+    "#,
+            expect![[r#"
+                ```erlang
+                -define(FOO(X, Y), foo + X + Y + 1).
+                ```
+
+                Expands to:
+
+                ```erlang
+                (((foo + 4) + 5) + 1)
+                ```
+
+                Links:
+            "#]],
+        );
+    }
+
+    #[test]
+    fn hover_variable_type() {
+        if otp_supported_by_eqwalizer() {
+            check_hover(
+                r#"
+//- eqwalizer
+//- /play/src/main.erl app:play
+-module(main).
+-spec foo(atom()) -> atom().
+foo(Name) ->
+  Na~me.
+    "#,
+                expect![[r#"
+                    ```erlang
+                    Name :: atom()
+                    ```
+
+                    Links:
+                "#]],
+            );
+        }
+    }
 }
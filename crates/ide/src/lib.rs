@@ -26,6 +26,7 @@ use elp_ide_assists::AssistId;
 use elp_ide_assists::AssistKind;
 use elp_ide_assists::AssistResolveStrategy;
 use elp_ide_completion::Completion;
+use elp_ide_completion::CompletionConfig;
 use elp_ide_db::Eqwalizer;
 use elp_ide_db::EqwalizerDatabase;
 use elp_ide_db::EqwalizerDiagnostics;
@@ -72,6 +73,7 @@ use fxhash::FxHashMap;
 use fxhash::FxHashSet;
 use handlers::get_docs;
 use handlers::goto_definition;
+use handlers::goto_spec;
 use handlers::goto_type_definition;
 use handlers::references;
 use hir::DefMap;
@@ -132,6 +134,8 @@ pub use handlers::references::ReferenceSearchResult;
 pub use highlight_related::HighlightedRange;
 pub use hover::HoverAction;
 pub use hover::HoverActionsConfig;
+pub use hover::HoverConfig;
+pub use hover::HoverResult;
 pub use inlay_hints::InlayHint;
 pub use inlay_hints::InlayHintLabel;
 pub use inlay_hints::InlayHintLabelPart;
@@ -170,6 +174,7 @@ pub struct AnalysisHost {
     db: RootDatabase,
 }
 
+#[derive(Debug, Clone, PartialEq, Eq)]
 pub struct DocResult {
     pub doc: Option<Doc>,
     pub token_kind: SyntaxKind,
@@ -596,6 +601,13 @@ impl Analysis {
         self.with_db(|db| goto_definition::goto_definition(db, position))
     }
 
+    pub fn goto_spec(
+        &self,
+        position: FilePosition,
+    ) -> Cancellable<Option<RangeInfo<Vec<NavigationTarget>>>> {
+        self.with_db(|db| goto_spec::goto_spec(db, position))
+    }
+
     pub fn goto_type_definition(
         &self,
         position: FilePosition,
@@ -638,6 +650,16 @@ impl Analysis {
         })
     }
 
+    /// Returns the combined eqwalizer type, spec, docs and documentation
+    /// links for the symbol at the given position
+    pub fn hover(
+        &self,
+        position: FilePosition,
+        config: &HoverConfig,
+    ) -> Cancellable<Option<HoverResult>> {
+        self.with_db(|db| hover::hover(db, position, config))
+    }
+
     /// Returns available hover actions (rendered as buttons)
     pub fn hover_actions(
         &self,
@@ -657,10 +679,11 @@ impl Analysis {
 
     pub fn completions(
         &self,
+        config: &CompletionConfig,
         position: FilePosition,
         trigger_character: Option<char>,
     ) -> Cancellable<Vec<Completion>> {
-        self.with_db(|db| elp_ide_completion::completions(db, position, trigger_character))
+        self.with_db(|db| elp_ide_completion::completions(db, config, position, trigger_character))
     }
 
     pub fn resolved_includes(&self, file_id: FileId) -> Cancellable<Option<Includes>> {
@@ -15,6 +15,7 @@
 //! assists if we are allowed to.
 
 use elp_ide_db::helpers::SnippetCap;
+use fxhash::FxHashSet;
 
 use crate::AssistKind;
 
@@ -22,4 +23,7 @@ use crate::AssistKind;
 pub struct AssistConfig {
     pub snippet_cap: Option<SnippetCap>,
     pub allowed: Option<Vec<AssistKind>>,
+    /// Ids of individual assists to never offer, e.g. `flip_sep`,
+    /// regardless of their kind.
+    pub disabled_ids: FxHashSet<String>,
 }
@@ -20,6 +20,7 @@ use elp_ide_db::elp_base_db::FileRange;
 use elp_ide_db::elp_base_db::SourceDatabase;
 use elp_ide_db::source_change::SourceChangeBuilder;
 use elp_syntax::Direction;
+use elp_syntax::NodeOrToken;
 use elp_syntax::SourceFile;
 use elp_syntax::SyntaxElement;
 use elp_syntax::SyntaxKind;
@@ -29,6 +30,7 @@ use elp_syntax::TextRange;
 use elp_syntax::TextSize;
 use elp_syntax::TokenAtOffset;
 use elp_syntax::algo;
+use elp_syntax::ast;
 use elp_syntax::ast::AstNode;
 use elp_syntax::label::Label;
 use fxhash::FxHashSet;
@@ -213,6 +215,27 @@ impl<'a> AssistContext<'a> {
             .covering_element(self.selection_trimmed())
     }
 
+    /// Returns the expression exactly covered by the selection range,
+    /// ignoring surrounding whitespace, or `None` if the selection is
+    /// empty or does not cover a whole expression.
+    #[allow(dead_code)]
+    pub(crate) fn selected_expr(&self) -> Option<ast::Expr> {
+        let range = self.selection_trimmed();
+        if range.is_empty() {
+            return None;
+        }
+        let node = match self.covering_element() {
+            NodeOrToken::Node(node) => node,
+            NodeOrToken::Token(token) => token.parent()?,
+        };
+        // Take the outermost one, should several expressions share the
+        // range
+        node.ancestors()
+            .take_while(|node| node.text_range() == range)
+            .filter_map(ast::Expr::cast)
+            .last()
+    }
+
     pub(crate) fn classify_offset(&self) -> Option<SymbolClass> {
         let token = self
             .source_file
@@ -305,6 +328,7 @@ pub(crate) struct Assists {
     resolve: AssistResolveStrategy,
     buf: Vec<Assist>,
     allowed: Option<Vec<AssistKind>>,
+    disabled_ids: FxHashSet<String>,
 }
 
 impl Assists {
@@ -314,6 +338,7 @@ impl Assists {
             file: ctx.frange.file_id,
             buf: Vec::new(),
             allowed: ctx.config.allowed.clone(),
+            disabled_ids: ctx.config.disabled_ids.clone(),
         }
     }
 
@@ -402,6 +427,9 @@ impl Assists {
     }
 
     fn is_allowed(&self, id: &AssistId) -> bool {
+        if self.disabled_ids.contains(id.0) {
+            return false;
+        }
         match &self.allowed {
             Some(allowed) => allowed.iter().any(|kind| kind.contains(id.1)),
             None => true,
@@ -12,9 +12,11 @@ use std::fmt::Write;
 
 use elp_ide_db::assists::AssistId;
 use elp_ide_db::assists::AssistKind;
+use elp_ide_db::elp_base_db::SourceDatabase;
 use elp_syntax::AstNode;
 use elp_syntax::ast;
 use elp_text_edit::TextSize;
+use hir::FormIdx;
 use hir::FunctionDef;
 
 use crate::AssistContext;
@@ -26,7 +28,9 @@ const ARG_TEXT: &str = "Argument description";
 
 // Assist: add_doc
 //
-// Adds an -doc attribute above a function, if it doesn't already have one.
+// Adds an -doc attribute above a function or type, if it doesn't already
+// have one. Not offered when the project uses an OTP older than 27, which
+// introduced -doc attributes.
 //
 // ```
 // foo(Arg1) -> ok.
@@ -40,11 +44,32 @@ const ARG_TEXT: &str = "Argument description";
 // foo(Arg1) -> ok.
 // ```
 pub(crate) fn add_doc(acc: &mut Assists, ctx: &AssistContext) -> Option<()> {
+    let db = ctx.db();
+    let project_id = db.file_app_data(ctx.file_id())?.project_id;
+    if !supports_doc_attributes(db.project_data(project_id).otp_release.as_deref()) {
+        return None;
+    }
     let name = match ctx.find_node_at_offset::<ast::Name>()? {
         ast::Name::Atom(name) => name,
         ast::Name::MacroCallExpr(_) | ast::Name::Var(_) => return None,
     };
-    let clause = ast::FunctionClause::cast(name.syntax().parent()?)?;
+    let parent = name.syntax().parent()?;
+    if let Some(clause) = ast::FunctionClause::cast(parent.clone()) {
+        add_function_doc(acc, ctx, &name, clause)
+    } else if ast::TypeName::can_cast(parent.kind()) {
+        let form = ast::Form::cast(parent.parent()?)?;
+        add_type_doc(acc, ctx, &name, form)
+    } else {
+        None
+    }
+}
+
+fn add_function_doc(
+    acc: &mut Assists,
+    ctx: &AssistContext,
+    name: &ast::Atom,
+    clause: ast::FunctionClause,
+) -> Option<()> {
     let function = ast::FunDecl::cast(clause.syntax().parent()?)?;
 
     let file_id = ctx.file_id();
@@ -74,14 +99,7 @@ pub(crate) fn add_doc(acc: &mut Assists, ctx: &AssistContext) -> Option<()> {
                 .map(|(arg_idx, expr)| arg_name(arg_idx + 1, expr));
 
             let mut idx = 1;
-            let header = match ctx.config.snippet_cap {
-                Some(_cap) => {
-                    format!("-doc \"\"\"\n${{{idx}:{DEFAULT_TEXT}}}\n\"\"\".")
-                }
-                None => {
-                    format!("-doc \"\"\"\n{DEFAULT_TEXT}\n\"\"\".")
-                }
-            };
+            let header = doc_header(ctx);
             let params = arg_names.fold(String::new(), |mut output, arg_name| {
                 idx += 1;
                 match ctx.config.snippet_cap {
@@ -114,6 +132,76 @@ pub(crate) fn add_doc(acc: &mut Assists, ctx: &AssistContext) -> Option<()> {
     )
 }
 
+fn add_type_doc(
+    acc: &mut Assists,
+    ctx: &AssistContext,
+    name: &ast::Atom,
+    form: ast::Form,
+) -> Option<()> {
+    if !matches!(
+        form,
+        ast::Form::TypeAlias(_) | ast::Form::Opaque(_) | ast::Form::Nominal(_)
+    ) {
+        return None;
+    }
+    let form_list = ctx.sema.form_list(ctx.file_id());
+    let form_idx = form_list.find_form(&form)?;
+    if has_doc_attribute(form_list.forms(), form_idx) {
+        return None;
+    }
+
+    let target = name.syntax().text_range();
+    let insert_offset = form.syntax().text_range().start();
+
+    acc.add(
+        AssistId("add_doc", AssistKind::Generate),
+        "Add -doc attribute",
+        None,
+        target,
+        None,
+        |builder| {
+            let text = format!("{}\n", doc_header(ctx));
+            builder.edit_file(ctx.frange.file_id);
+            match ctx.config.snippet_cap {
+                Some(cap) => {
+                    builder.insert_snippet(cap, insert_offset, text);
+                }
+                None => builder.insert(insert_offset, text),
+            }
+        },
+    )
+}
+
+/// Whether the form is preceded by a `-doc` attribute, possibly followed
+/// by doc metadata attributes, as when attaching the docs in the def map.
+fn has_doc_attribute(forms: &[FormIdx], form_idx: FormIdx) -> bool {
+    let Some(pos) = forms.iter().position(|form| *form == form_idx) else {
+        return false;
+    };
+    matches!(
+        forms[..pos]
+            .iter()
+            .rev()
+            .find(|form| !matches!(form, FormIdx::DocMetadataAttribute(_))),
+        Some(FormIdx::DocAttribute(_))
+    )
+}
+
+/// Whether an OTP release supports `-doc` attributes, assuming it does if
+/// the release is unknown.
+fn supports_doc_attributes(otp_release: Option<&str>) -> bool {
+    otp_release
+        .and_then(|release| release.parse::<u32>().ok())
+        .is_none_or(|release| release >= 27)
+}
+
+fn doc_header(ctx: &AssistContext) -> String {
+    match ctx.config.snippet_cap {
+        Some(_cap) => format!("-doc \"\"\"\n${{1:{DEFAULT_TEXT}}}\n\"\"\"."),
+        None => format!("-doc \"\"\"\n{DEFAULT_TEXT}\n\"\"\"."),
+    }
+}
+
 fn find_insert_offset(ctx: &AssistContext, def: &FunctionDef) -> Option<TextSize> {
     let db = ctx.db();
     let range = def
@@ -312,4 +400,79 @@ This is foo
             "#]],
         )
     }
+
+    #[test]
+    fn test_doc_attributes_need_otp27() {
+        assert!(!supports_doc_attributes(Some("26")));
+        assert!(supports_doc_attributes(Some("27")));
+        assert!(supports_doc_attributes(Some("100")));
+        assert!(supports_doc_attributes(None));
+    }
+
+    #[test]
+    fn test_type() {
+        check_assist(
+            add_doc,
+            "Add -doc attribute",
+            r#"
+-type ~foo() :: integer().
+"#,
+            expect![[r#"
+                -doc """
+                ${1:[How to write documentation](https://www.erlang.org/doc/system/documentation.html)}
+                """.
+                -type foo() :: integer().
+            "#]],
+        )
+    }
+
+    #[test]
+    fn test_opaque_type() {
+        check_assist(
+            add_doc,
+            "Add -doc attribute",
+            r#"
+-opaque ~foo(T) :: [T].
+"#,
+            expect![[r#"
+                -doc """
+                ${1:[How to write documentation](https://www.erlang.org/doc/system/documentation.html)}
+                """.
+                -opaque foo(T) :: [T].
+            "#]],
+        )
+    }
+
+    #[test]
+    fn test_type_already_doc_attribute() {
+        check_assist_not_applicable(
+            add_doc,
+            r#"
+-doc "A foo".
+-doc #{since => "1.0"}.
+-type ~foo() :: integer().
+"#,
+        );
+    }
+
+    #[test]
+    fn test_type_previous_has_doc_attribute() {
+        check_assist(
+            add_doc,
+            "Add -doc attribute",
+            r#"
+-doc "A bar".
+-type bar() :: atom().
+-type ~foo() :: integer().
+"#,
+            expect![[r#"
+                -doc "A bar".
+                -type bar() :: atom().
+                -doc """
+                ${1:[How to write documentation](https://www.erlang.org/doc/system/documentation.html)}
+                """.
+                -type foo() :: integer().
+            "#]],
+        )
+    }
 }
@@ -0,0 +1,229 @@
+/*
+ * Copyright (c) Meta Platforms, Inc. and affiliates.
+ *
+ * This source code is dual-licensed under either the MIT license found in the
+ * LICENSE-MIT file in the root directory of this source tree or the Apache
+ * License, Version 2.0 found in the LICENSE-APACHE file in the root directory
+ * of this source tree. You may select, at your option, one of the
+ * above-listed licenses.
+ */
+
+use elp_ide_db::SymbolClass;
+use elp_ide_db::SymbolDefinition;
+use elp_ide_db::assists::AssistId;
+use elp_ide_db::assists::AssistKind;
+use elp_ide_db::search::NameLike;
+use elp_ide_db::search::SearchScope;
+use elp_syntax::AstNode;
+use elp_syntax::TextSize;
+use elp_syntax::ast;
+use hir::NameArity;
+
+use crate::AssistContext;
+use crate::Assists;
+
+// Assist: add_type_parameter
+//
+// Add a type variable to a type declaration, passing `term()` for it in
+// every reference to the type in the module.
+//
+// ```
+// -type foo() :: ok.
+// -spec bar() -> foo().
+// ```
+// ->
+// ```
+// -type foo(T) :: ok.
+// -spec bar() -> foo(term()).
+// ```
+pub(crate) fn add_type_parameter(acc: &mut Assists, ctx: &AssistContext) -> Option<()> {
+    let Some(SymbolClass::Definition(SymbolDefinition::Type(type_alias))) = ctx.classify_offset()
+    else {
+        return None;
+    };
+    let file_id = ctx.file_id();
+    if type_alias.file.file_id != file_id {
+        return None;
+    }
+    let name_arity = type_alias.name().clone();
+    let new_name_arity = NameArity::new(name_arity.name().clone(), name_arity.arity() + 1);
+    let def_map = ctx.sema.def_map_local(file_id);
+    if def_map.get_type(&new_name_arity).is_some() {
+        return None;
+    }
+    let alias_range = type_alias.range(ctx.db().upcast())?;
+    let type_name = type_alias.source(ctx.db().upcast()).type_name()?;
+    let var_args = type_name.args()?;
+    let params: Vec<String> = var_args
+        .args()
+        .map(|var| var.syntax().text().to_string())
+        .collect();
+    let var = fresh_type_var(&params);
+    let var_offset = closing_paren_offset(var_args.syntax())?;
+
+    let scope = SearchScope::single_file(file_id, None);
+    let usages = SymbolDefinition::Type(type_alias)
+        .usages(&ctx.sema)
+        .set_scope(&scope)
+        .all();
+
+    let target = type_name.syntax().text_range();
+    acc.add(
+        AssistId("add_type_parameter", AssistKind::RefactorRewrite),
+        format!("Add type parameter to `{name_arity}`"),
+        None,
+        target,
+        None,
+        |builder| {
+            let separator = if params.is_empty() { "" } else { ", " };
+            builder.insert(var_offset, format!("{separator}{var}"));
+            for (usage_file_id, names) in usages.iter() {
+                if usage_file_id != file_id {
+                    continue;
+                }
+                for name in names {
+                    let NameLike::Name(name) = name else {
+                        continue;
+                    };
+                    // A recursive reference passes the new variable along
+                    let arg = if alias_range.contains_range(name.syntax().text_range()) {
+                        var.as_str()
+                    } else {
+                        "term()"
+                    };
+                    match Usage::find(name) {
+                        Some(Usage::Call(args)) => {
+                            let separator = if args.args().next().is_none() {
+                                ""
+                            } else {
+                                ", "
+                            };
+                            if let Some(offset) = closing_paren_offset(args.syntax()) {
+                                builder.insert(offset, format!("{separator}{arg}"));
+                            }
+                        }
+                        Some(Usage::Fa(arity)) => {
+                            builder.replace(
+                                arity.syntax().text_range(),
+                                new_name_arity.arity().to_string(),
+                            );
+                        }
+                        None => {}
+                    }
+                }
+            }
+        },
+    )
+}
+
+enum Usage {
+    /// A reference to the type, such as `foo()` or `mod:foo()`
+    Call(ast::ExprArgs),
+    /// An entry in an attribute, such as `foo/0` in `-export_type`
+    Fa(ast::Arity),
+}
+
+impl Usage {
+    fn find(name: &ast::Name) -> Option<Usage> {
+        let parent = name.syntax().parent()?;
+        if let Some(fa) = ast::Fa::cast(parent.clone()) {
+            return Some(Usage::Fa(fa.arity()?));
+        }
+        let call = match ast::Remote::cast(parent.clone()) {
+            Some(remote) => ast::Call::cast(remote.syntax().parent()?)?,
+            None => ast::Call::cast(parent)?,
+        };
+        Some(Usage::Call(call.args()?))
+    }
+}
+
+/// The first of `T`, `T1`, `T2`, ... which is not already a parameter.
+fn fresh_type_var(params: &[String]) -> String {
+    let mut var = "T".to_string();
+    let mut index = 0;
+    while params.contains(&var) {
+        index += 1;
+        var = format!("T{index}");
+    }
+    var
+}
+
+fn closing_paren_offset(args: &elp_syntax::SyntaxNode) -> Option<TextSize> {
+    let token = args.last_token()?;
+    if token.text() == ")" {
+        Some(token.text_range().start())
+    } else {
+        None
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use expect_test::expect;
+
+    use super::*;
+    use crate::tests::*;
+
+    #[test]
+    fn test_used_in_spec_and_type() {
+        check_assist(
+            add_type_parameter,
+            "Add type parameter to `foo/0`",
+            r#"
+-module(main).
+-export([f/0]).
+-export_type([foo/0]).
+
+-type f~oo() :: ok.
+-type bar() :: [foo()] | main:foo().
+
+-spec f() -> foo().
+f() -> ok.
+"#,
+            expect![[r#"
+                -module(main).
+                -export([f/0]).
+                -export_type([foo/1]).
+
+                -type foo(T) :: ok.
+                -type bar() :: [foo(term())] | main:foo(term()).
+
+                -spec f() -> foo(term()).
+                f() -> ok.
+            "#]],
+        )
+    }
+
+    #[test]
+    fn test_existing_parameters() {
+        check_assist(
+            add_type_parameter,
+            "Add type parameter to `tree/1`",
+            r#"
+-module(main).
+
+-type tr~ee(T) :: leaf | {T, tree(T), tree(T)}.
+-type ints() :: tree(integer()).
+"#,
+            expect![[r#"
+                -module(main).
+
+                -type tree(T, T1) :: leaf | {T, tree(T, T1), tree(T, T1)}.
+                -type ints() :: tree(integer(), term()).
+            "#]],
+        )
+    }
+
+    #[test]
+    fn test_arity_taken() {
+        check_assist_not_applicable(
+            add_type_parameter,
+            r#"
+-module(main).
+
+-type f~oo() :: ok.
+-type foo(T) :: T.
+"#,
+        );
+    }
+}
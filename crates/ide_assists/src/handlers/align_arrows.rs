@@ -0,0 +1,290 @@
+/*
+ * Copyright (c) Meta Platforms, Inc. and affiliates.
+ *
+ * This source code is dual-licensed under either the MIT license found in the
+ * LICENSE-MIT file in the root directory of this source tree or the Apache
+ * License, Version 2.0 found in the LICENSE-APACHE file in the root directory
+ * of this source tree. You may select, at your option, one of the
+ * above-listed licenses.
+ */
+
+use elp_ide_db::assists::AssistId;
+use elp_ide_db::assists::AssistKind;
+use elp_syntax::AstNode;
+use elp_syntax::SyntaxKind;
+use elp_syntax::SyntaxNode;
+use elp_syntax::SyntaxToken;
+use elp_syntax::TextRange;
+use elp_syntax::ast;
+
+use crate::AssistContext;
+use crate::Assists;
+
+// Assist: align_arrows
+//
+// Align the `->` arrows of consecutive single-line clauses.
+//
+// ```
+// foo(X) ->
+//     case X~ of
+//         a -> 1;
+//         bbb -> 2;
+//         _ -> 3
+//     end.
+// ```
+// ->
+// ```
+// foo(X) ->
+//     case X of
+//         a   -> 1;
+//         bbb -> 2;
+//         _   -> 3
+//     end.
+// ```
+pub(crate) fn align_arrows(acc: &mut Assists, ctx: &AssistContext) -> Option<()> {
+    let (target, clauses) = if let Some(case_expr) = ctx.find_node_at_offset::<ast::CaseExpr>() {
+        let clauses = case_expr
+            .clauses()
+            .map(|clause| match clause {
+                ast::CrClauseOrMacro::CrClause(clause) => {
+                    Some((clause.syntax().clone(), clause.body()?))
+                }
+                ast::CrClauseOrMacro::MacroCallExpr(_) => None,
+            })
+            .collect::<Option<Vec<_>>>()?;
+        (case_expr.syntax().text_range(), clauses)
+    } else {
+        let fun_decl = ctx.find_node_at_offset::<ast::FunDecl>()?;
+        let def = ctx
+            .sema
+            .find_enclosing_function_def(ctx.file_id(), fun_decl.syntax())?;
+        let clauses = def
+            .source(ctx.sema.db.upcast())
+            .iter()
+            .map(|fun_decl| match fun_decl.clause()? {
+                ast::FunctionOrMacroClause::FunctionClause(clause) => {
+                    Some((clause.syntax().clone(), clause.body()?))
+                }
+                ast::FunctionOrMacroClause::MacroCallExpr(_) => None,
+            })
+            .collect::<Option<Vec<_>>>()?;
+        (def.range(ctx.sema.db.upcast())?, clauses)
+    };
+    if clauses.len() < 2 {
+        return None;
+    }
+
+    let heads = clauses
+        .iter()
+        .map(|(clause, body)| ClauseHead::new(clause, body))
+        .collect::<Option<Vec<_>>>()?;
+    let arrow_column = heads.iter().map(|head| head.end_column()).max()? + 1;
+    let edits: Vec<(TextRange, String)> = heads
+        .iter()
+        .filter_map(|head| {
+            let padding = " ".repeat(arrow_column - head.end_column());
+            if head.padding != padding {
+                Some((head.padding_range, padding))
+            } else {
+                None
+            }
+        })
+        .collect();
+    if edits.is_empty() {
+        return None;
+    }
+
+    acc.add(
+        AssistId("align_arrows", AssistKind::RefactorRewrite),
+        "Align `->` arrows",
+        None,
+        target,
+        None,
+        |builder| {
+            for (range, padding) in edits {
+                builder.replace(range, padding);
+            }
+        },
+    )
+}
+
+/// The part of a single-line clause up to its `->` arrow.
+struct ClauseHead {
+    /// Column where the clause starts on its line
+    start_column: usize,
+    /// Width of the clause head, excluding the padding before `->`
+    width: usize,
+    /// The whitespace between the clause head and the `->` arrow
+    padding: String,
+    padding_range: TextRange,
+}
+
+impl ClauseHead {
+    fn new(clause: &SyntaxNode, body: &ast::ClauseBody) -> Option<ClauseHead> {
+        if clause.text().contains_char('\n') {
+            return None;
+        }
+        let start_column = line_column(&clause.first_token()?)?;
+        let arrow = body.syntax().first_token()?;
+        if arrow.kind() != SyntaxKind::ANON_DASH_GT {
+            return None;
+        }
+        let mut head_end = arrow.clone();
+        while let Some(prev) = head_end.prev_token() {
+            match prev.kind() {
+                SyntaxKind::WHITESPACE => head_end = prev,
+                // Do not move comments around
+                SyntaxKind::COMMENT => return None,
+                _ => break,
+            }
+        }
+        let padding_range =
+            TextRange::new(head_end.text_range().start(), arrow.text_range().start());
+        let clause_start = clause.text_range().start();
+        let head_range = TextRange::new(clause_start, padding_range.start());
+        let clause_text = clause.text().to_string();
+        let head_text = &clause_text[head_range - clause_start];
+        let padding_text = &clause_text[padding_range - clause_start];
+        Some(ClauseHead {
+            start_column,
+            width: head_text.chars().count(),
+            padding: padding_text.to_string(),
+            padding_range,
+        })
+    }
+
+    fn end_column(&self) -> usize {
+        self.start_column + self.width
+    }
+}
+
+/// The column `token` starts at, provided it is the first token on its line.
+fn line_column(token: &SyntaxToken) -> Option<usize> {
+    match token.prev_token() {
+        None => Some(0),
+        Some(prev) if prev.kind() == SyntaxKind::WHITESPACE => {
+            let text = prev.text();
+            let newline = text.rfind('\n')?;
+            Some(text[newline + 1..].chars().count())
+        }
+        Some(_) => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use expect_test::expect;
+
+    use super::*;
+    use crate::tests::*;
+
+    #[test]
+    fn align_case_branches() {
+        check_assist(
+            align_arrows,
+            "Align `->` arrows",
+            r#"
+foo(X) ->
+    case X~ of
+        a -> 1;
+        bbb -> 2;
+        {c, d}->3
+    end.
+"#,
+            expect![[r#"
+                foo(X) ->
+                    case X of
+                        a      -> 1;
+                        bbb    -> 2;
+                        {c, d} ->3
+                    end.
+            "#]],
+        )
+    }
+
+    #[test]
+    fn align_case_branches_with_guards() {
+        check_assist(
+            align_arrows,
+            "Align `->` arrows",
+            r#"
+foo(X) ->
+    case X~ of
+        N when N > 10    -> big;
+        N when N > 0 -> small;
+        _ -> other
+    end.
+"#,
+            expect![[r#"
+                foo(X) ->
+                    case X of
+                        N when N > 10 -> big;
+                        N when N > 0  -> small;
+                        _             -> other
+                    end.
+            "#]],
+        )
+    }
+
+    #[test]
+    fn align_function_clauses() {
+        check_assist(
+            align_arrows,
+            "Align `->` arrows",
+            r#"
+-module(main).
+count(~[]) -> 0;
+count([_]) -> 1;
+count(L) when is_list(L) -> many.
+"#,
+            expect![[r#"
+                -module(main).
+                count([])                -> 0;
+                count([_])               -> 1;
+                count(L) when is_list(L) -> many.
+            "#]],
+        )
+    }
+
+    #[test]
+    fn already_aligned() {
+        check_assist_not_applicable(
+            align_arrows,
+            r#"
+foo(X) ->
+    case X~ of
+        a   -> 1;
+        bbb -> 2
+    end.
+"#,
+        );
+    }
+
+    #[test]
+    fn multi_line_clause() {
+        check_assist_not_applicable(
+            align_arrows,
+            r#"
+foo(X) ->
+    case X~ of
+        a -> 1;
+        bbb ->
+            2
+    end.
+"#,
+        );
+    }
+
+    #[test]
+    fn single_clause() {
+        check_assist_not_applicable(
+            align_arrows,
+            r#"
+foo(X) ->
+    case X~ of
+        a -> 1
+    end.
+"#,
+        );
+    }
+}
@@ -0,0 +1,118 @@
+/*
+ * Copyright (c) Meta Platforms, Inc. and affiliates.
+ *
+ * This source code is dual-licensed under either the MIT license found in the
+ * LICENSE-MIT file in the root directory of this source tree or the Apache
+ * License, Version 2.0 found in the LICENSE-APACHE file in the root directory
+ * of this source tree. You may select, at your option, one of the
+ * above-listed licenses.
+ */
+
+use elp_ide_db::assists::AssistId;
+use elp_ide_db::assists::AssistKind;
+use elp_syntax::AstNode;
+use elp_syntax::ast;
+
+use crate::AssistContext;
+use crate::Assists;
+
+// Assist: binary_to_string
+//
+// Rewrite a binary literal made of a single plain string to a string literal.
+//
+// ```
+// foo() -> <<"he~llo">>.
+// ```
+// ->
+// ```
+// foo() -> "hello".
+// ```
+pub(crate) fn binary_to_string(acc: &mut Assists, ctx: &AssistContext) -> Option<()> {
+    let binary = ctx.find_node_at_offset::<ast::Binary>()?;
+    binary.syntax().ancestors().find_map(ast::FunDecl::cast)?;
+    let mut elements = binary.elements();
+    let element = elements.next()?;
+    if elements.next().is_some() || element.size().is_some() || element.types().is_some() {
+        return None;
+    }
+    let string = match element.element()? {
+        ast::BitExpr::ExprMax(ast::ExprMax::String(string)) => string,
+        _ => return None,
+    };
+    // The escape sequences mean the same in both literals
+    let replacement = string.syntax().text().to_string();
+    let range = binary.syntax().text_range();
+    acc.add(
+        AssistId("binary_to_string", AssistKind::RefactorRewrite),
+        "Convert binary to string",
+        None,
+        range,
+        None,
+        |builder| builder.replace(range, replacement),
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use expect_test::expect;
+
+    use super::*;
+    use crate::tests::*;
+
+    #[test]
+    fn plain_binary() {
+        check_assist(
+            binary_to_string,
+            "Convert binary to string",
+            r#"
+-module(main).
+foo() -> <<"he~llo">>.
+"#,
+            expect![[r#"
+                -module(main).
+                foo() -> "hello".
+            "#]],
+        )
+    }
+
+    #[test]
+    fn escaped_quotes() {
+        check_assist(
+            binary_to_string,
+            "Convert binary to string",
+            r#"
+-module(main).
+foo() -> <~<"say \"hi\"\n">>.
+"#,
+            expect![[r#"
+                -module(main).
+                foo() -> "say \"hi\"\n".
+            "#]],
+        )
+    }
+
+    #[test]
+    fn not_applicable_with_specifiers() {
+        check_assist_not_applicable(
+            binary_to_string,
+            r#"
+-module(main).
+foo() -> <<"caf~é"/utf8>>.
+"#,
+        );
+        check_assist_not_applicable(
+            binary_to_string,
+            r#"
+-module(main).
+foo(X) -> <<~X/utf8>>.
+"#,
+        );
+        check_assist_not_applicable(
+            binary_to_string,
+            r#"
+-module(main).
+foo(X) -> <<"a~b", X/binary>>.
+"#,
+        );
+    }
+}
@@ -0,0 +1,410 @@
+/*
+ * Copyright (c) Meta Platforms, Inc. and affiliates.
+ *
+ * This source code is dual-licensed under either the MIT license found in the
+ * LICENSE-MIT file in the root directory of this source tree or the Apache
+ * License, Version 2.0 found in the LICENSE-APACHE file in the root directory
+ * of this source tree. You may select, at your option, one of the
+ * above-listed licenses.
+ */
+
+use std::fmt::Write;
+
+use elp_ide_db::assists::AssistId;
+use elp_ide_db::assists::AssistKind;
+use elp_syntax::AstNode;
+use elp_syntax::SyntaxKind;
+use elp_syntax::SyntaxToken;
+use elp_syntax::TextRange;
+use elp_syntax::ast;
+use elp_syntax::ast::edit::IndentLevel;
+use itertools::Itertools;
+
+use crate::AssistContext;
+use crate::Assists;
+use crate::helpers::change_indent;
+
+// Assist: case_to_function_clauses
+//
+// Turn a function whose whole body is a `case` on one of its parameters
+// into one function clause per `case` clause, with the pattern in the head.
+//
+// ```
+// foo(X, Y) ->
+//     ~case X of
+//         {ok, V} -> V + Y;
+//         error -> Y
+//     end.
+// ```
+// ->
+// ```
+// foo({ok, V}, Y) -> V + Y;
+// foo(error, Y) -> Y.
+// ```
+//
+// A value matching none of the clauses now raises `function_clause` rather
+// than `case_clause`. Comments between the `case` clauses stay where they
+// are relative to the function clauses replacing them.
+pub(crate) fn case_to_function_clauses(acc: &mut Assists, ctx: &AssistContext) -> Option<()> {
+    let clause = ctx.find_node_at_offset::<ast::FunctionClause>()?;
+    let fun_decl = ast::FunDecl::cast(clause.syntax().parent()?)?;
+    let def = ctx
+        .sema
+        .find_enclosing_function_def(ctx.file_id(), clause.syntax())?;
+    if def.source(ctx.sema.db.upcast()).len() != 1 || clause.guard().is_some() {
+        return None;
+    }
+    let clause_body = clause.body()?;
+    let mut exprs = clause_body.exprs();
+    let case_expr = match exprs.next()? {
+        ast::Expr::ExprMax(ast::ExprMax::CaseExpr(case_expr)) => case_expr,
+        _ => return None,
+    };
+    if exprs.next().is_some() || case_expr.syntax().first_token()?.kind() != SyntaxKind::ANON_CASE {
+        return None;
+    }
+    let var = match case_expr.expr()? {
+        ast::Expr::ExprMax(ast::ExprMax::Var(var)) => var.syntax().text().to_string(),
+        _ => return None,
+    };
+    let args = clause
+        .args()?
+        .args()
+        .map(|arg| arg.syntax().text().to_string())
+        .collect::<Vec<_>>();
+    let position = args.iter().position(|arg| *arg == var)?;
+    let cr_clauses = case_expr
+        .clauses()
+        .map(|cr_clause| match cr_clause {
+            ast::CrClauseOrMacro::CrClause(cr_clause) => Some(cr_clause),
+            ast::CrClauseOrMacro::MacroCallExpr(_) => None,
+        })
+        .collect::<Option<Vec<_>>>()?;
+    if cr_clauses.is_empty() {
+        return None;
+    }
+    // Only offered on the `case ... of` head, not anywhere in the function
+    let of = case_expr
+        .syntax()
+        .children_with_tokens()
+        .find(|element| element.kind() == SyntaxKind::ANON_OF)?;
+    let head = TextRange::new(
+        case_expr.syntax().text_range().start(),
+        of.text_range().end(),
+    );
+    if !head.contains_inclusive(ctx.offset()) {
+        return None;
+    }
+
+    let name = clause.name()?.syntax().text().to_string();
+    let indent = IndentLevel::from_node(clause.syntax());
+    let (comments, comments_after) =
+        clause_comments(&fun_decl, &clause, &clause_body, &case_expr, &cr_clauses);
+    let new_clauses = cr_clauses
+        .iter()
+        .map(|cr_clause| {
+            let pat = cr_clause.pat()?.syntax().text().to_string();
+            let body = cr_clause.body()?;
+            let guard = cr_clause.guard();
+            // The parameter is no longer bound by itself, so bind it
+            // alongside the pattern if the clause still uses it
+            let uses_var = body
+                .syntax()
+                .descendants_with_tokens()
+                .chain(
+                    guard
+                        .iter()
+                        .flat_map(|guard| guard.syntax().descendants_with_tokens()),
+                )
+                .filter_map(|element| element.into_token())
+                .any(|token| token.kind() == SyntaxKind::VAR && token.text() == var);
+            let mut new_args = args.clone();
+            new_args[position] = match pat.as_str() {
+                "_" => var.clone(),
+                _ if uses_var => format!("{pat} = {var}"),
+                _ => pat,
+            };
+            let guard = guard
+                .map(|guard| format!(" when {}", guard.syntax().text()))
+                .unwrap_or_default();
+            let delta_indent = IndentLevel::from_node(cr_clause.syntax()).0 as i8 - indent.0 as i8;
+            let body = change_indent(-delta_indent, body.syntax().text().to_string());
+            Some(format!("{name}({}){guard} {body}", new_args.join(", ")))
+        })
+        .collect::<Option<Vec<_>>>()?;
+    // Replace up to the final `.`, so that a comment at the end of the last
+    // line can follow it
+    let range = TextRange::new(
+        clause.syntax().text_range().start(),
+        fun_decl.syntax().text_range().end(),
+    );
+    let last = new_clauses.len() - 1;
+    let mut text = String::new();
+    for (idx, (new_clause, comments)) in new_clauses.iter().zip(comments).enumerate() {
+        for comment in comments.leading {
+            let _ = write!(text, "{comment}\n{indent}");
+        }
+        text.push_str(new_clause);
+        text.push_str(if idx == last { "." } else { ";" });
+        for comment in comments.trailing {
+            let _ = write!(text, " {comment}");
+        }
+        if idx != last {
+            let _ = write!(text, "\n{indent}");
+        }
+    }
+    for comment in comments_after {
+        let _ = write!(text, "\n{indent}{comment}");
+    }
+
+    acc.add(
+        AssistId("case_to_function_clauses", AssistKind::RefactorRewrite),
+        "Convert `case` to function clauses",
+        None,
+        case_expr.syntax().text_range(),
+        None,
+        |builder| {
+            builder.replace(range, text);
+        },
+    )
+}
+
+/// The comments outside of the `case` clause bodies that belong with a
+/// clause.
+#[derive(Clone, Default)]
+struct ClauseComments {
+    /// Comments on their own lines before the clause
+    leading: Vec<SyntaxToken>,
+    /// Comments at the end of the line the clause ends on
+    trailing: Vec<SyntaxToken>,
+}
+
+/// The comments outside of the `case` clause bodies, grouped by the clause
+/// they belong with, and the comments on their own lines after the last
+/// clause.
+fn clause_comments(
+    fun_decl: &ast::FunDecl,
+    clause: &ast::FunctionClause,
+    clause_body: &ast::ClauseBody,
+    case_expr: &ast::CaseExpr,
+    cr_clauses: &[ast::CrClause],
+) -> (Vec<ClauseComments>, Vec<SyntaxToken>) {
+    let mut comments = vec![ClauseComments::default(); cr_clauses.len()];
+    let mut after = Vec::new();
+    [
+        fun_decl.syntax(),
+        clause.syntax(),
+        clause_body.syntax(),
+        case_expr.syntax(),
+    ]
+    .into_iter()
+    .chain(cr_clauses.iter().map(|cr_clause| cr_clause.syntax()))
+    .flat_map(|node| node.children_with_tokens())
+    .filter_map(|element| element.into_token())
+    .filter(|token| token.kind() == SyntaxKind::COMMENT)
+    .sorted_by_key(|token| token.text_range().start())
+    .for_each(|comment| {
+        let start = comment.text_range().start();
+        let own_line = comment.prev_token().is_none_or(|token| {
+            token.kind() == SyntaxKind::WHITESPACE && token.text().contains('\n')
+        });
+        if own_line {
+            match cr_clauses
+                .iter()
+                .position(|cr_clause| start < cr_clause.syntax().text_range().end())
+            {
+                Some(idx) => comments[idx].leading.push(comment),
+                None => after.push(comment),
+            }
+        } else {
+            match cr_clauses
+                .iter()
+                .rposition(|cr_clause| cr_clause.syntax().text_range().start() < start)
+            {
+                Some(idx) => comments[idx].trailing.push(comment),
+                None => comments[0].leading.push(comment),
+            }
+        }
+    });
+    (comments, after)
+}
+
+#[cfg(test)]
+mod tests {
+    use expect_test::expect;
+
+    use super::*;
+    use crate::tests::*;
+
+    #[test]
+    fn case_to_function_clauses_single_line() {
+        check_assist(
+            case_to_function_clauses,
+            "Convert `case` to function clauses",
+            r#"
+-module(main).
+foo(X, Y) -> c~ase X of {ok, V} -> V + Y; error -> Y end.
+"#,
+            expect![[r#"
+                -module(main).
+                foo({ok, V}, Y) -> V + Y;
+                foo(error, Y) -> Y.
+            "#]],
+        )
+    }
+
+    #[test]
+    fn case_to_function_clauses_multi_line() {
+        check_assist(
+            case_to_function_clauses,
+            "Convert `case` to function clauses",
+            r#"
+-module(main).
+foo(Y, X) ->
+    case~ X of
+        {ok, V} when V > 0 ->
+            bar(V),
+            V + Y;
+        _ ->
+            X
+    end.
+"#,
+            expect![[r#"
+                -module(main).
+                foo(Y, {ok, V}) when V > 0 ->
+                    bar(V),
+                    V + Y;
+                foo(Y, X) ->
+                    X.
+            "#]],
+        )
+    }
+
+    #[test]
+    fn case_to_function_clauses_keeps_comments() {
+        check_assist(
+            case_to_function_clauses,
+            "Convert `case` to function clauses",
+            r#"
+-module(main).
+foo(X) ->
+    c~ase X of
+        %% The usual case
+        ok ->
+            %% Nothing to do
+            ok;
+        %% Anything else
+        _ ->
+            error
+        %% No more clauses
+    end.
+"#,
+            expect![[r#"
+                -module(main).
+                %% The usual case
+                foo(ok) ->
+                    %% Nothing to do
+                    ok;
+                %% Anything else
+                foo(X) ->
+                    error.
+                %% No more clauses
+            "#]],
+        )
+    }
+
+    #[test]
+    fn scrutinee_not_a_parameter() {
+        check_assist_not_applicable(
+            case_to_function_clauses,
+            r#"
+-module(main).
+foo(X) ->
+    c~ase bar(X) of
+        ok -> ok;
+        _ -> error
+    end.
+"#,
+        );
+    }
+
+    #[test]
+    fn other_expressions_in_body() {
+        check_assist_not_applicable(
+            case_to_function_clauses,
+            r#"
+-module(main).
+foo(X) ->
+    bar(X),
+    c~ase X of
+        ok -> ok;
+        _ -> error
+    end.
+"#,
+        );
+        check_assist_not_applicable(
+            case_to_function_clauses,
+            r#"
+-module(main).
+foo(X) ->
+    c~ase X of
+        ok -> ok;
+        _ -> error
+    end,
+    bar(X).
+"#,
+        );
+    }
+
+    #[test]
+    fn several_function_clauses() {
+        check_assist_not_applicable(
+            case_to_function_clauses,
+            r#"
+-module(main).
+foo(X) ->
+    c~ase X of
+        ok -> ok;
+        _ -> error
+    end;
+foo(_) -> error.
+"#,
+        );
+    }
+
+    #[test]
+    fn case_to_function_clauses_keeps_trailing_comments() {
+        check_assist(
+            case_to_function_clauses,
+            "Convert `case` to function clauses",
+            r#"
+-module(main).
+foo(X) ->
+    c~ase X of
+        ok -> ok; % The usual case
+        _ -> error % Anything else
+    end.
+"#,
+            expect![[r#"
+                -module(main).
+                foo(ok) -> ok; % The usual case
+                foo(X) -> error. % Anything else
+            "#]],
+        )
+    }
+
+    #[test]
+    fn cursor_not_on_case() {
+        check_assist_not_applicable(
+            case_to_function_clauses,
+            r#"
+-module(main).
+foo(X) ->
+    case X of
+        ok -> o~k;
+        _ -> error
+    end.
+"#,
+        );
+    }
+}
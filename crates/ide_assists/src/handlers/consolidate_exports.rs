@@ -0,0 +1,249 @@
+/*
+ * Copyright (c) Meta Platforms, Inc. and affiliates.
+ *
+ * This source code is dual-licensed under either the MIT license found in the
+ * LICENSE-MIT file in the root directory of this source tree or the Apache
+ * License, Version 2.0 found in the LICENSE-APACHE file in the root directory
+ * of this source tree. You may select, at your option, one of the
+ * above-listed licenses.
+ */
+
+use std::collections::BTreeSet;
+
+use elp_ide_db::assists::AssistId;
+use elp_ide_db::assists::AssistKind;
+use elp_syntax::AstNode;
+use elp_syntax::SyntaxKind;
+use elp_syntax::TextRange;
+use elp_syntax::TextSize;
+use elp_syntax::ast;
+use hir::NameArity;
+
+use crate::AssistContext;
+use crate::Assists;
+
+// Assist: consolidate_exports
+//
+// Merge all the `-export` attributes of a module into a single sorted one,
+// without duplicates.
+//
+// ```
+// -export([~foo/0]).
+// -export([bar/1, foo/0]).
+// ```
+// ->
+// ```
+// -export([bar/1, foo/0]).
+// ```
+pub(crate) fn consolidate_exports(acc: &mut Assists, ctx: &AssistContext) -> Option<()> {
+    let attr = ctx.find_node_at_offset::<ast::ExportAttribute>()?;
+    let file_id = ctx.file_id();
+    let form_list = ctx.sema.form_list(file_id);
+    let source = ctx.sema.parse(file_id).value;
+    let exports: Vec<_> = form_list.exports().map(|(_, export)| export).collect();
+    // Exports under a preprocessor condition cannot be moved around
+    if exports.len() < 2 || exports.iter().any(|export| export.cond.is_some()) {
+        return None;
+    }
+    let attrs: Vec<ast::ExportAttribute> = exports
+        .iter()
+        .map(|export| export.form_id.get(&source))
+        .collect();
+    if !attrs.contains(&attr) {
+        return None;
+    }
+    // Comments may be grouping the exports, which merging them would lose
+    if attrs
+        .iter()
+        .enumerate()
+        .any(|(idx, attr)| has_comments(attr, idx > 0))
+    {
+        return None;
+    }
+    // The entries are rebuilt from their names, which would expand the
+    // macros they use
+    if attrs.iter().any(|attr| {
+        attr.syntax()
+            .descendants()
+            .any(|node| node.kind() == SyntaxKind::MACRO_CALL)
+    }) {
+        return None;
+    }
+    let names: BTreeSet<&NameArity> = exports
+        .iter()
+        .flat_map(|export| export.entries.clone().map(|fa| &form_list[fa].name))
+        .collect();
+    let merged = format!(
+        "-export([{}]).",
+        names
+            .iter()
+            .map(|name| name.to_string())
+            .collect::<Vec<_>>()
+            .join(", ")
+    );
+
+    acc.add(
+        AssistId("consolidate_exports", AssistKind::RefactorRewrite),
+        "Consolidate `-export` attributes",
+        None,
+        attr.syntax().text_range(),
+        None,
+        |builder| {
+            builder.replace(attrs[0].syntax().text_range(), merged);
+            for attr in &attrs[1..] {
+                builder.delete(line_range(attr));
+            }
+        },
+    )
+}
+
+/// Whether the attribute contains a comment, or, if `leading` is set, is
+/// directly preceded by one.
+fn has_comments(attr: &ast::ExportAttribute, leading: bool) -> bool {
+    let inner = attr
+        .syntax()
+        .descendants_with_tokens()
+        .any(|element| element.kind() == SyntaxKind::COMMENT);
+    let before = leading
+        && std::iter::successors(attr.syntax().prev_sibling_or_token(), |element| {
+            element.prev_sibling_or_token()
+        })
+        .take_while(|element| element.kind().is_trivia())
+        .any(|element| element.kind() == SyntaxKind::COMMENT);
+    inner || before
+}
+
+/// The range of the attribute, including the newline ending its line.
+fn line_range(attr: &ast::ExportAttribute) -> TextRange {
+    let range = attr.syntax().text_range();
+    match attr.syntax().next_sibling_or_token() {
+        Some(element)
+            if element.kind() == SyntaxKind::WHITESPACE
+                && element
+                    .as_token()
+                    .is_some_and(|token| token.text().starts_with('\n')) =>
+        {
+            TextRange::new(range.start(), range.end() + TextSize::from(1))
+        }
+        _ => range,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use expect_test::expect;
+
+    use super::*;
+    use crate::tests::*;
+
+    #[test]
+    fn consolidate_two_exports() {
+        check_assist(
+            consolidate_exports,
+            "Consolidate `-export` attributes",
+            r#"
+-module(main).
+-export([~foo/0]).
+-export([bar/1]).
+foo() -> ok.
+bar(X) -> X.
+"#,
+            expect![[r#"
+                -module(main).
+                -export([bar/1, foo/0]).
+                foo() -> ok.
+                bar(X) -> X.
+            "#]],
+        )
+    }
+
+    #[test]
+    fn consolidate_three_exports() {
+        check_assist(
+            consolidate_exports,
+            "Consolidate `-export` attributes",
+            r#"
+-module(main).
+-export([foo/0, baz/2]).
+foo() -> ok.
+-export([bar/1]).
+bar(X) -> X.
+-export([b~ar/0]).
+bar() -> ok.
+baz(X, Y) -> {X, Y}.
+"#,
+            expect![[r#"
+                -module(main).
+                -export([bar/0, bar/1, baz/2, foo/0]).
+                foo() -> ok.
+                bar(X) -> X.
+                bar() -> ok.
+                baz(X, Y) -> {X, Y}.
+            "#]],
+        )
+    }
+
+    #[test]
+    fn consolidate_duplicate_entries() {
+        check_assist(
+            consolidate_exports,
+            "Consolidate `-export` attributes",
+            r#"
+-module(main).
+-export([foo/0, bar/1]).
+-export([~foo/0]).
+foo() -> ok.
+bar(X) -> X.
+"#,
+            expect![[r#"
+                -module(main).
+                -export([bar/1, foo/0]).
+                foo() -> ok.
+                bar(X) -> X.
+            "#]],
+        )
+    }
+
+    #[test]
+    fn not_applicable_to_single_export() {
+        check_assist_not_applicable(
+            consolidate_exports,
+            r#"
+-module(main).
+-export([~foo/0, bar/1]).
+foo() -> ok.
+bar(X) -> X.
+"#,
+        );
+    }
+
+    #[test]
+    fn not_applicable_with_grouping_comments() {
+        check_assist_not_applicable(
+            consolidate_exports,
+            r#"
+-module(main).
+-export([~foo/0]).
+%% Callbacks
+-export([bar/1]).
+foo() -> ok.
+bar(X) -> X.
+"#,
+        );
+    }
+
+    #[test]
+    fn not_applicable_with_macros() {
+        check_assist_not_applicable(
+            consolidate_exports,
+            r#"
+-module(main).
+-define(NAME, foo).
+-export([~?NAME/0]).
+-export([bar/1]).
+foo() -> ok.
+bar(X) -> X.
+"#,
+        );
+    }
+}
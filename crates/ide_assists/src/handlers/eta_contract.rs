@@ -0,0 +1,211 @@
+/*
+ * Copyright (c) Meta Platforms, Inc. and affiliates.
+ *
+ * This source code is dual-licensed under either the MIT license found in the
+ * LICENSE-MIT file in the root directory of this source tree or the Apache
+ * License, Version 2.0 found in the LICENSE-APACHE file in the root directory
+ * of this source tree. You may select, at your option, one of the
+ * above-listed licenses.
+ */
+
+use elp_ide_db::assists::AssistId;
+use elp_ide_db::assists::AssistKind;
+use elp_syntax::AstNode;
+use elp_syntax::ast;
+
+use crate::AssistContext;
+use crate::Assists;
+
+// Assist: eta_contract
+//
+// Turn a fun which only forwards its parameters to a function into a
+// `fun Name/Arity` reference.
+//
+// ```
+// foo(L) -> lists:map(~fun(X) -> bar(X) end, L).
+// ```
+// ->
+// ```
+// foo(L) -> lists:map(fun bar/1, L).
+// ```
+pub(crate) fn eta_contract(acc: &mut Assists, ctx: &AssistContext) -> Option<()> {
+    let fun = ctx.find_node_at_offset::<ast::AnonymousFun>()?;
+    let mut clauses = fun.clauses();
+    let clause = clauses.next()?;
+    if clauses.next().is_some() || clause.name().is_some() || clause.guard().is_some() {
+        return None;
+    }
+    let params = clause
+        .args()?
+        .args()
+        .map(|param| match param {
+            ast::Expr::ExprMax(ast::ExprMax::Var(var)) if var.syntax().text() != "_" => {
+                Some(var.syntax().text().to_string())
+            }
+            _ => None,
+        })
+        .collect::<Option<Vec<_>>>()?;
+    let mut exprs = clause.body()?.exprs();
+    let call = match exprs.next()? {
+        ast::Expr::Call(call) => call,
+        _ => return None,
+    };
+    if exprs.next().is_some() {
+        return None;
+    }
+    // The arguments must be the parameters, each exactly once and in order
+    let args: Vec<String> = call
+        .args()?
+        .args()
+        .map(|arg| arg.syntax().text().to_string())
+        .collect();
+    if args != params || (1..params.len()).any(|i| params[i..].contains(&params[i - 1])) {
+        return None;
+    }
+    let callee = match call.expr()? {
+        ast::Expr::ExprMax(ast::ExprMax::Atom(atom)) => atom.syntax().text().to_string(),
+        ast::Expr::Remote(remote) => {
+            let module = match remote.module()?.module()? {
+                ast::ExprMax::Atom(atom) => atom,
+                _ => return None,
+            };
+            let name = match remote.fun()? {
+                ast::ExprMax::Atom(atom) => atom,
+                _ => return None,
+            };
+            format!("{}:{}", module.syntax().text(), name.syntax().text())
+        }
+        _ => return None,
+    };
+
+    let range = fun.syntax().text_range();
+    acc.add(
+        AssistId("eta_contract", AssistKind::RefactorRewrite),
+        format!("Convert to `fun {callee}/{}`", params.len()),
+        None,
+        range,
+        None,
+        |builder| {
+            builder.replace(range, format!("fun {callee}/{}", params.len()));
+        },
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use expect_test::expect;
+
+    use super::*;
+    use crate::tests::*;
+
+    #[test]
+    fn contract_local_call() {
+        check_assist(
+            eta_contract,
+            "Convert to `fun bar/1`",
+            r#"
+-module(main).
+foo(L) -> lists:map(f~un(X) -> bar(X) end, L).
+bar(X) -> X.
+"#,
+            expect![[r#"
+                -module(main).
+                foo(L) -> lists:map(fun bar/1, L).
+                bar(X) -> X.
+            "#]],
+        )
+    }
+
+    #[test]
+    fn contract_remote_call() {
+        check_assist(
+            eta_contract,
+            "Convert to `fun maps:put/3`",
+            r#"
+-module(main).
+foo(L) -> lists:foldl(f~un(K, V, M) -> maps:put(K, V, M) end, #{}, L).
+"#,
+            expect![[r#"
+                -module(main).
+                foo(L) -> lists:foldl(fun maps:put/3, #{}, L).
+            "#]],
+        )
+    }
+
+    #[test]
+    fn contract_nullary_call() {
+        check_assist(
+            eta_contract,
+            "Convert to `fun bar/0`",
+            r#"
+-module(main).
+foo() -> spawn(f~un() -> bar() end).
+bar() -> ok.
+"#,
+            expect![[r#"
+                -module(main).
+                foo() -> spawn(fun bar/0).
+                bar() -> ok.
+            "#]],
+        )
+    }
+
+    #[test]
+    fn not_applicable_with_reordered_params() {
+        check_assist_not_applicable(
+            eta_contract,
+            r#"
+-module(main).
+foo(L) -> lists:foldl(f~un(X, Acc) -> bar(Acc, X) end, [], L).
+bar(A, B) -> {A, B}.
+"#,
+        );
+    }
+
+    #[test]
+    fn not_applicable_with_duplicated_params() {
+        check_assist_not_applicable(
+            eta_contract,
+            r#"
+-module(main).
+foo(L) -> lists:map(f~un(X) -> bar(X, X) end, L).
+bar(A, B) -> {A, B}.
+"#,
+        );
+    }
+
+    #[test]
+    fn not_applicable_with_other_expressions() {
+        check_assist_not_applicable(
+            eta_contract,
+            r#"
+-module(main).
+foo(L) -> lists:map(f~un(X) -> log(X), bar(X) end, L).
+bar(X) -> X.
+"#,
+        );
+    }
+
+    #[test]
+    fn not_applicable_with_computed_arguments() {
+        check_assist_not_applicable(
+            eta_contract,
+            r#"
+-module(main).
+foo(L) -> lists:map(f~un(X) -> bar(X + 1) end, L).
+bar(X) -> X.
+"#,
+        );
+    }
+
+    #[test]
+    fn not_applicable_with_variable_callee() {
+        check_assist_not_applicable(
+            eta_contract,
+            r#"
+-module(main).
+foo(F, L) -> lists:map(f~un(X) -> F(X) end, L).
+"#,
+        );
+    }
+}
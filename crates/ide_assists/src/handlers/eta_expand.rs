@@ -0,0 +1,157 @@
+/*
+ * Copyright (c) Meta Platforms, Inc. and affiliates.
+ *
+ * This source code is dual-licensed under either the MIT license found in the
+ * LICENSE-MIT file in the root directory of this source tree or the Apache
+ * License, Version 2.0 found in the LICENSE-APACHE file in the root directory
+ * of this source tree. You may select, at your option, one of the
+ * above-listed licenses.
+ */
+
+use elp_ide_db::assists::AssistId;
+use elp_ide_db::assists::AssistKind;
+use elp_syntax::AstNode;
+use elp_syntax::SyntaxNode;
+use elp_syntax::ast;
+use hir::InFile;
+
+use crate::AssistContext;
+use crate::Assists;
+use crate::helpers::freshen_variable_name;
+
+// Assist: eta_expand
+//
+// Turn a `fun Name/Arity` reference into an explicit fun forwarding its
+// arguments to the function.
+//
+// ```
+// foo(L) -> lists:map(~fun bar/1, L).
+// ```
+// ->
+// ```
+// foo(L) -> lists:map(fun(Arg1) -> bar(Arg1) end, L).
+// ```
+pub(crate) fn eta_expand(acc: &mut Assists, ctx: &AssistContext) -> Option<()> {
+    let (syntax, callee, arity) = if let Some(fun) = ctx.find_node_at_offset::<ast::InternalFun>() {
+        let name = fun.fun()?.syntax().text().to_string();
+        (fun.syntax().clone(), name, arity(fun.arity()?)?)
+    } else {
+        let fun = ctx.find_node_at_offset::<ast::ExternalFun>()?;
+        let module = fun.module()?.name()?.syntax().text().to_string();
+        let name = fun.fun()?.syntax().text().to_string();
+        (
+            fun.syntax().clone(),
+            format!("{module}:{name}"),
+            arity(fun.arity()?)?,
+        )
+    };
+
+    let range = syntax.text_range();
+    acc.add(
+        AssistId("eta_expand", AssistKind::RefactorRewrite),
+        "Convert to an explicit fun",
+        None,
+        range,
+        None,
+        |builder| {
+            let params = fresh_params(ctx, &syntax, arity).join(", ");
+            builder.replace(range, format!("fun({params}) -> {callee}({params}) end"));
+        },
+    )
+}
+
+/// The arity of a fun reference, when it is a literal integer.
+fn arity(arity: ast::Arity) -> Option<u32> {
+    match arity.value()? {
+        ast::ArityValue::Integer(int) => int.syntax().text().to_string().parse().ok(),
+        _ => None,
+    }
+}
+
+/// Parameter names which do not shadow any variable of the enclosing clause.
+fn fresh_params(ctx: &AssistContext, syntax: &SyntaxNode, arity: u32) -> Vec<String> {
+    let vars_in_clause = ast::Expr::cast(syntax.clone()).and_then(|expr| {
+        ctx.sema
+            .find_vars_in_clause_ast(&InFile::new(ctx.file_id(), &expr))
+    });
+    let mut params: Vec<String> = Vec::new();
+    for i in 1..=arity {
+        let mut name = freshen_variable_name(&ctx.sema, format!("Arg{i}"), &vars_in_clause);
+        while params.contains(&name) {
+            name = format!("{name}_");
+        }
+        params.push(name);
+    }
+    params
+}
+
+#[cfg(test)]
+mod tests {
+    use expect_test::expect;
+
+    use super::*;
+    use crate::tests::*;
+
+    #[test]
+    fn expand_local_fun() {
+        check_assist(
+            eta_expand,
+            "Convert to an explicit fun",
+            r#"
+-module(main).
+foo(L) -> lists:map(fun b~ar/1, L).
+bar(X) -> X.
+"#,
+            expect![[r#"
+                -module(main).
+                foo(L) -> lists:map(fun(Arg1) -> bar(Arg1) end, L).
+                bar(X) -> X.
+            "#]],
+        )
+    }
+
+    #[test]
+    fn expand_remote_fun() {
+        check_assist(
+            eta_expand,
+            "Convert to an explicit fun",
+            r#"
+-module(main).
+foo(L) -> lists:foldl(fun ~maps:put/3, #{}, L).
+"#,
+            expect![[r#"
+                -module(main).
+                foo(L) -> lists:foldl(fun(Arg1, Arg2, Arg3) -> maps:put(Arg1, Arg2, Arg3) end, #{}, L).
+            "#]],
+        )
+    }
+
+    #[test]
+    fn expand_renames_clashing_parameter() {
+        check_assist(
+            eta_expand,
+            "Convert to an explicit fun",
+            r#"
+-module(main).
+foo(Arg1) -> Arg1(fun ~bar/1).
+bar(X) -> X.
+"#,
+            expect![[r#"
+                -module(main).
+                foo(Arg1) -> Arg1(fun(Arg10) -> bar(Arg10) end).
+                bar(X) -> X.
+            "#]],
+        )
+    }
+
+    #[test]
+    fn not_applicable_with_variable_arity() {
+        check_assist_not_applicable(
+            eta_expand,
+            r#"
+-module(main).
+foo(N) -> fun ~lists:map/N.
+"#,
+        );
+    }
+}
@@ -0,0 +1,182 @@
+/*
+ * Copyright (c) Meta Platforms, Inc. and affiliates.
+ *
+ * This source code is dual-licensed under either the MIT license found in the
+ * LICENSE-MIT file in the root directory of this source tree or the Apache
+ * License, Version 2.0 found in the LICENSE-APACHE file in the root directory
+ * of this source tree. You may select, at your option, one of the
+ * above-listed licenses.
+ */
+
+use elp_ide_db::assists::AssistId;
+use elp_ide_db::assists::AssistKind;
+use elp_syntax::AstNode;
+use elp_syntax::ast;
+use fxhash::FxHashSet;
+use hir::NameArity;
+
+use crate::AssistContext;
+use crate::Assists;
+use crate::helpers;
+use crate::helpers::remove_atoms_from_compile_attribute;
+
+const EXPORT_ALL_OPTIONS: &[&str] = &["export_all", "nowarn_export_all"];
+
+// Assist: export_all_to_explicit
+//
+// Replace `-compile(export_all)` by an explicit export of every function
+// defined in the module.
+//
+// ```
+// -compile(~export_all).
+// foo() -> bar().
+// bar() -> ok.
+// ```
+// ->
+// ```
+// -export([foo/0, bar/0]).
+// foo() -> bar().
+// bar() -> ok.
+// ```
+pub(crate) fn export_all_to_explicit(acc: &mut Assists, ctx: &AssistContext) -> Option<()> {
+    let attr = ctx.find_node_at_offset::<ast::CompileOptionsAttribute>()?;
+    let is_export_all = |expr: &ast::Expr| match expr {
+        ast::Expr::ExprMax(ast::ExprMax::Atom(atom)) => atom.syntax().text() == "export_all",
+        _ => false,
+    };
+    // Only a top-level option, not one nested in another option's value
+    let has_export_all = match attr.options()? {
+        ast::Expr::ExprMax(ast::ExprMax::List(list)) => list.exprs().any(|e| is_export_all(&e)),
+        option => is_export_all(&option),
+    };
+    if !has_export_all {
+        return None;
+    }
+    let file_id = ctx.file_id();
+    let form_list = ctx.sema.form_list(file_id);
+    let source = ctx.sema.parse(file_id).value;
+    let (co_id, _) = form_list
+        .compile_attributes()
+        .find(|(_, co)| co.form_id.get(&source).syntax() == attr.syntax())?;
+
+    // Functions already listed in an `-export` attribute stay as they are
+    let exported: FxHashSet<&NameArity> = form_list
+        .exports()
+        .flat_map(|(_, export)| export.entries.clone().map(|fa| &form_list[fa].name))
+        .collect();
+    let mut seen = FxHashSet::default();
+    let functions: Vec<NameArity> = form_list
+        .function_clauses()
+        .map(|(_, clause)| &clause.name)
+        .filter(|name| !exported.contains(name) && seen.insert(*name))
+        .cloned()
+        .collect();
+
+    acc.add(
+        AssistId("export_all_to_explicit", AssistKind::RefactorRewrite),
+        "Replace `export_all` with explicit exports",
+        None,
+        attr.syntax().text_range(),
+        None,
+        |builder| {
+            remove_atoms_from_compile_attribute(
+                &ctx.sema,
+                file_id,
+                &co_id,
+                EXPORT_ALL_OPTIONS,
+                builder,
+            );
+            if !functions.is_empty() {
+                helpers::ExportBuilder::new(
+                    &ctx.sema,
+                    file_id,
+                    helpers::ExportForm::Functions,
+                    &functions,
+                    builder,
+                )
+                .finish();
+            }
+        },
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use expect_test::expect;
+
+    use super::*;
+    use crate::tests::*;
+
+    #[test]
+    fn export_all_functions() {
+        check_assist(
+            export_all_to_explicit,
+            "Replace `export_all` with explicit exports",
+            r#"
+-module(main).
+-compile(export_a~ll).
+foo() -> bar(1).
+bar(X) -> baz(X, 2).
+baz(X, Y) when X > Y -> X;
+baz(_X, Y) -> Y.
+"#,
+            expect![[r#"
+                -module(main).
+
+                -export([foo/0, bar/1, baz/2]).
+                foo() -> bar(1).
+                bar(X) -> baz(X, 2).
+                baz(X, Y) when X > Y -> X;
+                baz(_X, Y) -> Y.
+            "#]],
+        )
+    }
+
+    #[test]
+    fn keeps_other_options_and_exports() {
+        check_assist(
+            export_all_to_explicit,
+            "Replace `export_all` with explicit exports",
+            r#"
+-module(main).
+-export([foo/0]).
+-compile([debug_info, export_a~ll, nowarn_export_all]).
+foo() -> bar(1).
+bar(X) -> baz(X).
+baz(X) -> X.
+"#,
+            expect![[r#"
+                -module(main).
+                -export([foo/0, bar/1, baz/1]).
+                -compile([debug_info]).
+                foo() -> bar(1).
+                bar(X) -> baz(X).
+                baz(X) -> X.
+            "#]],
+        )
+    }
+
+    #[test]
+    fn not_applicable_without_export_all() {
+        check_assist_not_applicable(
+            export_all_to_explicit,
+            r#"
+-module(main).
+-compile([debug_i~nfo]).
+foo() -> ok.
+"#,
+        );
+    }
+
+    #[test]
+    fn not_applicable_to_nested_export_all() {
+        check_assist_not_applicable(
+            export_all_to_explicit,
+            r#"
+-module(main).
+-compile([{parse_transform, [export_a~ll]}]).
+foo() -> ok.
+"#,
+        );
+    }
+}
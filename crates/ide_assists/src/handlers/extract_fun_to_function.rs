@@ -0,0 +1,299 @@
+/*
+ * Copyright (c) Meta Platforms, Inc. and affiliates.
+ *
+ * This source code is dual-licensed under either the MIT license found in the
+ * LICENSE-MIT file in the root directory of this source tree or the Apache
+ * License, Version 2.0 found in the LICENSE-APACHE file in the root directory
+ * of this source tree. You may select, at your option, one of the
+ * above-listed licenses.
+ */
+
+use elp_ide_db::assists::AssistId;
+use elp_ide_db::assists::AssistKind;
+use elp_ide_db::assists::AssistUserInput;
+use elp_ide_db::assists::AssistUserInputType;
+use elp_syntax::AstNode;
+use elp_syntax::SyntaxKind;
+use elp_syntax::ast;
+use elp_syntax::ast::edit::IndentLevel;
+use itertools::Itertools;
+
+use crate::AssistContext;
+use crate::Assists;
+use crate::helpers::DEFAULT_INDENT_STEP;
+use crate::helpers::change_indent;
+use crate::helpers::freshen_function_name;
+
+// Assist: extract_fun_to_function
+//
+// Move the body of an anonymous fun into a new local function. The
+// variables of the enclosing scope used in the fun become extra parameters
+// of the new function, forwarded by the fun.
+//
+// ```
+// foo(L, N) -> lists:map(~fun(X) -> X + N end, L).
+// ```
+// ->
+// ```
+// foo(L, N) -> lists:map(fun(X) -> fun_name(X, N) end, L).
+//
+// fun_name(X, N) -> X + N.
+// ```
+pub(crate) fn extract_fun_to_function(acc: &mut Assists, ctx: &AssistContext) -> Option<()> {
+    let fun = ctx.find_node_at_offset::<ast::AnonymousFun>()?;
+    let clauses: Vec<ast::FunClause> = fun.clauses().collect();
+    // A named fun can call itself, which the new function could not
+    if clauses.is_empty() || clauses.iter().any(|clause| clause.name().is_some()) {
+        return None;
+    }
+    let arity = clauses[0].args()?.args().count();
+    let expr = ast::Expr::ExprMax(ast::ExprMax::AnonymousFun(fun.clone()));
+    let free_vars: Vec<String> = ctx
+        .sema
+        .free_vars_ast(ctx.file_id(), &expr)?
+        .free
+        .iter()
+        .map(|(var, _)| ctx.db().lookup_var(*var).to_string())
+        .sorted()
+        .dedup()
+        .collect();
+    // A variable of a clause head shadows the free variable of the same
+    // name, which would become an equality match in the new function
+    if clauses.iter().any(|clause| {
+        clause.args().is_some_and(|args| {
+            args.syntax()
+                .descendants()
+                .filter_map(ast::Var::cast)
+                .any(|var| free_vars.contains(&var.syntax().text().to_string()))
+        })
+    }) {
+        return None;
+    }
+    let insert_after = fun
+        .syntax()
+        .ancestors()
+        .take_while(|node| node.kind() != SyntaxKind::SOURCE_FILE)
+        .last()?;
+    let range = fun.syntax().text_range();
+    acc.add(
+        AssistId("extract_fun_to_function", AssistKind::RefactorExtract),
+        "Extract fun into function",
+        None,
+        range,
+        Some(AssistUserInput {
+            input_type: AssistUserInputType::Atom,
+            prompt: None,
+            value: "fun_name".to_string(),
+            task_id: None,
+        }),
+        |builder| {
+            let name = freshen_function_name(
+                ctx,
+                ctx.user_input_or(|| "fun_name".to_string()),
+                (arity + free_vars.len()) as u32,
+            );
+            let replacement = if free_vars.is_empty() {
+                format!("fun {name}/{arity}")
+            } else {
+                let params = forwarded_params(&clauses[0], arity, &free_vars);
+                format!(
+                    "fun({}) -> {name}({}) end",
+                    params.join(", "),
+                    params.iter().chain(&free_vars).join(", ")
+                )
+            };
+            let function = clauses
+                .iter()
+                .map(|clause| make_clause(&name, clause, &free_vars))
+                .collect::<Option<Vec<_>>>();
+            if let Some(function) = function {
+                builder.replace(range, replacement);
+                let function = format!("{}.", function.join(";\n"));
+                let offset = insert_after.text_range().end();
+                match ctx.config.snippet_cap {
+                    Some(cap) => builder.insert_snippet(cap, offset, format!("\n\n$0{function}")),
+                    None => builder.insert(offset, format!("\n\n{function}")),
+                }
+            }
+        },
+    )
+}
+
+/// The names of the parameters forwarded by the fun, taken from the first
+/// clause where it binds a plain variable.
+fn forwarded_params(clause: &ast::FunClause, arity: usize, free_vars: &[String]) -> Vec<String> {
+    let mut params: Vec<String> = Vec::new();
+    let args: Vec<ast::Expr> = clause
+        .args()
+        .map(|args| args.args().collect())
+        .unwrap_or_default();
+    for i in 0..arity {
+        let name = match args.get(i) {
+            Some(ast::Expr::ExprMax(ast::ExprMax::Var(var)))
+                if !var.syntax().text().to_string().starts_with('_') =>
+            {
+                var.syntax().text().to_string()
+            }
+            _ => format!("Arg{}", i + 1),
+        };
+        if free_vars.contains(&name) || params.contains(&name) {
+            params.push(format!("Arg{}", i + 1));
+        } else {
+            params.push(name);
+        }
+    }
+    params
+}
+
+/// A clause of the new function, with the free variables appended to the
+/// parameters of the fun clause, and the body reindented. The free
+/// variables a clause does not use are prefixed with `_`, unless it uses
+/// macros, which may refer to them.
+fn make_clause(name: &str, clause: &ast::FunClause, free_vars: &[String]) -> Option<String> {
+    let nodes: Vec<_> = clause
+        .guard()
+        .map(|guard| guard.syntax().clone())
+        .into_iter()
+        .chain(clause.body().map(|body| body.syntax().clone()))
+        .collect();
+    let has_macros = nodes.iter().any(|node| {
+        node.descendants()
+            .any(|node| node.kind() == SyntaxKind::MACRO_CALL)
+    });
+    let used: Vec<String> = nodes
+        .iter()
+        .flat_map(|node| node.descendants().filter_map(ast::Var::cast))
+        .map(|var| var.syntax().text().to_string())
+        .collect();
+    let params = clause
+        .args()?
+        .args()
+        .map(|arg| arg.syntax().text().to_string())
+        .chain(free_vars.iter().map(|var| {
+            if has_macros || used.contains(var) {
+                var.clone()
+            } else {
+                format!("_{var}")
+            }
+        }))
+        .join(", ");
+    let guard = match clause.guard() {
+        Some(guard) => format!(" when {}", guard.syntax().text()),
+        None => String::new(),
+    };
+    let body = clause.body()?;
+    let old_indent = IndentLevel::from_node(body.syntax());
+    let body_text = format!(
+        "\n{}{}",
+        " ".repeat(old_indent.0 as usize),
+        body.syntax().text()
+    );
+    let body_text = change_indent(DEFAULT_INDENT_STEP - old_indent.0 as i8, body_text);
+    Some(format!("{name}({params}){guard} ->{body_text}"))
+}
+
+#[cfg(test)]
+mod tests {
+    use expect_test::expect;
+
+    use super::*;
+    use crate::tests::*;
+
+    #[test]
+    fn extract_closed_fun() {
+        check_assist(
+            extract_fun_to_function,
+            "Extract fun into function",
+            r#"
+-module(main).
+foo(L) ->
+    lists:map(f~un(X) -> X * 2 end, L).
+"#,
+            expect![[r#"
+                -module(main).
+                foo(L) ->
+                    lists:map(fun fun_name_edited/1, L).
+
+                $0fun_name_edited(X) ->
+                    X * 2.
+            "#]],
+        )
+    }
+
+    #[test]
+    fn extract_fun_with_free_vars() {
+        check_assist(
+            extract_fun_to_function,
+            "Extract fun into function",
+            r#"
+-module(main).
+foo(L, Min, Default) ->
+    lists:map(
+        f~un(X) when X > Min -> X;
+            (_) -> Default
+        end,
+        L).
+"#,
+            expect![[r#"
+                -module(main).
+                foo(L, Min, Default) ->
+                    lists:map(
+                        fun(X) -> fun_name_edited(X, Default, Min) end,
+                        L).
+
+                $0fun_name_edited(X, _Default, Min) when X > Min ->
+                    X;
+                fun_name_edited(_, Default, _Min) ->
+                    Default.
+            "#]],
+        )
+    }
+
+    #[test]
+    fn not_applicable_to_named_fun() {
+        check_assist_not_applicable(
+            extract_fun_to_function,
+            r#"
+-module(main).
+foo(N) ->
+    F = f~un Loop(0) -> ok; Loop(I) -> Loop(I - 1) end,
+    F(N).
+"#,
+        );
+    }
+
+    #[test]
+    fn not_applicable_when_head_shadows_free_var() {
+        check_assist_not_applicable(
+            extract_fun_to_function,
+            r#"
+-module(main).
+foo(L, N) ->
+    lists:map(f~un(N) -> N + 1; (_) -> N end, L).
+"#,
+        );
+    }
+
+    #[test]
+    fn extract_fun_using_macro() {
+        check_assist(
+            extract_fun_to_function,
+            "Extract fun into function",
+            r#"
+-module(main).
+-define(SCALE(X), X * Factor).
+foo(L, Factor) ->
+    lists:map(f~un(X) -> ?SCALE(X) end, L).
+"#,
+            expect![[r#"
+                -module(main).
+                -define(SCALE(X), X * Factor).
+                foo(L, Factor) ->
+                    lists:map(fun(X) -> fun_name_edited(X, Factor) end, L).
+
+                $0fun_name_edited(X, Factor) ->
+                    ?SCALE(X).
+            "#]],
+        )
+    }
+}
@@ -0,0 +1,272 @@
+/*
+ * Copyright (c) Meta Platforms, Inc. and affiliates.
+ *
+ * This source code is dual-licensed under either the MIT license found in the
+ * LICENSE-MIT file in the root directory of this source tree or the Apache
+ * License, Version 2.0 found in the LICENSE-APACHE file in the root directory
+ * of this source tree. You may select, at your option, one of the
+ * above-listed licenses.
+ */
+
+use elp_ide_db::assists::AssistId;
+use elp_ide_db::assists::AssistKind;
+use elp_ide_db::assists::AssistUserInput;
+use elp_ide_db::assists::AssistUserInputType;
+use elp_ide_db::helpers::escape_snippet;
+use elp_ide_db::helpers::top_insert_position;
+use elp_syntax::AstNode;
+use elp_syntax::SyntaxKind;
+use elp_syntax::SyntaxNode;
+use elp_syntax::ast;
+use fxhash::FxHashSet;
+
+use crate::AssistContext;
+use crate::Assists;
+
+// Assist: extract_to_macro
+//
+// Replace a literal integer or string, and its other occurrences in the
+// function or the module, by a new macro.
+//
+// ```
+// foo(X) -> X * ~3600.
+// bar(X) -> X div 3600.
+// ```
+// ->
+// ```
+// -define(${1:VALUE}, 3600).
+// foo(X) -> X * ?${1:VALUE}.
+// bar(X) -> X div ?VALUE.
+// ```
+pub(crate) fn extract_to_macro(acc: &mut Assists, ctx: &AssistContext) -> Option<()> {
+    let literal = ctx
+        .find_node_at_offset::<ast::Integer>()
+        .map(|integer| integer.syntax().clone())
+        .or_else(|| {
+            ctx.find_node_at_offset::<ast::String>()
+                .map(|string| string.syntax().clone())
+        })?;
+    if !literal.text_range().contains_range(ctx.selection_trimmed()) {
+        return None;
+    }
+    let function = literal.ancestors().find_map(ast::FunDecl::cast)?;
+    let source = ctx.sema.parse(ctx.file_id()).value;
+
+    let function_occurrences = occurrences(function.syntax(), &literal);
+    let module_occurrences: Vec<SyntaxNode> = source
+        .forms()
+        .filter_map(|form| match form {
+            ast::Form::FunDecl(fun) => Some(occurrences(fun.syntax(), &literal)),
+            _ => None,
+        })
+        .flatten()
+        .collect();
+
+    let mut scopes = vec![("function", function_occurrences)];
+    if module_occurrences.len() > scopes[0].1.len() {
+        scopes.push(("module", module_occurrences));
+    }
+    for (scope, occurrences) in scopes {
+        let source = &source;
+        let literal = &literal;
+        acc.add(
+            AssistId("extract_to_macro", AssistKind::RefactorExtract),
+            format!("Extract literal into macro (all occurrences in {scope})"),
+            None,
+            literal.text_range(),
+            Some(AssistUserInput {
+                input_type: AssistUserInputType::Variable,
+                prompt: Some("Macro name".to_string()),
+                value: suggest_macro_name(literal),
+                task_id: None,
+            }),
+            move |builder| {
+                let existing: FxHashSet<String> = ctx
+                    .sema
+                    .def_map(ctx.file_id())
+                    .get_macros()
+                    .keys()
+                    .map(|name| name.name().to_string())
+                    .collect();
+                let name = freshen_macro_name(
+                    ctx.user_input_or(|| suggest_macro_name(literal)),
+                    &existing,
+                );
+                let form_list = ctx.sema.form_list(ctx.file_id());
+                let insert = top_insert_position(&form_list, source);
+                match ctx.config.snippet_cap {
+                    Some(cap) => {
+                        builder.insert_snippet(
+                            cap,
+                            insert,
+                            format!(
+                                "\n-define(${{1:{}}}, {}).\n",
+                                escape_snippet(&name),
+                                escape_snippet(&literal.text().to_string())
+                            ),
+                        );
+                        // The selected literal gets the same tab stop, so the
+                        // name is edited at both places at once.
+                        for occurrence in &occurrences {
+                            let range = occurrence.text_range();
+                            if range == literal.text_range() {
+                                builder.delete(range);
+                                builder.insert_snippet(
+                                    cap,
+                                    range.start(),
+                                    format!("?${{1:{}}}", escape_snippet(&name)),
+                                );
+                            } else {
+                                builder.replace(range, format!("?{name}"));
+                            }
+                        }
+                    }
+                    None => {
+                        builder.insert(insert, format!("\n-define({name}, {literal}).\n"));
+                        for occurrence in &occurrences {
+                            builder.replace(occurrence.text_range(), format!("?{name}"));
+                        }
+                    }
+                }
+            },
+        );
+    }
+    Some(())
+}
+
+/// The literals with the same kind and text as `literal` in `scope`.
+/// Only whole literals match, so the text is never replaced inside a
+/// longer string or an atom.
+fn occurrences(scope: &SyntaxNode, literal: &SyntaxNode) -> Vec<SyntaxNode> {
+    scope
+        .descendants()
+        .filter(|node| node.kind() == literal.kind() && node.text() == literal.text())
+        .collect()
+}
+
+/// An uppercase macro name from the contents of a string, e.g. `"max
+/// retries"` gives `MAX_RETRIES`. Integers only give a generic name.
+fn suggest_macro_name(literal: &SyntaxNode) -> String {
+    let name = if literal.kind() == SyntaxKind::STRING {
+        literal
+            .text()
+            .to_string()
+            .to_uppercase()
+            .split(|c: char| !c.is_ascii_alphanumeric())
+            .filter(|part| !part.is_empty())
+            .collect::<Vec<_>>()
+            .join("_")
+    } else {
+        String::new()
+    };
+    if name.starts_with(|c: char| c.is_ascii_alphabetic()) {
+        name
+    } else {
+        "VALUE".to_string()
+    }
+}
+
+fn freshen_macro_name(name: String, existing: &FxHashSet<String>) -> String {
+    if !existing.contains(&name) {
+        return name;
+    }
+    (0..)
+        .map(|i| format!("{name}{i}"))
+        .find(|candidate| !existing.contains(candidate))
+        .unwrap()
+}
+
+#[cfg(test)]
+mod tests {
+    use expect_test::expect;
+
+    use super::*;
+    use crate::tests::*;
+
+    #[test]
+    fn extract_integer_in_function() {
+        check_assist(
+            extract_to_macro,
+            "Extract literal into macro (all occurrences in function)",
+            r#"
+-module(main).
+foo(X) -> {X * ~3600, X div 3600, 36000}.
+bar(X) -> X div 3600.
+"#,
+            expect![[r#"
+                -module(main).
+
+                -define(${1:VALUEEdited}, 3600).
+                foo(X) -> {X * ?${1:VALUEEdited}, X div ?VALUEEdited, 36000}.
+                bar(X) -> X div 3600.
+            "#]],
+        )
+    }
+
+    #[test]
+    fn extract_integer_in_module() {
+        check_assist(
+            extract_to_macro,
+            "Extract literal into macro (all occurrences in module)",
+            r#"
+-module(main).
+foo(X) -> X * ~3600.
+bar(X) -> X div 3600.
+"#,
+            expect![[r#"
+                -module(main).
+
+                -define(${1:VALUEEdited}, 3600).
+                foo(X) -> X * ?${1:VALUEEdited}.
+                bar(X) -> X div ?VALUEEdited.
+            "#]],
+        )
+    }
+
+    #[test]
+    fn extract_string() {
+        check_assist(
+            extract_to_macro,
+            "Extract literal into macro (all occurrences in function)",
+            r#"
+-module(main).
+foo() -> {"max ret~ries", 'max retries', "max retries!", "max retries"}.
+"#,
+            expect![[r#"
+                -module(main).
+
+                -define(${1:MAX_RETRIESEdited}, "max retries").
+                foo() -> {?${1:MAX_RETRIESEdited}, 'max retries', "max retries!", ?MAX_RETRIESEdited}.
+            "#]],
+        )
+    }
+
+    #[test]
+    fn not_applicable_outside_literal() {
+        check_assist_not_applicable(
+            extract_to_macro,
+            r#"
+-module(main).
+foo(X) -> ~X * 3600.
+"#,
+        );
+    }
+
+    #[test]
+    fn extract_string_escapes_snippet() {
+        check_assist(
+            extract_to_macro,
+            "Extract literal into macro (all occurrences in function)",
+            r#"
+-module(main).
+foo() -> "${pr~ice}".
+"#,
+            expect![[r#"
+                -module(main).
+
+                -define(${1:PRICEEdited}, "\${price\}").
+                foo() -> ?${1:PRICEEdited}.
+            "#]],
+        )
+    }
+}
@@ -0,0 +1,178 @@
+/*
+ * Copyright (c) Meta Platforms, Inc. and affiliates.
+ *
+ * This source code is dual-licensed under either the MIT license found in the
+ * LICENSE-MIT file in the root directory of this source tree or the Apache
+ * License, Version 2.0 found in the LICENSE-APACHE file in the root directory
+ * of this source tree. You may select, at your option, one of the
+ * above-listed licenses.
+ */
+
+use elp_ide_db::assists::AssistId;
+use elp_ide_db::assists::AssistKind;
+use elp_syntax::AstNode;
+use elp_syntax::ast;
+use elp_syntax::ast::BinaryOp;
+use elp_syntax::ast::LogicOp;
+
+use crate::AssistContext;
+use crate::Assists;
+use crate::helpers::is_safe_test;
+
+// Assist: guard_to_boolean_operators
+//
+// Rewrite a guard sequence using `,` and `;` into a single guard using
+// `andalso` and `orelse`.
+//
+// ```
+// foo(X) when is_integer(X), X > 0; is_float(X) -> ok.
+// ```
+// ->
+// ```
+// foo(X) when is_integer(X) andalso X > 0 orelse is_float(X) -> ok.
+// ```
+//
+// In `G1; G2`, an exception in `G1` only fails that guard, and `G2` is
+// still tried, while in `G1 orelse G2` it fails the whole guard. So `;` is
+// only rewritten when the guards before the last one are tests that
+// cannot raise an exception.
+pub(crate) fn guard_to_boolean_operators(acc: &mut Assists, ctx: &AssistContext) -> Option<()> {
+    let guard = ctx.find_node_at_offset::<ast::Guard>()?;
+    let clauses: Vec<Vec<ast::Expr>> = guard
+        .clauses()
+        .map(|clause| clause.exprs().collect())
+        .collect();
+    if clauses.iter().any(|exprs| exprs.is_empty())
+        || (clauses.len() < 2 && clauses.iter().all(|exprs| exprs.len() < 2))
+    {
+        return None;
+    }
+    let init = &clauses[..clauses.len() - 1];
+    if !init.iter().flatten().all(is_safe_test) {
+        return None;
+    }
+    let text = clauses
+        .iter()
+        .map(|exprs| conjunction(exprs))
+        .collect::<Vec<_>>()
+        .join(" orelse ");
+    let range = guard.syntax().text_range();
+    acc.add(
+        AssistId("guard_to_boolean_operators", AssistKind::RefactorRewrite),
+        "Rewrite guard using `andalso` and `orelse`",
+        None,
+        range,
+        None,
+        |builder| {
+            builder.replace(range, text);
+        },
+    )
+}
+
+/// The guard tests joined with `andalso`. Only `orelse` binds less
+/// tightly, so only an `orelse` test needs parentheses.
+fn conjunction(exprs: &[ast::Expr]) -> String {
+    if let [expr] = exprs {
+        return expr.syntax().text().to_string();
+    }
+    exprs
+        .iter()
+        .map(|expr| {
+            let text = expr.syntax().text().to_string();
+            if is_orelse(expr) {
+                format!("({text})")
+            } else {
+                text
+            }
+        })
+        .collect::<Vec<_>>()
+        .join(" andalso ")
+}
+
+fn is_orelse(expr: &ast::Expr) -> bool {
+    match expr {
+        ast::Expr::BinaryOpExpr(binary) => matches!(
+            binary.op(),
+            Some((BinaryOp::LogicOp(LogicOp::Or { lazy: true }), _))
+        ),
+        _ => false,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use expect_test::expect;
+
+    use super::*;
+    use crate::tests::*;
+
+    #[test]
+    fn test_comma() {
+        check_assist(
+            guard_to_boolean_operators,
+            "Rewrite guard using `andalso` and `orelse`",
+            r#"
+foo(X) when is_in~teger(X), X > 0, length(X) < 3 -> ok.
+"#,
+            expect![[r#"
+                foo(X) when is_integer(X) andalso X > 0 andalso length(X) < 3 -> ok.
+            "#]],
+        )
+    }
+
+    #[test]
+    fn test_semicolon() {
+        check_assist(
+            guard_to_boolean_operators,
+            "Rewrite guard using `andalso` and `orelse`",
+            r#"
+foo(X) ->
+    case X of
+        Y when is_atom(Y); is_bi~nary(Y); length(Y) > 1 -> Y
+    end.
+"#,
+            expect![[r#"
+                foo(X) ->
+                    case X of
+                        Y when is_atom(Y) orelse is_binary(Y) orelse length(Y) > 1 -> Y
+                    end.
+            "#]],
+        )
+    }
+
+    #[test]
+    fn test_mixed() {
+        check_assist(
+            guard_to_boolean_operators,
+            "Rewrite guard using `andalso` and `orelse`",
+            r#"
+foo(X, Y) when is_integer(X), X < 0 orelse X > 100; ~X =:= Y, is_atom(Y) -> ok.
+"#,
+            expect![[r#"
+                foo(X, Y) when is_integer(X) andalso (X < 0 orelse X > 100) orelse X =:= Y andalso is_atom(Y) -> ok.
+            "#]],
+        )
+    }
+
+    #[test]
+    fn test_single_test() {
+        check_assist_not_applicable(
+            guard_to_boolean_operators,
+            r#"
+foo(X) when is_in~teger(X) -> ok.
+"#,
+        );
+    }
+
+    #[test]
+    fn test_semicolon_after_raising_test() {
+        // `length(X)` raises for an atom, which then still matches with
+        // `;`, but not with `orelse`
+        check_assist_not_applicable(
+            guard_to_boolean_operators,
+            r#"
+foo(X) when length(X) > 1; is_at~om(X) -> ok.
+"#,
+        );
+    }
+}
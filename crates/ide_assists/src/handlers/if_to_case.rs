@@ -0,0 +1,332 @@
+/*
+ * Copyright (c) Meta Platforms, Inc. and affiliates.
+ *
+ * This source code is dual-licensed under either the MIT license found in the
+ * LICENSE-MIT file in the root directory of this source tree or the Apache
+ * License, Version 2.0 found in the LICENSE-APACHE file in the root directory
+ * of this source tree. You may select, at your option, one of the
+ * above-listed licenses.
+ */
+
+use elp_ide_db::assists::AssistId;
+use elp_ide_db::assists::AssistKind;
+use elp_syntax::AstNode;
+use elp_syntax::SyntaxKind;
+use elp_syntax::ast;
+use elp_syntax::ast::BinaryOp;
+use elp_syntax::ast::CompOp;
+
+use crate::AssistContext;
+use crate::Assists;
+use crate::helpers::is_safe_value;
+
+// Assist: if_to_case
+//
+// Rewrite an `if` expression as an equivalent `case` expression.
+//
+// ```
+// foo(X) ->
+//     i~f
+//         X > 10 -> big;
+//         true -> small
+//     end.
+// ```
+// ->
+// ```
+// foo(X) ->
+//     case ok of
+//         _ when X > 10 -> big;
+//         _ -> small
+//     end.
+// ```
+//
+// When all the guards compare the same variable or literal to a literal
+// with `=:=`, it becomes the subject of the `case`:
+//
+// ```
+// foo(X) ->
+//     i~f
+//         X =:= a -> one;
+//         true -> other
+//     end.
+// ```
+// ->
+// ```
+// foo(X) ->
+//     case X of
+//         a -> one;
+//         _ -> other
+//     end.
+// ```
+//
+// An `if` without a `true ->` branch gets a last clause raising the
+// `if_clause` error, so that the error raised when no guard succeeds is
+// unchanged.
+pub(crate) fn if_to_case(acc: &mut Assists, ctx: &AssistContext) -> Option<()> {
+    let if_expr = ctx.find_node_at_offset::<ast::IfExpr>()?;
+    let if_token = if_expr.syntax().first_token()?;
+    if if_token.kind() != SyntaxKind::ANON_IF {
+        return None;
+    }
+    let clauses: Vec<_> = if_expr.clauses().collect();
+    let guards = clauses
+        .iter()
+        .map(|clause| clause.guard())
+        .collect::<Option<Vec<_>>>()?;
+    let last_clause = clauses.last()?;
+    let if_clause_error = if guards.iter().any(is_catch_all) {
+        None
+    } else {
+        Some((
+            last_clause.syntax().text_range().end(),
+            format!(
+                ";{}_ -> erlang:error(if_clause)",
+                clause_separator(last_clause)
+            ),
+        ))
+    };
+
+    let (head, patterns) = match equality_patterns(&guards) {
+        Some((subject, patterns)) => (format!("case {subject} of"), patterns),
+        None => {
+            let patterns = guards
+                .iter()
+                .map(|guard| {
+                    if is_catch_all(guard) {
+                        "_".to_string()
+                    } else {
+                        format!("_ when {}", guard.syntax().text())
+                    }
+                })
+                .collect();
+            ("case ok of".to_string(), patterns)
+        }
+    };
+
+    acc.add(
+        AssistId("if_to_case", AssistKind::RefactorRewrite),
+        "Convert `if` to `case`",
+        None,
+        if_expr.syntax().text_range(),
+        None,
+        |builder| {
+            builder.replace(if_token.text_range(), head);
+            for (guard, pattern) in guards.iter().zip(patterns) {
+                builder.replace(guard.syntax().text_range(), pattern);
+            }
+            if let Some((offset, clause)) = if_clause_error {
+                builder.insert(offset, clause);
+            }
+        },
+    )
+}
+
+/// The whitespace separating `clause` from the previous one, or a single
+/// space.
+fn clause_separator(clause: &ast::IfClause) -> String {
+    match clause.syntax().prev_sibling_or_token() {
+        Some(prev) if prev.kind() == SyntaxKind::WHITESPACE => prev.to_string(),
+        _ => " ".to_string(),
+    }
+}
+
+/// The `true ->` branch of an `if` expression.
+fn is_catch_all(guard: &ast::Guard) -> bool {
+    guard.syntax().text() == "true"
+}
+
+/// If every guard, except the catch-all one, is of the form `X =:= V` with
+/// the same `X` and a literal `V`, return `X` and the pattern replacing each
+/// of the guards.
+fn equality_patterns(guards: &[ast::Guard]) -> Option<(String, Vec<String>)> {
+    let mut subject: Option<String> = None;
+    let mut patterns = Vec::new();
+    for guard in guards {
+        if is_catch_all(guard) {
+            patterns.push("_".to_string());
+            continue;
+        }
+        let (lhs, rhs) = strict_equality(guard)?;
+        match &subject {
+            Some(subject) if *subject != lhs => return None,
+            Some(_) => (),
+            None => subject = Some(lhs),
+        }
+        patterns.push(rhs);
+    }
+    Some((subject?, patterns))
+}
+
+/// The operands of a guard consisting of a single `X =:= V` test, where `V`
+/// is a literal that can be used as a pattern. `X` must not raise an
+/// exception, which would only fail the guard but crash the `case`.
+fn strict_equality(guard: &ast::Guard) -> Option<(String, String)> {
+    let mut clauses = guard.clauses();
+    let clause = clauses.next()?;
+    let mut exprs = clause.exprs();
+    let expr = exprs.next()?;
+    if clauses.next().is_some() || exprs.next().is_some() {
+        return None;
+    }
+    let ast::Expr::BinaryOpExpr(binop) = expr else {
+        return None;
+    };
+    match binop.op()?.0 {
+        BinaryOp::CompOp(CompOp::Eq {
+            strict: true,
+            negated: false,
+        }) => (),
+        _ => return None,
+    }
+    let lhs = binop.lhs()?;
+    if !is_safe_value(&lhs) {
+        return None;
+    }
+    let rhs = binop.rhs()?;
+    match &rhs {
+        ast::Expr::ExprMax(
+            ast::ExprMax::Atom(_)
+            | ast::ExprMax::Char(_)
+            | ast::ExprMax::Float(_)
+            | ast::ExprMax::Integer(_)
+            | ast::ExprMax::String(_),
+        ) => (),
+        _ => return None,
+    }
+    Some((
+        lhs.syntax().text().to_string(),
+        rhs.syntax().text().to_string(),
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use expect_test::expect;
+
+    use super::*;
+    use crate::tests::*;
+
+    #[test]
+    fn general_guards() {
+        check_assist(
+            if_to_case,
+            "Convert `if` to `case`",
+            r#"
+foo(X) ->
+    i~f
+        X > 10 -> big;
+        is_atom(X); X < 0 -> other;
+        true -> small
+    end.
+"#,
+            expect![[r#"
+                foo(X) ->
+                    case ok of
+                        _ when X > 10 -> big;
+                        _ when is_atom(X); X < 0 -> other;
+                        _ -> small
+                    end.
+            "#]],
+        )
+    }
+
+    #[test]
+    fn general_guards_without_catch_all() {
+        check_assist(
+            if_to_case,
+            "Convert `if` to `case`",
+            r#"
+foo(X) ->
+    ~if X > 10, X < 20 -> teen; X < 10 -> child end.
+"#,
+            expect![[r#"
+                foo(X) ->
+                    case ok of _ when X > 10, X < 20 -> teen; _ when X < 10 -> child; _ -> erlang:error(if_clause) end.
+            "#]],
+        )
+    }
+
+    #[test]
+    fn simple_equality() {
+        check_assist(
+            if_to_case,
+            "Convert `if` to `case`",
+            r#"
+foo(X) ->
+    i~f
+        X =:= a -> one;
+        X =:= 2 -> two;
+        X =:= "three" -> three;
+        true -> other
+    end.
+"#,
+            expect![[r#"
+                foo(X) ->
+                    case X of
+                        a -> one;
+                        2 -> two;
+                        "three" -> three;
+                        _ -> other
+                    end.
+            "#]],
+        )
+    }
+
+    #[test]
+    fn equality_on_different_expressions() {
+        check_assist(
+            if_to_case,
+            "Convert `if` to `case`",
+            r#"
+foo(X, Y) ->
+    i~f
+        X =:= a -> one;
+        Y =:= b -> two
+    end.
+"#,
+            expect![[r#"
+                foo(X, Y) ->
+                    case ok of
+                        _ when X =:= a -> one;
+                        _ when Y =:= b -> two;
+                        _ -> erlang:error(if_clause)
+                    end.
+            "#]],
+        )
+    }
+
+    #[test]
+    fn equality_on_expression_which_can_raise() {
+        check_assist(
+            if_to_case,
+            "Convert `if` to `case`",
+            r#"
+foo(L) ->
+    i~f
+        hd(L) =:= a -> one;
+        true -> other
+    end.
+"#,
+            expect![[r#"
+                foo(L) ->
+                    case ok of
+                        _ when hd(L) =:= a -> one;
+                        _ -> other
+                    end.
+            "#]],
+        )
+    }
+
+    #[test]
+    fn not_in_if() {
+        check_assist_not_applicable(
+            if_to_case,
+            r#"
+foo(X) ->
+    ca~se X of
+        a -> one
+    end.
+"#,
+        );
+    }
+}
@@ -13,9 +13,11 @@ use std::cmp::max;
 use elp_ide_db::assists::AssistId;
 use elp_ide_db::assists::AssistKind;
 use elp_syntax::AstNode;
+use elp_syntax::ast;
 use elp_syntax::ast::BehaviourAttribute;
 use elp_text_edit::TextRange;
 use elp_text_edit::TextSize;
+use hir::AsName;
 use hir::Callback;
 use hir::CallbackId;
 use hir::InFile;
@@ -28,26 +30,31 @@ use crate::helpers;
 
 // Assist: implement_behaviour
 //
-// Implement and export all callbacks when on behaviour attribute
+// Implement and export all callbacks when on behaviour attribute. The
+// stubs, with a spec taken from the callback, are added after the last
+// function of the module.
 //
 // ```
 // -behaviour(gen_server).
 //
+// foo() -> ok.
 // ->
 // ```
 // -behaviour(gen_server).
 //
 // %% Callbacks for `gen_server`
-// -export([init/1, handle_call/3, handle_cast/2]).
+// -export([init/1, handle_call/3]).
 //
+// foo() -> ok.
+//
+// -spec init(Args :: term()) -> {ok, State :: term()}.
 // init(Args) ->
 //     erlang:error(not_implemented).
 //
+// -spec handle_call(Request :: term(), From :: gen_server:from(), State :: term()) ->
+//     {reply, Reply :: term(), NewState :: term()}.
 // handle_call(Request,From,State) ->
 //     erlang:error(not_implemented).
-//
-// handle_cast(Request,State) ->
-//     erlang:error(not_implemented).
 // ```
 
 pub(crate) fn implement_behaviour(acc: &mut Assists, ctx: &AssistContext) -> Option<()> {
@@ -66,11 +73,8 @@ pub(crate) fn implement_behaviour(acc: &mut Assists, ctx: &AssistContext) -> Opt
     behaviour_forms
         .callback_attributes()
         .for_each(|(idx, callback)| {
-            let implemented = our_forms
-                .function_clauses()
-                .find(|(_, f)| f.name == callback.name);
-            match implemented {
-                Some((_idx, fun)) => {
+            match our_def_map.get_function(&callback.name) {
+                Some(fun) => {
                     if module_def_map.is_callback_optional(&callback.name) {
                         if existing_optional_callback.is_none() {
                             existing_optional_callback = Some(callback.name.clone());
@@ -110,9 +114,22 @@ pub(crate) fn implement_behaviour(acc: &mut Assists, ctx: &AssistContext) -> Opt
         None => attr_range.end(),
     };
     let insert_at = insert_start + TextSize::from(1);
+    let last_function_end = our_forms.function_clauses().last().map(|(_idx, fun)| {
+        fun.form_id
+            .get_ast(ctx.sema.db, ctx.file_id())
+            .syntax()
+            .text_range()
+            .end()
+    });
 
-    let mut implement_callbacks =
-        ImplementCallbacks::new(ctx, &behaviour, attr_range, insert_at, acc);
+    let mut implement_callbacks = ImplementCallbacks::new(
+        ctx,
+        &behaviour,
+        attr_range,
+        insert_at,
+        last_function_end,
+        acc,
+    );
 
     if !additions.is_empty() {
         let id = AssistId("implement_callbacks", AssistKind::QuickFix);
@@ -153,6 +170,7 @@ struct ImplementCallbacks<'a> {
     acc: &'a mut Assists,
     attr_range: TextRange,
     insert_at: TextSize,
+    last_function_end: Option<TextSize>,
 }
 
 impl<'a> ImplementCallbacks<'a> {
@@ -161,6 +179,7 @@ impl<'a> ImplementCallbacks<'a> {
         behaviour: &'a Module,
         attr_range: TextRange,
         insert_at: TextSize,
+        last_function_end: Option<TextSize>,
         acc: &'a mut Assists,
     ) -> ImplementCallbacks<'a> {
         ImplementCallbacks {
@@ -168,6 +187,7 @@ impl<'a> ImplementCallbacks<'a> {
             behaviour,
             attr_range,
             insert_at,
+            last_function_end,
             acc,
         }
     }
@@ -197,9 +217,16 @@ impl<'a> ImplementCallbacks<'a> {
                 }
                 export_builder.finish();
                 builder.edit_file(self.ctx.frange.file_id);
-                let mut text = texts.join("\n");
-                text.push('\n');
-                builder.insert(self.insert_at, text)
+                match self.last_function_end {
+                    Some(end) => {
+                        let text: String = texts.iter().map(|text| format!("\n\n{text}")).collect();
+                        builder.insert(end, text)
+                    }
+                    None => {
+                        let text: String = texts.iter().map(|text| format!("\n{text}\n")).collect();
+                        builder.insert(self.insert_at, text)
+                    }
+                }
             });
     }
 }
@@ -239,10 +266,11 @@ fn make_implementation(
             if let Some(sig) = callback_body.sigs.first() {
                 let function_args =
                     ctx.create_function_args_from_types(&sig.args, &callback_body.body);
+                let spec = make_spec(ctx, behaviour, callback)?;
                 let addition = (
                     callback.name.clone(),
                     Some(format!(
-                        "\n{function_name}({function_args}) ->\n    erlang:error(not_implemented)."
+                        "-spec {spec}\n{function_name}({function_args}) ->\n    erlang:error(not_implemented)."
                     )),
                 );
                 Some(addition)
@@ -254,6 +282,60 @@ fn make_implementation(
     }
 }
 
+/// The signature of the callback, with the types local to the behaviour
+/// module qualified by its name, so that they resolve in the implementing
+/// module. A local type which is not exported cannot be referenced from
+/// another module, so it is replaced by `term()`.
+fn make_spec(ctx: &AssistContext<'_>, behaviour: &Module, callback: &Callback) -> Option<String> {
+    let callback_ast = callback
+        .form_id
+        .get_ast(ctx.sema.db, behaviour.file.file_id);
+    let start = callback_ast.fun()?.syntax().text_range().start();
+    let behaviour_def_map = ctx.sema.def_map(behaviour.file.file_id);
+    let module = behaviour.name(ctx.db());
+    let mut edits: Vec<(TextRange, String)> = callback_ast
+        .syntax()
+        .descendants()
+        .filter_map(ast::Call::cast)
+        .filter_map(|call| match call.expr()? {
+            ast::Expr::ExprMax(ast::ExprMax::Atom(atom)) => {
+                let arity = call.args()?.args().count() as u32;
+                let type_alias =
+                    behaviour_def_map.get_type(&NameArity::new(atom.as_name(), arity))?;
+                if type_alias.exported {
+                    let type_start = atom.syntax().text_range().start();
+                    Some((TextRange::empty(type_start), format!("{module}:")))
+                } else {
+                    Some((call.syntax().text_range(), "term()".to_string()))
+                }
+            }
+            _ => None,
+        })
+        .collect();
+    // Drop the edits nested in a type replaced by `term()`, and apply the
+    // others from the end
+    let replaced: Vec<TextRange> = edits
+        .iter()
+        .map(|(range, _)| *range)
+        .filter(|range| !range.is_empty())
+        .collect();
+    edits.retain(|(range, _)| {
+        !replaced
+            .iter()
+            .any(|outer| outer.contains_range(*range) && outer != range)
+    });
+    edits.sort_by_key(|(range, _)| std::cmp::Reverse(range.start()));
+    let mut text = callback_ast.syntax().text().to_string();
+    let offset = callback_ast.syntax().text_range().start();
+    for (range, replacement) in edits {
+        text.replace_range(
+            usize::from(range.start() - offset)..usize::from(range.end() - offset),
+            &replacement,
+        );
+    }
+    Some(text.split_off(usize::from(start - offset)))
+}
+
 // ---------------------------------------------------------------------
 
 #[cfg(test)]
@@ -289,6 +371,9 @@ mod tests {
                 %% Callbacks for `supervisor`
                 -export([init/1]).
 
+                -spec init(Args :: term()) ->
+                    {ok, {SupFlags :: sup_flags(), [ChildSpec :: child_spec()]}}
+                    | ignore.
                 init(Args) ->
                     erlang:error(not_implemented).
 
@@ -329,14 +414,25 @@ mod tests {
                 %% Callbacks for `gen_server`
                 -export([init/1, handle_call/3]).
 
+                existing_fun() -> ok.
+
+                -spec init(Args :: term()) ->
+                    {ok, State :: term()} | {ok, State :: term(), timeout() | hibernate | {continue, term()}} |
+                    {stop, Reason :: term()} | ignore.
                 init(Args) ->
                     erlang:error(not_implemented).
 
+                -spec handle_call(Request :: term(), From :: from(),
+                                      State :: term()) ->
+                    {reply, Reply :: term(), NewState :: term()} |
+                    {reply, Reply :: term(), NewState :: term(), timeout() | hibernate | {continue, term()}} |
+                    {noreply, NewState :: term()} |
+                    {noreply, NewState :: term(), timeout() | hibernate | {continue, term()}} |
+                    {stop, Reason :: term(), Reply :: term(), NewState :: term()} |
+                    {stop, Reason :: term(), NewState :: term()}.
                 handle_call(Request,From,State) ->
                     erlang:error(not_implemented).
 
-                existing_fun() -> ok.
-
             "#]],
         )
     }
@@ -364,11 +460,12 @@ mod tests {
                 -behaviour(my_behaviour).
                 -export([init/1, another/0]).
 
+                init(_) -> already_done,ok.
+
+                -spec another() -> ok.
                 another() ->
                     erlang:error(not_implemented).
 
-                init(_) -> already_done,ok.
-
             "#]],
         )
     }
@@ -406,13 +503,14 @@ mod tests {
                 %% Callbacks for `my_behaviour`
                 -export([init/1, another/0]).
 
-                another() ->
-                    erlang:error(not_implemented).
-
                 init(_) -> already_done,ok.
 
                 foo() -> ok.
 
+                -spec another() -> ok.
+                another() ->
+                    erlang:error(not_implemented).
+
             "#]],
         )
     }
@@ -447,13 +545,14 @@ mod tests {
                 %% Callbacks for `my_behaviour`
                 -export([init/1, another/0]).
 
-                another() ->
-                    erlang:error(not_implemented).
-
                 init(_) -> already_done,ok.
 
                 foo() -> ok.
 
+                -spec another() -> ok.
+                another() ->
+                    erlang:error(not_implemented).
+
             "#]],
         )
     }
@@ -486,11 +585,12 @@ mod tests {
                 %% Callbacks for `my_behaviour`
                 -export([another/0]).
 
+                init(_) -> already_done,ok.
+
+                -spec another() -> ok.
                 another() ->
                     erlang:error(not_implemented).
 
-                init(_) -> already_done,ok.
-
             "#]],
         )
     }
@@ -523,11 +623,12 @@ mod tests {
                 %% Optional callbacks for `my_behaviour`
                 -export([init/1, optional/0]).
 
+                init(_) -> already_done,ok.
+
+                -spec optional() -> ok.
                 optional() ->
                     erlang:error(not_implemented).
 
-                init(_) -> already_done,ok.
-
             "#]],
         )
     }
@@ -556,12 +657,113 @@ mod tests {
                 %% Callbacks for `my_behaviour`
                 -export([init/1, another/0]).
 
+                init(_) -> already_done,ok.
+
+                -spec another() -> ok.
                 another() ->
                     erlang:error(not_implemented).
 
-                init(_) -> already_done,ok.
+            "#]],
+        )
+    }
+
+    #[test]
+    fn qualifies_behaviour_types_in_specs() {
+        check_assist(
+            implement_behaviour,
+            "Create callbacks for 'my_behaviour'",
+            r#"
+            //- /src/main.erl
+            -module(main).
+            -behaviour(my_b~ehaviour).
+            -export([foo/0]).
+
+            foo() -> ok.
+
+            //- /src/my_behaviour.erl
+            -module(my_behaviour).
+            -export_type([state/0]).
+            -type state() :: #{atom() => term()}.
+            -callback init(Args :: list()) -> {ok, state()}.
+            -callback handle(Msg :: term(), State :: state()) -> {ok, state()}.
+             "#,
+            expect![[r#"
+                -module(main).
+                -behaviour(my_behaviour).
+                -export([foo/0]).
+
+                %% Callbacks for `my_behaviour`
+                -export([init/1, handle/2]).
+
+                foo() -> ok.
+
+                -spec init(Args :: list()) -> {ok, my_behaviour:state()}.
+                init(Args) ->
+                    erlang:error(not_implemented).
+
+                -spec handle(Msg :: term(), State :: my_behaviour:state()) -> {ok, my_behaviour:state()}.
+                handle(Msg,State) ->
+                    erlang:error(not_implemented).
 
             "#]],
         )
     }
+
+    #[test]
+    fn unexported_behaviour_types_become_term() {
+        check_assist(
+            implement_behaviour,
+            "Create callbacks for 'my_behaviour'",
+            r#"
+            //- /src/main.erl
+            -module(main).
+            -behaviour(my_b~ehaviour).
+
+            //- /src/my_behaviour.erl
+            -module(my_behaviour).
+            -export_type([state/0]).
+            -type state() :: #{atom() => term()}.
+            -type result(T) :: {ok, T}.
+            -callback init(Args :: list()) -> result(state()).
+            -callback handle(State :: state()) -> state().
+             "#,
+            expect![[r#"
+                -module(main).
+                -behaviour(my_behaviour).
+
+                %% Callbacks for `my_behaviour`
+                -export([init/1, handle/1]).
+
+                -spec init(Args :: list()) -> term().
+                init(Args) ->
+                    erlang:error(not_implemented).
+
+                -spec handle(State :: my_behaviour:state()) -> my_behaviour:state().
+                handle(State) ->
+                    erlang:error(not_implemented).
+
+            "#]],
+        )
+    }
+
+    #[test]
+    fn not_applicable_when_all_callbacks_implemented() {
+        check_assist_not_applicable(
+            implement_behaviour,
+            r#"
+            //- /src/main.erl
+            -module(main).
+            -behaviour(my_b~ehaviour).
+            -export([init/1, another/0]).
+
+            init(_) -> ok.
+            another() -> ok.
+
+            //- /src/my_behaviour.erl
+            -module(my_behaviour).
+            -callback init(Args :: term()) -> ok.
+            -callback another() -> ok.
+             "#,
+        );
+    }
 }
@@ -0,0 +1,372 @@
+/*
+ * Copyright (c) Meta Platforms, Inc. and affiliates.
+ *
+ * This source code is dual-licensed under either the MIT license found in the
+ * LICENSE-MIT file in the root directory of this source tree or the Apache
+ * License, Version 2.0 found in the LICENSE-APACHE file in the root directory
+ * of this source tree. You may select, at your option, one of the
+ * above-listed licenses.
+ */
+
+use elp_ide_db::assists::AssistId;
+use elp_ide_db::assists::AssistKind;
+use elp_syntax::AstNode;
+use elp_syntax::SyntaxKind;
+use elp_syntax::TextRange;
+use elp_syntax::ast;
+use elp_syntax::match_ast;
+use fxhash::FxHashMap;
+use fxhash::FxHashSet;
+use hir::AsName;
+use hir::InFile;
+use hir::NameArity;
+
+use crate::AssistContext;
+use crate::Assists;
+use crate::helpers::expr_needs_parens_ast;
+use crate::helpers::freshen_variable_name;
+
+// Assist: inline_function_call
+//
+// Replace a call to a local function having a single clause, whose body is a
+// single expression, by that expression with the arguments substituted for
+// the parameters. The function definition is kept.
+//
+// Only variables and literals are substituted. Any other argument is first
+// bound to a fresh variable, so that it is still evaluated exactly once.
+//
+// ```
+// double(X) -> X * 2.
+// foo(Y) -> 1 + do~uble(Y).
+// ```
+// ->
+// ```
+// double(X) -> X * 2.
+// foo(Y) -> 1 + (Y * 2).
+// ```
+pub(crate) fn inline_function_call(acc: &mut Assists, ctx: &AssistContext) -> Option<()> {
+    let call = ctx.find_node_at_offset::<ast::Call>()?;
+    let atom = match call.expr()? {
+        ast::Expr::ExprMax(ast::ExprMax::Atom(atom)) => atom,
+        _ => return None,
+    };
+    if !atom.syntax().text_range().contains_inclusive(ctx.offset()) {
+        return None;
+    }
+    let args: Vec<ast::Expr> = call.args()?.args().collect();
+    let name = NameArity::new(atom.as_name(), args.len() as u32);
+    let fun = ctx.sema.def_map(ctx.file_id()).get_function(&name)?.clone();
+    if fun.file.file_id != ctx.file_id() {
+        return None;
+    }
+    let fun_decls = fun.source(ctx.sema.db.upcast());
+    let [fun_decl] = fun_decls.as_slice() else {
+        return None;
+    };
+    let clause = match fun_decl.clause()? {
+        ast::FunctionOrMacroClause::FunctionClause(clause) => clause,
+        ast::FunctionOrMacroClause::MacroCallExpr(_) => return None,
+    };
+    if clause.guard().is_some() {
+        return None;
+    }
+    let mut exprs = clause.body()?.exprs();
+    let body = exprs.next()?;
+    if exprs.next().is_some() || is_recursive(&body, &name) {
+        return None;
+    }
+
+    let params = clause
+        .args()?
+        .args()
+        .map(|param| match param {
+            ast::Expr::ExprMax(ast::ExprMax::Var(var)) => Some(var.syntax().text().to_string()),
+            _ => None,
+        })
+        .collect::<Option<Vec<_>>>()?;
+    if params
+        .iter()
+        .enumerate()
+        .any(|(i, p)| params[..i].contains(p))
+    {
+        return None;
+    }
+
+    // Only the parameters can be used in the body, so that no variable of
+    // the body can capture, or clash with, a variable of the call site
+    let vars: Vec<ast::Var> = body
+        .syntax()
+        .descendants()
+        .filter_map(ast::Var::cast)
+        .collect();
+    let mut used: FxHashSet<&str> = FxHashSet::default();
+    for var in &vars {
+        let param = params.iter().find(|p| **p == var.syntax().text())?;
+        used.insert(param.as_str());
+    }
+
+    // Substituting an argument having side effects could evaluate it
+    // several times, or not at all, so it is bound to a fresh variable
+    let vars_in_clause = ast::Expr::cast(call.syntax().clone()).and_then(|expr| {
+        ctx.sema
+            .find_vars_in_clause_ast(&InFile::new(ctx.file_id(), &expr))
+    });
+    let mut bindings: Vec<String> = Vec::new();
+    let mut fresh: Vec<String> = Vec::new();
+    let mut values: FxHashMap<String, String> = FxHashMap::default();
+    for (param, value) in params.iter().zip(args) {
+        let value_text = value.syntax().text().to_string();
+        if is_trivial(&value) {
+            values.insert(param.clone(), value_text);
+            continue;
+        }
+        if !used.contains(param.as_str()) {
+            bindings.push(format!("_ = {value_text}"));
+            continue;
+        }
+        let mut name = freshen_variable_name(&ctx.sema, param.clone(), &vars_in_clause);
+        while fresh.contains(&name) {
+            name = format!("{name}_");
+        }
+        bindings.push(format!("{name} = {value_text}"));
+        fresh.push(name.clone());
+        values.insert(param.clone(), name);
+    }
+
+    let body_range = body.syntax().text_range();
+    let mut replacements: Vec<(TextRange, String)> = vars
+        .iter()
+        .filter_map(|var| {
+            let value = values.get(&var.syntax().text().to_string())?;
+            Some((
+                var.syntax().text_range() - body_range.start(),
+                value.clone(),
+            ))
+        })
+        .collect();
+    replacements.sort_by_key(|(range, _)| std::cmp::Reverse(range.start()));
+    let mut text = body.syntax().text().to_string();
+    for (range, replacement) in replacements {
+        text.replace_range(std::ops::Range::<usize>::from(range), &replacement);
+    }
+    if !bindings.is_empty() {
+        bindings.push(text);
+        text = format!("begin {} end", bindings.join(", "));
+    } else if expr_needs_parens_ast(&body) && call_needs_parens(&call) {
+        text = format!("({text})");
+    }
+
+    let range = call.syntax().text_range();
+    acc.add(
+        AssistId("inline_function_call", AssistKind::RefactorInline),
+        format!("Inline call to `{name}`"),
+        None,
+        range,
+        None,
+        |builder| {
+            builder.replace(range, text);
+        },
+    )
+}
+
+fn is_recursive(body: &ast::Expr, name: &NameArity) -> bool {
+    body.syntax()
+        .descendants()
+        .filter_map(ast::Call::cast)
+        .any(|call| match (call.expr(), call.args()) {
+            (Some(ast::Expr::ExprMax(ast::ExprMax::Atom(atom))), Some(args)) => {
+                atom.as_name() == *name.name() && args.args().count() == name.arity() as usize
+            }
+            _ => false,
+        })
+}
+
+/// Variables and literals can be duplicated or dropped without changing
+/// the behaviour, and never need parentheses.
+fn is_trivial(expr: &ast::Expr) -> bool {
+    matches!(
+        expr,
+        ast::Expr::ExprMax(
+            ast::ExprMax::Atom(_)
+                | ast::ExprMax::Char(_)
+                | ast::ExprMax::Float(_)
+                | ast::ExprMax::Integer(_)
+                | ast::ExprMax::String(_)
+                | ast::ExprMax::Var(_)
+        )
+    )
+}
+
+/// Whether an operator expression replacing the call needs parentheses.
+fn call_needs_parens(call: &ast::Call) -> bool {
+    let Some(parent) = call.syntax().parent() else {
+        return false;
+    };
+    if parent.kind() == SyntaxKind::PAREN_EXPR {
+        return false;
+    }
+    match_ast! {
+        match parent {
+            ast::ExprArgs(_) => false,
+            ast::ClauseBody(_) => false,
+            ast::MatchExpr(_) => false,
+            ast::BlockExpr(_) => false,
+            ast::List(_) => false,
+            ast::Tuple(_) => false,
+            _ => true
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use expect_test::expect;
+
+    use super::*;
+    use crate::tests::*;
+
+    #[test]
+    fn inline_call() {
+        check_assist(
+            inline_function_call,
+            "Inline call to `double/1`",
+            r#"
+-module(main).
+double(X) -> X * 2.
+foo(Y) -> 1 + do~uble(Y).
+"#,
+            expect![[r#"
+                -module(main).
+                double(X) -> X * 2.
+                foo(Y) -> 1 + (Y * 2).
+            "#]],
+        )
+    }
+
+    #[test]
+    fn inline_call_with_expression_argument() {
+        check_assist(
+            inline_function_call,
+            "Inline call to `double/1`",
+            r#"
+-module(main).
+double(X) -> X * 2.
+foo(Y) -> do~uble(Y + 1).
+"#,
+            expect![[r#"
+                -module(main).
+                double(X) -> X * 2.
+                foo(Y) -> begin X = Y + 1, X * 2 end.
+            "#]],
+        )
+    }
+
+    #[test]
+    fn inline_call_binds_argument_evaluated_conditionally() {
+        check_assist(
+            inline_function_call,
+            "Inline call to `check/1`",
+            r#"
+-module(main).
+check(X) -> is_ok() andalso X.
+foo(X) -> ch~eck(validate(X)).
+"#,
+            expect![[r#"
+                -module(main).
+                check(X) -> is_ok() andalso X.
+                foo(X) -> begin X0 = validate(X), is_ok() andalso X0 end.
+            "#]],
+        )
+    }
+
+    #[test]
+    fn inline_call_binds_unused_argument() {
+        check_assist(
+            inline_function_call,
+            "Inline call to `ignore/1`",
+            r#"
+-module(main).
+ignore(_X) -> ok.
+foo() -> ig~nore(make_ref()).
+"#,
+            expect![[r#"
+                -module(main).
+                ignore(_X) -> ok.
+                foo() -> begin _ = make_ref(), ok end.
+            "#]],
+        )
+    }
+
+    #[test]
+    fn inline_call_duplicating_variable() {
+        check_assist(
+            inline_function_call,
+            "Inline call to `pair/1`",
+            r#"
+-module(main).
+pair(X) -> {X, X}.
+foo(Y) -> [pa~ir(Y)].
+"#,
+            expect![[r#"
+                -module(main).
+                pair(X) -> {X, X}.
+                foo(Y) -> [{Y, Y}].
+            "#]],
+        )
+    }
+
+    #[test]
+    fn inline_call_binds_duplicated_side_effects() {
+        check_assist(
+            inline_function_call,
+            "Inline call to `pair/1`",
+            r#"
+-module(main).
+pair(X) -> {X, X}.
+foo() -> pa~ir(make_ref()).
+"#,
+            expect![[r#"
+                -module(main).
+                pair(X) -> {X, X}.
+                foo() -> begin X = make_ref(), {X, X} end.
+            "#]],
+        )
+    }
+
+    #[test]
+    fn not_applicable_with_local_variables() {
+        check_assist_not_applicable(
+            inline_function_call,
+            r#"
+-module(main).
+add(X) -> fun(Y) -> X + Y end.
+foo(Y) -> a~dd(Y).
+"#,
+        );
+    }
+
+    #[test]
+    fn not_applicable_to_multi_clause_function() {
+        check_assist_not_applicable(
+            inline_function_call,
+            r#"
+-module(main).
+sign(0) -> zero;
+sign(_) -> other.
+foo(Y) -> si~gn(Y).
+"#,
+        );
+    }
+
+    #[test]
+    fn not_applicable_to_recursive_function() {
+        check_assist_not_applicable(
+            inline_function_call,
+            r#"
+-module(main).
+loop(X) -> loop(X).
+foo(Y) -> lo~op(Y).
+"#,
+        );
+    }
+}
@@ -0,0 +1,297 @@
+/*
+ * Copyright (c) Meta Platforms, Inc. and affiliates.
+ *
+ * This source code is dual-licensed under either the MIT license found in the
+ * LICENSE-MIT file in the root directory of this source tree or the Apache
+ * License, Version 2.0 found in the LICENSE-APACHE file in the root directory
+ * of this source tree. You may select, at your option, one of the
+ * above-listed licenses.
+ */
+
+use elp_ide_db::assists::AssistId;
+use elp_ide_db::assists::AssistKind;
+use elp_syntax::AstNode;
+use elp_syntax::SyntaxKind;
+use elp_syntax::TextRange;
+use elp_syntax::ast;
+use elp_syntax::ast::BinaryOp;
+use elp_syntax::ast::LogicOp;
+use elp_syntax::ast::UnaryOp;
+
+use crate::AssistContext;
+use crate::Assists;
+use crate::helpers::is_safe_test;
+
+// Assist: invert_boolean_condition
+//
+// Negate the condition of a boolean `case` or of a two clause `if`, and
+// swap the branches.
+//
+// ```
+// foo(X) ->
+//     c~ase is_ok(X) of
+//         true -> yes;
+//         false -> no
+//     end.
+// ```
+// ->
+// ```
+// foo(X) ->
+//     case not is_ok(X) of
+//         true -> no;
+//         false -> yes
+//     end.
+// ```
+pub(crate) fn invert_boolean_condition(acc: &mut Assists, ctx: &AssistContext) -> Option<()> {
+    let (condition, first, second) = boolean_case(ctx).or_else(|| boolean_if(ctx))?;
+    let negated = negate(&condition);
+    let first_range = first.syntax().text_range();
+    let second_range = second.syntax().text_range();
+    let first_text = first.syntax().text().to_string();
+    let second_text = second.syntax().text().to_string();
+    acc.add(
+        AssistId("invert_boolean_condition", AssistKind::RefactorRewrite),
+        "Invert condition and swap branches",
+        None,
+        TextRange::new(condition.syntax().text_range().start(), second_range.end()),
+        None,
+        |builder| {
+            builder.replace(condition.syntax().text_range(), negated);
+            builder.replace(first_range, second_text);
+            builder.replace(second_range, first_text);
+        },
+    )
+}
+
+/// `case C of true -> A; false -> B end`, in any order of the clauses
+fn boolean_case(ctx: &AssistContext) -> Option<(ast::Expr, ast::ClauseBody, ast::ClauseBody)> {
+    let case = ctx.find_node_at_offset::<ast::CaseExpr>()?;
+    let clauses = case
+        .clauses()
+        .map(|clause| match clause {
+            ast::CrClauseOrMacro::CrClause(clause) => Some(clause),
+            ast::CrClauseOrMacro::MacroCallExpr(_) => None,
+        })
+        .collect::<Option<Vec<_>>>()?;
+    let [first, second] = clauses.as_slice() else {
+        return None;
+    };
+    let mut patterns = [first, second].map(|clause| {
+        if clause.guard().is_some() {
+            return String::new();
+        }
+        clause
+            .pat()
+            .map(|pat| pat.syntax().text().to_string())
+            .unwrap_or_default()
+    });
+    patterns.sort();
+    if patterns != ["false", "true"] {
+        return None;
+    }
+    Some((case.expr()?, first.body()?, second.body()?))
+}
+
+/// `if G -> A; true -> B end`, where `G` is a single test. A test which
+/// can raise an exception fails the guard, so inverting it would change
+/// the branch taken: only tests which cannot raise are inverted.
+fn boolean_if(ctx: &AssistContext) -> Option<(ast::Expr, ast::ClauseBody, ast::ClauseBody)> {
+    let if_expr = ctx.find_node_at_offset::<ast::IfExpr>()?;
+    if if_expr.syntax().first_token()?.kind() != SyntaxKind::ANON_IF {
+        return None;
+    }
+    let clauses: Vec<ast::IfClause> = if_expr.clauses().collect();
+    let [first, second] = clauses.as_slice() else {
+        return None;
+    };
+    if second.guard()?.syntax().text() != "true" {
+        return None;
+    }
+    let mut guard_clauses = first.guard()?.clauses();
+    let guard_clause = guard_clauses.next()?;
+    let mut exprs = guard_clause.exprs();
+    let condition = exprs.next()?;
+    if guard_clauses.next().is_some() || exprs.next().is_some() || !is_safe_test(&condition) {
+        return None;
+    }
+    Some((condition, first.body()?, second.body()?))
+}
+
+/// The negation of `expr`, removing a leading `not` and applying De Morgan's
+/// laws to `andalso` and `orelse`.
+fn negate(expr: &ast::Expr) -> String {
+    match expr {
+        ast::Expr::BinaryOpExpr(binary) => match binary.op() {
+            Some((BinaryOp::LogicOp(LogicOp::And { lazy: true }), _)) => {
+                de_morgan(binary, "orelse").unwrap_or_else(|| negate_simple(expr))
+            }
+            Some((BinaryOp::LogicOp(LogicOp::Or { lazy: true }), _)) => {
+                de_morgan(binary, "andalso").unwrap_or_else(|| negate_simple(expr))
+            }
+            _ => negate_simple(expr),
+        },
+        _ => negate_simple(expr),
+    }
+}
+
+fn de_morgan(binary: &ast::BinaryOpExpr, op: &str) -> Option<String> {
+    let lhs = negate_simple(&binary.lhs()?);
+    let rhs = negate_simple(&binary.rhs()?);
+    Some(format!("{lhs} {op} {rhs}"))
+}
+
+/// `not X`, or `X` when the expression is already `not X`
+fn negate_simple(expr: &ast::Expr) -> String {
+    match expr {
+        ast::Expr::UnaryOpExpr(unary) => {
+            if let Some((UnaryOp::Not, _)) = unary.op()
+                && let Some(operand) = unary.operand()
+            {
+                return operand.syntax().text().to_string();
+            }
+            format!("not ({})", expr.syntax().text())
+        }
+        ast::Expr::ExprMax(ast::ExprMax::ParenExpr(paren)) => match paren.expr() {
+            Some(inner @ ast::Expr::UnaryOpExpr(_)) => negate_simple(&inner),
+            _ => format!("not {}", expr.syntax().text()),
+        },
+        ast::Expr::ExprMax(_) | ast::Expr::Call(_) | ast::Expr::Remote(_) => {
+            format!("not {}", expr.syntax().text())
+        }
+        _ => format!("not ({})", expr.syntax().text()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use expect_test::expect;
+
+    use super::*;
+    use crate::tests::*;
+
+    #[test]
+    fn invert_case() {
+        check_assist(
+            invert_boolean_condition,
+            "Invert condition and swap branches",
+            r#"
+-module(main).
+foo(X) ->
+    c~ase is_ok(X) of
+        true -> yes;
+        false -> no
+    end.
+"#,
+            expect![[r#"
+                -module(main).
+                foo(X) ->
+                    case not is_ok(X) of
+                        true -> no;
+                        false -> yes
+                    end.
+            "#]],
+        )
+    }
+
+    #[test]
+    fn invert_case_de_morgan() {
+        check_assist(
+            invert_boolean_condition,
+            "Invert condition and swap branches",
+            r#"
+-module(main).
+foo(X, Y) ->
+    c~ase X > 1 andalso is_ok(Y) of
+        false -> {error, X};
+        true -> ok
+    end.
+"#,
+            expect![[r#"
+                -module(main).
+                foo(X, Y) ->
+                    case not (X > 1) orelse not is_ok(Y) of
+                        false -> ok;
+                        true -> {error, X}
+                    end.
+            "#]],
+        )
+    }
+
+    #[test]
+    fn invert_if() {
+        check_assist(
+            invert_boolean_condition,
+            "Invert condition and swap branches",
+            r#"
+-module(main).
+foo(X) ->
+    i~f
+        X > 10 -> big;
+        true -> small
+    end.
+"#,
+            expect![[r#"
+                -module(main).
+                foo(X) ->
+                    if
+                        not (X > 10) -> small;
+                        true -> big
+                    end.
+            "#]],
+        )
+    }
+
+    #[test]
+    fn not_applicable_to_if_with_unsafe_guard() {
+        check_assist_not_applicable(
+            invert_boolean_condition,
+            r#"
+-module(main).
+foo(X) ->
+    i~f
+        length(X) > 10 -> long;
+        true -> short
+    end.
+"#,
+        );
+    }
+
+    #[test]
+    fn invert_not_not() {
+        check_assist(
+            invert_boolean_condition,
+            "Invert condition and swap branches",
+            r#"
+-module(main).
+foo(X) ->
+    c~ase not X of
+        true -> a;
+        false -> b
+    end.
+"#,
+            expect![[r#"
+                -module(main).
+                foo(X) ->
+                    case X of
+                        true -> b;
+                        false -> a
+                    end.
+            "#]],
+        )
+    }
+
+    #[test]
+    fn not_applicable_to_other_cases() {
+        check_assist_not_applicable(
+            invert_boolean_condition,
+            r#"
+-module(main).
+foo(X) ->
+    c~ase X of
+        true -> a;
+        _ -> b
+    end.
+"#,
+        );
+    }
+}
@@ -0,0 +1,329 @@
+/*
+ * Copyright (c) Meta Platforms, Inc. and affiliates.
+ *
+ * This source code is dual-licensed under either the MIT license found in the
+ * LICENSE-MIT file in the root directory of this source tree or the Apache
+ * License, Version 2.0 found in the LICENSE-APACHE file in the root directory
+ * of this source tree. You may select, at your option, one of the
+ * above-listed licenses.
+ */
+
+use elp_ide_db::assists::AssistId;
+use elp_ide_db::assists::AssistKind;
+use elp_syntax::AstNode;
+use elp_syntax::SyntaxKind;
+use elp_syntax::TextRange;
+use elp_syntax::ast;
+
+use crate::AssistContext;
+use crate::Assists;
+
+// Assist: merge_function_clauses
+//
+// Merge consecutive function clauses with identical bodies, whose arguments
+// only differ by a literal in a single position, into one guarded clause.
+//
+// ```
+// foo(~a, Y) -> Y;
+// foo(b, Y) -> Y;
+// foo(c, Y) -> {c, Y}.
+// ```
+// ->
+// ```
+// foo(X, Y) when X =:= a; X =:= b -> Y;
+// foo(c, Y) -> {c, Y}.
+// ```
+pub(crate) fn merge_function_clauses(acc: &mut Assists, ctx: &AssistContext) -> Option<()> {
+    let clause = ctx.find_node_at_offset::<ast::FunctionClause>()?;
+    let def = ctx
+        .sema
+        .find_enclosing_function_def(ctx.file_id(), clause.syntax())?;
+    let clauses = def
+        .source(ctx.sema.db.upcast())
+        .iter()
+        .map(|fun_decl| match fun_decl.clause()? {
+            ast::FunctionOrMacroClause::FunctionClause(clause) => Some(clause),
+            ast::FunctionOrMacroClause::MacroCallExpr(_) => None,
+        })
+        .collect::<Option<Vec<_>>>()?;
+    let index = clauses.iter().position(|c| *c == clause)?;
+
+    // The run of consecutive clauses around the one under the cursor that
+    // have the same body
+    let body = mergeable_body(&clause)?;
+    let same_body = |c: &ast::FunctionClause| mergeable_body(c).as_ref() == Some(&body);
+    let first = clauses[..index]
+        .iter()
+        .rposition(|c| !same_body(c))
+        .map_or(0, |i| i + 1);
+    let last = clauses[index..]
+        .iter()
+        .position(|c| !same_body(c))
+        .map_or(clauses.len(), |i| index + i);
+    let group = &clauses[first..last];
+    if group.len() < 2 {
+        return None;
+    }
+
+    let args = group
+        .iter()
+        .map(|c| {
+            Some(
+                c.args()?
+                    .args()
+                    .map(|arg| arg.syntax().text().to_string())
+                    .collect::<Vec<_>>(),
+            )
+        })
+        .collect::<Option<Vec<_>>>()?;
+    let arity = args[0].len();
+    if args.iter().any(|a| a.len() != arity) {
+        return None;
+    }
+    let mut differing = (0..arity).filter(|&i| args.iter().any(|a| a[i] != args[0][i]));
+    let position = differing.next()?;
+    if differing.next().is_some() {
+        return None;
+    }
+    let values = group
+        .iter()
+        .map(|c| {
+            let arg = c.args()?.args().nth(position)?;
+            is_literal(&arg).then(|| arg.syntax().text().to_string())
+        })
+        .collect::<Option<Vec<_>>>()?;
+
+    let var = fresh_var(group);
+    let mut merged_args = args[0].clone();
+    merged_args[position] = var.clone();
+    let guard = values
+        .iter()
+        .map(|value| format!("{var} =:= {value}"))
+        .collect::<Vec<_>>()
+        .join("; ");
+    let merged = format!(
+        "{}({}) when {} {}",
+        clause.name()?.syntax().text(),
+        merged_args.join(", "),
+        guard,
+        body
+    );
+    let range = TextRange::new(
+        group[0].syntax().text_range().start(),
+        group[group.len() - 1].syntax().text_range().end(),
+    );
+    // Only the bodies survive the rewrite verbatim, any other comment in
+    // the merged range would be lost
+    if has_comment_outside_bodies(&clause, group, range) {
+        return None;
+    }
+
+    acc.add(
+        AssistId("merge_function_clauses", AssistKind::RefactorRewrite),
+        "Merge clauses with identical bodies",
+        None,
+        range,
+        None,
+        |builder| {
+            builder.replace(range, merged);
+        },
+    )
+}
+
+/// The source of the body of a clause without a guard, including its `->`.
+fn mergeable_body(clause: &ast::FunctionClause) -> Option<String> {
+    if clause.guard().is_some() {
+        return None;
+    }
+    Some(clause.body()?.syntax().text().to_string())
+}
+
+fn has_comment_outside_bodies(
+    clause: &ast::FunctionClause,
+    group: &[ast::FunctionClause],
+    range: TextRange,
+) -> bool {
+    let bodies = group
+        .iter()
+        .filter_map(|c| Some(c.body()?.syntax().text_range()))
+        .collect::<Vec<_>>();
+    let root = clause.syntax().ancestors().last();
+    root.into_iter()
+        .flat_map(|root| root.descendants_with_tokens())
+        .filter_map(|element| element.into_token())
+        .filter(|token| token.kind() == SyntaxKind::COMMENT)
+        .map(|token| token.text_range())
+        .filter(|comment| range.contains_range(*comment))
+        .any(|comment| !bodies.iter().any(|body| body.contains_range(comment)))
+}
+
+fn is_literal(expr: &ast::Expr) -> bool {
+    matches!(
+        expr,
+        ast::Expr::ExprMax(
+            ast::ExprMax::Atom(_)
+                | ast::ExprMax::Char(_)
+                | ast::ExprMax::Float(_)
+                | ast::ExprMax::Integer(_)
+                | ast::ExprMax::String(_)
+        )
+    )
+}
+
+/// A variable name not used in any of the clauses.
+fn fresh_var(clauses: &[ast::FunctionClause]) -> String {
+    let used = |name: &str| {
+        clauses.iter().any(|clause| {
+            clause
+                .syntax()
+                .descendants_with_tokens()
+                .filter_map(|element| element.into_token())
+                .any(|token| token.kind() == SyntaxKind::VAR && token.text() == name)
+        })
+    };
+    let mut name = "X".to_string();
+    let mut suffix = 0;
+    while used(&name) {
+        suffix += 1;
+        name = format!("X{suffix}");
+    }
+    name
+}
+
+#[cfg(test)]
+mod tests {
+    use expect_test::expect;
+
+    use super::*;
+    use crate::tests::*;
+
+    #[test]
+    fn merge_clauses() {
+        check_assist(
+            merge_function_clauses,
+            "Merge clauses with identical bodies",
+            r#"
+-module(main).
+foo(~a, Y) -> Y;
+foo(b, Y) -> Y;
+foo(c, Y) -> {c, Y}.
+"#,
+            expect![[r#"
+                -module(main).
+                foo(X, Y) when X =:= a; X =:= b -> Y;
+                foo(c, Y) -> {c, Y}.
+            "#]],
+        )
+    }
+
+    #[test]
+    fn merge_clauses_from_last_in_group() {
+        check_assist(
+            merge_function_clauses,
+            "Merge clauses with identical bodies",
+            r#"
+-module(main).
+foo(X, 0) ->
+    ok;
+foo(X, 1) ->
+    X;
+foo(X, ~2) ->
+    X.
+"#,
+            expect![[r#"
+                -module(main).
+                foo(X, 0) ->
+                    ok;
+                foo(X, X1) when X1 =:= 1; X1 =:= 2 ->
+                    X.
+            "#]],
+        )
+    }
+
+    #[test]
+    fn different_bodies() {
+        check_assist_not_applicable(
+            merge_function_clauses,
+            r#"
+-module(main).
+foo(~a) -> ok;
+foo(b) -> error.
+"#,
+        );
+    }
+
+    #[test]
+    fn clause_with_guard() {
+        check_assist_not_applicable(
+            merge_function_clauses,
+            r#"
+-module(main).
+foo(~a, Y) when Y > 0 -> ok;
+foo(b, Y) -> ok.
+"#,
+        );
+    }
+
+    #[test]
+    fn several_differing_arguments() {
+        check_assist_not_applicable(
+            merge_function_clauses,
+            r#"
+-module(main).
+foo(~a, 1) -> ok;
+foo(b, 2) -> ok.
+"#,
+        );
+    }
+
+    #[test]
+    fn differing_argument_not_literal() {
+        check_assist_not_applicable(
+            merge_function_clauses,
+            r#"
+-module(main).
+foo(~a) -> ok;
+foo({b}) -> ok.
+"#,
+        );
+    }
+
+    #[test]
+    fn comment_between_clauses() {
+        check_assist_not_applicable(
+            merge_function_clauses,
+            r#"
+-module(main).
+foo(~a, Y) -> Y;
+%% b is the legacy spelling
+foo(b, Y) -> Y;
+foo(c, Y) -> {c, Y}.
+"#,
+        );
+    }
+
+    #[test]
+    fn comment_in_body_kept() {
+        check_assist(
+            merge_function_clauses,
+            "Merge clauses with identical bodies",
+            r#"
+-module(main).
+foo(~a, Y) ->
+    %% pass it on
+    Y;
+foo(b, Y) ->
+    %% pass it on
+    Y;
+foo(c, Y) -> {c, Y}.
+"#,
+            expect![[r#"
+                -module(main).
+                foo(X, Y) when X =:= a; X =:= b ->
+                    %% pass it on
+                    Y;
+                foo(c, Y) -> {c, Y}.
+            "#]],
+        )
+    }
+}
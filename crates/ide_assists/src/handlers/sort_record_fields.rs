@@ -0,0 +1,345 @@
+/*
+ * Copyright (c) Meta Platforms, Inc. and affiliates.
+ *
+ * This source code is dual-licensed under either the MIT license found in the
+ * LICENSE-MIT file in the root directory of this source tree or the Apache
+ * License, Version 2.0 found in the LICENSE-APACHE file in the root directory
+ * of this source tree. You may select, at your option, one of the
+ * above-listed licenses.
+ */
+
+use elp_ide_db::assists::AssistId;
+use elp_ide_db::assists::AssistKind;
+use elp_syntax::AstNode;
+use elp_syntax::Direction;
+use elp_syntax::SyntaxKind;
+use elp_syntax::SyntaxToken;
+use elp_syntax::TextRange;
+use elp_syntax::TextSize;
+use elp_syntax::ast;
+use hir::InFile;
+
+use crate::AssistContext;
+use crate::Assists;
+
+// Assist: sort_record_fields
+//
+// Reorder the fields of a record expression to match the `-record` declaration.
+//
+// ```
+// -record(rec, {a, b, c}).
+// foo() -> #rec{~c = 1, a = 2, b = 3}.
+// ```
+// ->
+// ```
+// -record(rec, {a, b, c}).
+// foo() -> #rec{a = 2, b = 3, c = 1}.
+// ```
+pub(crate) fn sort_record_fields(acc: &mut Assists, ctx: &AssistContext) -> Option<()> {
+    let record_expr = ctx.find_node_at_offset::<ast::RecordExpr>()?;
+    let record_name = record_expr.name()?;
+    let record = ctx.sema.to_def(InFile::new(ctx.file_id(), &record_name))?;
+    let declared: Vec<String> = record
+        .field_names(ctx.db())
+        .map(|name| name.as_str().to_string())
+        .collect();
+
+    let fields: Vec<ast::RecordField> = record_expr.fields().collect();
+    let mut sorted = fields
+        .iter()
+        .map(|field| {
+            let name = field.name()?.text()?;
+            // The `_ = V` initializer for all other fields goes last
+            let position = if name == "_" {
+                declared.len()
+            } else {
+                declared.iter().position(|declared| declared == &name)?
+            };
+            Some((position, field))
+        })
+        .collect::<Option<Vec<_>>>()?;
+    sorted.sort_by_key(|(position, _)| *position);
+
+    let in_order = fields
+        .iter()
+        .zip(sorted.iter())
+        .all(|(field, (_, sorted_field))| field == *sorted_field);
+    if in_order {
+        return None;
+    }
+
+    let moved = fields
+        .iter()
+        .map(FieldWithComments::new)
+        .collect::<Vec<_>>();
+    // A trailing comment can only be moved to a field which is followed
+    // by a line break, otherwise it would comment out the rest of the line
+    let trailing_ok = moved
+        .iter()
+        .zip(sorted.iter())
+        .all(|(slot, (_, sorted_field))| {
+            FieldWithComments::new(sorted_field).trailing.is_none() || slot.ends_line
+        });
+    if !trailing_ok {
+        return None;
+    }
+
+    let target = record_expr.syntax().text_range();
+    acc.add(
+        AssistId("sort_record_fields", AssistKind::RefactorRewrite),
+        format!(
+            "Sort fields of record `{}` in declaration order",
+            record.record.name
+        ),
+        None,
+        target,
+        None,
+        |builder| {
+            // Replace each field in place, so that separators and layout
+            // are preserved. Comments on their own lines before a field,
+            // and a comment at the end of its line, move with the field.
+            for (slot, (_, sorted_field)) in moved.iter().zip(sorted.iter()) {
+                if slot.field == **sorted_field {
+                    continue;
+                }
+                let sorted_field = FieldWithComments::new(sorted_field);
+                builder.replace(slot.range, sorted_field.text());
+                match (&slot.trailing, &sorted_field.trailing) {
+                    (Some(old), Some(new)) => {
+                        builder.replace(old.text_range(), new.text().to_string())
+                    }
+                    (Some(old), None) => builder.delete(TextRange::new(
+                        slot.trailing_start.unwrap_or(old.text_range().start()),
+                        old.text_range().end(),
+                    )),
+                    (None, Some(new)) => {
+                        builder.insert(slot.trailing_insert, format!(" {}", new.text()))
+                    }
+                    (None, None) => {}
+                }
+            }
+        },
+    )
+}
+
+/// A record field, together with the comments that belong to it.
+struct FieldWithComments {
+    field: ast::RecordField,
+    /// The field, preceded by any comments on their own lines before it
+    range: TextRange,
+    /// A comment at the end of the line of the field
+    trailing: Option<SyntaxToken>,
+    /// Start of the whitespace before `trailing`
+    trailing_start: Option<TextSize>,
+    /// Where a trailing comment goes, after the separator if any
+    trailing_insert: TextSize,
+    /// Whether the field is the last thing on its line
+    ends_line: bool,
+}
+
+impl FieldWithComments {
+    fn new(field: &ast::RecordField) -> Self {
+        let syntax = field.syntax();
+        let mut start = syntax.text_range().start();
+        let mut pending = None;
+        for element in syntax.siblings_with_tokens(Direction::Prev).skip(1) {
+            match element.kind() {
+                SyntaxKind::WHITESPACE => {
+                    if element.to_string().contains('\n')
+                        && let Some(comment_start) = pending.take()
+                    {
+                        start = comment_start;
+                    }
+                }
+                // Only own-line comments lead the field, a comment after
+                // the previous separator trails the previous field
+                SyntaxKind::COMMENT => pending = Some(element.text_range().start()),
+                _ => break,
+            }
+        }
+
+        let mut trailing = None;
+        let mut trailing_start = None;
+        let mut trailing_insert = syntax.text_range().end();
+        let mut ends_line = false;
+        for element in syntax.siblings_with_tokens(Direction::Next).skip(1) {
+            match element.kind() {
+                SyntaxKind::ANON_COMMA => trailing_insert = element.text_range().end(),
+                SyntaxKind::WHITESPACE => {
+                    if element.to_string().contains('\n') {
+                        ends_line = true;
+                        break;
+                    }
+                    trailing_start = Some(element.text_range().start());
+                }
+                SyntaxKind::COMMENT => {
+                    trailing = element.into_token();
+                    ends_line = true;
+                    break;
+                }
+                _ => break,
+            }
+        }
+        if trailing.is_none() {
+            trailing_start = None;
+        }
+
+        FieldWithComments {
+            field: field.clone(),
+            range: TextRange::new(start, syntax.text_range().end()),
+            trailing,
+            trailing_start,
+            trailing_insert,
+            ends_line,
+        }
+    }
+
+    fn text(&self) -> String {
+        let root = self.field.syntax().ancestors().last();
+        root.map(|root| root.text().slice(self.range).to_string())
+            .unwrap_or_default()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use expect_test::expect;
+
+    use super::*;
+    use crate::tests::*;
+
+    #[test]
+    fn sort_fields() {
+        check_assist(
+            sort_record_fields,
+            "Sort fields of record `rec` in declaration order",
+            r#"
+-record(rec, {a, b, c}).
+foo() -> #rec{~c = 1, a = 2, b = 3}.
+"#,
+            expect![[r#"
+                -record(rec, {a, b, c}).
+                foo() -> #rec{a = 2, b = 3, c = 1}.
+            "#]],
+        )
+    }
+
+    #[test]
+    fn sort_fields_preserves_values_and_layout() {
+        check_assist(
+            sort_record_fields,
+            "Sort fields of record `rec` in declaration order",
+            r#"
+-record(rec, {a, b = 2, c}).
+foo(X) ->
+    #rec{
+        c = bar(X, [1, 2]),
+        %% the first field
+        a = #{X => ok}~
+    }.
+"#,
+            expect![[r#"
+                -record(rec, {a, b = 2, c}).
+                foo(X) ->
+                    #rec{
+                        %% the first field
+                        a = #{X => ok},
+                        c = bar(X, [1, 2])
+                    }.
+            "#]],
+        )
+    }
+
+    #[test]
+    fn sort_fields_with_wildcard() {
+        check_assist(
+            sort_record_fields,
+            "Sort fields of record `rec` in declaration order",
+            r#"
+-record(rec, {a, b, c, d}).
+foo() -> #rec{_ = undefined, c~ = 1, a = 2}.
+"#,
+            expect![[r#"
+                -record(rec, {a, b, c, d}).
+                foo() -> #rec{a = 2, c = 1, _ = undefined}.
+            "#]],
+        )
+    }
+
+    #[test]
+    fn sort_fields_in_pattern() {
+        check_assist(
+            sort_record_fields,
+            "Sort fields of record `rec` in declaration order",
+            r#"
+-record(rec, {a, b}).
+foo(#rec{b = B, ~a = A}) -> {A, B}.
+"#,
+            expect![[r#"
+                -record(rec, {a, b}).
+                foo(#rec{a = A, b = B}) -> {A, B}.
+            "#]],
+        )
+    }
+
+    #[test]
+    fn already_sorted() {
+        check_assist_not_applicable(
+            sort_record_fields,
+            r#"
+-record(rec, {a, b, c}).
+foo() -> #rec{~a = 1, c = 3, _ = 0}.
+"#,
+        );
+    }
+
+    #[test]
+    fn unknown_field() {
+        check_assist_not_applicable(
+            sort_record_fields,
+            r#"
+-record(rec, {a, b, c}).
+foo() -> #rec{~c = 1, x = 2}.
+"#,
+        );
+    }
+
+    #[test]
+    fn sort_fields_moves_trailing_comments() {
+        check_assist(
+            sort_record_fields,
+            "Sort fields of record `rec` in declaration order",
+            r#"
+-record(rec, {a, b, c}).
+foo() ->
+    #rec{
+        c = 1, % the last field
+        b = 2,
+        a~ = 3 % the first field
+    }.
+"#,
+            expect![[r#"
+                -record(rec, {a, b, c}).
+                foo() ->
+                    #rec{
+                        a = 3, % the first field
+                        b = 2,
+                        c = 1 % the last field
+                    }.
+            "#]],
+        )
+    }
+
+    #[test]
+    fn sort_fields_trailing_comment_on_shared_line() {
+        check_assist_not_applicable(
+            sort_record_fields,
+            r#"
+-record(rec, {a, b}).
+foo() ->
+    #rec{b = 1, a~ = 2 % the first field
+    }.
+"#,
+        );
+    }
+}
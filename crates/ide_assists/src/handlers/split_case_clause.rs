@@ -0,0 +1,198 @@
+/*
+ * Copyright (c) Meta Platforms, Inc. and affiliates.
+ *
+ * This source code is dual-licensed under either the MIT license found in the
+ * LICENSE-MIT file in the root directory of this source tree or the Apache
+ * License, Version 2.0 found in the LICENSE-APACHE file in the root directory
+ * of this source tree. You may select, at your option, one of the
+ * above-listed licenses.
+ */
+
+use elp_ide_db::assists::AssistId;
+use elp_ide_db::assists::AssistKind;
+use elp_syntax::AstNode;
+use elp_syntax::SyntaxKind;
+use elp_syntax::ast;
+use elp_syntax::ast::edit::IndentLevel;
+
+use crate::AssistContext;
+use crate::Assists;
+
+// Assist: split_case_clause
+//
+// Hoist the guards of an `if` making up the whole body of a `case` clause
+// into guards of the clause, splitting it into one clause per branch.
+//
+// ```
+// foo(Y) ->
+//     case Y of
+//         ~X -> if X > 0 -> a; true -> b end
+//     end.
+// ```
+// ->
+// ```
+// foo(Y) ->
+//     case Y of
+//         X when X > 0 -> a;
+//         X -> b
+//     end.
+// ```
+pub(crate) fn split_case_clause(acc: &mut Assists, ctx: &AssistContext) -> Option<()> {
+    let clause = ctx.find_node_at_offset::<ast::CrClause>()?;
+    ast::CaseExpr::cast(clause.syntax().parent()?)?;
+    if clause.guard().is_some() {
+        return None;
+    }
+    let pat = clause.pat()?;
+    // Anything evaluated before the `if` would no longer be evaluated
+    // before the guards
+    let mut exprs = clause.body()?.exprs();
+    let if_expr = match exprs.next()? {
+        ast::Expr::ExprMax(ast::ExprMax::IfExpr(if_expr)) => if_expr,
+        _ => return None,
+    };
+    if exprs.next().is_some() || if_expr.syntax().first_token()?.kind() != SyntaxKind::ANON_IF {
+        return None;
+    }
+    let branches = if_expr
+        .clauses()
+        .map(|branch| Some((branch.guard()?, branch.body()?)))
+        .collect::<Option<Vec<_>>>()?;
+    // Without a catch-all branch, a value not matching any guard would
+    // fall through to the next clauses instead of raising `if_clause`
+    let (last_guard, _) = branches.last()?;
+    if last_guard.syntax().text() != "true" {
+        return None;
+    }
+
+    let pat = pat.syntax().text().to_string();
+    let separator = format!(";\n{}", IndentLevel::from_node(clause.syntax()));
+    let clauses = branches
+        .iter()
+        .map(|(guard, body)| {
+            if guard.syntax().text() == "true" {
+                format!("{pat} {}", body.syntax().text())
+            } else {
+                format!(
+                    "{pat} when {} {}",
+                    guard.syntax().text(),
+                    body.syntax().text()
+                )
+            }
+        })
+        .collect::<Vec<_>>()
+        .join(&separator);
+    let range = clause.syntax().text_range();
+
+    acc.add(
+        AssistId("split_case_clause", AssistKind::RefactorRewrite),
+        "Split clause on the guards of its `if`",
+        None,
+        range,
+        None,
+        |builder| {
+            builder.replace(range, clauses);
+        },
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use expect_test::expect;
+
+    use super::*;
+    use crate::tests::*;
+
+    #[test]
+    fn split_clause() {
+        check_assist(
+            split_case_clause,
+            "Split clause on the guards of its `if`",
+            r#"
+foo(Y) ->
+    case Y of
+        ~X -> if X > 0 -> a; true -> b end
+    end.
+"#,
+            expect![[r#"
+                foo(Y) ->
+                    case Y of
+                        X when X > 0 -> a;
+                        X -> b
+                    end.
+            "#]],
+        )
+    }
+
+    #[test]
+    fn split_clause_with_several_branches() {
+        check_assist(
+            split_case_clause,
+            "Split clause on the guards of its `if`",
+            r#"
+foo(Y) ->
+    case Y of
+        {ok, ~N} ->
+            if
+                N > 10 -> big;
+                N > 0 -> small;
+                true -> none
+            end;
+        error ->
+            error
+    end.
+"#,
+            expect![[r#"
+                foo(Y) ->
+                    case Y of
+                        {ok, N} when N > 10 -> big;
+                        {ok, N} when N > 0 -> small;
+                        {ok, N} -> none;
+                        error ->
+                            error
+                    end.
+            "#]],
+        )
+    }
+
+    #[test]
+    fn not_applicable_with_expressions_before_if() {
+        check_assist_not_applicable(
+            split_case_clause,
+            r#"
+foo(Y) ->
+    case Y of
+        ~X ->
+            log(X),
+            if X > 0 -> a; true -> b end
+    end.
+"#,
+        );
+    }
+
+    #[test]
+    fn not_applicable_without_catch_all() {
+        check_assist_not_applicable(
+            split_case_clause,
+            r#"
+foo(Y) ->
+    case Y of
+        ~X -> if X > 0 -> a; X < 0 -> b end
+    end.
+"#,
+        );
+    }
+
+    #[test]
+    fn not_applicable_with_guard() {
+        check_assist_not_applicable(
+            split_case_clause,
+            r#"
+foo(Y) ->
+    case Y of
+        ~X when is_integer(X) -> if X > 0 -> a; true -> b end
+    end.
+"#,
+        );
+    }
+}
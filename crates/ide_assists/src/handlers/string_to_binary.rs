@@ -0,0 +1,128 @@
+/*
+ * Copyright (c) Meta Platforms, Inc. and affiliates.
+ *
+ * This source code is dual-licensed under either the MIT license found in the
+ * LICENSE-MIT file in the root directory of this source tree or the Apache
+ * License, Version 2.0 found in the LICENSE-APACHE file in the root directory
+ * of this source tree. You may select, at your option, one of the
+ * above-listed licenses.
+ */
+
+use elp_ide_db::assists::AssistId;
+use elp_ide_db::assists::AssistKind;
+use elp_syntax::AstNode;
+use elp_syntax::SyntaxKind;
+use elp_syntax::ast;
+
+use crate::AssistContext;
+use crate::Assists;
+
+// Assist: string_to_binary
+//
+// Rewrite a string literal to a binary literal.
+//
+// ```
+// foo() -> "he~llo".
+// ```
+// ->
+// ```
+// foo() -> <<"hello">>.
+// ```
+//
+// A string with non-ASCII characters becomes a `/utf8` segment.
+pub(crate) fn string_to_binary(acc: &mut Assists, ctx: &AssistContext) -> Option<()> {
+    let string = ctx.find_node_at_offset::<ast::String>()?;
+    string.syntax().ancestors().find_map(ast::FunDecl::cast)?;
+    match string.syntax().parent()?.kind() {
+        // Already a binary
+        SyntaxKind::BIN_ELEMENT => return None,
+        // Only part of a concatenation like `"a" "b"`
+        SyntaxKind::CONCATABLES => return None,
+        _ => {}
+    }
+    let literal = string.syntax().text().to_string();
+    // The escape sequences mean the same in both literals. Any character
+    // outside of ASCII, including latin-1 ones, makes the segment UTF-8
+    // encoded, the usual encoding of text in binaries.
+    let replacement = if literal.is_ascii() && !literal.contains("\\x{") {
+        format!("<<{literal}>>")
+    } else {
+        format!("<<{literal}/utf8>>")
+    };
+    let range = string.syntax().text_range();
+    acc.add(
+        AssistId("string_to_binary", AssistKind::RefactorRewrite),
+        "Convert string to binary",
+        None,
+        range,
+        None,
+        |builder| builder.replace(range, replacement),
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use expect_test::expect;
+
+    use super::*;
+    use crate::tests::*;
+
+    #[test]
+    fn plain_string() {
+        check_assist(
+            string_to_binary,
+            "Convert string to binary",
+            r#"
+-module(main).
+foo() -> "he~llo".
+"#,
+            expect![[r#"
+                -module(main).
+                foo() -> <<"hello">>.
+            "#]],
+        )
+    }
+
+    #[test]
+    fn escaped_quotes() {
+        check_assist(
+            string_to_binary,
+            "Convert string to binary",
+            r#"
+-module(main).
+foo() -> "say \"h~i\"\n".
+"#,
+            expect![[r#"
+                -module(main).
+                foo() -> <<"say \"hi\"\n">>.
+            "#]],
+        )
+    }
+
+    #[test]
+    fn unicode_string() {
+        check_assist(
+            string_to_binary,
+            "Convert string to binary",
+            r#"
+-module(main).
+foo() -> "caf~é".
+"#,
+            expect![[r#"
+                -module(main).
+                foo() -> <<"café"/utf8>>.
+            "#]],
+        )
+    }
+
+    #[test]
+    fn not_applicable_in_binary() {
+        check_assist_not_applicable(
+            string_to_binary,
+            r#"
+-module(main).
+foo() -> <<"he~llo">>.
+"#,
+        );
+    }
+}
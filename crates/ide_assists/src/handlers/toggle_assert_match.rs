@@ -0,0 +1,235 @@
+/*
+ * Copyright (c) Meta Platforms, Inc. and affiliates.
+ *
+ * This source code is dual-licensed under either the MIT license found in the
+ * LICENSE-MIT file in the root directory of this source tree or the Apache
+ * License, Version 2.0 found in the LICENSE-APACHE file in the root directory
+ * of this source tree. You may select, at your option, one of the
+ * above-listed licenses.
+ */
+
+use elp_ide_db::assists::AssistId;
+use elp_ide_db::assists::AssistKind;
+use elp_ide_db::elp_base_db::FileKind;
+use elp_syntax::AstNode;
+use elp_syntax::ast;
+
+use crate::AssistContext;
+use crate::Assists;
+
+// Assist: toggle_assert_match
+//
+// In a test file, convert an `?assertEqual` into an `?assertMatch`, or an
+// `?assertMatch` whose pattern binds no variable into an `?assertEqual`.
+//
+// Maps and records are left alone: they are written differently in
+// patterns and expressions, and a map or record pattern only matches the
+// fields it lists, while an expression compares all of them.
+//
+// ```
+// foo_test() ->
+//     ?assert~Equal({ok, 1}, foo()).
+// ```
+// ->
+// ```
+// foo_test() ->
+//     ?assertMatch({ok, 1}, foo()).
+// ```
+pub(crate) fn toggle_assert_match(acc: &mut Assists, ctx: &AssistContext) -> Option<()> {
+    let file_id = ctx.file_id();
+    let is_test_file = ctx.sema.db.file_kind(file_id) == FileKind::TestModule
+        || ctx.sema.db.is_test_suite_or_test_helper(file_id) == Some(true);
+    if !is_test_file {
+        return None;
+    }
+    let call = ctx.find_node_at_offset::<ast::MacroCallExpr>()?;
+    let name = call.name()?;
+    let [expected, actual]: [ast::MacroExpr; 2] =
+        call.args()?.args().collect::<Vec<_>>().try_into().ok()?;
+    if expected.guard().is_some() || actual.guard().is_some() {
+        return None;
+    }
+    let expected = expected.expr()?;
+    let (id, label, new_name) = match name.syntax().text().to_string().as_str() {
+        "assertEqual" if is_pattern(&expected) => (
+            "assert_equal_to_assert_match",
+            "Convert to `?assertMatch`",
+            "assertMatch",
+        ),
+        "assertMatch" if !binds_variables(&expected) && !contains_map_or_record(&expected) => (
+            "assert_match_to_assert_equal",
+            "Convert to `?assertEqual`",
+            "assertEqual",
+        ),
+        _ => return None,
+    };
+    let name_range = name.syntax().text_range();
+    acc.add(
+        AssistId(id, AssistKind::RefactorRewrite),
+        label,
+        None,
+        call.syntax().text_range(),
+        None,
+        |builder| {
+            builder.replace(name_range, new_name);
+        },
+    )
+}
+
+/// Whether the expression is also a valid pattern with the same meaning,
+/// i.e. it is only made of literals, variables, lists, tuples and binaries.
+fn is_pattern(expr: &ast::Expr) -> bool {
+    expr.syntax()
+        .descendants()
+        .filter_map(ast::Expr::cast)
+        .all(|expr| match expr {
+            ast::Expr::ExprMax(expr_max) => matches!(
+                expr_max,
+                ast::ExprMax::Atom(_)
+                    | ast::ExprMax::Binary(_)
+                    | ast::ExprMax::Char(_)
+                    | ast::ExprMax::Concatables(_)
+                    | ast::ExprMax::Float(_)
+                    | ast::ExprMax::Integer(_)
+                    | ast::ExprMax::List(_)
+                    | ast::ExprMax::MacroCallExpr(_)
+                    | ast::ExprMax::ParenExpr(_)
+                    | ast::ExprMax::String(_)
+                    | ast::ExprMax::Tuple(_)
+                    | ast::ExprMax::Var(_)
+            ),
+            ast::Expr::Pipe(_) | ast::Expr::UnaryOpExpr(_) => true,
+            _ => false,
+        })
+}
+
+fn contains_map_or_record(pattern: &ast::Expr) -> bool {
+    pattern
+        .syntax()
+        .descendants()
+        .any(|node| ast::MapExpr::can_cast(node.kind()) || ast::RecordExpr::can_cast(node.kind()))
+}
+
+/// Whether the pattern contains a variable or a wildcard, which only make
+/// sense in a match. Macro names such as `?FOO` are not variables.
+fn binds_variables(pattern: &ast::Expr) -> bool {
+    pattern
+        .syntax()
+        .descendants()
+        .filter_map(ast::Var::cast)
+        .any(|var| {
+            !var.syntax()
+                .parent()
+                .and_then(ast::MacroCallExpr::cast)
+                .and_then(|call| call.name())
+                .is_some_and(|name| name.syntax() == var.syntax())
+        })
+}
+
+#[cfg(test)]
+mod tests {
+    use expect_test::expect;
+
+    use super::*;
+    use crate::tests::*;
+
+    #[test]
+    fn assert_equal_to_assert_match() {
+        check_assist(
+            toggle_assert_match,
+            "Convert to `?assertMatch`",
+            r#"
+//- /test/main_SUITE.erl extra:test
+-module(main_SUITE).
+foo_test() ->
+    ?assert~Equal({ok, [1, 2]}, foo()).
+"#,
+            expect![[r#"
+                -module(main_SUITE).
+                foo_test() ->
+                    ?assertMatch({ok, [1, 2]}, foo()).
+            "#]],
+        )
+    }
+
+    #[test]
+    fn assert_match_to_assert_equal() {
+        check_assist(
+            toggle_assert_match,
+            "Convert to `?assertEqual`",
+            r#"
+//- /test/main_SUITE.erl extra:test
+-module(main_SUITE).
+foo_test() ->
+    ?assertMatch({ok, [?VALUE]}, ~foo()).
+"#,
+            expect![[r#"
+                -module(main_SUITE).
+                foo_test() ->
+                    ?assertEqual({ok, [?VALUE]}, foo()).
+            "#]],
+        )
+    }
+
+    #[test]
+    fn not_applicable_to_pattern_with_variables() {
+        check_assist_not_applicable(
+            toggle_assert_match,
+            r#"
+//- /test/main_SUITE.erl extra:test
+-module(main_SUITE).
+foo_test() ->
+    ?assert~Match({ok, _}, foo()),
+    ?assertMatch({ok, X}, foo()).
+"#,
+        );
+    }
+
+    #[test]
+    fn not_applicable_to_maps() {
+        check_assist_not_applicable(
+            toggle_assert_match,
+            r#"
+//- /test/main_SUITE.erl extra:test
+-module(main_SUITE).
+foo_test() ->
+    ?assert~Equal(#{key => 1}, foo()).
+"#,
+        );
+        check_assist_not_applicable(
+            toggle_assert_match,
+            r#"
+//- /test/main_SUITE.erl extra:test
+-module(main_SUITE).
+foo_test() ->
+    ?assert~Match(#{key := ?VALUE}, foo()).
+"#,
+        );
+    }
+
+    #[test]
+    fn not_applicable_to_expression_which_is_not_a_pattern() {
+        check_assist_not_applicable(
+            toggle_assert_match,
+            r#"
+//- /test/main_SUITE.erl extra:test
+-module(main_SUITE).
+foo_test() ->
+    ?assert~Equal(bar(), foo()).
+"#,
+        );
+    }
+
+    #[test]
+    fn not_applicable_outside_test_files() {
+        check_assist_not_applicable(
+            toggle_assert_match,
+            r#"
+//- /src/main.erl
+-module(main).
+foo() ->
+    ?assert~Equal({ok, 1}, foo()).
+"#,
+        );
+    }
+}
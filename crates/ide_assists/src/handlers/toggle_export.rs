@@ -0,0 +1,262 @@
+/*
+ * Copyright (c) Meta Platforms, Inc. and affiliates.
+ *
+ * This source code is dual-licensed under either the MIT license found in the
+ * LICENSE-MIT file in the root directory of this source tree or the Apache
+ * License, Version 2.0 found in the LICENSE-APACHE file in the root directory
+ * of this source tree. You may select, at your option, one of the
+ * above-listed licenses.
+ */
+
+use elp_ide_db::SymbolClass;
+use elp_ide_db::SymbolDefinition;
+use elp_ide_db::assists::AssistId;
+use elp_ide_db::assists::AssistKind;
+use elp_ide_db::source_change::SourceChangeBuilder;
+use elp_syntax::AstNode;
+use elp_syntax::TextRange;
+use hir::NameArity;
+
+use crate::AssistContext;
+use crate::Assists;
+use crate::helpers;
+use crate::helpers::extend_form_range_for_delete;
+
+// Assist: toggle_export
+//
+// Export a function which is not exported, or remove an exported function
+// from the `-export` attributes.
+//
+// ```
+// -export([foo/0, bar/0]).
+// ~foo() -> ok.
+// bar() -> ok.
+// ```
+// ->
+// ```
+// -export([bar/0]).
+// foo() -> ok.
+// bar() -> ok.
+// ```
+pub(crate) fn toggle_export(acc: &mut Assists, ctx: &AssistContext) -> Option<()> {
+    let fun = match ctx.classify_offset()? {
+        SymbolClass::Definition(SymbolDefinition::Function(fun)) => fun,
+        _ => return None,
+    };
+    if fun.file.file_id != ctx.file_id() {
+        return None;
+    }
+    // Every function is exported, whatever the export lists say
+    if ctx.sema.def_map_local(ctx.file_id()).is_export_all() {
+        return None;
+    }
+    let range = fun.name_range(ctx.sema.db.upcast())?;
+    let name = fun.name;
+    let id = AssistId("toggle_export", AssistKind::RefactorRewrite);
+    if fun.exported {
+        acc.add(
+            id,
+            format!("Unexport `{name}`"),
+            None,
+            range,
+            None,
+            |builder| remove_from_exports(ctx, &name, builder),
+        )
+    } else {
+        acc.add(
+            id,
+            format!("Export `{name}`"),
+            None,
+            range,
+            None,
+            |builder| {
+                helpers::ExportBuilder::new(
+                    &ctx.sema,
+                    ctx.file_id(),
+                    helpers::ExportForm::Functions,
+                    &[name.clone()],
+                    builder,
+                )
+                .finish();
+            },
+        )
+    }
+}
+
+/// Delete every entry for the function from the `-export` attributes,
+/// and the attributes left empty.
+fn remove_from_exports(ctx: &AssistContext, name: &NameArity, builder: &mut SourceChangeBuilder) {
+    let source = ctx.sema.parse(ctx.file_id()).value;
+    let form_list = ctx.sema.form_list(ctx.file_id());
+    for (_, export) in form_list.exports() {
+        let entries: Vec<&NameArity> = export
+            .entries
+            .clone()
+            .map(|fa| &form_list[fa].name)
+            .collect();
+        if !entries.contains(&name) {
+            continue;
+        }
+        let export_ast = export.form_id.get(&source);
+        if entries.iter().all(|entry| *entry == name) {
+            builder.delete(extend_form_range_for_delete(export_ast.syntax()));
+            continue;
+        }
+        let funs: Vec<TextRange> = export_ast
+            .funs()
+            .map(|fa| fa.syntax().text_range())
+            .collect();
+        if funs.len() != entries.len() {
+            continue;
+        }
+        // Delete each run of consecutive entries for the function, which
+        // can be listed more than once, along with the separators on one
+        // side of the run, so that the deleted ranges never overlap
+        let mut i = 0;
+        while i < entries.len() {
+            if entries[i] != name {
+                i += 1;
+                continue;
+            }
+            let first = i;
+            while i < entries.len() && entries[i] == name {
+                i += 1;
+            }
+            let last = i - 1;
+            let delete = if let Some(next) = funs.get(i) {
+                TextRange::new(funs[first].start(), next.start())
+            } else if let Some(prev) = first.checked_sub(1).map(|prev| funs[prev]) {
+                TextRange::new(prev.end(), funs[last].end())
+            } else {
+                TextRange::new(funs[first].start(), funs[last].end())
+            };
+            builder.delete(delete);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use expect_test::expect;
+
+    use super::*;
+    use crate::tests::*;
+
+    #[test]
+    fn export_unexported_function() {
+        check_assist(
+            toggle_export,
+            "Export `bar/1`",
+            r#"
+-module(main).
+-export([foo/0]).
+foo() -> ok.
+b~ar(X) -> X.
+"#,
+            expect![[r#"
+                -module(main).
+                -export([foo/0, bar/1]).
+                foo() -> ok.
+                bar(X) -> X.
+            "#]],
+        )
+    }
+
+    #[test]
+    fn not_applicable_under_export_all() {
+        check_assist_not_applicable(
+            toggle_export,
+            r#"
+-module(main).
+-compile(export_all).
+-export([foo/0]).
+f~oo() -> ok.
+"#,
+        )
+    }
+
+    #[test]
+    fn unexport_last_entry() {
+        check_assist(
+            toggle_export,
+            "Unexport `foo/0`",
+            r#"
+-module(main).
+-export([foo/0]).
+f~oo() -> ok.
+"#,
+            expect![[r#"
+                -module(main).
+                foo() -> ok.
+            "#]],
+        )
+    }
+
+    #[test]
+    fn unexport_one_of_many() {
+        check_assist(
+            toggle_export,
+            "Unexport `foo/0`",
+            r#"
+-module(main).
+-export([bar/0, foo/0, baz/0]).
+-export([foo/0]).
+f~oo() -> ok.
+bar() -> ok.
+baz() -> ok.
+"#,
+            expect![[r#"
+                -module(main).
+                -export([bar/0, baz/0]).
+                foo() -> ok.
+                bar() -> ok.
+                baz() -> ok.
+            "#]],
+        )
+    }
+
+    #[test]
+    fn unexport_trailing_entry() {
+        check_assist(
+            toggle_export,
+            "Unexport `foo/0`",
+            r#"
+-module(main).
+-export([bar/0,
+         foo/0]).
+f~oo() -> ok.
+bar() -> ok.
+"#,
+            expect![[r#"
+                -module(main).
+                -export([bar/0]).
+                foo() -> ok.
+                bar() -> ok.
+            "#]],
+        )
+    }
+
+    #[test]
+    fn unexport_duplicate_entries() {
+        check_assist(
+            toggle_export,
+            "Unexport `foo/0`",
+            r#"
+-module(main).
+-export([bar/0, foo/0, foo/0]).
+-export([foo/0, foo/0, baz/0]).
+f~oo() -> ok.
+bar() -> ok.
+baz() -> ok.
+"#,
+            expect![[r#"
+                -module(main).
+                -export([bar/0]).
+                -export([baz/0]).
+                foo() -> ok.
+                bar() -> ok.
+                baz() -> ok.
+            "#]],
+        )
+    }
+}
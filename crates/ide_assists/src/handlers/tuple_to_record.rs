@@ -0,0 +1,231 @@
+/*
+ * Copyright (c) Meta Platforms, Inc. and affiliates.
+ *
+ * This source code is dual-licensed under either the MIT license found in the
+ * LICENSE-MIT file in the root directory of this source tree or the Apache
+ * License, Version 2.0 found in the LICENSE-APACHE file in the root directory
+ * of this source tree. You may select, at your option, one of the
+ * above-listed licenses.
+ */
+
+use elp_ide_db::assists::AssistId;
+use elp_ide_db::assists::AssistKind;
+use elp_ide_db::helpers::escape_snippet;
+use elp_ide_db::helpers::top_insert_position;
+use elp_syntax::AstNode;
+use elp_syntax::ast;
+
+use crate::AssistContext;
+use crate::Assists;
+
+// Assist: tuple_to_record
+//
+// Define a record for a tagged tuple, and construct the record instead.
+// The record has the same runtime representation as the tuple.
+//
+// ```
+// -module(main).
+// foo(Name, Age) -> ~{person, Name, Age}.
+// ```
+// ->
+// ```
+// -module(main).
+// -record(person, {name, age}).
+// foo(Name, Age) -> #person{name = Name, age = Age}.
+// ```
+pub(crate) fn tuple_to_record(acc: &mut Assists, ctx: &AssistContext) -> Option<()> {
+    let tuple = ctx.find_node_at_offset::<ast::Tuple>()?;
+    let mut elements = tuple.expr();
+    let tag = match elements.next()? {
+        ast::Expr::ExprMax(ast::ExprMax::Atom(atom)) => atom.syntax().text().to_string(),
+        _ => return None,
+    };
+    let values: Vec<String> = elements.map(|e| e.syntax().text().to_string()).collect();
+    if values.is_empty() {
+        return None;
+    }
+    let def_map = ctx.sema.def_map(ctx.file_id());
+    if def_map
+        .get_records()
+        .keys()
+        .any(|name| name.to_quoted_string() == tag)
+    {
+        return None;
+    }
+    let fields = field_names(&tuple);
+
+    let source = ctx.sema.parse(ctx.file_id()).value;
+    let form_list = ctx.sema.form_list(ctx.file_id());
+    let insert = top_insert_position(&form_list, &source);
+    let range = tuple.syntax().text_range();
+    acc.add(
+        AssistId("tuple_to_record", AssistKind::RefactorRewrite),
+        format!("Convert tuple to record `{tag}`"),
+        None,
+        range,
+        None,
+        |builder| match ctx.config.snippet_cap {
+            Some(cap) => {
+                let placeholders: Vec<String> = fields
+                    .iter()
+                    .enumerate()
+                    .map(|(i, field)| format!("${{{}:{}}}", i + 1, field))
+                    .collect();
+                let tag = escape_snippet(&tag);
+                let values: Vec<String> = values.iter().map(|v| escape_snippet(v)).collect();
+                builder.insert_snippet(cap, insert, record_definition(&tag, &placeholders));
+                builder.delete(range);
+                builder.insert_snippet(
+                    cap,
+                    range.start(),
+                    record_construction(&tag, &placeholders, &values),
+                );
+            }
+            None => {
+                builder.insert(insert, record_definition(&tag, &fields));
+                builder.replace(range, record_construction(&tag, &fields, &values));
+            }
+        },
+    )
+}
+
+/// Field names for the elements of the tuple after its tag, based on the
+/// variable they are bound to if any.
+fn field_names(tuple: &ast::Tuple) -> Vec<String> {
+    let mut names: Vec<String> = Vec::new();
+    for (i, element) in tuple.expr().skip(1).enumerate() {
+        let name = match element {
+            ast::Expr::ExprMax(ast::ExprMax::Var(var)) => {
+                to_snake_case(var.syntax().text().to_string().trim_start_matches('_'))
+            }
+            _ => String::new(),
+        };
+        if name.is_empty() || names.contains(&name) {
+            names.push(format!("field{}", i + 1));
+        } else {
+            names.push(name);
+        }
+    }
+    names
+}
+
+fn to_snake_case(name: &str) -> String {
+    let mut res = String::new();
+    for (i, c) in name.chars().enumerate() {
+        if c.is_uppercase() {
+            if i > 0 {
+                res.push('_');
+            }
+            res.extend(c.to_lowercase());
+        } else {
+            res.push(c);
+        }
+    }
+    res
+}
+
+fn record_definition(tag: &str, fields: &[String]) -> String {
+    format!("-record({tag}, {{{}}}).\n", fields.join(", "))
+}
+
+fn record_construction(tag: &str, fields: &[String], values: &[String]) -> String {
+    let fields: Vec<String> = fields
+        .iter()
+        .zip(values)
+        .map(|(field, value)| format!("{field} = {value}"))
+        .collect();
+    format!("#{tag}{{{}}}", fields.join(", "))
+}
+
+#[cfg(test)]
+mod tests {
+    use expect_test::expect;
+
+    use super::*;
+    use crate::tests::*;
+
+    #[test]
+    fn convert_tuple() {
+        check_assist(
+            tuple_to_record,
+            "Convert tuple to record `person`",
+            r#"
+-module(main).
+foo(Name, Age) -> ~{person, Name, Age}.
+"#,
+            expect![[r#"
+                -module(main).
+                -record(person, {${1:name}, ${2:age}}).
+                foo(Name, Age) -> #person{${1:name} = Name, ${2:age} = Age}.
+            "#]],
+        )
+    }
+
+    #[test]
+    fn convert_tuple_with_literals() {
+        check_assist(
+            tuple_to_record,
+            "Convert tuple to record `ok`",
+            r#"
+-module(main).
+foo(UserId, X) -> {o~k, UserId, 42, X, X}.
+"#,
+            expect![[r#"
+                -module(main).
+                -record(ok, {${1:user_id}, ${2:field2}, ${3:x}, ${4:field4}}).
+                foo(UserId, X) -> #ok{${1:user_id} = UserId, ${2:field2} = 42, ${3:x} = X, ${4:field4} = X}.
+            "#]],
+        )
+    }
+
+    #[test]
+    fn not_applicable_without_tag() {
+        check_assist_not_applicable(
+            tuple_to_record,
+            r#"
+-module(main).
+foo(Name, Age) -> ~{Name, Age}.
+"#,
+        );
+    }
+
+    #[test]
+    fn not_applicable_on_empty_tuple() {
+        check_assist_not_applicable(
+            tuple_to_record,
+            r#"
+-module(main).
+foo() -> ~{}.
+"#,
+        );
+    }
+
+    #[test]
+    fn not_applicable_when_record_exists() {
+        check_assist_not_applicable(
+            tuple_to_record,
+            r#"
+-module(main).
+-record(person, {name}).
+foo(Name) -> ~{person, Name}.
+"#,
+        );
+    }
+
+    #[test]
+    fn convert_tuple_escapes_snippet() {
+        check_assist(
+            tuple_to_record,
+            "Convert tuple to record `'cost$'`",
+            r#"
+-module(main).
+foo(Price) -> ~{'cost$', Price, <<"${amount}">>}.
+"#,
+            expect![[r#"
+                -module(main).
+                -record('cost\$', {${1:price}, ${2:field2}}).
+                foo(Price) -> #'cost\$'{${1:price} = Price, ${2:field2} = <<"\${amount\}">>}.
+            "#]],
+        )
+    }
+}
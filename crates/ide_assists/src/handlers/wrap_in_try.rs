@@ -0,0 +1,230 @@
+/*
+ * Copyright (c) Meta Platforms, Inc. and affiliates.
+ *
+ * This source code is dual-licensed under either the MIT license found in the
+ * LICENSE-MIT file in the root directory of this source tree or the Apache
+ * License, Version 2.0 found in the LICENSE-APACHE file in the root directory
+ * of this source tree. You may select, at your option, one of the
+ * above-listed licenses.
+ */
+
+use elp_ide_db::assists::AssistId;
+use elp_ide_db::assists::AssistKind;
+use elp_ide_db::helpers::escape_snippet;
+use elp_syntax::AstNode;
+use elp_syntax::NodeOrToken;
+use elp_syntax::SyntaxKind;
+use elp_syntax::SyntaxNode;
+use elp_syntax::TextRange;
+use elp_syntax::ast;
+use hir::InFile;
+
+use crate::AssistContext;
+use crate::Assists;
+use crate::helpers::freshen_variable_name;
+
+// Assist: wrap_in_try
+//
+// Wrap the selected expressions in a `try ... catch` re-raising any
+// exception, ready to be edited.
+//
+// ```
+// foo() ->
+//     ~bar()~,
+//     ok.
+// ```
+// ->
+// ```
+// foo() ->
+//     try bar() catch Class:Reason:Stacktrace -> erlang:raise(Class, Reason, Stacktrace) end,
+//     ok.
+// ```
+pub(crate) fn wrap_in_try(acc: &mut Assists, ctx: &AssistContext) -> Option<()> {
+    if ctx.has_empty_selection() {
+        return None;
+    }
+    let node = match ctx.covering_element() {
+        NodeOrToken::Node(it) => it,
+        NodeOrToken::Token(it) if it.kind() == SyntaxKind::COMMENT => return None,
+        NodeOrToken::Token(it) => it.parent()?,
+    };
+    if !in_function_body(&node) {
+        return None;
+    }
+    let range = statements_range(ctx, &node)?;
+    let text = ctx.sema.db.file_text(ctx.file_id());
+    let selected = &text[range];
+    let [class, reason, stacktrace] = catch_vars(ctx, &node);
+
+    acc.add(
+        AssistId("wrap_in_try", AssistKind::RefactorRewrite),
+        "Wrap in `try ... catch`",
+        None,
+        range,
+        None,
+        |builder| match ctx.config.snippet_cap {
+            Some(cap) => {
+                let snippet = format!(
+                    "try {} catch ${{1:{class}}}:${{2:{reason}}}:${{3:{stacktrace}}} -> ${{0:erlang:raise({class}, {reason}, {stacktrace})}} end",
+                    escape_snippet(selected)
+                );
+                builder.delete(range);
+                builder.insert_snippet(cap, range.start(), snippet);
+            }
+            None => {
+                builder.replace(
+                    range,
+                    format!(
+                        "try {selected} catch {class}:{reason}:{stacktrace} -> erlang:raise({class}, {reason}, {stacktrace}) end"
+                    ),
+                );
+            }
+        },
+    )
+}
+
+/// Names for the variables bound by the catch clause, not clashing with
+/// any variable of the enclosing clause.
+fn catch_vars(ctx: &AssistContext, node: &SyntaxNode) -> [String; 3] {
+    let expr = match ast::ClauseBody::cast(node.clone()) {
+        Some(body) => body.exprs().next(),
+        None => node.ancestors().find_map(ast::Expr::cast),
+    };
+    let vars_in_clause = expr.and_then(|expr| {
+        ctx.sema
+            .find_vars_in_clause_ast(&InFile::new(ctx.file_id(), &expr))
+    });
+    ["Class", "Reason", "Stacktrace"]
+        .map(|name| freshen_variable_name(&ctx.sema, name.to_string(), &vars_in_clause))
+}
+
+/// Guards and specs cannot contain a `try`, so only expressions in the body
+/// of a clause can be wrapped.
+fn in_function_body(node: &SyntaxNode) -> bool {
+    let mut in_body = false;
+    for ancestor in node.ancestors() {
+        if ast::Guard::can_cast(ancestor.kind()) || ast::Spec::can_cast(ancestor.kind()) {
+            return false;
+        }
+        in_body |= ast::ClauseBody::can_cast(ancestor.kind());
+    }
+    in_body
+}
+
+/// The range of the statements covered by the selection: either a single
+/// expression, or a sequence of expressions of a clause body.
+fn statements_range(ctx: &AssistContext, node: &SyntaxNode) -> Option<TextRange> {
+    let selection = ctx.selection_trimmed();
+    if let Some(body) = ast::ClauseBody::cast(node.clone()) {
+        let exprs: Vec<_> = body
+            .exprs()
+            .filter(|expr| selection.contains_range(expr.syntax().text_range()))
+            .collect();
+        let first = exprs.first()?.syntax().text_range();
+        let last = exprs.last()?.syntax().text_range();
+        return Some(first.cover(last));
+    }
+    let expr = node.ancestors().find_map(ast::Expr::cast)?;
+    Some(expr.syntax().text_range())
+}
+
+#[cfg(test)]
+mod tests {
+    use expect_test::expect;
+
+    use super::*;
+    use crate::tests::*;
+
+    #[test]
+    fn wrap_expression() {
+        check_assist(
+            wrap_in_try,
+            "Wrap in `try ... catch`",
+            r#"
+foo() ->
+    ~bar()~,
+    ok.
+"#,
+            expect![[r#"
+                foo() ->
+                    try bar() catch ${1:Class}:${2:Reason}:${3:Stacktrace} -> ${0:erlang:raise(Class, Reason, Stacktrace)} end,
+                    ok.
+            "#]],
+        )
+    }
+
+    #[test]
+    fn wrap_statements() {
+        check_assist(
+            wrap_in_try,
+            "Wrap in `try ... catch`",
+            r#"
+foo() ->
+    ~X = bar(),
+    baz(X)~,
+    ok.
+"#,
+            expect![[r#"
+                foo() ->
+                    try X = bar(),
+                    baz(X) catch ${1:Class}:${2:Reason}:${3:Stacktrace} -> ${0:erlang:raise(Class, Reason, Stacktrace)} end,
+                    ok.
+            "#]],
+        )
+    }
+
+    #[test]
+    fn wrap_freshens_catch_vars() {
+        check_assist(
+            wrap_in_try,
+            "Wrap in `try ... catch`",
+            r#"
+foo(Reason) ->
+    Class = bar,
+    ~baz(Class, Reason)~.
+"#,
+            expect![[r#"
+                foo(Reason) ->
+                    Class = bar,
+                    try baz(Class, Reason) catch ${1:Class0}:${2:Reason0}:${3:Stacktrace} -> ${0:erlang:raise(Class0, Reason0, Stacktrace)} end.
+            "#]],
+        )
+    }
+
+    #[test]
+    fn wrap_escapes_snippet() {
+        check_assist(
+            wrap_in_try,
+            "Wrap in `try ... catch`",
+            r#"
+foo() ->
+    ~bar($})~.
+"#,
+            expect![[r#"
+                foo() ->
+                    try bar(\$\}) catch ${1:Class}:${2:Reason}:${3:Stacktrace} -> ${0:erlang:raise(Class, Reason, Stacktrace)} end.
+            "#]],
+        )
+    }
+
+    #[test]
+    fn not_applicable_in_guard() {
+        check_assist_not_applicable(
+            wrap_in_try,
+            r#"
+foo(X) when ~is_atom(X)~ -> X.
+"#,
+        );
+    }
+
+    #[test]
+    fn not_applicable_in_spec() {
+        check_assist_not_applicable(
+            wrap_in_try,
+            r#"
+-spec foo(~atom()~) -> ok.
+foo(_) -> ok.
+"#,
+        );
+    }
+}
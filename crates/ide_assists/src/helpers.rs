@@ -33,6 +33,8 @@ use elp_syntax::TextRange;
 use elp_syntax::algo;
 use elp_syntax::algo::skip_inline_comment;
 use elp_syntax::ast;
+use elp_syntax::ast::BinaryOp;
+use elp_syntax::ast::UnaryOp;
 use elp_syntax::match_ast;
 use elp_text_edit::TextEdit;
 use elp_text_edit::TextSize;
@@ -80,7 +82,7 @@ pub(crate) fn suggest_name_for_variable(_expr: &ast::Expr, _sema: &Semantic) ->
 
 /// Given a variable name and vars in scope, return either the
 /// original if it does not clash, or one with the smallest numeric suffix to be fresh.
-pub(crate) fn freshen_variable_name(
+pub fn freshen_variable_name(
     sema: &Semantic,
     var_name: String,
     vars_in_clause: &Option<FxHashSet<Var>>,
@@ -714,6 +716,53 @@ pub fn rename_atom_in_compile_attribute(
     Some(())
 }
 
+/// Remove the given atoms from the options of a compile attribute, and
+/// the attribute itself if no option is left.
+pub fn remove_atoms_from_compile_attribute(
+    sema: &Semantic,
+    file_id: FileId,
+    co_id: &CompileOptionId,
+    atoms: &[&str],
+    builder: &mut SourceChangeBuilder,
+) -> Option<()> {
+    let form_list = sema.form_list(file_id);
+    let source = sema.parse(file_id).value;
+    let co = &form_list[*co_id];
+    let attr_ast = co.form_id.get(&source);
+    let is_removed = |expr: &ast::Expr| match expr {
+        ast::Expr::ExprMax(ast::ExprMax::Atom(a)) => {
+            atoms.contains(&a.syntax().text().to_string().as_str())
+        }
+        _ => false,
+    };
+    let options = attr_ast.options()?;
+    let remaining = match &options {
+        ast::Expr::ExprMax(ast::ExprMax::List(list)) => {
+            let exprs: Vec<ast::Expr> = list.exprs().collect();
+            let remaining: Vec<String> = exprs
+                .iter()
+                .filter(|e| !is_removed(e))
+                .map(|e| e.syntax().text().to_string())
+                .collect();
+            if remaining.len() == exprs.len() {
+                return None;
+            }
+            remaining
+        }
+        option if is_removed(option) => vec![],
+        _ => return None,
+    };
+    if remaining.is_empty() {
+        builder.delete(extend_form_range_for_delete(attr_ast.syntax()));
+    } else {
+        builder.replace(
+            options.syntax().text_range(),
+            format!("[{}]", remaining.join(", ")),
+        );
+    }
+    Some(())
+}
+
 pub fn moduledoc_insert_offset(sema: &Semantic, file_id: FileId) -> Option<TextSize> {
     let form_list = sema.form_list(file_id);
     let module_attribute_range = form_list.module_attribute()?;
@@ -1007,3 +1056,81 @@ impl<'a> ExportBuilder<'a> {
         }
     }
 }
+
+/// Guard BIFs testing the type of their single argument.
+const TYPE_TESTS: &[&str] = &[
+    "is_atom",
+    "is_binary",
+    "is_bitstring",
+    "is_boolean",
+    "is_float",
+    "is_function",
+    "is_integer",
+    "is_list",
+    "is_map",
+    "is_number",
+    "is_pid",
+    "is_port",
+    "is_reference",
+    "is_tuple",
+];
+
+/// A test which always evaluates to a boolean without raising an
+/// exception, such as a type test or a comparison of variables.
+pub(crate) fn is_safe_test(expr: &ast::Expr) -> bool {
+    match expr {
+        ast::Expr::ExprMax(ast::ExprMax::Atom(atom)) => {
+            matches!(atom.syntax().text().to_string().as_str(), "true" | "false")
+        }
+        ast::Expr::ExprMax(ast::ExprMax::ParenExpr(paren)) => {
+            paren.expr().is_some_and(|expr| is_safe_test(&expr))
+        }
+        ast::Expr::UnaryOpExpr(unary) => {
+            matches!(unary.op(), Some((UnaryOp::Not, _)))
+                && unary.operand().is_some_and(|expr| is_safe_test(&expr))
+        }
+        ast::Expr::BinaryOpExpr(binary) => match binary.op() {
+            Some((BinaryOp::CompOp(_), _)) => {
+                binary.lhs().is_some_and(|expr| is_safe_value(&expr))
+                    && binary.rhs().is_some_and(|expr| is_safe_value(&expr))
+            }
+            Some((BinaryOp::LogicOp(_), _)) => {
+                binary.lhs().is_some_and(|expr| is_safe_test(&expr))
+                    && binary.rhs().is_some_and(|expr| is_safe_test(&expr))
+            }
+            _ => false,
+        },
+        ast::Expr::Call(call) => {
+            let is_type_test = match call.expr() {
+                Some(ast::Expr::ExprMax(ast::ExprMax::Atom(atom))) => {
+                    TYPE_TESTS.contains(&atom.syntax().text().to_string().as_str())
+                }
+                _ => false,
+            };
+            let args: Vec<ast::Expr> = call
+                .args()
+                .map(|args| args.args().collect())
+                .unwrap_or_default();
+            is_type_test && args.len() == 1 && args.iter().all(is_safe_value)
+        }
+        _ => false,
+    }
+}
+
+/// A value which evaluates without raising an exception.
+pub(crate) fn is_safe_value(expr: &ast::Expr) -> bool {
+    match expr {
+        ast::Expr::ExprMax(
+            ast::ExprMax::Var(_)
+            | ast::ExprMax::Atom(_)
+            | ast::ExprMax::Char(_)
+            | ast::ExprMax::Float(_)
+            | ast::ExprMax::Integer(_)
+            | ast::ExprMax::String(_),
+        ) => true,
+        ast::Expr::ExprMax(ast::ExprMax::ParenExpr(paren)) => {
+            paren.expr().is_some_and(|expr| is_safe_value(&expr))
+        }
+        _ => false,
+    }
+}
@@ -71,18 +71,40 @@ mod handlers {
     mod add_format;
     mod add_impl;
     mod add_spec;
+    mod add_type_parameter;
+    mod align_arrows;
+    mod binary_to_string;
     mod bump_variables;
+    mod case_to_function_clauses;
+    mod consolidate_exports;
     mod create_function;
     mod delete_function;
+    mod eta_contract;
+    mod eta_expand;
+    mod export_all_to_explicit;
     mod export_function;
     mod export_type;
+    mod extract_fun_to_function;
     mod extract_function;
+    mod extract_to_macro;
     mod extract_variable;
     mod flip_sep;
+    mod guard_to_boolean_operators;
+    mod if_to_case;
     mod ignore_variable;
     mod implement_behaviour;
     mod inline_function;
+    mod inline_function_call;
     mod inline_local_variable;
+    mod invert_boolean_condition;
+    mod merge_function_clauses;
+    mod sort_record_fields;
+    mod split_case_clause;
+    mod string_to_binary;
+    mod toggle_assert_match;
+    mod toggle_export;
+    mod tuple_to_record;
+    mod wrap_in_try;
 
     pub(crate) fn all() -> &'static [Handler] {
         &[
@@ -92,18 +114,40 @@ mod handlers {
             add_format::add_format,
             add_impl::add_impl,
             add_spec::add_spec,
+            add_type_parameter::add_type_parameter,
+            align_arrows::align_arrows,
+            binary_to_string::binary_to_string,
             bump_variables::bump_variables,
+            case_to_function_clauses::case_to_function_clauses,
+            consolidate_exports::consolidate_exports,
             create_function::create_function,
             delete_function::delete_function,
+            eta_contract::eta_contract,
+            eta_expand::eta_expand,
+            export_all_to_explicit::export_all_to_explicit,
             export_function::export_function,
             export_type::export_type,
+            extract_fun_to_function::extract_fun_to_function,
             extract_function::extract_function,
+            extract_to_macro::extract_to_macro,
             extract_variable::extract_variable,
             flip_sep::flip_sep,
+            guard_to_boolean_operators::guard_to_boolean_operators,
+            if_to_case::if_to_case,
             ignore_variable::ignore_variable,
             implement_behaviour::implement_behaviour,
             inline_function::inline_function,
+            inline_function_call::inline_function_call,
             inline_local_variable::inline_local_variable,
+            invert_boolean_condition::invert_boolean_condition,
+            merge_function_clauses::merge_function_clauses,
+            sort_record_fields::sort_record_fields,
+            split_case_clause::split_case_clause,
+            string_to_binary::string_to_binary,
+            toggle_assert_match::toggle_assert_match,
+            toggle_export::toggle_export,
+            tuple_to_record::tuple_to_record,
+            wrap_in_try::wrap_in_try,
             // These are manually sorted for better priorities. By default,
             // priority is determined by the size of the target range (smaller
             // target wins). If the ranges are equal, position in this list is
@@ -26,10 +26,12 @@ use elp_ide_db::elp_base_db::remove_annotations;
 use elp_ide_db::helpers::SnippetCap;
 use elp_ide_db::source_change::FileSystemEdit;
 use elp_ide_db::source_change::SourceChangeBuilder;
+use elp_syntax::AstNode;
 use elp_syntax::SourceFile;
 use elp_syntax::ast;
 use expect_test::Expect;
 use expect_test::expect;
+use fxhash::FxHashSet;
 use hir::Expr;
 use hir::InFile;
 use hir::Semantic;
@@ -42,10 +44,13 @@ use crate::Assists;
 use crate::handlers::Handler;
 use crate::helpers;
 
-pub(crate) const TEST_CONFIG: AssistConfig = AssistConfig {
-    snippet_cap: SnippetCap::new(true),
-    allowed: None,
-};
+pub(crate) fn test_config() -> AssistConfig {
+    AssistConfig {
+        snippet_cap: SnippetCap::new(true),
+        allowed: None,
+        disabled_ids: FxHashSet::default(),
+    }
+}
 
 #[track_caller]
 pub(crate) fn check_assist(
@@ -159,7 +164,7 @@ fn check(
     let frange = fixture.range();
 
     let sema = &db;
-    let config = TEST_CONFIG;
+    let config = test_config();
     let context_diagnostics = fixture.annotations_by_file_id(&file_id);
     let mut diagnostics = vec![];
     for (range, text) in &context_diagnostics {
@@ -302,6 +307,31 @@ fn check(
     };
 }
 
+#[test]
+fn disabled_assist_ids_are_skipped() {
+    let fixture = r#"
+-module(main).
+foo(L) -> lists:map(fun bar/1~, L).
+bar(X) -> X.
+"#;
+    let (db, frange) = RootDatabase::with_range(fixture);
+    let ids = |config: &AssistConfig| {
+        crate::assists(&db, config, AssistResolveStrategy::None, frange, &[], None)
+            .into_iter()
+            .map(|assist| assist.id.0)
+            .collect::<Vec<_>>()
+    };
+    let mut config = test_config();
+    let enabled = ids(&config);
+    assert!(enabled.contains(&"flip_sep"), "{enabled:?}");
+    assert!(enabled.contains(&"eta_expand"), "{enabled:?}");
+
+    config.disabled_ids.insert("flip_sep".to_string());
+    let remaining = ids(&config);
+    assert!(!remaining.contains(&"flip_sep"), "{remaining:?}");
+    assert!(remaining.contains(&"eta_expand"), "{remaining:?}");
+}
+
 #[test]
 fn test_whitespace_skip1() {
     let before = r#"
@@ -314,7 +344,7 @@ bar() ->
 
     let (db, frange) = RootDatabase::with_range(before);
     let sema = &db;
-    let config = TEST_CONFIG;
+    let config = test_config();
     let diagnostics = vec![];
     let ctx = AssistContext::new(sema, &config, frange, &diagnostics, None);
     expect![[r#"
@@ -344,7 +374,7 @@ bar() ->
 
     let (db, frange) = RootDatabase::with_range(before);
     let sema = &db;
-    let config = TEST_CONFIG;
+    let config = test_config();
     let diagnostics = vec![];
     let ctx = AssistContext::new(sema, &config, frange, &diagnostics, None);
     expect![[r#"
@@ -362,13 +392,62 @@ bar() ->
     .assert_debug_eq(&ctx.selection_trimmed());
 }
 
+#[track_caller]
+fn check_selected_expr(fixture: &str, expect: Expect) {
+    let (db, frange) = RootDatabase::with_range(fixture);
+    let config = test_config();
+    let diagnostics = vec![];
+    let ctx = AssistContext::new(&db, &config, frange, &diagnostics, None);
+    let actual = match ctx.selected_expr() {
+        Some(expr) => expr.syntax().text().to_string(),
+        None => "None".to_string(),
+    };
+    expect.assert_eq(&actual);
+}
+
+#[test]
+fn test_selected_expr_exact() {
+    check_selected_expr(
+        r#"
+bar(B) ->
+  A = ~ foo(3 + B) ~,
+  A.
+"#,
+        expect!["foo(3 + B)"],
+    );
+}
+
+#[test]
+fn test_selected_expr_partial() {
+    check_selected_expr(
+        r#"
+bar(B) ->
+  A = foo(3 ~+ B)~,
+  A.
+"#,
+        expect!["None"],
+    );
+}
+
+#[test]
+fn test_selected_expr_multiple_statements() {
+    check_selected_expr(
+        r#"
+bar(B) ->
+  ~A = foo(3 + B),
+  A~.
+"#,
+        expect!["None"],
+    );
+}
+
 #[test]
 fn test_function_args() {
     let fixture = r#"heavy_calculations(X) -> ~life:foo(X, X+1)~."#;
 
     let (db, frange) = RootDatabase::with_range(fixture);
     let sema = &db;
-    let config = TEST_CONFIG;
+    let config = test_config();
     let diagnostics = vec![];
     let ctx = AssistContext::new(sema, &config, frange, &diagnostics, None);
     let call: ast::Call = ctx.find_node_at_offset().unwrap();
@@ -395,7 +474,7 @@ fn test_function_args_from_type() {
 
     let (db, frange) = RootDatabase::with_range(fixture);
     let sema = &db;
-    let config = TEST_CONFIG;
+    let config = test_config();
     let diagnostics = vec![];
     let ctx = AssistContext::new(sema, &config, frange, &diagnostics, None);
     let behaviour_forms = ctx.sema.form_list(ctx.file_id());
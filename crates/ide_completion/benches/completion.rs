@@ -0,0 +1,45 @@
+/*
+ * Copyright (c) Meta Platforms, Inc. and affiliates.
+ *
+ * This source code is dual-licensed under either the MIT license found in the
+ * LICENSE-MIT file in the root directory of this source tree or the Apache
+ * License, Version 2.0 found in the LICENSE-APACHE file in the root directory
+ * of this source tree. You may select, at your option, one of the
+ * above-listed licenses.
+ */
+
+//! Repeated completion requests at the same position of a large module,
+//! as sent by clients while the user keeps typing or re-triggers
+//! completion.
+
+use criterion::Criterion;
+use criterion::criterion_group;
+use criterion::criterion_main;
+use elp_ide_completion::CompletionConfig;
+use elp_ide_completion::completions;
+use elp_ide_db::RootDatabase;
+use elp_ide_db::elp_base_db::fixture::WithFixture;
+
+/// A module of about 5000 lines, with the cursor in the last function.
+fn large_module() -> String {
+    let mut text = String::from("-module(large).\n-export([main/0]).\n");
+    for i in 0..1000 {
+        text.push_str(&format!(
+            "fun_{i}(X) ->\n    Y = X + {i},\n    Z = lists:map(fun(E) -> E * Y end, [X, Y]),\n    {{Y, Z}}.\n\n"
+        ));
+    }
+    text.push_str("main() ->\n    fun_~.\n");
+    text
+}
+
+fn bench_completions(c: &mut Criterion) {
+    let (db, fixture) = RootDatabase::with_fixture(&large_module());
+    let position = fixture.position();
+    let config = CompletionConfig::default();
+    c.bench_function("completions in 5k line module", |b| {
+        b.iter(|| completions(&db, &config, position, None))
+    });
+}
+
+criterion_group!(benches, bench_completions);
+criterion_main!(benches);
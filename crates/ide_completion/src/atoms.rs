@@ -0,0 +1,172 @@
+/*
+ * Copyright (c) Meta Platforms, Inc. and affiliates.
+ *
+ * This source code is dual-licensed under either the MIT license found in the
+ * LICENSE-MIT file in the root directory of this source tree or the Apache
+ * License, Version 2.0 found in the LICENSE-APACHE file in the root directory
+ * of this source tree. You may select, at your option, one of the
+ * above-listed licenses.
+ */
+
+use fxhash::FxHashSet;
+use hir::AnyExpr;
+use hir::Atom;
+use hir::CallTarget;
+use hir::Expr;
+use hir::Literal;
+use hir::Pat;
+use hir::Strategy;
+use hir::fold::MacroStrategy;
+use hir::fold::ParenStrategy;
+
+use crate::Completion;
+use crate::Contents;
+use crate::Ctx;
+use crate::DoneFlag;
+use crate::Kind;
+use crate::helpers;
+use crate::helpers::Proximity;
+use crate::keywords;
+
+/// Complete atoms already used in the bodies of the functions of the
+/// current module, to help avoid typos such as `error_code` vs
+/// `error_codes`.
+pub(crate) fn add_completions(
+    acc: &mut Vec<Completion>,
+    Ctx {
+        sema,
+        trigger,
+        file_position,
+        previous_tokens,
+        ..
+    }: &Ctx,
+) -> DoneFlag {
+    use elp_syntax::SyntaxKind as K;
+    let default = vec![];
+    let previous_tokens: &[_] = previous_tokens.as_ref().unwrap_or(&default);
+    let prefix = match previous_tokens {
+        [
+            ..,
+            (K::ANON_COLON | K::ANON_POUND | K::ANON_QMARK, _),
+            (K::ATOM, _),
+        ] => return false,
+        [.., (K::ATOM, atom)]
+            if trigger.is_none() && atom.text_range().end() == file_position.offset =>
+        {
+            atom.text()
+        }
+        _ => return false,
+    };
+
+    let mut atoms: FxHashSet<Atom> = FxHashSet::default();
+    // Names of called functions and modules are already completed as such
+    let mut call_targets: FxHashSet<Atom> = FxHashSet::default();
+    sema.for_each_function(file_position.file_id, |def| {
+        let def_fb = def.in_function_body(sema, def);
+        def_fb.fold_function(
+            Strategy {
+                macros: MacroStrategy::DoNotExpand,
+                parens: ParenStrategy::InvisibleParens,
+            },
+            (),
+            &mut |_acc, clause_id, ctx| match ctx.item {
+                AnyExpr::Expr(Expr::Literal(Literal::Atom(atom)))
+                | AnyExpr::Pat(Pat::Literal(Literal::Atom(atom))) => {
+                    atoms.insert(atom);
+                }
+                AnyExpr::Expr(Expr::Call { target, .. }) => {
+                    let body = def_fb.body(clause_id);
+                    let targets = match target {
+                        CallTarget::Local { name } => vec![name],
+                        CallTarget::Remote { module, name, .. } => vec![module, name],
+                    };
+                    call_targets.extend(targets.into_iter().filter_map(|id| body[id].as_atom()));
+                }
+                _ => {}
+            },
+        );
+    });
+
+    let def_map = sema.def_map(file_position.file_id);
+    let functions: FxHashSet<_> = def_map
+        .get_functions()
+        .map(|(na, _)| na.name().clone())
+        .collect();
+    let completions = atoms
+        .difference(&call_targets)
+        .map(|atom| sema.db.lookup_atom(*atom))
+        .filter(|name| {
+            name.as_str() != prefix
+                && name.starts_with(prefix)
+                && !functions.contains(name)
+                && !keywords::is_keyword(name.as_str())
+        })
+        .map(|name| {
+            let label = name.to_quoted_string().to_string();
+            Completion {
+                sort_text: helpers::sort_text(Proximity::Local, &label),
+                label,
+                kind: Kind::Keyword,
+                contents: Contents::SameAsLabel,
+                position: None,
+                deprecated: false,
+                additional_edit: None,
+            }
+        });
+    acc.extend(completions);
+    false
+}
+
+#[cfg(test)]
+mod test {
+    use expect_test::Expect;
+    use expect_test::expect;
+
+    use crate::Kind;
+    use crate::tests::get_completions;
+    use crate::tests::render_completions;
+
+    fn check(code: &str, prefix: &str, expect: Expect) {
+        // Keywords are offered regardless of the prefix, so only look at
+        // the atoms for the prefix being typed
+        let completions = get_completions(code, None)
+            .into_iter()
+            .filter(|c| c.kind == Kind::Keyword && c.label.starts_with(prefix))
+            .collect();
+        let actual = &render_completions(completions);
+        expect.assert_eq(actual);
+    }
+
+    #[test]
+    fn test_atoms_in_module() {
+        check(
+            r#"
+-module(sample).
+foo(error_code) -> {error_codes, bar()};
+foo(X) -> X =:= error_count.
+bar() -> error_co~.
+"#,
+            "error_co",
+            expect![[r#"
+                {label:error_code, kind:Keyword, contents:SameAsLabel, position:None}
+                {label:error_codes, kind:Keyword, contents:SameAsLabel, position:None}
+                {label:error_count, kind:Keyword, contents:SameAsLabel, position:None}"#]],
+        );
+    }
+
+    #[test]
+    fn test_atoms_excludes_function_names() {
+        check(
+            r#"
+-module(sample).
+foo() -> lists:reverse(foo_bar()), foo_baz.
+foo_bar() -> [foo_qux].
+bar() -> foo~.
+"#,
+            "foo",
+            expect![[r#"
+                {label:foo_baz, kind:Keyword, contents:SameAsLabel, position:None}
+                {label:foo_qux, kind:Keyword, contents:SameAsLabel, position:None}"#]],
+        );
+    }
+}
@@ -8,11 +8,17 @@
  * above-listed licenses.
  */
 
+use elp_base_db::FileId;
+use fxhash::FxHashSet;
+use hir::Semantic;
+
 use crate::Completion;
 use crate::Contents;
 use crate::Ctx;
 use crate::DoneFlag;
 use crate::Kind;
+use crate::helpers;
+use crate::helpers::Proximity;
 
 pub(crate) fn add_completions(
     acc: &mut Vec<Completion>,
@@ -35,47 +41,39 @@ pub(crate) fn add_completions(
             (K::ANON_BEHAVIOR | K::ANON_BEHAVIOUR, _),
             (K::ANON_LPAREN, _),
             (K::ATOM, behavior_name_prefix),
-        ] if trigger.is_none() => || -> _ {
-            let modules = sema.resolve_module_names(file_position.file_id)?;
-            let completions = modules.into_iter().filter_map(|m| {
-                if m.starts_with(behavior_name_prefix.text()) {
-                    let module = sema.resolve_module_name(file_position.file_id, &m)?;
-                    let def_map = sema.def_map(module.file.file_id);
-                    if def_map.get_callbacks().is_empty() {
-                        None
-                    } else {
-                        Some(Completion {
-                            label: m.to_string(),
-                            kind: Kind::Behavior,
-                            contents: Contents::SameAsLabel,
-                            position: None,
-                            sort_text: None,
-                            deprecated: false,
-                            additional_edit: None,
-                        })
-                    }
-                } else {
-                    None
-                }
-            });
-
-            acc.extend(completions);
-            Some(true)
-        }()
-        .unwrap_or_default(),
+        ] if trigger.is_none() => {
+            add_behavior_completions(
+                acc,
+                sema,
+                file_position.file_id,
+                behavior_name_prefix.text(),
+            );
+            true
+        }
+        // -behavior(~
+        [
+            ..,
+            (K::ANON_DASH, _),
+            (K::ANON_BEHAVIOR | K::ANON_BEHAVIOUR, _),
+            (K::ANON_LPAREN, _),
+        ] if trigger.is_none() => {
+            add_behavior_completions(acc, sema, file_position.file_id, "");
+            true
+        }
 
         [.., (K::ANON_DASH, _), (K::ATOM, attr_name)] if matches!(trigger, Some('-') | None) => {
             if "module".starts_with(attr_name.text()) {
                 if let Some(module) = sema.module_name(file_position.file_id) {
+                    let label = format!("-module({}).", module.to_quoted_string());
                     acc.push(Completion {
                         kind: Kind::Attribute,
-                        label: format!("-module({}).", module.to_quoted_string()),
+                        sort_text: helpers::sort_text(Proximity::Syntax, &label),
+                        label,
                         contents: Contents::Snippet(format!(
                             "module({}).",
                             module.to_quoted_string()
                         )),
                         position: None,
-                        sort_text: None,
                         deprecated: false,
                         additional_edit: None,
                     });
@@ -84,12 +82,13 @@ pub(crate) fn add_completions(
                     false
                 }
             } else if "typing".starts_with(attr_name.text()) {
+                let label = "-typing([eqwalizer]).".to_string();
                 acc.push(Completion {
                     kind: Kind::Attribute,
-                    label: "-typing([eqwalizer]).".to_string(),
+                    sort_text: helpers::sort_text(Proximity::Syntax, &label),
+                    label,
                     contents: Contents::Snippet("typing([eqwalizer]).".to_string()),
                     position: None,
-                    sort_text: None,
                     deprecated: false,
                     additional_edit: None,
                 });
@@ -103,6 +102,61 @@ pub(crate) fn add_completions(
     }
 }
 
+/// Behaviours of OTP, offered even when OTP is not part of the project.
+const OTP_BEHAVIORS: &[&str] = &[
+    "application",
+    "gen_event",
+    "gen_server",
+    "gen_statem",
+    "supervisor",
+];
+
+/// Complete the known OTP behaviours and the modules defining `-callback`
+/// attributes whose name starts with `prefix`.
+fn add_behavior_completions(
+    acc: &mut Vec<Completion>,
+    sema: &Semantic,
+    file_id: FileId,
+    prefix: &str,
+) {
+    let mut behaviors: FxHashSet<String> = OTP_BEHAVIORS
+        .iter()
+        .filter(|b| b.starts_with(prefix))
+        .map(|b| b.to_string())
+        .collect();
+    if let Some(modules) = sema.resolve_module_names(file_id) {
+        behaviors.extend(modules.into_iter().filter_map(|m| {
+            if !m.starts_with(prefix) {
+                return None;
+            }
+            let module = sema.resolve_module_name(file_id, &m)?;
+            let def_map = sema.def_map(module.file.file_id);
+            if def_map.get_callbacks().is_empty() {
+                None
+            } else {
+                Some(m.to_string())
+            }
+        }));
+    }
+    acc.extend(behaviors.into_iter().map(|label| {
+        // The OTP behaviours are offered even when OTP is not loaded
+        let proximity = sema
+            .resolve_module_name(file_id, &label)
+            .map_or(Proximity::Otp, |module| {
+                Proximity::of_file(sema, file_id, module.file.file_id)
+            });
+        Completion {
+            sort_text: helpers::sort_text(proximity, &label),
+            label,
+            kind: Kind::Behavior,
+            contents: Contents::SameAsLabel,
+            position: None,
+            deprecated: false,
+            additional_edit: None,
+        }
+    }));
+}
+
 #[cfg(test)]
 mod test {
     use expect_test::Expect;
@@ -142,7 +196,10 @@ mod test {
             None,
             expect![[r#"
                 {label:gen_book, kind:Behavior, contents:SameAsLabel, position:None}
-                {label:gen_look, kind:Behavior, contents:SameAsLabel, position:None}"#]],
+                {label:gen_event, kind:Behavior, contents:SameAsLabel, position:None}
+                {label:gen_look, kind:Behavior, contents:SameAsLabel, position:None}
+                {label:gen_server, kind:Behavior, contents:SameAsLabel, position:None}
+                {label:gen_statem, kind:Behavior, contents:SameAsLabel, position:None}"#]],
         );
     }
 
@@ -158,7 +215,10 @@ mod test {
 -callback init(term()) -> term().
 "#,
             None,
-            expect!["{label:gen_server, kind:Behavior, contents:SameAsLabel, position:None}"],
+            expect![[r#"
+                {label:gen_event, kind:Behavior, contents:SameAsLabel, position:None}
+                {label:gen_server, kind:Behavior, contents:SameAsLabel, position:None}
+                {label:gen_statem, kind:Behavior, contents:SameAsLabel, position:None}"#]],
         );
     }
 
@@ -175,7 +235,11 @@ mod test {
             -callback bookit(term()) -> term().
         "#,
             None,
-            expect!["{label:gen_book, kind:Behavior, contents:SameAsLabel, position:None}"],
+            expect![[r#"
+                {label:gen_book, kind:Behavior, contents:SameAsLabel, position:None}
+                {label:gen_event, kind:Behavior, contents:SameAsLabel, position:None}
+                {label:gen_server, kind:Behavior, contents:SameAsLabel, position:None}
+                {label:gen_statem, kind:Behavior, contents:SameAsLabel, position:None}"#]],
         );
 
         check(
@@ -189,7 +253,33 @@ mod test {
             -callback bookit(term()) -> term().
         "#,
             None,
-            expect!["{label:gen_book, kind:Behavior, contents:SameAsLabel, position:None}"],
+            expect![[r#"
+                {label:gen_book, kind:Behavior, contents:SameAsLabel, position:None}
+                {label:gen_event, kind:Behavior, contents:SameAsLabel, position:None}
+                {label:gen_server, kind:Behavior, contents:SameAsLabel, position:None}
+                {label:gen_statem, kind:Behavior, contents:SameAsLabel, position:None}"#]],
+        );
+    }
+
+    #[test]
+    fn test_behaviors_without_prefix() {
+        check(
+            r#"
+    //- /src/sample.erl
+    -module(sample1).
+    -behaviour(~).
+    //- /src/book.erl
+    -module(book).
+    -callback bookit(term()) -> term().
+    "#,
+            None,
+            expect![[r#"
+                {label:application, kind:Behavior, contents:SameAsLabel, position:None}
+                {label:book, kind:Behavior, contents:SameAsLabel, position:None}
+                {label:gen_event, kind:Behavior, contents:SameAsLabel, position:None}
+                {label:gen_server, kind:Behavior, contents:SameAsLabel, position:None}
+                {label:gen_statem, kind:Behavior, contents:SameAsLabel, position:None}
+                {label:supervisor, kind:Behavior, contents:SameAsLabel, position:None}"#]],
         );
     }
 
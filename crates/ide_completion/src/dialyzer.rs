@@ -0,0 +1,168 @@
+/*
+ * Copyright (c) Meta Platforms, Inc. and affiliates.
+ *
+ * This source code is dual-licensed under either the MIT license found in the
+ * LICENSE-MIT file in the root directory of this source tree or the Apache
+ * License, Version 2.0 found in the LICENSE-APACHE file in the root directory
+ * of this source tree. You may select, at your option, one of the
+ * above-listed licenses.
+ */
+
+use elp_syntax::SyntaxKind;
+use elp_syntax::SyntaxToken;
+
+use crate::Completion;
+use crate::Contents;
+use crate::Ctx;
+use crate::DoneFlag;
+use crate::Kind;
+use crate::helpers;
+use crate::helpers::Proximity;
+
+/// The options accepted by a `-dialyzer` attribute, either on their own or
+/// as the first element of a `{Option, Functions}` tuple.
+const OPTIONS: &[&str] = &[
+    "error_handling",
+    "extra_return",
+    "missing_return",
+    "no_behaviours",
+    "no_contracts",
+    "no_extra_return",
+    "no_fail_call",
+    "no_fun_app",
+    "no_improper_lists",
+    "no_match",
+    "no_missing_calls",
+    "no_missing_return",
+    "no_opaque",
+    "no_return",
+    "no_undefined_callbacks",
+    "no_underspecs",
+    "no_unknown",
+    "no_unused",
+    "nowarn_function",
+    "overspecs",
+    "specdiffs",
+    "underspecs",
+    "unknown",
+    "unmatched_returns",
+];
+
+/// Complete the options of a `-dialyzer` attribute. Function names, e.g.
+/// after `{nowarn_function, `, are left to `functions::add_completions`.
+pub(crate) fn add_completions(
+    acc: &mut Vec<Completion>,
+    Ctx {
+        previous_tokens,
+        trigger,
+        ..
+    }: &Ctx,
+) -> DoneFlag {
+    if trigger.is_some() {
+        return false;
+    }
+    let default = vec![];
+    let previous_tokens: &[_] = previous_tokens.as_ref().unwrap_or(&default);
+    let (prefix, tokens) = match previous_tokens {
+        [tokens @ .., (SyntaxKind::ATOM, prefix)] => (prefix.text(), tokens),
+        tokens => ("", tokens),
+    };
+    if !is_option_position(tokens) {
+        return false;
+    }
+    let completions = OPTIONS
+        .iter()
+        .filter(|option| option.starts_with(prefix))
+        .map(|option| Completion {
+            label: option.to_string(),
+            kind: Kind::Keyword,
+            contents: Contents::SameAsLabel,
+            position: None,
+            sort_text: helpers::sort_text(Proximity::Syntax, option),
+            deprecated: false,
+            additional_edit: None,
+        });
+    acc.extend(completions);
+    true
+}
+
+/// Whether the tokens before the cursor end at an option, rather than at
+/// the functions of a `{Option, Functions}` tuple. Walks back to the
+/// opening `(` of the attribute: being past the first element of an
+/// enclosing tuple means functions are expected.
+fn is_option_position(tokens: &[(SyntaxKind, SyntaxToken)]) -> bool {
+    let mut depth = 0;
+    let mut after_comma = false;
+    for (kind, _) in tokens.iter().rev() {
+        match kind {
+            SyntaxKind::ANON_RPAREN | SyntaxKind::ANON_RBRACK | SyntaxKind::ANON_RRACE => {
+                depth += 1
+            }
+            SyntaxKind::ANON_LBRACK | SyntaxKind::ANON_LBRACE if depth > 0 => depth -= 1,
+            SyntaxKind::ANON_LBRACE if after_comma => return false,
+            SyntaxKind::ANON_LBRACK | SyntaxKind::ANON_LBRACE => after_comma = false,
+            SyntaxKind::ANON_LPAREN if depth > 0 => depth -= 1,
+            SyntaxKind::ANON_LPAREN => return true,
+            SyntaxKind::ANON_COMMA if depth == 0 => after_comma = true,
+            _ => (),
+        }
+    }
+    // The opening `(` is too far back to tell
+    false
+}
+
+#[cfg(test)]
+mod test {
+    use expect_test::Expect;
+    use expect_test::expect;
+
+    use crate::tests::get_completions;
+    use crate::tests::render_completions;
+
+    fn check(code: &str, expect: Expect) {
+        let completions = get_completions(code, None);
+        let actual = &render_completions(completions);
+        expect.assert_eq(actual);
+    }
+
+    #[test]
+    fn test_options() {
+        check(
+            r#"
+    -module(sample).
+    -dialyzer([no_return, no_u~]).
+    foo() -> ok.
+    "#,
+            expect![[r#"
+                {label:no_undefined_callbacks, kind:Keyword, contents:SameAsLabel, position:None}
+                {label:no_underspecs, kind:Keyword, contents:SameAsLabel, position:None}
+                {label:no_unknown, kind:Keyword, contents:SameAsLabel, position:None}
+                {label:no_unused, kind:Keyword, contents:SameAsLabel, position:None}"#]],
+        );
+    }
+
+    #[test]
+    fn test_option_in_tuple() {
+        check(
+            r#"
+    -module(sample).
+    -dialyzer({now~, [foo/0]}).
+    foo() -> ok.
+    "#,
+            expect!["{label:nowarn_function, kind:Keyword, contents:SameAsLabel, position:None}"],
+        );
+    }
+
+    #[test]
+    fn test_functions_in_tuple() {
+        check(
+            r#"
+    -module(sample).
+    -dialyzer({nowarn_function, [foo/0, no~]}).
+    foo() -> ok.
+    no_return() -> ok.
+    "#,
+            expect!["{label:no_return/0, kind:Function, contents:SameAsLabel, position:None}"],
+        );
+    }
+}
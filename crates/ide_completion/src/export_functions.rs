@@ -9,6 +9,7 @@
  */
 
 use elp_syntax::AstNode;
+use elp_syntax::SyntaxKind;
 use elp_syntax::algo;
 use elp_syntax::ast;
 
@@ -16,10 +17,12 @@ use crate::Completion;
 use crate::Ctx;
 use crate::Kind;
 use crate::helpers;
+use crate::helpers::Proximity;
 
 pub(crate) fn add_completions(
     acc: &mut Vec<Completion>,
     Ctx {
+        config,
         file_position,
         parsed,
         sema,
@@ -42,21 +45,28 @@ pub(crate) fn add_completions(
         None => {
             // T126163640 / T125984246
             // When we have better error recovery, delete this branch
-            node.token_at_offset(file_position.offset)
-                .peekable()
-                .peek()
-                .map(|token| token.text().to_string())
-                .unwrap_or_default()
+            match node.token_at_offset(file_position.offset).left_biased() {
+                Some(token) if token.kind() == SyntaxKind::ATOM => token.text().to_string(),
+                _ => String::new(),
+            }
         }
     };
 
+    // The def map of the module also has the functions defined in the
+    // headers it includes, each name/arity only once.
     let def_map = sema.def_map(file_position.file_id);
     let exported_functions = def_map.get_exported_functions();
     let completions = def_map.get_functions().filter_map(|(na, _)| {
         if exported_functions.contains(na) {
             None
         } else {
-            helpers::name_slash_arity_completion(na, prefix, Kind::Function)
+            helpers::name_slash_arity_completion(
+                na,
+                prefix,
+                Kind::Function,
+                Proximity::Local,
+                config.fuzzy,
+            )
         }
     });
 
@@ -120,4 +130,41 @@ mod test {
                 {label:function_b/1, kind:Function, contents:SameAsLabel, position:None}"#]],
         );
     }
+
+    #[test]
+    fn test_functions_from_header() {
+        check(
+            r#"
+        //- /include/helpers.hrl include_path:/include
+        helper() -> ok.
+        helper_exported(X) -> X.
+        //- /src/sample.erl
+        -module(sample).
+        -include("helpers.hrl").
+        -export([
+            helper_exported/1,
+            hel~
+        ]).
+        "#,
+            None,
+            expect![[r#"
+                {label:helper/0, kind:Function, contents:SameAsLabel, position:None}"#]],
+        );
+    }
+
+    #[test]
+    fn test_no_junk_prefix() {
+        check(
+            r#"
+        -module(sample).
+        -export([foo/0, ~
+        ]).
+        foo() -> ok.
+        bar() -> ok.
+        "#,
+            None,
+            expect![[r#"
+                {label:bar/0, kind:Function, contents:SameAsLabel, position:None}"#]],
+        );
+    }
 }
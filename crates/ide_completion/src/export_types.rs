@@ -9,6 +9,7 @@
  */
 
 use elp_syntax::AstNode;
+use elp_syntax::SyntaxKind;
 use elp_syntax::algo;
 use elp_syntax::ast;
 
@@ -16,6 +17,7 @@ use crate::Completion;
 use crate::Ctx;
 use crate::Kind;
 use crate::helpers;
+use crate::helpers::Proximity;
 
 pub(crate) fn add_completions(
     acc: &mut Vec<Completion>,
@@ -42,19 +44,22 @@ pub(crate) fn add_completions(
         None => {
             // T126163640 / T125984246
             // When we have better error recovery, delete this branch
-            node.token_at_offset(file_position.offset)
-                .peekable()
-                .peek()
-                .map(|token| token.text().to_string())
-                .unwrap_or_default()
+            match node.token_at_offset(file_position.offset).left_biased() {
+                Some(token) if token.kind() == SyntaxKind::ATOM => token.text().to_string(),
+                _ => String::new(),
+            }
         }
     };
 
     let def_map = sema.def_map(file_position.file_id);
-    let completions = def_map
-        .get_types()
-        .iter()
-        .filter_map(|(na, _)| helpers::name_slash_arity_completion(na, prefix, Kind::Type));
+    let exported_types = def_map.get_exported_types();
+    let completions = def_map.get_types().iter().filter_map(|(na, _)| {
+        if exported_types.contains(na) {
+            None
+        } else {
+            helpers::name_slash_arity_completion(na, prefix, Kind::Type, Proximity::Local, false)
+        }
+    });
 
     acc.extend(completions);
 }
@@ -97,4 +102,22 @@ mod test {
                 {label:foon/0, kind:Type, contents:SameAsLabel, position:None}"#]],
         );
     }
+
+    #[test]
+    fn test_exclude_already_exported_types() {
+        check(
+            r#"
+        -module(sample).
+        -export_type([foo/0]).
+        -export_type([
+            fo~
+        ]).
+        -type foo() :: ok.
+        -type foon() :: ok.
+        "#,
+            None,
+            expect![[r#"
+                {label:foon/0, kind:Type, contents:SameAsLabel, position:None}"#]],
+        );
+    }
 }
@@ -10,20 +10,24 @@
 
 use elp_base_db::FileId;
 use elp_base_db::FilePosition;
+use elp_ide_db::completion_context::CtxKind;
 use elp_syntax::AstNode;
 use elp_syntax::SyntaxToken;
 use hir::Semantic;
 use hir::sema::to_def::resolve_module_name;
 
 use crate::Completion;
+use crate::CompletionConfig;
 use crate::Ctx;
 use crate::DoneFlag;
 use crate::Kind;
 use crate::helpers;
+use crate::helpers::Proximity;
 
 pub(crate) fn add_completions(
     acc: &mut Vec<Completion>,
     Ctx {
+        config,
         sema,
         trigger,
         file_position,
@@ -42,7 +46,13 @@ pub(crate) fn add_completions(
             let def_map = sema.def_map(file_position.file_id);
 
             let completions = def_map.get_functions().filter_map(|(na, _)| {
-                helpers::name_slash_arity_completion(na, function_prefix.text(), Kind::Function)
+                helpers::name_slash_arity_completion(
+                    na,
+                    function_prefix.text(),
+                    Kind::Function,
+                    Proximity::Local,
+                    config.fuzzy,
+                )
             });
             acc.extend(completions);
             true
@@ -59,8 +69,16 @@ pub(crate) fn add_completions(
                 sema.resolve_module_name(file_position.file_id, module_name.text())
             {
                 let def_map = sema.def_map(module.file.file_id);
+                let proximity =
+                    Proximity::of_file(sema, file_position.file_id, module.file.file_id);
                 let completions = def_map.get_exported_functions().iter().filter_map(|na| {
-                    helpers::name_slash_arity_completion(na, function_prefix.text(), Kind::Function)
+                    helpers::name_slash_arity_completion(
+                        na,
+                        function_prefix.text(),
+                        Kind::Function,
+                        proximity,
+                        config.fuzzy,
+                    )
                 });
                 acc.extend(completions);
                 true
@@ -81,6 +99,7 @@ pub(crate) fn add_completions(
                 module.text(),
                 name_prefix.text(),
                 next_token,
+                config,
                 acc,
             );
             true
@@ -100,6 +119,7 @@ pub(crate) fn add_completions(
                     module_name.as_str(),
                     name_prefix.text(),
                     next_token,
+                    config,
                     acc,
                 );
                 true
@@ -115,6 +135,7 @@ pub(crate) fn add_completions(
                 module.text(),
                 "",
                 next_token,
+                config,
                 acc,
             );
             true
@@ -130,6 +151,7 @@ pub(crate) fn add_completions(
                     module_name.as_str(),
                     "",
                     next_token,
+                    config,
                     acc,
                 );
                 true
@@ -140,52 +162,61 @@ pub(crate) fn add_completions(
         // foo
         [.., (K::ATOM, function_prefix)] if trigger.is_none() => {
             let def_map = sema.def_map(file_position.file_id);
-            let completions = def_map
-                .get_functions_in_scope()
-                .filter(|(na, _)| na.name().starts_with(function_prefix.text()))
-                .filter_map(|(na, module)| {
-                    let function_name = na.name().to_quoted_string();
-                    let module_file_id = module
-                        .and_then(|module| {
-                            Some(
-                                resolve_module_name(sema, file_position.file_id, module.as_str())?
-                                    .file
-                                    .file_id,
-                            )
+            let completions = def_map.get_functions_in_scope().filter_map(|(na, module)| {
+                let score = helpers::match_score(na.name(), function_prefix.text(), config.fuzzy)?;
+                let function_name = na.name().to_quoted_string();
+                let module_file_id = module
+                    .and_then(|module| {
+                        Some(
+                            resolve_module_name(sema, file_position.file_id, module.as_str())?
+                                .file
+                                .file_id,
+                        )
+                    })
+                    .unwrap_or(file_position.file_id);
+                let def_map = sema.db.def_map(module_file_id);
+                let def = def_map.get_function(na)?;
+                // The file defining the function, which can be a header
+                let proximity = Proximity::of_file(sema, file_position.file_id, def.file.file_id);
+                let fun_decl_ast = def.source(sema.db.upcast());
+                let deprecated = def_map.is_deprecated(na);
+                match ctx {
+                    CtxKind::Dialyzer => helpers::name_slash_arity_completion(
+                        na,
+                        function_prefix.text(),
+                        Kind::Function,
+                        proximity,
+                        config.fuzzy,
+                    ),
+                    _ => {
+                        let contents = helpers::function_contents(
+                            sema.db.upcast(),
+                            def,
+                            &function_name,
+                            helpers::should_include_args(next_token),
+                            config.snippet_cap,
+                        )?;
+                        let label = na.to_string();
+                        Some(Completion {
+                            sort_text: helpers::score_sort_text(
+                                proximity,
+                                score,
+                                &label,
+                                config.fuzzy,
+                            ),
+                            label,
+                            kind: Kind::Function,
+                            contents,
+                            position: Some(FilePosition {
+                                file_id: def.file.file_id,
+                                offset: fun_decl_ast.first()?.syntax().text_range().start(),
+                            }),
+                            deprecated,
+                            additional_edit: None,
                         })
-                        .unwrap_or(file_position.file_id);
-                    let def_map = sema.db.def_map(module_file_id);
-                    let def = def_map.get_function(na)?;
-                    let fun_decl_ast = def.source(sema.db.upcast());
-                    let deprecated = def_map.is_deprecated(na);
-                    match ctx {
-                        crate::ctx::CtxKind::Dialyzer => helpers::name_slash_arity_completion(
-                            na,
-                            function_prefix.text(),
-                            Kind::Function,
-                        ),
-                        _ => {
-                            let contents = helpers::function_contents(
-                                sema.db.upcast(),
-                                def,
-                                &function_name,
-                                helpers::should_include_args(next_token),
-                            )?;
-                            Some(Completion {
-                                label: na.to_string(),
-                                kind: Kind::Function,
-                                contents,
-                                position: Some(FilePosition {
-                                    file_id: def.file.file_id,
-                                    offset: fun_decl_ast.first()?.syntax().text_range().start(),
-                                }),
-                                sort_text: None,
-                                deprecated,
-                                additional_edit: None,
-                            })
-                        }
                     }
-                });
+                }
+            });
             acc.extend(completions);
             false
         }
@@ -199,11 +230,13 @@ fn complete_remote_function_call<'a>(
     module_name: &'a str,
     fun_prefix: &'a str,
     next_token: &Option<SyntaxToken>,
+    config: &CompletionConfig,
     acc: &mut Vec<Completion>,
 ) {
     || -> Option<_> {
         let module = sema.resolve_module_name(from_file, module_name)?;
         let def_map = sema.def_map(module.file.file_id);
+        let proximity = Proximity::of_file(sema, from_file, module.file.file_id);
         let completions = def_map.get_exported_functions().iter().filter_map(|na| {
             helpers::name_arity_to_call_completion(
                 sema,
@@ -211,6 +244,8 @@ fn complete_remote_function_call<'a>(
                 na,
                 fun_prefix,
                 next_token,
+                proximity,
+                config,
             )
         });
         acc.extend(completions);
@@ -223,8 +258,10 @@ mod test {
     use expect_test::Expect;
     use expect_test::expect;
 
+    use crate::CompletionConfig;
     use crate::Kind;
     use crate::tests::get_completions;
+    use crate::tests::get_completions_with_config;
     use crate::tests::render_completions;
 
     // keywords are filtered out to avoid noise
@@ -237,6 +274,53 @@ mod test {
         expect.assert_eq(actual);
     }
 
+    // completions are rendered in `sort_text` order, to check the ranking
+    fn check_fuzzy(code: &str, expect: Expect) {
+        let config = CompletionConfig {
+            fuzzy: true,
+            ..CompletionConfig::default()
+        };
+        let mut completions: Vec<_> = get_completions_with_config(&config, code, None)
+            .into_iter()
+            .filter(|c| c.kind != Kind::Keyword)
+            .collect();
+        completions.sort_by(|c1, c2| c1.sort_text.cmp(&c2.sort_text));
+        let actual = completions
+            .iter()
+            .map(|c| format!("{:?} {c}", c.sort_text))
+            .collect::<Vec<_>>()
+            .join("\n");
+        expect.assert_eq(&actual);
+    }
+
+    // completions are rendered in `sort_text` order, to check the ranking
+    fn check_sort_text(code: &str, expect: Expect) {
+        let mut completions: Vec<_> = get_completions(code, None)
+            .into_iter()
+            .filter(|c| c.kind != Kind::Keyword)
+            .collect();
+        completions.sort_by(|c1, c2| c1.sort_text.cmp(&c2.sort_text));
+        let actual = completions
+            .iter()
+            .map(|c| format!("{:?} {}", c.sort_text, c.label))
+            .collect::<Vec<_>>()
+            .join("\n");
+        expect.assert_eq(&actual);
+    }
+
+    fn check_no_snippets(code: &str, trigger_character: Option<char>, expect: Expect) {
+        let config = CompletionConfig {
+            snippet_cap: None,
+            ..CompletionConfig::default()
+        };
+        let completions = get_completions_with_config(&config, code, trigger_character)
+            .into_iter()
+            .filter(|c| c.kind != Kind::Keyword)
+            .collect();
+        let actual = &render_completions(completions);
+        expect.assert_eq(actual);
+    }
+
     #[test]
     fn test_remote_calls_with_trigger() {
         assert!(serde_json::to_string(&lsp_types::CompletionItemKind::FUNCTION).unwrap() == "3");
@@ -953,4 +1037,148 @@ foo(X, Y) -> ok.
             ]],
         );
     }
+
+    #[test]
+    fn test_local_calls_multiple_arities_with_spec() {
+        check(
+            r#"
+    -module(sample1).
+    -spec foo(Name :: atom()) -> ok.
+    foo(_) -> ok.
+    foo(A, B) -> ok.
+    main() ->
+        fo~
+    "#,
+            None,
+            expect![[r#"
+                {label:foo/1, kind:Function, contents:Snippet("foo(${1:Name})"), position:Some(FilePosition { file_id: FileId(0), offset: 51 })}
+                {label:foo/2, kind:Function, contents:Snippet("foo(${1:A}, ${2:B})"), position:Some(FilePosition { file_id: FileId(0), offset: 65 })}"#]],
+        );
+    }
+
+    #[test]
+    fn test_local_calls_no_snippet_support() {
+        check_no_snippets(
+            r#"
+    -module(sample1).
+    foo() ->
+        b~.
+    bar() -> ok.
+    baz(X) -> X.
+    "#,
+            None,
+            expect![[r#"
+                {label:bar/0, kind:Function, contents:String("bar"), position:Some(FilePosition { file_id: FileId(0), offset: 34 })}
+                {label:baz/1, kind:Function, contents:String("baz"), position:Some(FilePosition { file_id: FileId(0), offset: 47 })}"#]],
+        );
+    }
+
+    #[test]
+    fn test_remote_calls_no_snippet_support() {
+        check_no_snippets(
+            r#"
+    //- /src/sample1.erl
+    -module(sample1).
+    local() ->
+        sample2:~.
+    //- /src/sample2.erl
+    -module(sample2).
+    -export([foo/0]).
+    -export([foon/2]).
+    -export([bar/2]).
+    foo() -> ok.
+    foon(A, B) -> ok.
+    bar(A, B, C) -> ok.
+    "#,
+            Some(':'),
+            expect![[r#"
+                {label:bar/2, kind:Function, contents:String("bar"), position:None}
+                {label:foo/0, kind:Function, contents:String("foo"), position:Some(FilePosition { file_id: FileId(1), offset: 73 })}
+                {label:foon/2, kind:Function, contents:String("foon"), position:Some(FilePosition { file_id: FileId(1), offset: 86 })}"#]],
+        );
+    }
+
+    #[test]
+    fn test_local_calls_fuzzy() {
+        check_fuzzy(
+            r#"
+    -module(sample1).
+    fold_left_map(F, Acc, L) -> ok.
+    filter_map(F, L) -> ok.
+    main() ->
+        flm~
+    "#,
+            expect![[r#"
+                Some("1_0001_fold_left_map/3") {label:fold_left_map/3, kind:Function, contents:Snippet("fold_left_map(${1:F}, ${2:Acc}, ${3:L})"), position:Some(FilePosition { file_id: FileId(0), offset: 18 })}
+                Some("1_0002_filter_map/2") {label:filter_map/2, kind:Function, contents:Snippet("filter_map(${1:F}, ${2:L})"), position:Some(FilePosition { file_id: FileId(0), offset: 50 })}"#]],
+        );
+    }
+
+    #[test]
+    fn test_sort_text_by_proximity() {
+        check_sort_text(
+            r#"
+    //- /src/sample1.erl
+    -module(sample1).
+    -import(foo_remote, [foo_imported/0]).
+    foo_local() -> ok.
+    main() ->
+        foo~
+    //- /src/foo_remote.erl
+    -module(foo_remote).
+    -export([foo_imported/0]).
+    foo_imported() -> ok.
+    //- /opt/lib/stdlib-3.17/src/foo_otp.erl otp_app:/opt/lib/stdlib-3.17
+    -module(foo_otp).
+    "#,
+            expect![[r#"
+                Some("1_foo_local/0") foo_local/0
+                Some("2_foo_imported/0") foo_imported/0
+                Some("2_foo_remote") foo_remote
+                Some("4_foo_otp") foo_otp"#]],
+        );
+    }
+
+    #[test]
+    fn test_sort_text_remote() {
+        check_sort_text(
+            r#"
+    //- /src/sample1.erl
+    -module(sample1).
+    main() ->
+        lists:rev~
+    //- /opt/lib/stdlib-3.17/src/lists.erl otp_app:/opt/lib/stdlib-3.17
+    -module(lists).
+    -export([reverse/1, reverse/2]).
+    reverse(L) -> L.
+    reverse(L, T) -> L ++ T.
+    "#,
+            expect![[r#"
+                Some("4_reverse/1") reverse/1
+                Some("4_reverse/2") reverse/2"#]],
+        );
+    }
+
+    #[test]
+    fn test_sort_text_header_and_other_app() {
+        check_sort_text(
+            r#"
+    //- /app_a/src/sample1.erl app:app_a
+    -module(sample1).
+    -include("header.hrl").
+    main() ->
+        foo~
+    //- /app_a/src/header.hrl app:app_a
+    foo_header() -> ok.
+    //- /app_a/src/foo_same_app.erl app:app_a
+    -module(foo_same_app).
+    //- /app_b/src/foo_other_app.erl app:app_b
+    -module(foo_other_app).
+    "#,
+            expect![[r#"
+                Some("1_foo_header/0") foo_header/0
+                Some("2_foo_same_app") foo_same_app
+                Some("3_foo_other_app") foo_other_app"#]],
+        );
+    }
 }
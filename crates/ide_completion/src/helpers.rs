@@ -8,9 +8,13 @@
  * above-listed licenses.
  */
 
+use std::sync::Arc;
+
 use elp_base_db::FileId;
 use elp_base_db::FilePosition;
+use elp_base_db::ProjectId;
 use elp_base_db::SourceDatabase;
+use elp_ide_db::helpers::SnippetCap;
 use elp_syntax::AstNode;
 use elp_syntax::SmolStr;
 use elp_syntax::SourceFile;
@@ -24,10 +28,14 @@ use hir::FunctionDef;
 use hir::InFile;
 use hir::NameArity;
 use hir::Semantic;
+use hir::sema::HeaderNameIndex;
 
 use crate::Completion;
+use crate::CompletionConfig;
 use crate::Contents;
+use crate::IncludeFile;
 use crate::Kind;
+use crate::macros::get_include_file;
 
 pub(crate) fn atom_value(parsed: &InFile<SourceFile>, offset: TextSize) -> Option<String> {
     let node = parsed.value.syntax();
@@ -63,19 +71,119 @@ pub(crate) fn name_slash_arity_completion(
     na: &NameArity,
     prefix: &str,
     kind: Kind,
+    proximity: Proximity,
+    fuzzy: bool,
 ) -> Option<Completion> {
-    if na.name().starts_with(prefix) {
-        Some(Completion {
-            label: na.to_string(),
-            kind,
-            contents: Contents::SameAsLabel,
-            position: None,
-            sort_text: None,
-            deprecated: false,
-            additional_edit: None,
-        })
+    let score = match_score(na.name(), prefix, fuzzy)?;
+    let label = na.to_string();
+    Some(Completion {
+        sort_text: score_sort_text(proximity, score, &label, fuzzy),
+        label,
+        kind,
+        contents: Contents::SameAsLabel,
+        position: None,
+        deprecated: false,
+        additional_edit: None,
+    })
+}
+
+/// Score how well `name` matches the `pattern` typed so far, lower is
+/// better. A prefix match scores 0. When `fuzzy` is set, `pattern` may
+/// also match as a subsequence of `name`, e.g. `flm` matches
+/// `fold_left_map`. Every matched character that neither starts a word
+/// nor directly follows the previous match costs one point, and the
+/// cheapest way to match the subsequence is used, so that `flm` prefers
+/// the word starts of `fold_left_map` over the `l` in `fold`.
+pub(crate) fn match_score(name: &str, pattern: &str, fuzzy: bool) -> Option<u32> {
+    if name.starts_with(pattern) {
+        return Some(0);
+    }
+    if !fuzzy {
+        return None;
+    }
+    let name: Vec<char> = name.chars().collect();
+    let cost = |i: usize, prev: Option<usize>| {
+        let word_start = i == 0 || name[i - 1] == '_';
+        let consecutive = prev.is_some_and(|prev| prev + 1 == i);
+        if word_start || consecutive { 0 } else { 1 }
+    };
+    // best[i]: the cheapest match of the pattern so far, with its last
+    // character matched at position `i` of the name
+    let mut best: Vec<Option<u32>> = vec![None; name.len()];
+    for (n, c) in pattern.chars().enumerate() {
+        let mut next = vec![None; name.len()];
+        for i in 0..name.len() {
+            if name[i] != c {
+                continue;
+            }
+            next[i] = if n == 0 {
+                Some(cost(i, None))
+            } else {
+                (0..i)
+                    .filter_map(|prev| Some(best[prev]? + cost(i, Some(prev))))
+                    .min()
+            };
+        }
+        best = next;
+    }
+    best.into_iter().flatten().min().map(|score| score + 1)
+}
+
+/// How close a completion candidate is to the cursor. It is the leading
+/// part of `sort_text`, so that nearby candidates rank above distant ones.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, PartialOrd, Ord)]
+pub(crate) enum Proximity {
+    /// A variable in scope at the cursor
+    Variable = 0,
+    /// Defined in the current file or in one of its headers
+    Local = 1,
+    /// Defined in another module of the same application
+    Application = 2,
+    /// Defined in another application of the project
+    Project = 3,
+    /// Defined in OTP
+    Otp = 4,
+    /// Keywords, snippets and other syntax
+    Syntax = 5,
+}
+
+impl Proximity {
+    /// The proximity of a definition in `def_file`, seen from `from_file`.
+    pub(crate) fn of_file(sema: &Semantic, from_file: FileId, def_file: FileId) -> Proximity {
+        if def_file == from_file
+            || sema
+                .def_map(from_file)
+                .get_included_files()
+                .any(|included| included == def_file)
+        {
+            Proximity::Local
+        } else if sema.db.is_otp(def_file) == Some(true) {
+            Proximity::Otp
+        } else if sema.db.file_source_root(def_file) == sema.db.file_source_root(from_file) {
+            Proximity::Application
+        } else {
+            Proximity::Project
+        }
+    }
+}
+
+/// Completions are ranked by proximity first, and alphabetically second.
+pub(crate) fn sort_text(proximity: Proximity, label: &str) -> Option<String> {
+    Some(format!("{}_{label}", proximity as u8))
+}
+
+/// With fuzzy matching, completions of the same proximity are also ranked
+/// by their match score, before the alphabetical order.
+pub(crate) fn score_sort_text(
+    proximity: Proximity,
+    score: u32,
+    label: &str,
+    fuzzy: bool,
+) -> Option<String> {
+    if fuzzy {
+        Some(format!("{}_{score:04}_{label}", proximity as u8))
     } else {
-        None
+        sort_text(proximity, label)
     }
 }
 
@@ -94,6 +202,8 @@ pub(crate) fn name_arity_to_call_completion(
     na: &NameArity,
     prefix: &str,
     next_token: &Option<SyntaxToken>,
+    proximity: Proximity,
+    config: &CompletionConfig,
 ) -> Option<Completion> {
     let db = sema.db.upcast();
     let def_map = sema.def_map(file_id);
@@ -109,16 +219,21 @@ pub(crate) fn name_arity_to_call_completion(
     let deprecated = def_map.is_deprecated(na);
     let include_args = should_include_args(next_token);
 
-    if na.name().starts_with(prefix) {
-        let contents = def.map_or(Some(format_call(na.name(), na.arity())), |def| {
-            function_contents(db, def, &na.name().to_quoted_string(), include_args)
-        })?;
+    if let Some(score) = match_score(na.name(), prefix, config.fuzzy) {
+        let label = na.to_string();
+        let contents = match (def, config.snippet_cap) {
+            (_, None) => Contents::String(na.name().to_quoted_string()),
+            (None, Some(_)) => format_call(na.name(), na.arity()),
+            (Some(def), cap) => {
+                function_contents(db, def, &na.name().to_quoted_string(), include_args, cap)?
+            }
+        };
         Some(Completion {
-            label: na.to_string(),
+            sort_text: score_sort_text(proximity, score, &label, config.fuzzy),
+            label,
             kind: Kind::Function,
             contents,
             position,
-            sort_text: None,
             deprecated,
             additional_edit: None,
         })
@@ -153,8 +268,12 @@ pub(crate) fn function_contents(
     def: &FunctionDef,
     function_name: &str,
     include_args: bool,
+    snippet_cap: Option<SnippetCap>,
 ) -> Option<Contents> {
-    if include_args {
+    if snippet_cap.is_none() {
+        // Clients without snippet support get the bare function name
+        Some(Contents::String(function_name.to_string()))
+    } else if include_args {
         let function_arg_names = function_arg_names(db, def)?;
         Some(Contents::Snippet(format!(
             "{function_name}({function_arg_names})"
@@ -163,3 +282,37 @@ pub(crate) fn function_contents(
         Some(Contents::Snippet(function_name.to_string()))
     }
 }
+
+/// The headers of the project not included by `file_id` and accepted by
+/// `has_completions`, each with the edit adding the include attribute for
+/// it, for completing the records and types they define. Only the headers
+/// defining a name starting with `prefix` in the `index` of the project are
+/// considered, and the include edit is only computed for the accepted ones.
+pub(crate) fn unincluded_headers(
+    sema: &Semantic,
+    file_id: FileId,
+    index: impl Fn(ProjectId) -> Arc<HeaderNameIndex>,
+    prefix: &str,
+    has_completions: impl Fn(FileId) -> bool,
+) -> Vec<(FileId, (FilePosition, IncludeFile))> {
+    let Some(project_id) = sema.db.file_project_id(file_id) else {
+        return vec![];
+    };
+    let candidates = index(project_id).complete(prefix);
+    if candidates.is_empty() {
+        return vec![];
+    }
+    let include_file_index = sema.db.include_file_index(project_id);
+    include_file_index
+        .path_to_file_id
+        .iter()
+        .filter(|(_, header)| {
+            **header != file_id && candidates.contains(*header) && has_completions(**header)
+        })
+        .filter_map(|(path, header)| {
+            let include = get_include_file(sema.db, file_id, *header, path.clone())?;
+            let position = include.insert_position_if_needed(sema, file_id)?;
+            Some((*header, (position, include)))
+        })
+        .collect()
+}
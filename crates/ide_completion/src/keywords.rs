@@ -14,6 +14,8 @@ use crate::Completion;
 use crate::Contents;
 use crate::Ctx;
 use crate::DoneFlag;
+use crate::helpers;
+use crate::helpers::Proximity;
 
 lazy_static! {
     // adapted from https://github.com/erlang-ls/erlang_ls d067267b906239c883fed6e0f9e69c4eb94dd580
@@ -56,12 +58,16 @@ lazy_static! {
         kind: crate::Kind::Keyword,
         contents: Contents::SameAsLabel,
         position: None,
-        sort_text: None,
+        sort_text: helpers::sort_text(Proximity::Syntax, label),
         deprecated: false,
         additional_edit: None,
     }).collect();
 }
 
+pub(crate) fn is_keyword(label: &str) -> bool {
+    KEYWORDS.iter().any(|keyword| keyword.label == label)
+}
+
 pub(crate) fn add_completions(acc: &mut Vec<Completion>, Ctx { trigger, .. }: &Ctx) -> DoneFlag {
     if trigger.is_some() {
         return false;
@@ -114,6 +120,7 @@ mod test {
                 {label:bxor, kind:Keyword, contents:SameAsLabel, position:None}
                 {label:case, kind:Keyword, contents:SameAsLabel, position:None}
                 {label:case, kind:Keyword, contents:SameAsLabel, position:None}
+                {label:case ... end, kind:Keyword, contents:Snippet("case ${1:Expr} of\n    ${2:Pattern} -> ${3:Body}\nend"), position:None}
                 {label:catch, kind:Keyword, contents:SameAsLabel, position:None}
                 {label:cond, kind:Keyword, contents:SameAsLabel, position:None}
                 {label:div, kind:Keyword, contents:SameAsLabel, position:None}
@@ -128,9 +135,11 @@ mod test {
                 {label:or, kind:Keyword, contents:SameAsLabel, position:None}
                 {label:orelse, kind:Keyword, contents:SameAsLabel, position:None}
                 {label:receive, kind:Keyword, contents:SameAsLabel, position:None}
+                {label:receive ... end, kind:Keyword, contents:Snippet("receive\n    ${1:Pattern} -> ${2:Body}\nend"), position:None}
                 {label:rem, kind:Keyword, contents:SameAsLabel, position:None}
                 {label:true, kind:Keyword, contents:SameAsLabel, position:None}
                 {label:try, kind:Keyword, contents:SameAsLabel, position:None}
+                {label:try ... catch ... end, kind:Keyword, contents:Snippet("try ${1:Expr}\ncatch\n    ${2:Class}:${3:Reason} -> ${4:Body}\nend"), position:None}
                 {label:undefined, kind:Keyword, contents:SameAsLabel, position:None}
                 {label:when, kind:Keyword, contents:SameAsLabel, position:None}
                 {label:xor, kind:Keyword, contents:SameAsLabel, position:None}"#]],
@@ -252,6 +261,7 @@ mod test {
                 {label:bxor, kind:Keyword, contents:SameAsLabel, position:None}
                 {label:case, kind:Keyword, contents:SameAsLabel, position:None}
                 {label:case, kind:Keyword, contents:SameAsLabel, position:None}
+                {label:case ... end, kind:Keyword, contents:Snippet("case ${1:Expr} of\n    ${2:Pattern} -> ${3:Body}\nend"), position:None}
                 {label:catch, kind:Keyword, contents:SameAsLabel, position:None}
                 {label:cond, kind:Keyword, contents:SameAsLabel, position:None}
                 {label:div, kind:Keyword, contents:SameAsLabel, position:None}
@@ -267,9 +277,11 @@ mod test {
                 {label:or, kind:Keyword, contents:SameAsLabel, position:None}
                 {label:orelse, kind:Keyword, contents:SameAsLabel, position:None}
                 {label:receive, kind:Keyword, contents:SameAsLabel, position:None}
+                {label:receive ... end, kind:Keyword, contents:Snippet("receive\n    ${1:Pattern} -> ${2:Body}\nend"), position:None}
                 {label:rem, kind:Keyword, contents:SameAsLabel, position:None}
                 {label:true, kind:Keyword, contents:SameAsLabel, position:None}
                 {label:try, kind:Keyword, contents:SameAsLabel, position:None}
+                {label:try ... catch ... end, kind:Keyword, contents:Snippet("try ${1:Expr}\ncatch\n    ${2:Class}:${3:Reason} -> ${4:Body}\nend"), position:None}
                 {label:undefined, kind:Keyword, contents:SameAsLabel, position:None}
                 {label:when, kind:Keyword, contents:SameAsLabel, position:None}
                 {label:xor, kind:Keyword, contents:SameAsLabel, position:None}"#]],
@@ -9,17 +9,21 @@
  */
 
 use std::fmt;
+use std::iter;
 
-use ctx::CtxKind;
 use elp_base_db::FileId;
 use elp_ide_db::RootDatabase;
+use elp_ide_db::completion_context::CompletionContextDatabase;
+use elp_ide_db::completion_context::CtxKind;
 use elp_ide_db::elp_base_db::FilePosition;
+use elp_ide_db::helpers::SnippetCap;
 use elp_ide_db::helpers::top_insert_position;
 use elp_syntax::AstNode;
 use elp_syntax::SourceFile;
 use elp_syntax::SyntaxKind;
 use elp_syntax::SyntaxNode;
 use elp_syntax::SyntaxToken;
+use elp_syntax::TextRange;
 use elp_syntax::TextSize;
 use hir::InFile;
 use hir::IncludeAttribute;
@@ -31,8 +35,9 @@ type DoneFlag = bool;
 #[cfg(test)]
 mod tests;
 
+mod atoms;
 mod attributes;
-mod ctx;
+mod dialyzer;
 mod export_functions;
 mod export_types;
 mod functions;
@@ -42,16 +47,13 @@ mod macros;
 mod maps;
 // @fb-only
 mod modules;
+mod proplists;
 mod records;
+mod snippets;
 mod spec;
 mod types;
 mod vars;
 
-/*
-For token-based completions, this is the maximum number of previous tokens we consider.
-*/
-static MAX_PREVIOUS_TOKENS_LEN: usize = 16;
-
 #[derive(Clone, Debug, Hash, Eq, PartialEq)]
 pub struct Completion {
     pub label: String,
@@ -59,6 +61,7 @@ pub struct Completion {
     pub contents: Contents,
     // The position is used in the 'resolve' phase to look for documentation
     pub position: Option<FilePosition>,
+    // Ranks the completion by its proximity to the cursor, see `helpers::Proximity`
     pub sort_text: Option<String>,
     pub deprecated: bool,
     pub additional_edit: Option<(FilePosition, IncludeFile)>,
@@ -71,11 +74,21 @@ impl fmt::Display for Completion {
             false => "".to_string(),
         };
         let include = match &self.additional_edit {
-            Some((file_pos, include)) => format!(
+            Some((file_pos, include)) if include.alternatives.is_empty() => format!(
                 ", include:{:?}:{:?}",
                 &file_pos.offset,
                 include.as_attribute().trim_end()
             ),
+            Some((file_pos, include)) => format!(
+                ", include:{:?}:{:?}, alternatives:{:?}",
+                &file_pos.offset,
+                include.as_attribute().trim_end(),
+                include
+                    .alternatives
+                    .iter()
+                    .map(|alternative| alternative.as_attribute().trim_end().to_string())
+                    .collect::<Vec<_>>()
+            ),
             None => "".to_string(),
         };
         write!(
@@ -113,8 +126,29 @@ pub enum Kind {
     Map,
 }
 
+/// Settings for tweaking completions, based on the client capabilities.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct CompletionConfig {
+    /// When `None`, completions never produce `Contents::Snippet` for
+    /// function calls, and insert the bare function name instead.
+    pub snippet_cap: Option<SnippetCap>,
+    /// Also match function names on a subsequence of the typed prefix,
+    /// e.g. `flm` for `fold_left_map`, ranking them via `sort_text`.
+    pub fuzzy: bool,
+}
+
+impl Default for CompletionConfig {
+    fn default() -> Self {
+        CompletionConfig {
+            snippet_cap: SnippetCap::new(true),
+            fuzzy: false,
+        }
+    }
+}
+
 #[derive(Debug)]
 struct Ctx<'a> {
+    config: &'a CompletionConfig,
     ctx_kind: CtxKind,
     sema: &'a Semantic<'a>,
     parsed: InFile<SourceFile>,
@@ -126,17 +160,23 @@ struct Ctx<'a> {
 
 pub fn completions(
     db: &RootDatabase,
+    config: &CompletionConfig,
     file_position: FilePosition,
     trigger: Option<char>,
 ) -> Vec<Completion> {
     let sema = &Semantic::new(db);
     let parsed = sema.parse(file_position.file_id);
     let node = parsed.value.syntax();
-    let ctx_kind = CtxKind::new(node, file_position.offset);
+    let context = db.completion_context(file_position.file_id, file_position.offset);
+    let ctx_kind = context.ctx_kind.clone();
     let mut acc = Vec::new();
-    let previous_tokens = get_previous_tokens(node, file_position);
+    let previous_tokens = context
+        .previous_tokens
+        .as_ref()
+        .map(|tokens| resolve_tokens(node, tokens));
     let next_token = right_biased_token(node, file_position);
     let ctx = &Ctx {
+        config,
         ctx_kind: ctx_kind.clone(),
         sema,
         parsed,
@@ -151,14 +191,21 @@ pub fn completions(
         CtxKind::Expr => {
             let _ = macros::add_completions(&mut acc, ctx)
                 || maps::add_completions(&mut acc, ctx)
+                || proplists::add_completions(&mut acc, ctx)
                 || records::add_completions(&mut acc, ctx)
                 || functions::add_completions(&mut acc, ctx)
                 || vars::add_completions(&mut acc, ctx)
                 || modules::add_completions(&mut acc, ctx)
+                || atoms::add_completions(&mut acc, ctx)
+                || snippets::add_completions(&mut acc, ctx)
                 || keywords::add_completions(&mut acc, ctx);
         }
         CtxKind::Type => {
             let _ = macros::add_completions(&mut acc, ctx)
+                || maps::add_type_completions(&mut acc, ctx)
+                || spec::add_return_type_completions(&mut acc, ctx)
+                || spec::add_arg_completions(&mut acc, ctx)
+                || spec::add_constraint_completions(&mut acc, ctx)
                 || types::add_completions(&mut acc, ctx)
                 || modules::add_completions(&mut acc, ctx);
         }
@@ -172,50 +219,36 @@ pub fn completions(
             spec::add_completions(&mut acc, ctx);
         }
         CtxKind::Dialyzer => {
-            functions::add_completions(&mut acc, ctx);
+            let _ = dialyzer::add_completions(&mut acc, ctx)
+                || functions::add_completions(&mut acc, ctx);
         }
         CtxKind::Other => {
             let _ = attributes::add_completions(&mut acc, ctx)
                 // @fb-only
+                || macros::add_completions(&mut acc, ctx)
                 || vars::add_completions(&mut acc, ctx)
                 || maps::add_completions(&mut acc, ctx)
                 || records::add_completions(&mut acc, ctx);
         }
     }
-    // Sort for maintainable snapshot tests:
-    // sorting isn't necessary for prod because LSP client sorts
+    // Sort by label for maintainable snapshot tests only: the LSP client
+    // ranks completions by their `sort_text`.
     acc.sort_by(|c1, c2| c1.label.cmp(&c2.label));
     acc
 }
 
-// Note: in an ideal world, we would not need to use much token-level information
-// to get reasonable error-recovery for completions.
-// See T154356210
-fn get_previous_tokens(
+/// The tokens of the memoized completion context, in the current tree.
+fn resolve_tokens(
     node: &SyntaxNode,
-    file_position: FilePosition,
-) -> Option<Vec<(SyntaxKind, SyntaxToken)>> {
-    let mut token = node.token_at_offset(file_position.offset).left_biased()?;
-    let mut tokens = Vec::new();
-
-    while token.text_range().start() >= 0.into() && tokens.len() < MAX_PREVIOUS_TOKENS_LEN {
-        let next_opt = token.prev_token();
-        if !token.kind().is_trivia() {
-            tokens.push(token.clone());
-        }
-        if let Some(next) = next_opt {
-            token = next;
-        } else {
-            break;
-        }
-    }
-    Some(
-        tokens
-            .into_iter()
-            .rev()
-            .map(|tok| (tok.kind(), tok))
-            .collect::<Vec<_>>(),
-    )
+    tokens: &[(SyntaxKind, TextRange)],
+) -> Vec<(SyntaxKind, SyntaxToken)> {
+    tokens
+        .iter()
+        .filter_map(|(kind, range)| {
+            let token = node.covering_element(*range).into_token()?;
+            (token.kind() == *kind).then_some((*kind, token))
+        })
+        .collect()
 }
 
 fn right_biased_token(node: &SyntaxNode, file_position: FilePosition) -> Option<SyntaxToken> {
@@ -227,9 +260,17 @@ pub struct IncludeFile {
     include_lib: bool,
     pub path: String,
     pub app_name: String,
+    /// Other ways of including the same file, less specific than this one,
+    /// e.g. a relative `-include` for an `-include_lib`.
+    pub alternatives: Vec<IncludeFile>,
 }
 
 impl IncludeFile {
+    /// This include followed by its alternatives, most specific first.
+    pub fn candidates(&self) -> impl Iterator<Item = &IncludeFile> {
+        iter::once(self).chain(self.alternatives.iter())
+    }
+
     pub fn as_attribute(&self) -> String {
         if self.include_lib {
             format!("-include_lib(\"{}\").\n", self.path)
@@ -244,9 +285,12 @@ impl IncludeFile {
         file_id: FileId,
     ) -> Option<FilePosition> {
         let form_list = sema.form_list(file_id);
-        let existing_import = form_list.includes().any(|(_, include)| match include {
-            IncludeAttribute::Include { path, .. } => path == &self.path,
-            IncludeAttribute::IncludeLib { path, .. } => path == &self.path,
+        let existing_import = form_list.includes().any(|(_, include)| {
+            let path = match include {
+                IncludeAttribute::Include { path, .. } => path,
+                IncludeAttribute::IncludeLib { path, .. } => path,
+            };
+            self.candidates().any(|candidate| path == &candidate.path)
         });
         if existing_import {
             None
@@ -31,6 +31,7 @@ use crate::DoneFlag;
 use crate::IncludeFile;
 use crate::Kind;
 use crate::helpers;
+use crate::helpers::Proximity;
 
 pub(crate) fn add_completions(
     acc: &mut Vec<Completion>,
@@ -60,10 +61,45 @@ pub(crate) fn add_completions(
             let def_map = sema.def_map(file_position.file_id);
             let user_defined = def_map
                 .get_macros()
-                .keys()
-                .filter(|macro_name| macro_name.name().starts_with(&prefix))
-                .map(|name| {
-                    macro_name_to_completion(sema, file_position.file_id, name, None, false)
+                .iter()
+                .filter(|(macro_name, define)| {
+                    define.file.file_id != file_position.file_id
+                        && macro_name.name().starts_with(&prefix)
+                })
+                .map(|(name, define)| {
+                    macro_name_to_completion(
+                        sema,
+                        file_position.file_id,
+                        name,
+                        &define.source(sema.db),
+                        None,
+                        false,
+                    )
+                });
+
+            // Only the macros defined earlier in the file itself are visible
+            let form_list = sema.form_list(file_position.file_id);
+            let local = form_list
+                .define_attributes()
+                .filter(|(_, define)| {
+                    define
+                        .form_id
+                        .get(&parsed.value)
+                        .syntax()
+                        .text_range()
+                        .start()
+                        < file_position.offset
+                })
+                .filter(|(_, define)| define.name.name().starts_with(&prefix))
+                .map(|(_, define)| {
+                    macro_name_to_completion(
+                        sema,
+                        file_position.file_id,
+                        &define.name,
+                        &define.form_id.get(&parsed.value),
+                        None,
+                        false,
+                    )
                 });
 
             let mut known_macros = macro_index_completion(sema, file_position.file_id, &prefix);
@@ -72,6 +108,7 @@ pub(crate) fn add_completions(
             // precedence, so we will not insert an include if it is
             // already visible.
             known_macros.extend(user_defined);
+            known_macros.extend(local);
             acc.extend(known_macros);
 
             let built_in = BUILT_IN;
@@ -112,14 +149,22 @@ fn macro_define_as_completion(
     define: &InFile<DefineId>,
     with_app: bool,
 ) -> Option<Completion> {
+    if define.file_id == file_id {
+        // Defined in the file itself, so visible without an include
+        return None;
+    }
     let include_path = path_for_file(sema.db, define.file_id)?;
     let include = get_include_file(sema.db, file_id, define.file_id, include_path.clone())?;
     let form_list = sema.form_list(define.file_id);
+    let source = form_list[define.value]
+        .form_id
+        .get_ast(sema.db, define.file_id);
     let define = &form_list[define.value];
     Some(macro_name_to_completion(
         sema,
         file_id,
         &define.name,
+        &source,
         Some(include),
         with_app,
     ))
@@ -129,6 +174,7 @@ fn macro_name_to_completion(
     sema: &Semantic,
     file_id: FileId,
     macro_name: &MacroName,
+    define: &ast::PpDefine,
     include: Option<IncludeFile>,
     with_app: bool,
 ) -> Completion {
@@ -148,36 +194,61 @@ fn macro_name_to_completion(
     };
     match macro_name.arity() {
         Some(arity) => {
-            let contents = helpers::format_call(macro_name.name(), arity);
+            let contents = macro_call_contents(macro_name, arity, define);
             Completion {
+                sort_text: helpers::sort_text(Proximity::Local, &label),
                 label,
                 kind: Kind::Macro,
                 contents,
                 position: None,
-                sort_text: None,
                 deprecated: false,
                 additional_edit,
             }
         }
         None => Completion {
+            sort_text: helpers::sort_text(Proximity::Local, &label),
             label,
             kind: Kind::Macro,
             contents: Contents::SameAsLabel,
             position: None,
-            sort_text: None,
             deprecated: false,
             additional_edit,
         },
     }
 }
 
+/// A snippet with a placeholder for each parameter of a function-like
+/// macro, named as in its definition, e.g. `FOO(${1:X}, ${2:Y})`.
+fn macro_call_contents(macro_name: &MacroName, arity: u32, define: &ast::PpDefine) -> Contents {
+    let params: Vec<String> = define
+        .lhs()
+        .and_then(|lhs| lhs.args())
+        .map(|args| {
+            args.args()
+                .map(|var| var.syntax().text().to_string())
+                .collect()
+        })
+        .unwrap_or_default();
+    if params.len() != arity as usize {
+        return helpers::format_call(macro_name.name(), arity);
+    }
+    let args = params
+        .iter()
+        .enumerate()
+        .map(|(i, param)| format!("${{{}:{param}}}", i + 1))
+        .collect::<Vec<_>>()
+        .join(", ");
+    Contents::Snippet(format!("{}({args})", macro_name.name()))
+}
+
 fn built_in_macro_name_to_completion(name: &Name) -> Completion {
+    let label = name.to_string();
     Completion {
-        label: name.to_string(),
+        sort_text: helpers::sort_text(Proximity::Local, &label),
+        label,
         kind: Kind::Macro,
         contents: Contents::SameAsLabel,
         position: None,
-        sort_text: None,
         deprecated: false,
         additional_edit: None,
     }
@@ -222,11 +293,27 @@ pub fn get_include_file(
     // Check that it is valid
     let resolved_file_id = IncludeCtx::new(db, None, file_id).resolve_include_lib(&candidate)?;
     if resolved_file_id == included_file_id {
-        // We have an equivalent include
+        // We have an equivalent include. A plain `-include` of the file
+        // name may reach it too, e.g. from the same directory.
+        let include_ctx = IncludeCtx::new(db, None, file_id);
+        let alternatives = candidate_path
+            .as_str()
+            .rsplit('/')
+            .next()
+            .filter(|file_name| include_ctx.resolve_include(file_name) == Some(included_file_id))
+            .map(|file_name| IncludeFile {
+                include_lib: false,
+                path: file_name.to_string(),
+                app_name: inc_app_data.name.to_string(),
+                alternatives: vec![],
+            })
+            .into_iter()
+            .collect();
         Some(IncludeFile {
             include_lib: true,
             path: candidate,
             app_name: inc_app_data.name.to_string(),
+            alternatives,
         })
     } else {
         None
@@ -266,7 +353,7 @@ mod test {
                 {label:FOB, kind:Macro, contents:SameAsLabel, position:None}
                 {label:FOO, kind:Macro, contents:SameAsLabel, position:None}
                 {label:FOO/0, kind:Macro, contents:Snippet("FOO()"), position:None}
-                {label:FOO/3, kind:Macro, contents:Snippet("FOO(${1:Arg1}, ${2:Arg2}, ${3:Arg3})"), position:None}"#]],
+                {label:FOO/3, kind:Macro, contents:Snippet("FOO(${1:X}, ${2:Y}, ${3:Z})"), position:None}"#]],
         );
 
         check(
@@ -356,7 +443,7 @@ mod test {
     "#,
             Some('?'),
             expect![[
-                r#"{label:assertEqual/2, kind:Macro, contents:Snippet("assertEqual(${1:Arg1}, ${2:Arg2})"), position:None, include:18:"-include_lib(\"another/include/inc.hrl\")."}"#
+                r#"{label:assertEqual/2, kind:Macro, contents:Snippet("assertEqual(${1:A}, ${2:B})"), position:None, include:18:"-include_lib(\"another/include/inc.hrl\")."}"#
             ]],
         );
     }
@@ -393,7 +480,7 @@ mod test {
     "#,
             Some('?'),
             expect![[r#"
-                {label:assertEqual/2, kind:Macro, contents:Snippet("assertEqual(${1:Arg1}, ${2:Arg2})"), position:None}"#]],
+                {label:assertEqual/2, kind:Macro, contents:Snippet("assertEqual(${1:A}, ${2:B})"), position:None}"#]],
         );
     }
 
@@ -424,8 +511,24 @@ mod test {
     "#,
             Some('?'),
             expect![[r#"
-                {label:FOO, kind:Macro, contents:SameAsLabel, position:None, include:20:"-include_lib(\"test-fixture/src/header.hrl\")."}
-                {label:FOO/1, kind:Macro, contents:Snippet("FOO(${1:Arg1})"), position:None, include:20:"-include_lib(\"test-fixture/src/header.hrl\")."}"#]],
+                {label:FOO, kind:Macro, contents:SameAsLabel, position:None, include:20:"-include_lib(\"test-fixture/src/header.hrl\").", alternatives:["-include(\"header.hrl\")."]}
+                {label:FOO/1, kind:Macro, contents:Snippet("FOO(${1:X})"), position:None, include:20:"-include_lib(\"test-fixture/src/header.hrl\").", alternatives:["-include(\"header.hrl\")."]}"#]],
+        );
+    }
+
+    #[test]
+    fn detect_macros_already_included_relatively() {
+        check(
+            r#"
+         //- /src/sample1.erl
+           -module(sample1).
+           -include("header.hrl").
+           foo() -> ?FO~
+         //- /src/header.hrl include_path:/src
+           -define(FOO,3).
+    "#,
+            Some('?'),
+            expect!["{label:FOO, kind:Macro, contents:SameAsLabel, position:None}"],
         );
     }
 
@@ -450,8 +553,41 @@ mod test {
             expect![[r#"
                 {label:FOO (app_a), kind:Macro, contents:SameAsLabel, position:None, include:20:"-include_lib(\"app_a/include/header.hrl\")."}
                 {label:FOO (app_b), kind:Macro, contents:SameAsLabel, position:None, include:20:"-include_lib(\"app_b/include/header.hrl\")."}
-                {label:FOO/1 (app_a), kind:Macro, contents:Snippet("FOO(${1:Arg1})"), position:None, include:20:"-include_lib(\"app_a/include/header.hrl\")."}
-                {label:FOO/1 (app_b), kind:Macro, contents:Snippet("FOO(${1:Arg1})"), position:None, include:20:"-include_lib(\"app_b/include/header.hrl\")."}"#]],
+                {label:FOO/1 (app_a), kind:Macro, contents:Snippet("FOO(${1:X})"), position:None, include:20:"-include_lib(\"app_a/include/header.hrl\")."}
+                {label:FOO/1 (app_b), kind:Macro, contents:Snippet("FOO(${1:X})"), position:None, include:20:"-include_lib(\"app_b/include/header.hrl\")."}"#]],
+        );
+    }
+
+    #[test]
+    fn test_macros_in_header() {
+        check(
+            r#"
+         //- /include/header.hrl include_path:/include
+           -define(BASE, 1).
+           -define(BASE_PLUS(X), ?BASE + X).
+           -define(NEXT, ?BA~).
+           -define(BACKUP, 2).
+    "#,
+            Some('?'),
+            expect![[r#"
+                {label:BASE, kind:Macro, contents:SameAsLabel, position:None}
+                {label:BASE_PLUS/1, kind:Macro, contents:Snippet("BASE_PLUS(${1:X})"), position:None}"#]],
+        );
+    }
+
+    #[test]
+    fn test_macro_parameter_names() {
+        check(
+            r#"
+    -module(sample1).
+    -define(MY_MACRO(Key, Value), {Key, Value}).
+    -define(MY_CONST, 42).
+    foo() -> ?MY_~
+    "#,
+            Some('?'),
+            expect![[r#"
+                {label:MY_CONST, kind:Macro, contents:SameAsLabel, position:None}
+                {label:MY_MACRO/2, kind:Macro, contents:Snippet("MY_MACRO(${1:Key}, ${2:Value})"), position:None}"#]],
         );
     }
 }
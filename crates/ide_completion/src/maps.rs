@@ -9,13 +9,23 @@
  */
 
 use elp_base_db::FilePosition;
+use elp_base_db::FileRange;
+use elp_ide_db::helpers::escape_snippet;
 use elp_syntax::AstNode;
+use elp_syntax::SyntaxKind;
+use elp_syntax::SyntaxNode;
+use elp_syntax::SyntaxToken;
+use elp_syntax::TextRange;
+use elp_syntax::algo;
+use elp_syntax::ast;
 use elp_syntax::ast::Expr;
 use elp_syntax::ast::MapExpr;
+use elp_types_db::eqwalizer::types::Type;
 use fxhash::FxHashMap;
 use hir::AnyExpr;
 use hir::DefMap;
 use hir::InFile;
+use hir::Name;
 use hir::NameArity;
 use hir::Semantic;
 use hir::Spec;
@@ -32,9 +42,249 @@ use crate::Contents;
 use crate::Ctx;
 use crate::DoneFlag;
 use crate::Kind;
+use crate::helpers;
+use crate::helpers::Proximity;
 
 pub(crate) fn add_completions(acc: &mut Vec<Completion>, args: &Ctx) -> DoneFlag {
-    add_token_based_completions(acc, args)
+    add_typed_key_completions(acc, args) || add_token_based_completions(acc, args)
+}
+
+/// Completions inside a map type in a spec or type declaration
+pub(crate) fn add_type_completions(acc: &mut Vec<Completion>, args: &Ctx) -> DoneFlag {
+    add_alias_key_completions(acc, args) || add_type_operator_completions(acc, args)
+}
+
+/// Fields of the type alias in the map type `alias()#{~`
+fn add_alias_key_completions(
+    acc: &mut Vec<Completion>,
+    Ctx {
+        file_position,
+        previous_tokens,
+        sema,
+        ..
+    }: &Ctx,
+) -> DoneFlag {
+    use elp_syntax::SyntaxKind as K;
+    let default = vec![];
+    let previous_tokens: &[_] = previous_tokens.as_ref().unwrap_or(&default);
+    let (prefix, previous_tokens) = match previous_tokens {
+        [previous_tokens @ .., (K::ATOM, prefix)]
+            if prefix.text_range().end() == file_position.offset =>
+        {
+            (prefix.text(), previous_tokens)
+        }
+        _ => ("", previous_tokens),
+    };
+    let previous_tokens = match previous_tokens {
+        [
+            previous_tokens @ ..,
+            (K::ANON_RPAREN, _),
+            (K::ANON_POUND, _),
+            (K::ANON_LBRACE, _),
+        ] => previous_tokens,
+        _ => return false,
+    };
+    let Some(name_arity) = alias_call_before(previous_tokens) else {
+        return false;
+    };
+    let def_map = sema.def_map(file_position.file_id);
+    let Some(map_expr) = def_map
+        .get_types()
+        .get(&name_arity)
+        .and_then(|def| def.map_expr_for_completion(sema.db.upcast()))
+    else {
+        return false;
+    };
+    let completions = map_expr.fields().filter_map(|field| {
+        let key = field.key()?.syntax().text().to_string();
+        if !key.starts_with(prefix) {
+            return None;
+        }
+        let (_, op) = field.op()?;
+        let value = field.value()?.syntax().text().to_string();
+        Some(Completion {
+            contents: Contents::Snippet(format!(
+                "{} {} ${{1:{}}}",
+                escape_snippet(&key),
+                op.text(),
+                escape_snippet(&value)
+            )),
+            sort_text: helpers::sort_text(Proximity::Local, &key),
+            label: key,
+            kind: Kind::Map,
+            position: None,
+            deprecated: false,
+            additional_edit: None,
+        })
+    });
+    acc.extend(completions);
+    true
+}
+
+/// The local type called by the tokens ending with the closing parenthesis
+/// of its arguments, e.g. `alias(integer()`.
+fn alias_call_before(tokens: &[(SyntaxKind, SyntaxToken)]) -> Option<NameArity> {
+    use elp_syntax::SyntaxKind as K;
+    let mut depth = 0;
+    let mut commas = 0;
+    let mut empty = true;
+    for (i, (kind, _)) in tokens.iter().enumerate().rev() {
+        match kind {
+            K::ANON_RPAREN | K::ANON_RRACE | K::ANON_RBRACK | K::ANON_GT_GT => depth += 1,
+            K::ANON_LPAREN if depth == 0 => {
+                let (K::ATOM, name) = tokens[..i].last()? else {
+                    return None;
+                };
+                let arity = if empty { 0 } else { commas + 1 };
+                return Some(NameArity::new(
+                    Name::from_erlang_service(name.text()),
+                    arity,
+                ));
+            }
+            K::ANON_LPAREN | K::ANON_LBRACE | K::ANON_LBRACK | K::ANON_LT_LT if depth > 0 => {
+                depth -= 1
+            }
+            K::ANON_LBRACE | K::ANON_LBRACK | K::ANON_LT_LT => return None,
+            K::ANON_COMMA if depth == 0 => commas += 1,
+            _ => {}
+        }
+        empty = false;
+    }
+    None
+}
+
+/// The `:=` and `=>` operators after a key in a map type, e.g. `#{key ~`
+fn add_type_operator_completions(
+    acc: &mut Vec<Completion>,
+    Ctx {
+        file_position,
+        previous_tokens,
+        trigger,
+        ..
+    }: &Ctx,
+) -> DoneFlag {
+    use elp_syntax::SyntaxKind as K;
+    if trigger.is_some() {
+        return false;
+    }
+    let default = vec![];
+    let previous_tokens: &[_] = previous_tokens.as_ref().unwrap_or(&default);
+    match previous_tokens {
+        [.., (K::ANON_POUND, _), (K::ANON_LBRACE, _), (K::ATOM, key)]
+        | [.., (K::ANON_COMMA, _), (K::ATOM, key)]
+            if key.text_range().end() < file_position.offset && in_map_type(previous_tokens) =>
+        {
+            for op in [":=", "=>"] {
+                acc.push(Completion {
+                    label: op.to_string(),
+                    kind: Kind::Operator,
+                    contents: Contents::Snippet(format!("{op} ${{1:term()}}")),
+                    position: None,
+                    sort_text: helpers::sort_text(Proximity::Syntax, op),
+                    deprecated: false,
+                    additional_edit: None,
+                });
+            }
+            true
+        }
+        _ => false,
+    }
+}
+
+/// Whether the tokens end inside an unclosed `#{`
+fn in_map_type(tokens: &[(SyntaxKind, SyntaxToken)]) -> bool {
+    use elp_syntax::SyntaxKind as K;
+    let mut depth = 0;
+    for (i, (kind, _)) in tokens.iter().enumerate().rev() {
+        match kind {
+            K::ANON_RPAREN | K::ANON_RRACE | K::ANON_RBRACK | K::ANON_GT_GT => depth += 1,
+            K::ANON_LBRACE if depth == 0 => {
+                return matches!(tokens[..i].last(), Some((K::ANON_POUND, _)));
+            }
+            K::ANON_LPAREN | K::ANON_LBRACE | K::ANON_LBRACK | K::ANON_LT_LT => {
+                if depth == 0 {
+                    return false;
+                }
+                depth -= 1
+            }
+            _ => {}
+        }
+    }
+    false
+}
+
+/// Keys of the map in `M#{~` or `maps:get(~, M)`, based on its eqwalizer type
+fn add_typed_key_completions(
+    acc: &mut Vec<Completion>,
+    Ctx {
+        sema,
+        file_position,
+        parsed,
+        previous_tokens,
+        ..
+    }: &Ctx,
+) -> DoneFlag {
+    use elp_syntax::SyntaxKind as K;
+    let default = vec![];
+    let previous_tokens: &[_] = previous_tokens.as_ref().unwrap_or(&default);
+    let (prefix, previous_tokens) = match previous_tokens {
+        [previous_tokens @ .., (K::ATOM, prefix)] => (prefix.text(), previous_tokens),
+        _ => ("", previous_tokens),
+    };
+    let map_range = match previous_tokens {
+        // M#{~
+        [.., (K::VAR, var), (K::ANON_POUND, _), (K::ANON_LBRACE, _)] => var.text_range(),
+        // maps:get(~, M)
+        [
+            ..,
+            (K::ATOM, module),
+            (K::ANON_COLON, _),
+            (K::ATOM, function),
+            (K::ANON_LPAREN, _),
+        ] if module.text() == "maps" && function.text() == "get" => {
+            match maps_get_map_arg(parsed.value.syntax(), function) {
+                Some(range) => range,
+                None => return false,
+            }
+        }
+        _ => return false,
+    };
+    let type_info = match sema.db.eqwalizer_type_at_position(FileRange {
+        file_id: file_position.file_id,
+        range: map_range,
+    }) {
+        Some(type_info) => type_info,
+        None => return false,
+    };
+    match &type_info.0 {
+        Type::MapType(map_type) if !map_type.props.is_empty() => {
+            let completions = map_type
+                .props
+                .keys()
+                .map(|key| key.to_string())
+                .filter(|key| key.starts_with(prefix))
+                .map(|key| Completion {
+                    sort_text: helpers::sort_text(Proximity::Local, &key),
+                    label: key,
+                    kind: Kind::Map,
+                    contents: Contents::SameAsLabel,
+                    position: None,
+                    deprecated: false,
+                    additional_edit: None,
+                });
+            acc.extend(completions);
+            true
+        }
+        _ => false,
+    }
+}
+
+/// The range of the map argument of the `maps:get/2` call whose function
+/// name is `function`.
+fn maps_get_map_arg(node: &SyntaxNode, function: &SyntaxToken) -> Option<TextRange> {
+    let call = algo::find_node_at_offset::<ast::Call>(node, function.text_range().start())?;
+    let map = call.args()?.args().nth(1)?;
+    Some(map.syntax().text_range())
 }
 
 fn add_token_based_completions(
@@ -137,12 +387,13 @@ fn completion(
     // Setting a position ensures a "resolve" request will be triggered when the user selects the completion.
     let preview = preview(expr, &op);
     let snippet = snippet(expr, &op);
+    let label = format!("{} {preview}", name.as_label());
     Completion {
-        label: format!("{} {preview}", name.as_label()),
+        sort_text: helpers::sort_text(Proximity::Local, &label),
+        label,
         kind: Kind::Map,
         contents: Contents::Snippet(snippet),
         position: position(sema, def),
-        sort_text: None,
         deprecated: false,
         additional_edit: None,
     }
@@ -194,6 +445,7 @@ fn format_field(idx: i32, key: Expr, op: &str) -> String {
 
 #[cfg(test)]
 mod test {
+    use elp_project_model::otp::otp_supported_by_eqwalizer;
     use expect_test::Expect;
     use expect_test::expect;
 
@@ -369,4 +621,99 @@ mod test {
                 {label:my_map/0 #{field1 => Field1, ... }, kind:Map, contents:Snippet("{field1 => ${1:Field1}, field2 => ${2:Field2}}"), position:Some(FilePosition { file_id: FileId(0), offset: 32 })}"#]],
         );
     }
+
+    #[test]
+    fn test_map_type_update_of_alias() {
+        check(
+            r#"
+         -module(sample).
+         -type my_map() :: #{name := binary(), port => integer()}.
+         -type my_map(T) :: #{value := T}.
+         -spec foo(my_map()#{~}) -> ok.
+         "#,
+            None,
+            expect![[r#"
+                {label:name, kind:Map, contents:Snippet("name := ${1:binary()}"), position:None}
+                {label:port, kind:Map, contents:Snippet("port => ${1:integer()}"), position:None}"#]],
+        );
+
+        check(
+            r#"
+         -module(sample).
+         -type my_map() :: #{name := binary(), port => integer()}.
+         -type my_map(T) :: #{value := T}.
+         -spec foo(my_map(atom())#{va~}) -> ok.
+         "#,
+            None,
+            expect![[
+                r#"{label:value, kind:Map, contents:Snippet("value := ${1:T}"), position:None}"#
+            ]],
+        );
+    }
+
+    #[test]
+    fn test_map_type_update_escapes_snippet() {
+        check(
+            r#"
+         -module(sample).
+         -type my_map() :: #{opts := #{debug => boolean()}}.
+         -spec foo(my_map()#{~}) -> ok.
+         "#,
+            None,
+            expect![[
+                r#"{label:opts, kind:Map, contents:Snippet("opts := ${1:#{debug => boolean()\\}}"), position:None}"#
+            ]],
+        );
+    }
+
+    #[test]
+    fn test_map_type_operator_after_key() {
+        check(
+            r#"
+         -module(sample).
+         -spec foo(#{name ~}) -> ok.
+         "#,
+            None,
+            expect![[r#"
+                {label::=, kind:Operator, contents:Snippet(":= ${1:term()}"), position:None}
+                {label:=>, kind:Operator, contents:Snippet("=> ${1:term()}"), position:None}"#]],
+        );
+    }
+
+    #[test]
+    fn test_map_update_keys_from_type() {
+        if otp_supported_by_eqwalizer() {
+            check(
+                r#"
+         //- eqwalizer
+         //- /play/src/main.erl app:play
+         -module(main).
+         -spec foo(#{name := binary(), port := integer()}) -> map().
+         foo(M) -> M#{~}.
+         "#,
+                None,
+                expect![[r#"
+                    {label:name, kind:Map, contents:SameAsLabel, position:None}
+                    {label:port, kind:Map, contents:SameAsLabel, position:None}"#]],
+            );
+        }
+    }
+
+    #[test]
+    fn test_maps_get_keys_from_type() {
+        if otp_supported_by_eqwalizer() {
+            check(
+                r#"
+         //- eqwalizer
+         //- /play/src/main.erl app:play
+         -module(main).
+         -spec foo(#{name := binary(), port := integer()}) -> term().
+         foo(M) -> maps:get(po~, M).
+         "#,
+                None,
+                expect![[r#"
+                    {label:port, kind:Map, contents:SameAsLabel, position:None}"#]],
+            );
+        }
+    }
 }
@@ -14,6 +14,7 @@ use crate::Ctx;
 use crate::DoneFlag;
 use crate::Kind;
 use crate::helpers;
+use crate::helpers::Proximity;
 
 pub(crate) fn add_completions(
     acc: &mut Vec<Completion>,
@@ -32,12 +33,18 @@ pub(crate) fn add_completions(
     if let Some(modules) = sema.resolve_module_names(file_position.file_id) {
         let completions = modules.into_iter().filter_map(|m| {
             if m.starts_with(prefix) {
+                let proximity = sema
+                    .resolve_module_name(file_position.file_id, &m)
+                    .map_or(Proximity::Project, |module| {
+                        Proximity::of_file(sema, file_position.file_id, module.file.file_id)
+                    });
+                let label = m.to_string();
                 Some(Completion {
-                    label: m.to_string(),
+                    sort_text: helpers::sort_text(proximity, &label),
+                    label,
                     kind: Kind::Module,
                     contents: Contents::SameAsLabel,
                     position: None,
-                    sort_text: None,
                     deprecated: false,
                     additional_edit: None,
                 })
@@ -86,7 +93,8 @@ mod test {
     "#,
             expect![[r#"
                 {label:sample1, kind:Module, contents:SameAsLabel, position:None}
-                {label:sample2, kind:Module, contents:SameAsLabel, position:None}"#]],
+                {label:sample2, kind:Module, contents:SameAsLabel, position:None}
+                {label:string/0, kind:Type, contents:Snippet("string()"), position:None}"#]],
         );
 
         check(
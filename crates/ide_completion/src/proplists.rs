@@ -0,0 +1,321 @@
+/*
+ * Copyright (c) Meta Platforms, Inc. and affiliates.
+ *
+ * This source code is dual-licensed under either the MIT license found in the
+ * LICENSE-MIT file in the root directory of this source tree or the Apache
+ * License, Version 2.0 found in the LICENSE-APACHE file in the root directory
+ * of this source tree. You may select, at your option, one of the
+ * above-listed licenses.
+ */
+
+use std::collections::BTreeSet;
+
+use elp_base_db::FileId;
+use elp_syntax::SyntaxKind;
+use elp_syntax::SyntaxToken;
+use hir::Body;
+use hir::CallTarget;
+use hir::InFile;
+use hir::ListType;
+use hir::Literal;
+use hir::Name;
+use hir::Semantic;
+use hir::TypeAliasDef;
+use hir::TypeExpr;
+use hir::TypeExprId;
+
+use crate::Completion;
+use crate::Contents;
+use crate::Ctx;
+use crate::DoneFlag;
+use crate::Kind;
+use crate::helpers;
+use crate::helpers::Proximity;
+
+/// How many type aliases to go through when looking for the option keys,
+/// which also guards against recursive types.
+const MAX_ALIAS_DEPTH: usize = 5;
+
+/// Keys of an options proplist passed to a function, e.g. `foo([{~`, when
+/// the spec of the function types the parameter as a list of `{Key, Value}`
+/// tuples with literal atom keys.
+pub(crate) fn add_completions(
+    acc: &mut Vec<Completion>,
+    Ctx {
+        file_position,
+        previous_tokens,
+        sema,
+        trigger,
+        ..
+    }: &Ctx,
+) -> DoneFlag {
+    use elp_syntax::SyntaxKind as K;
+    if trigger.is_some() {
+        return false;
+    }
+    let default = vec![];
+    let previous_tokens: &[_] = previous_tokens.as_ref().unwrap_or(&default);
+    let (prefix, previous_tokens) = match previous_tokens {
+        [previous_tokens @ .., (K::ATOM, prefix)]
+            if prefix.text_range().end() == file_position.offset =>
+        {
+            (prefix.text(), previous_tokens)
+        }
+        _ => ("", previous_tokens),
+    };
+    let previous_tokens = match previous_tokens {
+        [.., (K::ANON_LBRACK | K::ANON_COMMA, _), (K::ANON_LBRACE, _)] => {
+            &previous_tokens[..previous_tokens.len() - 1]
+        }
+        _ => return false,
+    };
+    let Some(call) = call_before(previous_tokens) else {
+        return false;
+    };
+    let keys = option_keys(sema, file_position.file_id, &call);
+    if keys.is_empty() {
+        return false;
+    }
+    let completions = keys
+        .into_iter()
+        .filter(|key| key.starts_with(prefix))
+        .map(|key| Completion {
+            sort_text: helpers::sort_text(Proximity::Local, &key),
+            label: key,
+            kind: Kind::Keyword,
+            contents: Contents::SameAsLabel,
+            position: None,
+            deprecated: false,
+            additional_edit: None,
+        });
+    acc.extend(completions);
+    true
+}
+
+/// A call whose argument at `index` is the list being written.
+struct CallBefore {
+    module: Option<String>,
+    name: String,
+    index: usize,
+}
+
+/// The call enclosing the list opened by the last of the `tokens`, which
+/// must be the `[` of the list, or a comma between its elements.
+fn call_before(tokens: &[(SyntaxKind, SyntaxToken)]) -> Option<CallBefore> {
+    use elp_syntax::SyntaxKind as K;
+    let mut depth = 0;
+    let mut in_list = true;
+    let mut index = 0;
+    for (i, (kind, _)) in tokens.iter().enumerate().rev() {
+        match kind {
+            K::ANON_RPAREN | K::ANON_RRACE | K::ANON_RBRACK | K::ANON_GT_GT => depth += 1,
+            K::ANON_LPAREN | K::ANON_LBRACE | K::ANON_LBRACK | K::ANON_LT_LT if depth > 0 => {
+                depth -= 1
+            }
+            // The opening bracket of the list being written
+            K::ANON_LBRACK if in_list => in_list = false,
+            K::ANON_LPAREN if !in_list => {
+                return match &tokens[..i] {
+                    [.., (K::ATOM, module), (K::ANON_COLON, _), (K::ATOM, name)] => {
+                        Some(CallBefore {
+                            module: Some(module.text().to_string()),
+                            name: name.text().to_string(),
+                            index,
+                        })
+                    }
+                    [.., (K::ATOM, name)] => Some(CallBefore {
+                        module: None,
+                        name: name.text().to_string(),
+                        index,
+                    }),
+                    _ => None,
+                };
+            }
+            K::ANON_LPAREN | K::ANON_LBRACE | K::ANON_LBRACK | K::ANON_LT_LT => return None,
+            K::ANON_COMMA if depth == 0 && !in_list => index += 1,
+            _ => {}
+        }
+    }
+    None
+}
+
+/// The keys of the options accepted as argument `index` by the functions
+/// named as the call, in any arity.
+fn option_keys(sema: &Semantic, file_id: FileId, call: &CallBefore) -> BTreeSet<String> {
+    let mut keys = BTreeSet::new();
+    let (file_id, exported_only) = match &call.module {
+        Some(module) => match sema.resolve_module_name(file_id, module) {
+            Some(module) => (module.file.file_id, true),
+            None => return keys,
+        },
+        None => (file_id, false),
+    };
+    let name = Name::from_erlang_service(&call.name);
+    let def_map = sema.def_map(file_id);
+    for (name_arity, def) in def_map.get_functions() {
+        if name_arity.name() != &name || (exported_only && !def.exported) {
+            continue;
+        }
+        let Some(spec) = &def.spec else {
+            continue;
+        };
+        let spec_body = sema
+            .db
+            .spec_body(InFile::new(spec.file.file_id, spec.spec_id));
+        for sig in &spec_body.sigs {
+            if let Some(arg) = sig.args.get(call.index) {
+                collect_keys(
+                    &mut keys,
+                    sema,
+                    spec.file.file_id,
+                    &spec_body.body,
+                    *arg,
+                    false,
+                    0,
+                );
+            }
+        }
+    }
+    keys
+}
+
+/// Collect the atom keys of `{Key, Value}` tuples in the type, looking
+/// inside a list type first unless `in_list` is set.
+fn collect_keys(
+    keys: &mut BTreeSet<String>,
+    sema: &Semantic,
+    file_id: FileId,
+    body: &Body,
+    ty: TypeExprId,
+    in_list: bool,
+    depth: usize,
+) {
+    match &body[ty] {
+        TypeExpr::AnnType { ty, .. } => {
+            collect_keys(keys, sema, file_id, body, *ty, in_list, depth)
+        }
+        TypeExpr::Union { types } => {
+            for ty in types {
+                collect_keys(keys, sema, file_id, body, *ty, in_list, depth);
+            }
+        }
+        TypeExpr::List(ListType::Regular(ty) | ListType::NonEmpty(ty)) if !in_list => {
+            collect_keys(keys, sema, file_id, body, *ty, true, depth)
+        }
+        TypeExpr::Tuple { args } if in_list && args.len() == 2 => {
+            if let TypeExpr::Literal(Literal::Atom(atom)) = &body[args[0]] {
+                keys.insert(atom.as_name(sema.db.upcast()).to_string());
+            }
+        }
+        TypeExpr::Call { target, args } => {
+            // `list(T)` and `nonempty_list(T)`
+            if let CallTarget::Local { name } = target
+                && !in_list
+                && args.len() == 1
+                && let TypeExpr::Literal(Literal::Atom(name)) = &body[*name]
+                && matches!(
+                    name.as_name(sema.db.upcast()).as_str(),
+                    "list" | "nonempty_list"
+                )
+            {
+                return collect_keys(keys, sema, file_id, body, args[0], true, depth);
+            }
+            if depth >= MAX_ALIAS_DEPTH {
+                return;
+            }
+            if let Some(alias) = target.resolve_call(args.len() as u32, sema, file_id, body) {
+                collect_alias_keys(keys, sema, &alias, in_list, depth + 1);
+            }
+        }
+        _ => {}
+    }
+}
+
+fn collect_alias_keys(
+    keys: &mut BTreeSet<String>,
+    sema: &Semantic,
+    alias: &TypeAliasDef,
+    in_list: bool,
+    depth: usize,
+) {
+    let file_id = alias.file.file_id;
+    let form_list = sema.form_list(file_id);
+    let Some((idx, _)) = form_list
+        .type_aliases()
+        .find(|(_, type_alias)| type_alias.name() == alias.name())
+    else {
+        return;
+    };
+    let type_body = sema.db.type_body(InFile::new(file_id, idx));
+    collect_keys(
+        keys,
+        sema,
+        file_id,
+        &type_body.body,
+        type_body.ty,
+        in_list,
+        depth,
+    );
+}
+
+#[cfg(test)]
+mod test {
+    use expect_test::Expect;
+    use expect_test::expect;
+
+    use crate::tests::get_completions;
+    use crate::tests::render_completions;
+
+    fn check(code: &str, expect: Expect) {
+        let completions = get_completions(code, None);
+        let actual = &render_completions(completions);
+        expect.assert_eq(actual);
+    }
+
+    #[test]
+    fn test_option_keys() {
+        check(
+            r#"
+    -module(sample).
+    -type option() :: {timeout, timeout()} | {name, atom()} | verbose.
+    -spec start(atom(), [option()]) -> ok.
+    start(_Id, _Options) -> ok.
+    foo() -> start(my_id, [{~
+    "#,
+            expect![[r#"
+                {label:name, kind:Keyword, contents:SameAsLabel, position:None}
+                {label:timeout, kind:Keyword, contents:SameAsLabel, position:None}"#]],
+        );
+    }
+
+    #[test]
+    fn test_option_keys_with_prefix() {
+        check(
+            r#"
+    -module(sample).
+    -spec start(Options :: list({timeout, timeout()} | {name, atom()})) -> ok.
+    start(_Options) -> ok.
+    foo() -> start([{name, n}, {ti~
+    "#,
+            expect!["{label:timeout, kind:Keyword, contents:SameAsLabel, position:None}"],
+        );
+    }
+
+    #[test]
+    fn test_option_keys_remote() {
+        check(
+            r#"
+    //- /src/sample.erl
+    -module(sample).
+    foo() -> server:start([{~
+    //- /src/server.erl
+    -module(server).
+    -export([start/1]).
+    -type options() :: [{port, integer()}].
+    -spec start(options()) -> ok.
+    start(_Options) -> ok.
+    "#,
+            expect!["{label:port, kind:Keyword, contents:SameAsLabel, position:None}"],
+        );
+    }
+}
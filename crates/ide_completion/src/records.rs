@@ -19,9 +19,13 @@ use crate::Contents;
 use crate::Ctx;
 use crate::DoneFlag;
 use crate::Kind;
+use crate::helpers;
+use crate::helpers::Proximity;
 
 pub(crate) fn add_completions(acc: &mut Vec<Completion>, ctx: &Ctx) -> DoneFlag {
-    add_in_create_or_update(acc, ctx) || add_token_based_completions(acc, ctx)
+    add_in_create_or_update(acc, ctx)
+        || add_in_field_access(acc, ctx)
+        || add_token_based_completions(acc, ctx)
 }
 
 /// #rec{field1~} or X#rec{field1~}
@@ -67,6 +71,59 @@ pub(crate) fn add_in_create_or_update(
     }
 }
 
+/// X#rec.field~ or #rec.field~
+fn add_in_field_access(
+    acc: &mut Vec<Completion>,
+    Ctx {
+        file_position,
+        previous_tokens,
+        sema,
+        trigger,
+        ..
+    }: &Ctx,
+) -> DoneFlag {
+    use elp_syntax::SyntaxKind as K;
+    if !matches!(trigger, Some('.') | None) {
+        return false;
+    }
+    let default = vec![];
+    let previous_tokens: &[_] = previous_tokens.as_ref().unwrap_or(&default);
+    let (rec_name, field_prefix) = match previous_tokens {
+        [
+            ..,
+            (K::ANON_POUND, _),
+            (K::ATOM, rec_name),
+            (K::ANON_DOT, _),
+            (K::ATOM, field_prefix),
+        ] if field_prefix.text_range().end() == file_position.offset => {
+            (rec_name, field_prefix.text())
+        }
+        [
+            ..,
+            (K::ANON_POUND, _),
+            (K::ATOM, rec_name),
+            (K::ANON_DOT, _),
+        ] => (rec_name, ""),
+        _ => return false,
+    };
+    let def_map = sema.def_map(file_position.file_id);
+    // The record name is written as in the source, so possibly quoted
+    let Some(record) = def_map
+        .get_records()
+        .iter()
+        .find(|(name, _)| name.to_quoted_string() == rec_name.text())
+        .map(|(_, record)| record)
+    else {
+        return false;
+    };
+    let completions = record
+        .field_names(sema.db)
+        .filter(|name| name.as_str().starts_with(field_prefix))
+        .map(field_name_to_completion);
+    acc.extend(completions);
+    true
+}
+
 fn add_token_based_completions(
     acc: &mut Vec<Completion>,
     Ctx {
@@ -83,16 +140,58 @@ fn add_token_based_completions(
             .get_records()
             .iter()
             .filter(|(name, _)| name.starts_with(name_prefix))
-            .map(|(name, _)| Completion {
-                label: name.to_quoted_string().into_owned(),
-                kind: Kind::Record,
-                contents: Contents::SameAsLabel,
-                position: None,
-                sort_text: None,
-                deprecated: false,
-                additional_edit: None,
+            .map(|(name, _)| {
+                let label = name.to_quoted_string().into_owned();
+                Completion {
+                    sort_text: helpers::sort_text(Proximity::Local, &label),
+                    label,
+                    kind: Kind::Record,
+                    contents: Contents::SameAsLabel,
+                    position: None,
+                    deprecated: false,
+                    additional_edit: None,
+                }
             });
         acc.extend(completions);
+        // Records from headers not included yet, adding the include
+        let is_candidate = |name: &Name| {
+            name.starts_with(name_prefix) && !def_map.get_records().contains_key(name)
+        };
+        let headers = helpers::unincluded_headers(
+            sema,
+            file_position.file_id,
+            |project_id| sema.record_define_index(project_id),
+            name_prefix,
+            |header| {
+                sema.def_map_local(header)
+                    .get_records()
+                    .keys()
+                    .any(is_candidate)
+            },
+        );
+        for (header, additional_edit) in headers {
+            let header_def_map = sema.def_map_local(header);
+            let completions = header_def_map
+                .get_records()
+                .iter()
+                .filter(|(name, _)| is_candidate(name))
+                .map(|(name, _)| {
+                    let label = name.to_quoted_string().into_owned();
+                    Completion {
+                        sort_text: helpers::sort_text(
+                            Proximity::of_file(sema, file_position.file_id, header),
+                            &label,
+                        ),
+                        label,
+                        kind: Kind::Record,
+                        contents: Contents::SameAsLabel,
+                        position: None,
+                        deprecated: false,
+                        additional_edit: Some(additional_edit.clone()),
+                    }
+                });
+            acc.extend(completions);
+        }
         true
     };
     let add_record_index_completions =
@@ -129,25 +228,6 @@ fn add_token_based_completions(
         [.., (K::ANON_POUND, _)] if matches!(trigger, Some('#') | None) => {
             add_record_name_completions("", acc)
         }
-        // #rec_name.field_prefix
-        [
-            ..,
-            (K::ANON_POUND, _),
-            (K::ATOM, rec_name),
-            (K::ANON_DOT, _),
-            (K::ATOM, field_prefix),
-        ] if matches!(trigger, Some('.') | None) => {
-            add_record_index_completions(rec_name.text(), field_prefix.text(), acc)
-        }
-        // #rec_name.
-        [
-            ..,
-            (K::ANON_POUND, _),
-            (K::ATOM, rec_name),
-            (K::ANON_DOT, _),
-        ] if matches!(trigger, Some('.') | None) => {
-            add_record_index_completions(rec_name.text(), "", acc)
-        }
         // #rec_name{field_prefix~
         [
             ..,
@@ -163,24 +243,26 @@ fn add_token_based_completions(
 }
 
 fn field_name_to_completion_with_equals(field_name: Name) -> Completion {
+    let label = field_name.to_string();
     Completion {
-        label: field_name.to_string(),
+        sort_text: helpers::sort_text(Proximity::Local, &label),
+        label,
         kind: Kind::RecordField,
         contents: Contents::String(format!("{} = ", &field_name)),
         position: None,
-        sort_text: None,
         deprecated: false,
         additional_edit: None,
     }
 }
 
 fn field_name_to_completion(field_name: Name) -> Completion {
+    let label = field_name.to_string();
     Completion {
-        label: field_name.to_string(),
+        sort_text: helpers::sort_text(Proximity::Local, &label),
+        label,
         kind: Kind::RecordField,
         contents: Contents::SameAsLabel,
         position: None,
-        sort_text: None,
         deprecated: false,
         additional_edit: None,
     }
@@ -254,8 +336,52 @@ mod test {
         -module(sample).
         -record(rec, {field1, field2, other}).
         foo(X) -> _ = X#rec.f~.
+        "#,
+            None,
+            expect![[r#"
+                {label:field1, kind:RecordField, contents:SameAsLabel, position:None}
+                {label:field2, kind:RecordField, contents:SameAsLabel, position:None}"#]],
+        );
+    }
+
+    #[test]
+    fn test_record_field_access_without_prefix() {
+        check(
+            r#"
+        -module(sample).
+        -record(rec, {field1, field2, other}).
+        foo(X) -> X#rec.~
         "#,
             Some('.'),
+            expect![[r#"
+                {label:field1, kind:RecordField, contents:SameAsLabel, position:None}
+                {label:field2, kind:RecordField, contents:SameAsLabel, position:None}
+                {label:other, kind:RecordField, contents:SameAsLabel, position:None}"#]],
+        );
+
+        check(
+            r#"
+        -module(sample).
+        -record(rec, {field1, field2, other}).
+        foo() -> #rec.~
+        "#,
+            Some('.'),
+            expect![[r#"
+                {label:field1, kind:RecordField, contents:SameAsLabel, position:None}
+                {label:field2, kind:RecordField, contents:SameAsLabel, position:None}
+                {label:other, kind:RecordField, contents:SameAsLabel, position:None}"#]],
+        );
+    }
+
+    #[test]
+    fn test_record_field_access_quoted_record() {
+        check(
+            r#"
+        -module(sample).
+        -record('my.rec', {field1, field2, other}).
+        foo(X) -> X#'my.rec'.f~
+        "#,
+            None,
             expect![[r#"
                 {label:field1, kind:RecordField, contents:SameAsLabel, position:None}
                 {label:field2, kind:RecordField, contents:SameAsLabel, position:None}"#]],
@@ -460,4 +586,38 @@ mod test {
                 {label:field2, kind:RecordField, contents:SameAsLabel, position:None}"#]],
         );
     }
+
+    #[test]
+    fn test_record_name_from_unincluded_header() {
+        check(
+            r#"
+         //- /src/sample1.erl
+           -module(sample1).
+           foo(X) -> #th~
+         //- /src/header.hrl include_path:/src
+           -record(this_record, {field1}).
+           -record(another, {}).
+    "#,
+            None,
+            expect![[
+                r#"{label:this_record, kind:Record, contents:SameAsLabel, position:None, include:20:"-include_lib(\"test-fixture/src/header.hrl\").", alternatives:["-include(\"header.hrl\")."]}"#
+            ]],
+        );
+    }
+
+    #[test]
+    fn test_record_name_from_included_header() {
+        check(
+            r#"
+         //- /src/sample1.erl
+           -module(sample1).
+           -include("header.hrl").
+           foo(X) -> #th~
+         //- /src/header.hrl include_path:/src
+           -record(this_record, {field1}).
+    "#,
+            None,
+            expect!["{label:this_record, kind:Record, contents:SameAsLabel, position:None}"],
+        );
+    }
 }
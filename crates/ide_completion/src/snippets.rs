@@ -0,0 +1,291 @@
+/*
+ * Copyright (c) Meta Platforms, Inc. and affiliates.
+ *
+ * This source code is dual-licensed under either the MIT license found in the
+ * LICENSE-MIT file in the root directory of this source tree or the Apache
+ * License, Version 2.0 found in the LICENSE-APACHE file in the root directory
+ * of this source tree. You may select, at your option, one of the
+ * above-listed licenses.
+ */
+
+use elp_syntax::AstNode;
+use elp_syntax::SyntaxKind;
+use elp_syntax::SyntaxToken;
+use elp_syntax::ast;
+
+use crate::Completion;
+use crate::Contents;
+use crate::Ctx;
+use crate::DoneFlag;
+use crate::Kind;
+use crate::helpers;
+use crate::helpers::Proximity;
+
+/// Skeletons of the common compound expressions, as (label, snippet).
+const SNIPPETS: &[(&str, &str)] = &[
+    (
+        "case ... end",
+        "case ${1:Expr} of\n    ${2:Pattern} -> ${3:Body}\nend",
+    ),
+    (
+        "receive ... end",
+        "receive\n    ${1:Pattern} -> ${2:Body}\nend",
+    ),
+    (
+        "try ... catch ... end",
+        "try ${1:Expr}\ncatch\n    ${2:Class}:${3:Reason} -> ${4:Body}\nend",
+    ),
+];
+
+/// Only offered when the `maybe_expr` feature is enabled in the module.
+const MAYBE_SNIPPET: (&str, &str) = ("maybe ... end", "maybe\n    ${1:Pattern} ?= ${2:Expr}\nend");
+
+/// Complete the skeleton of a compound expression at the start of a
+/// statement, i.e. right after `->` or a `,` separating statements.
+pub(crate) fn add_completions(
+    acc: &mut Vec<Completion>,
+    Ctx {
+        config,
+        trigger,
+        parsed,
+        previous_tokens,
+        file_position,
+        ..
+    }: &Ctx,
+) -> DoneFlag {
+    use SyntaxKind as K;
+    if trigger.is_some() || config.snippet_cap.is_none() {
+        return false;
+    }
+    let default = vec![];
+    let previous_tokens: &[_] = previous_tokens.as_ref().unwrap_or(&default);
+    let previous_tokens = match previous_tokens {
+        [previous_tokens @ .., (K::ATOM, atom)]
+            if atom.text_range().end() == file_position.offset =>
+        {
+            previous_tokens
+        }
+        _ => previous_tokens,
+    };
+    let at_statement_start = match previous_tokens {
+        [.., (K::ANON_DASH_GT, _)] => true,
+        [previous_tokens @ .., (K::ANON_COMMA, _)] => separates_statements(previous_tokens),
+        _ => false,
+    };
+    if at_statement_start {
+        let maybe_enabled = parsed.value.forms().any(|form| match form {
+            ast::Form::FeatureAttribute(feature) => {
+                feature
+                    .feature()
+                    .is_some_and(|f| f.syntax().text() == "maybe_expr")
+                    && feature
+                        .flag()
+                        .is_some_and(|f| f.syntax().text() == "enable")
+            }
+            _ => false,
+        });
+        let snippets = SNIPPETS
+            .iter()
+            .chain(maybe_enabled.then_some(&MAYBE_SNIPPET));
+        acc.extend(snippets.map(|(label, snippet)| Completion {
+            label: label.to_string(),
+            kind: Kind::Keyword,
+            contents: Contents::Snippet(snippet.to_string()),
+            position: None,
+            sort_text: helpers::sort_text(Proximity::Syntax, label),
+            deprecated: false,
+            additional_edit: None,
+        }));
+    }
+    false
+}
+
+/// Whether a `,` following `tokens` separates two statements, rather than
+/// arguments, elements or guard tests, based on the closest unmatched
+/// opening token before it.
+fn separates_statements(tokens: &[(SyntaxKind, SyntaxToken)]) -> bool {
+    use SyntaxKind as K;
+    let mut depth = 0;
+    let mut next = None;
+    for (kind, _) in tokens.iter().rev() {
+        match kind {
+            K::ANON_RPAREN | K::ANON_RBRACK | K::ANON_RRACE | K::ANON_GT_GT | K::ANON_END => {
+                depth += 1
+            }
+            K::ANON_LPAREN
+            | K::ANON_LBRACK
+            | K::ANON_LBRACE
+            | K::ANON_LT_LT
+            | K::ANON_BEGIN
+            | K::ANON_CASE
+            | K::ANON_IF
+            | K::ANON_MAYBE
+            | K::ANON_RECEIVE
+            | K::ANON_TRY
+                if depth > 0 =>
+            {
+                depth -= 1
+            }
+            // Only a `fun (...) -> ... end` has an `end`
+            K::ANON_FUN if depth > 0 && next == Some(K::ANON_LPAREN) => depth -= 1,
+            K::ANON_DASH_GT | K::ANON_BEGIN | K::ANON_MAYBE | K::ANON_TRY | K::ANON_AFTER
+                if depth == 0 =>
+            {
+                return true;
+            }
+            K::ANON_LPAREN
+            | K::ANON_LBRACK
+            | K::ANON_LBRACE
+            | K::ANON_LT_LT
+            | K::ANON_CASE
+            | K::ANON_IF
+            | K::ANON_RECEIVE
+            | K::ANON_WHEN
+            | K::ANON_DOT
+                if depth == 0 =>
+            {
+                return false;
+            }
+            _ => (),
+        }
+        next = Some(*kind);
+    }
+    // The start of the statement is too far back to tell
+    false
+}
+
+#[cfg(test)]
+mod test {
+    use expect_test::Expect;
+    use expect_test::expect;
+
+    use crate::CompletionConfig;
+    use crate::Contents;
+    use crate::Kind;
+    use crate::tests::get_completions_with_config;
+    use crate::tests::render_completions;
+
+    fn check(code: &str, expect: Expect) {
+        check_with_config(&CompletionConfig::default(), code, expect)
+    }
+
+    fn check_with_config(config: &CompletionConfig, code: &str, expect: Expect) {
+        let completions = get_completions_with_config(config, code, None)
+            .into_iter()
+            .filter(|c| c.kind == Kind::Keyword && matches!(c.contents, Contents::Snippet(_)))
+            .collect();
+        let actual = &render_completions(completions);
+        expect.assert_eq(actual);
+    }
+
+    #[test]
+    fn test_case_snippet() {
+        check(
+            r#"
+    -module(sample).
+    test(X) ->
+        ca~
+    "#,
+            expect![[r#"
+                {label:case ... end, kind:Keyword, contents:Snippet("case ${1:Expr} of\n    ${2:Pattern} -> ${3:Body}\nend"), position:None}
+                {label:receive ... end, kind:Keyword, contents:Snippet("receive\n    ${1:Pattern} -> ${2:Body}\nend"), position:None}
+                {label:try ... catch ... end, kind:Keyword, contents:Snippet("try ${1:Expr}\ncatch\n    ${2:Class}:${3:Reason} -> ${4:Body}\nend"), position:None}"#]],
+        );
+    }
+
+    #[test]
+    fn test_receive_snippet_after_comma() {
+        check(
+            r#"
+    -module(sample).
+    test(Pid) ->
+        Pid ! ping,
+        ~
+    "#,
+            expect![[r#"
+                {label:case ... end, kind:Keyword, contents:Snippet("case ${1:Expr} of\n    ${2:Pattern} -> ${3:Body}\nend"), position:None}
+                {label:receive ... end, kind:Keyword, contents:Snippet("receive\n    ${1:Pattern} -> ${2:Body}\nend"), position:None}
+                {label:try ... catch ... end, kind:Keyword, contents:Snippet("try ${1:Expr}\ncatch\n    ${2:Class}:${3:Reason} -> ${4:Body}\nend"), position:None}"#]],
+        );
+    }
+
+    #[test]
+    fn test_maybe_snippet_with_feature() {
+        check(
+            r#"
+    -module(sample).
+    -feature(maybe_expr, enable).
+    test(X) ->
+        ~
+    "#,
+            expect![[r#"
+                {label:case ... end, kind:Keyword, contents:Snippet("case ${1:Expr} of\n    ${2:Pattern} -> ${3:Body}\nend"), position:None}
+                {label:maybe ... end, kind:Keyword, contents:Snippet("maybe\n    ${1:Pattern} ?= ${2:Expr}\nend"), position:None}
+                {label:receive ... end, kind:Keyword, contents:Snippet("receive\n    ${1:Pattern} -> ${2:Body}\nend"), position:None}
+                {label:try ... catch ... end, kind:Keyword, contents:Snippet("try ${1:Expr}\ncatch\n    ${2:Class}:${3:Reason} -> ${4:Body}\nend"), position:None}"#]],
+        );
+    }
+
+    #[test]
+    fn test_no_snippets_after_argument_comma() {
+        check(
+            r#"
+    -module(sample).
+    test(X) ->
+        foo(X, ~
+    "#,
+            expect![""],
+        );
+        check(
+            r#"
+    -module(sample).
+    test(X) when is_atom(X), ~
+    "#,
+            expect![""],
+        );
+    }
+
+    #[test]
+    fn test_snippets_after_nested_expression() {
+        check(
+            r#"
+    -module(sample).
+    test(X) ->
+        Y = foo(bar(X), [a]),
+        ~
+    "#,
+            expect![[r#"
+                {label:case ... end, kind:Keyword, contents:Snippet("case ${1:Expr} of\n    ${2:Pattern} -> ${3:Body}\nend"), position:None}
+                {label:receive ... end, kind:Keyword, contents:Snippet("receive\n    ${1:Pattern} -> ${2:Body}\nend"), position:None}
+                {label:try ... catch ... end, kind:Keyword, contents:Snippet("try ${1:Expr}\ncatch\n    ${2:Class}:${3:Reason} -> ${4:Body}\nend"), position:None}"#]],
+        );
+    }
+
+    #[test]
+    fn test_no_snippets_mid_expression() {
+        check(
+            r#"
+    -module(sample).
+    test(X) ->
+        X + ~
+    "#,
+            expect![""],
+        );
+    }
+
+    #[test]
+    fn test_no_snippets_without_snippet_support() {
+        check_with_config(
+            &CompletionConfig {
+                snippet_cap: None,
+                fuzzy: false,
+            },
+            r#"
+    -module(sample).
+    test(X) ->
+        ~
+    "#,
+            expect![""],
+        );
+    }
+}
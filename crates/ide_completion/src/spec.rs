@@ -8,18 +8,31 @@
  * above-listed licenses.
  */
 
+use elp_ide_db::helpers::escape_snippet;
 use elp_syntax::AstNode;
+use elp_syntax::SyntaxToken;
 use elp_syntax::algo;
 use elp_syntax::ast;
+use hir::Body;
+use hir::ComprehensionBuilder;
+use hir::Expr;
+use hir::ExprId;
 use hir::InFile;
+use hir::Literal;
+use hir::Semantic;
 
 use crate::Completion;
+use crate::Contents;
 use crate::Ctx;
+use crate::DoneFlag;
+use crate::Kind;
 use crate::helpers;
+use crate::helpers::Proximity;
 
 pub(crate) fn add_completions(
     acc: &mut Vec<Completion>,
     Ctx {
+        config,
         file_position,
         parsed,
         sema,
@@ -48,6 +61,8 @@ pub(crate) fn add_completions(
                     &sp.name,
                     spec_fun_prefix.text(),
                     next_token,
+                    Proximity::Local,
+                    config,
                 )
             {
                 fun_completion_to_spec(&mut completion);
@@ -63,6 +78,8 @@ pub(crate) fn add_completions(
                     na,
                     spec_fun_prefix.text(),
                     next_token,
+                    Proximity::Local,
+                    config,
                 ) {
                     fun_completion_to_spec(&mut completion);
                     Some(completion)
@@ -77,6 +94,365 @@ pub(crate) fn add_completions(
     };
 }
 
+/// Propose a return type for the spec being written, inferred from the
+/// values returned by the clauses of the function. This is best-effort:
+/// anything not recognised is typed as `term()`.
+pub(crate) fn add_return_type_completions(
+    acc: &mut Vec<Completion>,
+    Ctx {
+        file_position,
+        parsed,
+        sema,
+        trigger,
+        previous_tokens,
+        ..
+    }: &Ctx,
+) -> DoneFlag {
+    use elp_syntax::SyntaxKind as K;
+    if trigger.is_some() {
+        return false;
+    }
+    let default = vec![];
+    let previous_tokens: &[_] = previous_tokens.as_ref().unwrap_or(&default);
+    let (arrow, prefix) = match previous_tokens {
+        [.., (K::ANON_DASH_GT, arrow)] => (arrow, ""),
+        [.., (K::ANON_DASH_GT, arrow), (K::ATOM, prefix)] => (arrow, prefix.text()),
+        _ => return false,
+    };
+    // Only the arrow of the signature itself, not of a `fun()` type
+    if arrow.parent().and_then(ast::TypeSig::cast).is_none() {
+        return false;
+    }
+    if let Some(return_type) = || -> Option<String> {
+        let spec =
+            algo::find_node_at_offset::<ast::Spec>(parsed.value.syntax(), file_position.offset)?;
+        let spec = sema.find_form::<ast::Spec>(InFile::new(parsed.file_id, &spec))?;
+        let def_map = sema.def_map(file_position.file_id);
+        let def = def_map.get_function(&spec.name)?;
+        let def_fb = def.in_function_body(sema, ());
+        let mut types = Vec::new();
+        for (_, clause) in def_fb.clauses() {
+            if let Some(expr_id) = clause.clause.exprs.last() {
+                add_return_types(sema, &clause.body, *expr_id, &mut types);
+            }
+        }
+        Some(union(types))
+    }() {
+        // The client filters out the items not starting with the prefix
+        if !return_type.starts_with(prefix) {
+            return false;
+        }
+        acc.push(Completion {
+            label: return_type.clone(),
+            kind: Kind::Type,
+            position: None,
+            sort_text: helpers::sort_text(Proximity::Local, &return_type),
+            contents: Contents::Snippet(escape_snippet(&return_type)),
+            deprecated: false,
+            additional_edit: None,
+        });
+    }
+    false
+}
+
+/// Propose the parameter names of the first clause of the function as
+/// annotated arguments of the spec being written, e.g. `Name :: term()`.
+pub(crate) fn add_arg_completions(
+    acc: &mut Vec<Completion>,
+    Ctx {
+        file_position,
+        parsed,
+        sema,
+        trigger,
+        previous_tokens,
+        ..
+    }: &Ctx,
+) -> DoneFlag {
+    use elp_syntax::SyntaxKind as K;
+    if trigger.is_some() {
+        return false;
+    }
+    let default = vec![];
+    let previous_tokens: &[_] = previous_tokens.as_ref().unwrap_or(&default);
+    let prefix = match previous_tokens {
+        [.., (K::ANON_LPAREN | K::ANON_COMMA, _)] => "",
+        [.., (K::ANON_LPAREN | K::ANON_COMMA, _), (K::VAR, prefix)] => prefix.text(),
+        _ => return false,
+    };
+    if let Some(name) = || -> Option<String> {
+        let node = parsed.value.syntax();
+        let args = algo::find_node_at_offset::<ast::TypeSig>(node, file_position.offset)?.args()?;
+        // Only the arguments, not the return type
+        if !args.syntax().text_range().contains(file_position.offset) {
+            return None;
+        }
+        let index = args
+            .syntax()
+            .children_with_tokens()
+            .filter(|element| {
+                element.kind() == K::ANON_COMMA
+                    && element.text_range().end() <= file_position.offset
+            })
+            .count();
+        let spec = algo::find_node_at_offset::<ast::Spec>(node, file_position.offset)?;
+        let spec = sema.find_form::<ast::Spec>(InFile::new(parsed.file_id, &spec))?;
+        // The signature may still be incomplete, so its arity is only a
+        // hint
+        let def_map = sema.def_map(file_position.file_id);
+        let def = def_map.get_function(&spec.name).or_else(|| {
+            def_map
+                .get_functions()
+                .filter(|(na, _)| na.name() == spec.name.name() && na.arity() as usize > index)
+                .min_by_key(|(na, _)| na.arity())
+                .map(|(_, def)| def)
+        })?;
+        let clause = match def.source(sema.db.upcast()).first()?.clause()? {
+            ast::FunctionOrMacroClause::FunctionClause(clause) => clause,
+            ast::FunctionOrMacroClause::MacroCallExpr(_) => return None,
+        };
+        match clause.args()?.args().nth(index)? {
+            ast::Expr::ExprMax(ast::ExprMax::Var(var)) if var.syntax().text() != "_" => {
+                Some(var.syntax().text().to_string())
+            }
+            _ => None,
+        }
+    }() && name.starts_with(prefix)
+    {
+        let label = format!("{name} :: term()");
+        acc.push(Completion {
+            sort_text: helpers::sort_text(Proximity::Variable, &label),
+            label,
+            kind: Kind::Variable,
+            contents: Contents::Snippet(format!("{name} :: ${{1:term()}}")),
+            position: None,
+            deprecated: false,
+            additional_edit: None,
+        });
+    }
+    false
+}
+
+/// Propose the type variables of the signature being written as the
+/// constraints following its `when`, e.g. `Acc :: term()`, skipping the
+/// ones already constrained.
+pub(crate) fn add_constraint_completions(
+    acc: &mut Vec<Completion>,
+    Ctx {
+        file_position,
+        parsed,
+        trigger,
+        previous_tokens,
+        ..
+    }: &Ctx,
+) -> DoneFlag {
+    use elp_syntax::SyntaxKind as K;
+    if trigger.is_some() {
+        return false;
+    }
+    let default = vec![];
+    let previous_tokens: &[_] = previous_tokens.as_ref().unwrap_or(&default);
+    let (separator, prefix) = match previous_tokens {
+        [.., (K::ANON_WHEN | K::ANON_COMMA, separator)] => (separator, ""),
+        [
+            ..,
+            (K::ANON_WHEN | K::ANON_COMMA, separator),
+            (K::VAR, prefix),
+        ] => (separator, prefix.text()),
+        _ => return false,
+    };
+    // A comma must separate constraints, not the elements of a type
+    if separator.kind() == K::ANON_COMMA && !separates_constraints(separator) {
+        return false;
+    }
+    let node = parsed.value.syntax();
+    let Some(spec) = algo::find_node_at_offset::<ast::Spec>(node, file_position.offset) else {
+        return false;
+    };
+    // An incomplete constraint may not be part of the signature yet, so
+    // take the last one starting before the cursor
+    let Some(sig) = spec
+        .sigs()
+        .take_while(|sig| sig.syntax().text_range().start() < file_position.offset)
+        .last()
+    else {
+        return false;
+    };
+    let sig_start = sig.syntax().text_range().start();
+    let in_constraints = spec.syntax().descendants_with_tokens().any(|element| {
+        element.kind() == K::ANON_WHEN
+            && element.text_range().start() >= sig_start
+            && element.text_range().end() <= file_position.offset
+    });
+    if !in_constraints {
+        return false;
+    }
+    let constrained: Vec<String> = sig
+        .guard()
+        .into_iter()
+        .flat_map(|guard| guard.guards())
+        .filter(|ann| {
+            !ann.syntax()
+                .text_range()
+                .contains_inclusive(file_position.offset)
+        })
+        .filter_map(|ann| ann.var()?.var())
+        .map(|var| var.syntax().text().to_string())
+        .collect();
+    let mut names: Vec<String> = Vec::new();
+    let signature = sig
+        .args()
+        .map(|args| args.syntax().clone())
+        .into_iter()
+        .chain(sig.ty().map(|ty| ty.syntax().clone()));
+    for var in signature.flat_map(|node| node.descendants().filter_map(ast::Var::cast)) {
+        let name = var.syntax().text().to_string();
+        if name != "_"
+            && name.starts_with(prefix)
+            && !constrained.contains(&name)
+            && !names.contains(&name)
+        {
+            names.push(name);
+        }
+    }
+    acc.extend(names.into_iter().map(|name| {
+        let label = format!("{name} :: term()");
+        Completion {
+            sort_text: helpers::sort_text(Proximity::Variable, &label),
+            label,
+            kind: Kind::Variable,
+            contents: Contents::Snippet(format!("{name} :: ${{1:term()}}")),
+            position: None,
+            deprecated: false,
+            additional_edit: None,
+        }
+    }));
+    false
+}
+
+/// Whether the comma is outside of any brackets since the preceding `when`.
+/// The tokens are scanned rather than the tree, as an incomplete constraint
+/// may not be parsed as one.
+fn separates_constraints(comma: &SyntaxToken) -> bool {
+    use elp_syntax::SyntaxKind as K;
+    let mut depth = 0;
+    let mut token = comma.prev_token();
+    while let Some(current) = token {
+        match current.kind() {
+            K::ANON_WHEN => return depth == 0,
+            K::ANON_RPAREN | K::ANON_RRACE | K::ANON_RBRACK | K::ANON_GT_GT => depth += 1,
+            K::ANON_LPAREN | K::ANON_LBRACE | K::ANON_LBRACK | K::ANON_LT_LT => {
+                if depth == 0 {
+                    return false;
+                }
+                depth -= 1;
+            }
+            K::ANON_DOT => return false,
+            _ => {}
+        }
+        token = current.prev_token();
+    }
+    false
+}
+
+/// Collect the types of the values an expression can evaluate to, looking
+/// through the branches of control flow expressions.
+fn add_return_types(sema: &Semantic, body: &Body, expr_id: ExprId, acc: &mut Vec<String>) {
+    let add_last = |exprs: &[ExprId], acc: &mut Vec<String>| {
+        if let Some(expr_id) = exprs.last() {
+            add_return_types(sema, body, *expr_id, acc);
+        }
+    };
+    match &body[expr_id] {
+        Expr::Literal(Literal::Atom(atom)) => {
+            acc.push(sema.db.lookup_atom(*atom).to_quoted_string().into_owned())
+        }
+        Expr::Block { exprs } => add_last(exprs, acc),
+        Expr::If { clauses } => clauses
+            .iter()
+            .for_each(|clause| add_last(&clause.exprs, acc)),
+        Expr::Case { clauses, .. } => clauses
+            .iter()
+            .for_each(|clause| add_last(&clause.exprs, acc)),
+        Expr::Receive { clauses, after } => {
+            clauses
+                .iter()
+                .for_each(|clause| add_last(&clause.exprs, acc));
+            if let Some(after) = after {
+                add_last(&after.exprs, acc);
+            }
+        }
+        Expr::Try {
+            exprs,
+            of_clauses,
+            catch_clauses,
+            ..
+        } => {
+            if of_clauses.is_empty() {
+                add_last(exprs, acc);
+            }
+            of_clauses
+                .iter()
+                .for_each(|clause| add_last(&clause.exprs, acc));
+            catch_clauses
+                .iter()
+                .for_each(|clause| add_last(&clause.exprs, acc));
+        }
+        Expr::Paren { expr } => add_return_types(sema, body, *expr, acc),
+        Expr::MacroCall { expansion, .. } => add_return_types(sema, body, *expansion, acc),
+        _ => acc.push(type_of(body, expr_id)),
+    }
+}
+
+fn type_of(body: &Body, expr_id: ExprId) -> String {
+    match &body[expr_id] {
+        Expr::Literal(Literal::Atom(_)) => "atom()".to_string(),
+        Expr::Literal(Literal::Integer(_)) => "integer()".to_string(),
+        Expr::Literal(Literal::Float(_)) => "float()".to_string(),
+        Expr::Literal(Literal::Char(_)) => "char()".to_string(),
+        Expr::Literal(Literal::String(_)) => "string()".to_string(),
+        Expr::Tuple { exprs } => {
+            let elems: Vec<String> = exprs.iter().map(|expr| type_of(body, *expr)).collect();
+            format!("{{{}}}", elems.join(", "))
+        }
+        Expr::List { .. }
+        | Expr::Comprehension {
+            builder: ComprehensionBuilder::List(_),
+            ..
+        } => "list()".to_string(),
+        Expr::Binary { .. }
+        | Expr::Comprehension {
+            builder: ComprehensionBuilder::Binary(_),
+            ..
+        } => "binary()".to_string(),
+        Expr::Map { .. }
+        | Expr::MapUpdate { .. }
+        | Expr::Comprehension {
+            builder: ComprehensionBuilder::Map(_, _),
+            ..
+        } => "map()".to_string(),
+        Expr::Closure { .. } | Expr::CaptureFun { .. } => "fun()".to_string(),
+        Expr::Paren { expr } => type_of(body, *expr),
+        _ => "term()".to_string(),
+    }
+}
+
+fn union(types: Vec<String>) -> String {
+    let mut alternatives: Vec<String> = Vec::new();
+    for ty in types {
+        if ty == "term()" {
+            return ty;
+        }
+        if !alternatives.contains(&ty) {
+            alternatives.push(ty);
+        }
+    }
+    if alternatives.is_empty() {
+        "term()".to_string()
+    } else {
+        alternatives.join(" | ")
+    }
+}
+
 fn fun_completion_to_spec(completion: &mut Completion) {
     match &completion.contents {
         crate::Contents::SameAsLabel => {}
@@ -94,6 +470,7 @@ mod test {
     use expect_test::Expect;
     use expect_test::expect;
 
+    use crate::Kind;
     use crate::tests::get_completions;
     use crate::tests::render_completions;
 
@@ -142,4 +519,162 @@ mod test {
                 {label:frog/2, kind:Function, contents:Snippet("frog(${1:A}, ${2:B}) -> return_type()."), position:Some(FilePosition { file_id: FileId(0), offset: 18 })}"#]],
         );
     }
+
+    #[test]
+    fn test_spec_arg_names() {
+        let check_args = |code: &str, expect: Expect| {
+            let completions = get_completions(code, None)
+                .into_iter()
+                .filter(|c| c.kind == Kind::Variable)
+                .collect();
+            expect.assert_eq(&render_completions(completions));
+        };
+        check_args(
+            r#"
+        -module(sample).
+
+        -spec foo(~) -> ok.
+        foo(Name, {_, _} = Opts) -> ok.
+        "#,
+            expect![[r#"
+                {label:Name :: term(), kind:Variable, contents:Snippet("Name :: ${1:term()}"), position:None}"#]],
+        );
+        check_args(
+            r#"
+        -module(sample).
+
+        -spec foo(binary(), Cou~) -> ok.
+        foo(Name, Count) -> ok.
+        "#,
+            expect![[r#"
+                {label:Count :: term(), kind:Variable, contents:Snippet("Count :: ${1:term()}"), position:None}"#]],
+        );
+    }
+
+    #[test]
+    fn test_spec_constraint_names() {
+        let check_constraints = |code: &str, expect: Expect| {
+            let completions = get_completions(code, None)
+                .into_iter()
+                .filter(|c| c.kind == Kind::Variable)
+                .collect();
+            expect.assert_eq(&render_completions(completions));
+        };
+        check_constraints(
+            r#"
+        -module(sample).
+
+        -spec foo([X], Acc) -> Acc when ~
+        foo(L, Acc) -> Acc.
+        "#,
+            expect![[r#"
+                {label:X :: term(), kind:Variable, contents:Snippet("X :: ${1:term()}"), position:None}
+                {label:Acc :: term(), kind:Variable, contents:Snippet("Acc :: ${1:term()}"), position:None}"#]],
+        );
+        check_constraints(
+            r#"
+        -module(sample).
+
+        -spec foo([X], Acc) -> Acc when X :: integer(), ~
+        foo(L, Acc) -> Acc.
+        "#,
+            expect![[r#"
+                {label:Acc :: term(), kind:Variable, contents:Snippet("Acc :: ${1:term()}"), position:None}"#]],
+        );
+        check_constraints(
+            r#"
+        -module(sample).
+
+        -spec foo([X], Acc) -> Acc when X :: {a, ~
+        foo(L, Acc) -> Acc.
+        "#,
+            expect![""],
+        );
+    }
+
+    #[test]
+    fn test_spec_return_type() {
+        check(
+            r#"
+        -module(sample).
+
+        -spec foo(integer()) -> o~.
+        foo(0) -> ok;
+        foo(N) when N > 0 -> 'EXIT';
+        foo(_) ->
+            case rand:uniform() of
+                0.5 -> ok;
+                _ -> error
+            end.
+        "#,
+            None,
+            expect![[r#"
+                {label:ok | 'EXIT' | error, kind:Type, contents:Snippet("ok | 'EXIT' | error"), position:None}"#]],
+        );
+    }
+
+    #[test]
+    fn test_spec_return_type_tuple() {
+        check(
+            r#"
+        -module(sample).
+
+        -spec foo(term()) -> ~.
+        foo(X) -> {ok, 1};
+        foo(X) -> {error, X}.
+        "#,
+            None,
+            expect![[r#"
+                {label:main, kind:Module, contents:SameAsLabel, position:None}
+                {label:{atom(), integer()} | {atom(), term()}, kind:Type, contents:Snippet("{atom(), integer()} | {atom(), term()}"), position:None}"#]],
+        );
+    }
+
+    #[test]
+    fn test_spec_return_type_fallback() {
+        check(
+            r#"
+        -module(sample).
+
+        -spec foo(term()) -> t~.
+        foo(X) -> X.
+        "#,
+            None,
+            expect![[r#"
+                {label:term(), kind:Type, contents:Snippet("term()"), position:None}
+                {label:term/0, kind:Type, contents:Snippet("term()"), position:None}
+                {label:timeout/0, kind:Type, contents:Snippet("timeout()"), position:None}
+                {label:tuple/0, kind:Type, contents:Snippet("tuple()"), position:None}"#]],
+        );
+    }
+
+    #[test]
+    fn test_spec_return_type_prefix_mismatch() {
+        check(
+            r#"
+        -module(sample).
+
+        -spec foo(integer()) -> e~.
+        foo(0) -> ok.
+        "#,
+            None,
+            expect![""],
+        );
+    }
+
+    #[test]
+    fn test_spec_return_type_escaped() {
+        check(
+            r#"
+        -module(sample).
+
+        -spec foo() -> ~.
+        foo() -> '$end_of_table'.
+        "#,
+            None,
+            expect![[r#"
+                {label:'$end_of_table', kind:Type, contents:Snippet("'\\$end_of_table'"), position:None}
+                {label:main, kind:Module, contents:SameAsLabel, position:None}"#]],
+        );
+    }
 }
@@ -12,6 +12,7 @@ use elp_ide_db::RootDatabase;
 use elp_ide_db::elp_base_db::fixture::WithFixture;
 
 use crate::Completion;
+use crate::CompletionConfig;
 
 pub(crate) fn render_completions(completions: Vec<Completion>) -> String {
     completions
@@ -22,9 +23,17 @@ pub(crate) fn render_completions(completions: Vec<Completion>) -> String {
 }
 
 pub(crate) fn get_completions(code: &str, trigger_character: Option<char>) -> Vec<Completion> {
+    get_completions_with_config(&CompletionConfig::default(), code, trigger_character)
+}
+
+pub(crate) fn get_completions_with_config(
+    config: &CompletionConfig,
+    code: &str,
+    trigger_character: Option<char>,
+) -> Vec<Completion> {
     let (db, fixture) = RootDatabase::with_fixture(code);
     let position = fixture.position();
-    crate::completions(&db, position, trigger_character)
+    crate::completions(&db, config, position, trigger_character)
 }
 
 #[test]
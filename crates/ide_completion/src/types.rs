@@ -12,15 +12,18 @@ use elp_syntax::AstNode;
 use elp_syntax::algo;
 use elp_syntax::ast;
 use elp_syntax::ast::Atom;
+use elp_syntax::ast::ERLANG_TYPES;
 use hir::AtomDef;
 use hir::InFile;
 use hir::NameArity;
 
 use crate::Completion;
+use crate::Contents;
 use crate::Ctx;
 use crate::DoneFlag;
 use crate::Kind;
 use crate::helpers;
+use crate::helpers::Proximity;
 
 pub(crate) fn add_completions(acc: &mut Vec<Completion>, args: &Ctx) -> DoneFlag {
     add_remote(acc, args) || add_local(acc, args)
@@ -70,7 +73,12 @@ fn complete_remote_name(
                 .get_exported_types()
                 .iter()
                 .filter(|na| na.name().starts_with(fun_prefix))
-                .map(create_call_completion);
+                .map(|na| {
+                    create_call_completion(
+                        na,
+                        Proximity::of_file(sema, file_position.file_id, module.file.file_id),
+                    )
+                });
             acc.extend(completions);
             Some(())
         }
@@ -95,23 +103,92 @@ pub(crate) fn add_local(
     let def_map = sema.def_map(file_position.file_id);
     let completions = def_map.get_types().iter().filter_map(|(name_arity, _)| {
         if name_arity.name().starts_with(prefix) {
-            Some(create_call_completion(name_arity))
+            Some(create_call_completion(name_arity, Proximity::Local))
         } else {
             None
         }
     });
     acc.extend(completions);
+    // Types from headers not included yet, adding the include
+    let is_candidate = |name_arity: &NameArity| {
+        name_arity.name().starts_with(prefix) && !def_map.get_types().contains_key(name_arity)
+    };
+    let headers = helpers::unincluded_headers(
+        sema,
+        file_position.file_id,
+        |project_id| sema.type_define_index(project_id),
+        prefix,
+        |header| {
+            sema.def_map_local(header)
+                .get_types()
+                .keys()
+                .any(is_candidate)
+        },
+    );
+    for (header, additional_edit) in headers {
+        let header_def_map = sema.def_map_local(header);
+        let completions = header_def_map
+            .get_types()
+            .iter()
+            .filter(|(name_arity, _)| is_candidate(name_arity))
+            .map(|(name_arity, _)| Completion {
+                additional_edit: Some(additional_edit.clone()),
+                ..create_call_completion(
+                    name_arity,
+                    Proximity::of_file(sema, file_position.file_id, header),
+                )
+            });
+        acc.extend(completions);
+    }
+    add_builtin(acc, prefix);
     false
 }
 
-fn create_call_completion(name_arity: &NameArity) -> Completion {
+fn add_builtin(acc: &mut Vec<Completion>, prefix: &str) {
+    // Without a prefix the whole list is mostly noise
+    if prefix.is_empty() {
+        return;
+    }
+    let completions = ERLANG_TYPES
+        .iter()
+        .filter(|(name, _)| name.starts_with(prefix))
+        .map(|(name, arity)| {
+            let label = format!("{name}/{arity}");
+            Completion {
+                sort_text: helpers::sort_text(Proximity::Otp, &label),
+                label,
+                kind: Kind::Type,
+                contents: helpers::format_call(name, *arity as u32),
+                position: None,
+                deprecated: false,
+                additional_edit: None,
+            }
+        });
+    acc.extend(completions);
+    // The literal map type, offered along `map()`
+    if "map".starts_with(prefix) {
+        let label = "map/0 #{}".to_string();
+        acc.push(Completion {
+            sort_text: helpers::sort_text(Proximity::Otp, &label),
+            label,
+            kind: Kind::Type,
+            contents: Contents::Snippet("#{${1}}".to_string()),
+            position: None,
+            deprecated: false,
+            additional_edit: None,
+        });
+    }
+}
+
+fn create_call_completion(name_arity: &NameArity, proximity: Proximity) -> Completion {
     let contents = helpers::format_call(name_arity.name(), name_arity.arity());
+    let label = name_arity.to_string();
     Completion {
-        label: name_arity.to_string(),
+        sort_text: helpers::sort_text(proximity, &label),
+        label,
         kind: Kind::Type,
         contents,
         position: None,
-        sort_text: None,
         deprecated: false,
         additional_edit: None,
     }
@@ -159,7 +236,52 @@ mod test {
                 {label:alias_nominal/1, kind:Type, contents:Snippet("alias_nominal(${1:Arg1})"), position:None}
                 {label:alias_opaque/0, kind:Type, contents:Snippet("alias_opaque()"), position:None}
                 {label:alias_opaque/1, kind:Type, contents:Snippet("alias_opaque(${1:Arg1})"), position:None}
-                {label:another_module, kind:Module, contents:SameAsLabel, position:None}"#]],
+                {label:another_module, kind:Module, contents:SameAsLabel, position:None}
+                {label:any/0, kind:Type, contents:Snippet("any()"), position:None}
+                {label:arity/0, kind:Type, contents:Snippet("arity()"), position:None}
+                {label:atom/0, kind:Type, contents:Snippet("atom()"), position:None}"#]],
+        );
+    }
+
+    #[test]
+    fn builtin() {
+        check(
+            r#"
+        //- /src/sample.erl
+        -module(sample).
+        -type alias() :: integ~.
+        "#,
+            None,
+            expect![[
+                r#"{label:integer/0, kind:Type, contents:Snippet("integer()"), position:None}"#
+            ]],
+        );
+
+        check(
+            r#"
+        //- /src/sample.erl
+        -module(sample).
+        -spec foo() -> nonempty_l~.
+        foo() -> [ok].
+        "#,
+            None,
+            expect![[r#"
+                {label:nonempty_list/0, kind:Type, contents:Snippet("nonempty_list()"), position:None}
+                {label:nonempty_list/1, kind:Type, contents:Snippet("nonempty_list(${1:Arg1})"), position:None}"#]],
+        );
+
+        check(
+            r#"
+        //- /src/sample.erl
+        -module(sample).
+        -type alias() :: ma~.
+        "#,
+            None,
+            expect![[r#"
+                {label:map/0, kind:Type, contents:Snippet("map()"), position:None}
+                {label:map/0 #{}, kind:Type, contents:Snippet("#{${1}}"), position:None}
+                {label:maybe_improper_list/0, kind:Type, contents:Snippet("maybe_improper_list()"), position:None}
+                {label:maybe_improper_list/2, kind:Type, contents:Snippet("maybe_improper_list(${1:Arg1}, ${2:Arg2})"), position:None}"#]],
         );
     }
 
@@ -276,4 +398,22 @@ mod test {
             expect![""],
         );
     }
+
+    #[test]
+    fn user_defined_from_unincluded_header() {
+        check(
+            r#"
+         //- /src/sample1.erl
+           -module(sample1).
+           -spec foo() -> my_t~.
+           foo() -> ok.
+         //- /src/header.hrl include_path:/src
+           -type my_type() :: ok.
+    "#,
+            None,
+            expect![[
+                r#"{label:my_type/0, kind:Type, contents:Snippet("my_type()"), position:None, include:20:"-include_lib(\"test-fixture/src/header.hrl\").", alternatives:["-include(\"header.hrl\")."]}"#
+            ]],
+        );
+    }
 }
@@ -18,6 +18,8 @@ use crate::Contents;
 use crate::Ctx;
 use crate::DoneFlag;
 use crate::Kind;
+use crate::helpers;
+use crate::helpers::Proximity;
 
 pub(crate) fn add_completions(
     acc: &mut Vec<Completion>,
@@ -65,12 +67,13 @@ pub(crate) fn add_completions(
 
 fn complete_var(var: &SyntaxToken, candidate: &SyntaxToken, acc: &mut FxHashSet<Completion>) {
     if candidate.text().starts_with(var.text()) {
+        let label = candidate.text().to_string();
         acc.insert(Completion {
-            label: candidate.text().to_string(),
+            sort_text: helpers::sort_text(Proximity::Variable, &label),
+            label,
             kind: Kind::Variable,
             contents: Contents::SameAsLabel,
             position: None,
-            sort_text: None,
             deprecated: false,
             additional_edit: None,
         });
@@ -8,18 +8,102 @@
  * above-listed licenses.
  */
 
+//! Syntactic context of a completion request, memoized per position so
+//! that repeated requests at the same offset of an unchanged file do not
+//! walk the tree again.
+
+use std::sync::Arc;
+
+use elp_base_db::FileId;
+use elp_base_db::SourceDatabase;
+use elp_base_db::salsa;
 use elp_syntax::AstNode;
 use elp_syntax::NodeOrToken;
 use elp_syntax::SyntaxElement;
 use elp_syntax::SyntaxKind;
 use elp_syntax::SyntaxNode;
 use elp_syntax::SyntaxToken;
+use elp_syntax::TextRange;
 use elp_syntax::TextSize;
 use elp_syntax::algo;
 use elp_syntax::ast;
 use elp_syntax::match_ast;
 use elp_syntax::ted::Element;
 
+/*
+For token-based completions, this is the maximum number of previous tokens we consider.
+*/
+static MAX_PREVIOUS_TOKENS_LEN: usize = 16;
+
+/// Number of memoized completion contexts to keep. Only the last few
+/// positions are ever asked for again, and every keystroke creates a
+/// new one, so without a bound they would accumulate for the lifetime
+/// of the session.
+pub const COMPLETION_CONTEXT_LRU_CAPACITY: usize = 16;
+
+#[salsa::query_group(CompletionContextDatabaseStorage)]
+pub trait CompletionContextDatabase: SourceDatabase {
+    #[salsa::lru]
+    fn completion_context(&self, file_id: FileId, offset: TextSize) -> Arc<CompletionContext>;
+}
+
+/// The parts of the completion context derived from the syntax tree only.
+/// Tokens are stored as ranges, since syntax tokens cannot be shared
+/// between threads: use `SyntaxNode::covering_element` to get them back.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct CompletionContext {
+    pub ctx_kind: CtxKind,
+    pub previous_tokens: Option<Vec<(SyntaxKind, TextRange)>>,
+}
+
+fn completion_context(
+    db: &dyn CompletionContextDatabase,
+    file_id: FileId,
+    offset: TextSize,
+) -> Arc<CompletionContext> {
+    let parsed = db.parse(file_id);
+    let node = parsed.syntax_node();
+    Arc::new(CompletionContext {
+        ctx_kind: CtxKind::new(&node, offset),
+        previous_tokens: get_previous_tokens(&node, offset).map(|tokens| {
+            tokens
+                .into_iter()
+                .map(|(kind, token)| (kind, token.text_range()))
+                .collect()
+        }),
+    })
+}
+
+// Note: in an ideal world, we would not need to use much token-level information
+// to get reasonable error-recovery for completions.
+// See T154356210
+fn get_previous_tokens(
+    node: &SyntaxNode,
+    offset: TextSize,
+) -> Option<Vec<(SyntaxKind, SyntaxToken)>> {
+    let mut token = node.token_at_offset(offset).left_biased()?;
+    let mut tokens = Vec::new();
+
+    while token.text_range().start() >= 0.into() && tokens.len() < MAX_PREVIOUS_TOKENS_LEN {
+        let next_opt = token.prev_token();
+        if !token.kind().is_trivia() {
+            tokens.push(token.clone());
+        }
+        if let Some(next) = next_opt {
+            token = next;
+        } else {
+            break;
+        }
+    }
+    Some(
+        tokens
+            .into_iter()
+            .rev()
+            .map(|tok| (tok.kind(), tok))
+            .collect::<Vec<_>>(),
+    )
+}
+
 #[derive(Clone, Debug, PartialEq, Eq)]
 pub enum CtxKind {
     Comment,
@@ -258,14 +342,17 @@ impl CtxKind {
 /// Tests of internals, delete when autocomplete is full-featured T126163525
 #[cfg(test)]
 mod ctx_tests {
+    use std::sync::Arc;
+
+    use elp_base_db::FilePosition;
     use elp_base_db::SourceDatabase;
-    use elp_ide_db::RootDatabase;
-    use elp_ide_db::elp_base_db::FilePosition;
-    use elp_ide_db::elp_base_db::fixture::WithFixture;
+    use elp_base_db::fixture::WithFixture;
     use elp_syntax::AstNode;
     use hir::Semantic;
 
-    use crate::CtxKind;
+    use super::CompletionContextDatabase;
+    use super::CtxKind;
+    use crate::RootDatabase;
 
     fn ctx(code: &str) -> CtxKind {
         let (db, fixture) = RootDatabase::with_fixture(code);
@@ -685,4 +772,32 @@ mod ctx_tests {
             CtxKind::Expr,
         );
     }
+
+    #[test]
+    fn memoized_context() {
+        let (db, fixture) = RootDatabase::with_fixture(
+            r#"
+        -module(sample).
+        test() ->
+            lists:ma~.
+        "#,
+        );
+        let FilePosition { file_id, offset } = fixture.position();
+        let context = db.completion_context(file_id, offset);
+        assert_eq!(context.ctx_kind, CtxKind::Expr);
+        let text = db.file_text(file_id);
+        let previous: Vec<_> = context
+            .previous_tokens
+            .iter()
+            .flatten()
+            .rev()
+            .take(3)
+            .map(|(_, range)| &text[*range])
+            .collect();
+        assert_eq!(previous, vec!["ma", ":", "lists"]);
+        assert!(Arc::ptr_eq(
+            &context,
+            &db.completion_context(file_id, offset)
+        ));
+    }
 }
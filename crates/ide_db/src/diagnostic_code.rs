@@ -89,6 +89,41 @@ pub enum DiagnosticCode {
     NoErrorLogger,
     NoNoWarnSuppressions,
     CouldBeAStringLiteral,
+    UseExactEquality,
+    UnusedComparisonResult,
+    RedundantMapMerge,
+    FormatArgsMismatch,
+    StringTokens,
+    StringStrip,
+    StringSubstr,
+    UndefinedEquality,
+    RecordMissingFields,
+    FoldlAppendAccumulator,
+    RedundantTryAfter,
+    DeadFunction,
+    RedundantBlock,
+    ErlangGetStacktrace,
+    LiteralApply,
+    UnnecessaryListAppend,
+    IgnoredFunctionArg,
+    UnsafeKeyfindElement,
+    NestedCaseToMaybe,
+    UndefinedSpawnTarget,
+    UnnecessaryTry,
+    BlockingReceiveInCallback,
+    EmptyRecordUpdate,
+    TimerSleepInfinity,
+    IdentityListOperation,
+    ErlangNow,
+    FunctionBudget,
+    RecursiveAppendAccumulator,
+    FloatEquality,
+    ListSubtraction,
+    ElementIndexOutOfRange,
+    DuplicateMapKey,
+    LengthAtLeast,
+    RethrowWithoutStacktrace,
+    SpecificSizeBif,
 
     // Wrapper for erlang service diagnostic codes
     ErlangService(String),
@@ -238,6 +273,41 @@ impl DiagnosticCode {
             DiagnosticCode::NoErrorLogger => "W0053".to_string(),
             DiagnosticCode::NoNoWarnSuppressions => "W0054".to_string(),
             DiagnosticCode::CouldBeAStringLiteral => "W0055".to_string(),
+            DiagnosticCode::UseExactEquality => "W0056".to_string(),
+            DiagnosticCode::UnusedComparisonResult => "W0057".to_string(),
+            DiagnosticCode::RedundantMapMerge => "W0058".to_string(),
+            DiagnosticCode::FormatArgsMismatch => "W0059".to_string(),
+            DiagnosticCode::StringTokens => "W0060".to_string(),
+            DiagnosticCode::StringStrip => "W0061".to_string(),
+            DiagnosticCode::StringSubstr => "W0062".to_string(),
+            DiagnosticCode::UndefinedEquality => "W0063".to_string(),
+            DiagnosticCode::RecordMissingFields => "W0064".to_string(),
+            DiagnosticCode::FoldlAppendAccumulator => "W0065".to_string(),
+            DiagnosticCode::RedundantTryAfter => "W0066".to_string(),
+            DiagnosticCode::DeadFunction => "W0067".to_string(),
+            DiagnosticCode::RedundantBlock => "W0068".to_string(),
+            DiagnosticCode::ErlangGetStacktrace => "W0069".to_string(),
+            DiagnosticCode::LiteralApply => "W0070".to_string(),
+            DiagnosticCode::UnnecessaryListAppend => "W0071".to_string(),
+            DiagnosticCode::IgnoredFunctionArg => "W0072".to_string(),
+            DiagnosticCode::UnsafeKeyfindElement => "W0073".to_string(),
+            DiagnosticCode::NestedCaseToMaybe => "W0074".to_string(),
+            DiagnosticCode::UndefinedSpawnTarget => "W0075".to_string(),
+            DiagnosticCode::UnnecessaryTry => "W0076".to_string(),
+            DiagnosticCode::BlockingReceiveInCallback => "W0077".to_string(),
+            DiagnosticCode::EmptyRecordUpdate => "W0078".to_string(),
+            DiagnosticCode::TimerSleepInfinity => "W0079".to_string(),
+            DiagnosticCode::IdentityListOperation => "W0080".to_string(),
+            DiagnosticCode::ErlangNow => "W0081".to_string(),
+            DiagnosticCode::FunctionBudget => "W0082".to_string(),
+            DiagnosticCode::RecursiveAppendAccumulator => "W0083".to_string(),
+            DiagnosticCode::FloatEquality => "W0084".to_string(),
+            DiagnosticCode::ListSubtraction => "W0085".to_string(),
+            DiagnosticCode::ElementIndexOutOfRange => "W0086".to_string(),
+            DiagnosticCode::DuplicateMapKey => "W0087".to_string(),
+            DiagnosticCode::LengthAtLeast => "W0088".to_string(),
+            DiagnosticCode::RethrowWithoutStacktrace => "W0089".to_string(),
+            DiagnosticCode::SpecificSizeBif => "W0090".to_string(),
             DiagnosticCode::ErlangService(c) => c.to_string(),
             DiagnosticCode::Eqwalizer(c) => format!("eqwalizer: {c}"),
             DiagnosticCode::AdHoc(c) => format!("ad-hoc: {c}"),
@@ -334,6 +404,43 @@ impl DiagnosticCode {
             DiagnosticCode::NoErrorLogger => "no_error_logger".to_string(),
             DiagnosticCode::NoNoWarnSuppressions => "no_nowarn_suppressions".to_string(),
             DiagnosticCode::CouldBeAStringLiteral => "could_be_a_binary_string_literal".to_string(),
+            DiagnosticCode::UseExactEquality => "use_exact_equality".to_string(),
+            DiagnosticCode::UnusedComparisonResult => "unused_comparison_result".to_string(),
+            DiagnosticCode::RedundantMapMerge => "redundant_map_merge".to_string(),
+            DiagnosticCode::FormatArgsMismatch => "format_args_mismatch".to_string(),
+            DiagnosticCode::StringTokens => "string_tokens".to_string(),
+            DiagnosticCode::StringStrip => "string_strip".to_string(),
+            DiagnosticCode::StringSubstr => "string_substr".to_string(),
+            DiagnosticCode::UndefinedEquality => "undefined_equality".to_string(),
+            DiagnosticCode::RecordMissingFields => "record_missing_fields".to_string(),
+            DiagnosticCode::FoldlAppendAccumulator => "foldl_append_accumulator".to_string(),
+            DiagnosticCode::RedundantTryAfter => "redundant_try_after".to_string(),
+            DiagnosticCode::DeadFunction => "dead_function".to_string(),
+            DiagnosticCode::RedundantBlock => "redundant_block".to_string(),
+            DiagnosticCode::ErlangGetStacktrace => "erlang_get_stacktrace".to_string(),
+            DiagnosticCode::LiteralApply => "literal_apply".to_string(),
+            DiagnosticCode::UnnecessaryListAppend => "unnecessary_list_append".to_string(),
+            DiagnosticCode::IgnoredFunctionArg => "ignored_function_arg".to_string(),
+            DiagnosticCode::UnsafeKeyfindElement => "unsafe_keyfind_element".to_string(),
+            DiagnosticCode::NestedCaseToMaybe => "nested_case_to_maybe".to_string(),
+            DiagnosticCode::UndefinedSpawnTarget => "undefined_spawn_target".to_string(),
+            DiagnosticCode::UnnecessaryTry => "unnecessary_try".to_string(),
+            DiagnosticCode::BlockingReceiveInCallback => "blocking_receive_in_callback".to_string(),
+            DiagnosticCode::EmptyRecordUpdate => "empty_record_update".to_string(),
+            DiagnosticCode::TimerSleepInfinity => "timer_sleep_infinity".to_string(),
+            DiagnosticCode::IdentityListOperation => "identity_list_operation".to_string(),
+            DiagnosticCode::ErlangNow => "erlang_now".to_string(),
+            DiagnosticCode::FunctionBudget => "function_budget".to_string(),
+            DiagnosticCode::RecursiveAppendAccumulator => {
+                "recursive_append_accumulator".to_string()
+            }
+            DiagnosticCode::FloatEquality => "float_equality".to_string(),
+            DiagnosticCode::ListSubtraction => "list_subtraction".to_string(),
+            DiagnosticCode::ElementIndexOutOfRange => "element_index_out_of_range".to_string(),
+            DiagnosticCode::DuplicateMapKey => "duplicate_map_key".to_string(),
+            DiagnosticCode::LengthAtLeast => "length_at_least".to_string(),
+            DiagnosticCode::RethrowWithoutStacktrace => "rethrow_without_stacktrace".to_string(),
+            DiagnosticCode::SpecificSizeBif => "specific_size_bif".to_string(),
 
             DiagnosticCode::ErlangService(c) => c.to_string(),
             DiagnosticCode::Eqwalizer(c) => c.to_string(),
@@ -479,6 +586,41 @@ impl DiagnosticCode {
             DiagnosticCode::NoCatch => false,
             DiagnosticCode::NoErrorLogger => false,
             DiagnosticCode::NoNoWarnSuppressions => false,
+            DiagnosticCode::UseExactEquality => false,
+            DiagnosticCode::UnusedComparisonResult => false,
+            DiagnosticCode::RedundantMapMerge => false,
+            DiagnosticCode::FormatArgsMismatch => false,
+            DiagnosticCode::StringTokens => false,
+            DiagnosticCode::StringStrip => false,
+            DiagnosticCode::StringSubstr => false,
+            DiagnosticCode::UndefinedEquality => false,
+            DiagnosticCode::RecordMissingFields => false,
+            DiagnosticCode::FoldlAppendAccumulator => false,
+            DiagnosticCode::RedundantTryAfter => false,
+            DiagnosticCode::DeadFunction => false,
+            DiagnosticCode::RedundantBlock => false,
+            DiagnosticCode::ErlangGetStacktrace => false,
+            DiagnosticCode::LiteralApply => false,
+            DiagnosticCode::UnnecessaryListAppend => false,
+            DiagnosticCode::IgnoredFunctionArg => false,
+            DiagnosticCode::UnsafeKeyfindElement => false,
+            DiagnosticCode::NestedCaseToMaybe => false,
+            DiagnosticCode::UndefinedSpawnTarget => false,
+            DiagnosticCode::UnnecessaryTry => false,
+            DiagnosticCode::BlockingReceiveInCallback => false,
+            DiagnosticCode::EmptyRecordUpdate => false,
+            DiagnosticCode::TimerSleepInfinity => false,
+            DiagnosticCode::IdentityListOperation => false,
+            DiagnosticCode::ErlangNow => false,
+            DiagnosticCode::FunctionBudget => false,
+            DiagnosticCode::RecursiveAppendAccumulator => false,
+            DiagnosticCode::FloatEquality => false,
+            DiagnosticCode::ListSubtraction => false,
+            DiagnosticCode::ElementIndexOutOfRange => false,
+            DiagnosticCode::DuplicateMapKey => false,
+            DiagnosticCode::LengthAtLeast => false,
+            DiagnosticCode::RethrowWithoutStacktrace => false,
+            DiagnosticCode::SpecificSizeBif => false,
 
             DiagnosticCode::BinaryStringToSigil => false,
             DiagnosticCode::ErlangService(_) => false,
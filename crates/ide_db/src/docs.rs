@@ -28,11 +28,13 @@ use elp_syntax::ast;
 use elp_syntax::match_ast;
 use fxhash::FxHashMap;
 use hir::CallDef;
+use hir::FormIdx;
 use hir::InFile;
 use hir::Name;
 use hir::NameArity;
 use hir::Semantic;
 use hir::db::DefDatabase;
+use hir::form_list::DocAttributeId;
 
 use crate::ErlAstDatabase;
 
@@ -253,7 +255,10 @@ fn get_file_docs(db: &dyn DocDatabase, file_id: FileId) -> Arc<FileDoc> {
         DocOrigin::Edoc
     };
 
-    let descriptions = db.load_doc_descriptions(file_id, origin);
+    let mut descriptions = db.load_doc_descriptions(file_id, origin);
+    let (function_docs, type_docs) = get_file_doc_attributes(db.upcast(), file_id);
+    descriptions.function_docs.extend(function_docs);
+    descriptions.type_docs.extend(type_docs);
     let specs = get_file_function_specs(db.upcast(), file_id);
     Arc::new(FileDoc {
         module_doc: descriptions.module_doc,
@@ -333,6 +338,56 @@ fn get_file_function_specs(def_db: &dyn DefDatabase, file_id: FileId) -> FxHashM
         .collect::<FxHashMap<NameArity, Doc>>()
 }
 
+/// The EEP-48 `-doc` attributes of a file, attached to the function or
+/// type they document. A `-doc` attribute applies to the next function or
+/// type, possibly after its `-spec`.
+fn get_file_doc_attributes(
+    def_db: &dyn DefDatabase,
+    file_id: FileId,
+) -> (FxHashMap<NameArity, Doc>, FxHashMap<NameArity, Doc>) {
+    let form_list = def_db.file_form_list(file_id);
+    let source = def_db.parse(file_id).tree();
+    let doc_text = |doc_id: DocAttributeId| {
+        doc_attribute_text(&form_list[doc_id].form_id.get(&source)).map(Doc::new)
+    };
+    let function_docs = def_db
+        .def_map_local(file_id)
+        .get_functions()
+        .filter_map(|(name, def)| Some((name.clone(), doc_text(def.doc_id?)?)))
+        .collect();
+    // The def map only records the `-doc` attribute of functions
+    let mut type_docs = FxHashMap::default();
+    let mut pending: Option<DocAttributeId> = None;
+    for form in form_list.forms() {
+        match form {
+            FormIdx::DocAttribute(idx) => pending = Some(*idx),
+            FormIdx::TypeAlias(idx) => {
+                if let Some(doc) = pending.take().and_then(doc_text) {
+                    type_docs.insert(form_list[*idx].name().clone(), doc);
+                }
+            }
+            FormIdx::Spec(_) | FormIdx::DocMetadataAttribute(_) => {}
+            _ => pending = None,
+        }
+    }
+    (function_docs, type_docs)
+}
+
+/// The text of a `-doc` attribute given as a string, such as
+/// `-doc("Text").`, `-doc ~"Text".` or a triple-quoted string.
+/// Hidden (`-doc false.`) and external (`-doc {file, Path}.`) docs have
+/// no text.
+fn doc_attribute_text(attribute: &ast::WildAttribute) -> Option<String> {
+    let mut value = attribute.value()?;
+    while let ast::Expr::ExprMax(ast::ExprMax::ParenExpr(paren)) = value {
+        value = paren.expr()?;
+    }
+    match value {
+        ast::Expr::ExprMax(ast::ExprMax::String(string)) => Some(string.into()),
+        _ => None,
+    }
+}
+
 impl DocLoader for crate::RootDatabase {
     fn load_doc_descriptions(&self, file_id: FileId, doc_origin: DocOrigin) -> FileDoc {
         _ = SourceDatabaseExt::file_text(self, file_id); // Take dependency on the contents of the file we're getting docs for
@@ -35,6 +35,14 @@ impl SnippetCap {
     }
 }
 
+/// Escape the characters with a special meaning in a snippet, so that
+/// `text` is inserted as is.
+pub fn escape_snippet(text: &str) -> String {
+    text.replace('\\', "\\\\")
+        .replace('$', "\\$")
+        .replace('}', "\\}")
+}
+
 /// Picks the token with the highest rank returned by the passed in function.
 pub fn pick_best_token(
     tokens: TokenAtOffset<SyntaxToken>,
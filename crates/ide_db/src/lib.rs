@@ -46,11 +46,13 @@ use parking_lot::RwLock;
 use parking_lot::RwLockUpgradableReadGuard;
 use paths::Utf8PathBuf;
 use salsa::Database;
+use salsa::Query;
 use serde::Deserialize;
 use serde::Serialize;
 
 mod apply_change;
 pub mod common_test;
+pub mod completion_context;
 mod defs;
 pub mod diagnostic_code;
 pub mod docs;
@@ -102,6 +104,7 @@ pub trait EqwalizerProgressReporter: Send + Sync + RefUnwindSafe {
 
 #[salsa::database(
     LineIndexDatabaseStorage,
+    completion_context::CompletionContextDatabaseStorage,
     docs::DocDatabaseStorage,
     elp_base_db::SourceDatabaseExtStorage,
     elp_base_db::SourceDatabaseStorage,
@@ -131,6 +134,9 @@ impl Default for RootDatabase {
             ipc_handles: Arc::default(),
         };
         db.set_eqwalizer_config(Arc::new(EqwalizerConfig::default()));
+        completion_context::CompletionContextQuery
+            .in_db_mut(&mut db)
+            .set_lru_capacity(completion_context::COMPLETION_CONTEXT_LRU_CAPACITY);
         db
     }
 }
@@ -156,6 +156,10 @@ pub fn collect_metadata(
         let pattern_string = pattern.to_string();
         let len = pattern_string.len();
         for (i, _) in file_text.match_indices(&pattern_string) {
+            // `% elp:ignore-start` and `% elp:ignore-end` are handled by `collect_ignore_ranges`
+            if pattern.source == Source::Elp && file_text[i + len..].starts_with('-') {
+                continue;
+            }
             let pattern_start = TextSize::from(i as u32);
             let pattern_end = TextSize::from((i + len) as u32);
             let line_num = line_index.line_col(pattern_start).line;
@@ -184,9 +188,87 @@ pub fn collect_metadata(
             }
         }
     }
+    annotations.extend(collect_ignore_ranges(line_index, file_text, source));
     Metadata { annotations }
 }
 
+const IGNORE_START: &str = "% elp:ignore-start";
+const IGNORE_END: &str = "% elp:ignore-end";
+
+/// Turn the `% elp:ignore-start CODE` / `% elp:ignore-end` pairs into
+/// annotations suppressing the lines in between.
+///
+/// An end closes the innermost open start sharing one of its codes, or the
+/// innermost open start if it lists no code. An unmatched end is ignored,
+/// and an unmatched start suppresses up to the end of the file.
+fn collect_ignore_ranges(
+    line_index: &LineIndex,
+    file_text: &str,
+    source: &Parse<SourceFile>,
+) -> Vec<Annotation> {
+    let mut markers: Vec<(usize, bool)> = file_text
+        .match_indices(IGNORE_START)
+        .map(|(i, _)| (i, true))
+        .chain(file_text.match_indices(IGNORE_END).map(|(i, _)| (i, false)))
+        .collect();
+    markers.sort_unstable();
+
+    let mut open: Vec<Annotation> = Vec::new();
+    let mut annotations = Vec::new();
+    for (i, is_start) in markers {
+        let len = if is_start {
+            IGNORE_START.len()
+        } else {
+            IGNORE_END.len()
+        };
+        let pattern_start = TextSize::from(i as u32);
+        let pattern_end = TextSize::from((i + len) as u32);
+        let Some(token) = source
+            .syntax_node()
+            .token_at_offset(pattern_end)
+            .left_biased()
+            .filter(|token| token.kind() == SyntaxKind::COMMENT)
+        else {
+            continue;
+        };
+        let comment = token.to_string();
+        let codes: FxHashSet<DiagnosticCode> = comment
+            .split_whitespace()
+            .filter_map(DiagnosticCode::maybe_from_string)
+            .collect();
+        let line_num = line_index.line_col(pattern_start).line;
+        if is_start {
+            let start = line_start(line_index, line_num + 1, file_text);
+            open.push(Annotation {
+                comment,
+                comment_range: TextRange::new(pattern_start, pattern_end),
+                suppression_range: TextRange::new(start, start),
+                codes,
+                source: Source::Elp,
+                kind: Kind::Ignore,
+            });
+        } else {
+            let matching = open
+                .iter()
+                .rposition(|ann| codes.is_empty() || !ann.codes.is_disjoint(&codes));
+            if let Some(idx) = matching {
+                let mut annotation = open.remove(idx);
+                let end = line_start(line_index, line_num, file_text);
+                let start = annotation.suppression_range.start().min(end);
+                annotation.suppression_range = TextRange::new(start, end);
+                annotations.push(annotation);
+            }
+        }
+    }
+    let end_of_file = TextSize::of(file_text);
+    annotations.extend(open.into_iter().map(|mut annotation| {
+        let start = annotation.suppression_range.start().min(end_of_file);
+        annotation.suppression_range = TextRange::new(start, end_of_file);
+        annotation
+    }));
+    annotations
+}
+
 fn line_start(line_index: &LineIndex, line_num: u32, text: &str) -> TextSize {
     line_index.line_at(line_num as usize).unwrap_or_else(
         // end of last line
@@ -53,6 +53,7 @@ use thiserror::Error;
 
 use crate::buck::BuckProject;
 use crate::json::JsonConfig;
+use crate::mix::MixProject;
 use crate::otp::Otp;
 use crate::rebar::Profile;
 use crate::rebar::RebarConfig;
@@ -61,6 +62,7 @@ use crate::rebar::RebarProject;
 pub mod buck;
 pub mod eqwalizer_support;
 pub mod json;
+pub mod mix;
 pub mod no_manifest;
 pub mod otp;
 pub mod rebar;
@@ -355,6 +357,7 @@ pub enum ProjectBuildData {
     Rebar(RebarProject),
     Buck(BuckProject),
     Static(StaticProject),
+    Mix(MixProject),
 }
 
 #[derive(Clone, Debug, PartialEq, Eq)]
@@ -767,6 +770,7 @@ impl Project {
                 Some(parent) => Cow::Owned(parent.to_path_buf()),
                 None => Cow::Owned(AbsPathBuf::assert(Utf8PathBuf::from("/"))),
             },
+            ProjectBuildData::Mix(mix) => Cow::Borrowed(&mix.root),
         }
     }
 
@@ -993,6 +997,13 @@ impl Project {
             }
             ProjectBuildData::Buck(_) => Ok(()),
             ProjectBuildData::Static(_) => Ok(()),
+            ProjectBuildData::Mix(mix) => {
+                let mut cmd = mix.mix_command();
+                cmd.arg("deps.compile");
+
+                let _ = utf8_stdout(&mut cmd)?;
+                Ok(())
+            }
         }
     }
 
@@ -1055,9 +1066,13 @@ impl Project {
                 let mut apps = config.to_project_app_data(abs_otp_root);
                 let eqwalizer_support_app =
                     eqwalizer_support::eqwalizer_suppport_data(abs_otp_root);
-                let project = StaticProject { config_path };
                 apps.push(eqwalizer_support_app);
-                (ProjectBuildData::Static(project), apps, otp_root, None)
+                // A mixed Erlang/Elixir project built with Mix
+                let project = match MixProject::discover(&config.root_path) {
+                    Some(mix) => ProjectBuildData::Mix(mix),
+                    None => ProjectBuildData::Static(StaticProject { config_path }),
+                };
+                (project, apps, otp_root, None)
             }
         };
 
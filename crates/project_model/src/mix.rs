@@ -0,0 +1,119 @@
+/*
+ * Copyright (c) Meta Platforms, Inc. and affiliates.
+ *
+ * This source code is dual-licensed under either the MIT license found in the
+ * LICENSE-MIT file in the root directory of this source tree or the Apache
+ * License, Version 2.0 found in the LICENSE-APACHE file in the root directory
+ * of this source tree. You may select, at your option, one of the
+ * above-listed licenses.
+ */
+
+use std::env;
+use std::ffi::OsStr;
+use std::path::PathBuf;
+use std::process::Command;
+
+use paths::AbsPath;
+use paths::AbsPathBuf;
+
+const MIX_FILE: &str = "mix.exs";
+
+/// A Mix (Elixir) project, e.g. a mixed Erlang/Elixir umbrella project.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MixProject {
+    pub root: AbsPathBuf,
+    /// The `mix` executable found when the project was discovered.
+    pub mix: PathBuf,
+}
+
+impl MixProject {
+    /// The Mix project rooted at `root`, if it has a `mix.exs` file and the
+    /// `mix` executable can be found in the `PATH`.
+    pub fn discover(root: &AbsPath) -> Option<Self> {
+        Self::discover_with_mix(root, Self::find_mix())
+    }
+
+    fn discover_with_mix(root: &AbsPath, mix: Option<PathBuf>) -> Option<Self> {
+        if !root.join(MIX_FILE).exists() {
+            return None;
+        }
+        Some(Self {
+            root: root.to_path_buf(),
+            mix: mix?,
+        })
+    }
+
+    /// Locate the `mix` executable in the `PATH`, if available.
+    pub fn find_mix() -> Option<PathBuf> {
+        Self::find_mix_in(&env::var_os("PATH")?)
+    }
+
+    fn find_mix_in(paths: &OsStr) -> Option<PathBuf> {
+        let mix_name = if cfg!(target_os = "windows") {
+            "mix.bat"
+        } else {
+            "mix"
+        };
+        env::split_paths(paths)
+            .map(|dir| dir.join(mix_name))
+            .find(|candidate| candidate.is_file())
+    }
+
+    /// A `mix` command running in the project root.
+    pub fn mix_command(&self) -> Command {
+        let mut cmd = Command::new(&self.mix);
+        cmd.current_dir(&self.root);
+        cmd
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::fs;
+
+    use super::*;
+    use crate::temp_dir::TempDir;
+
+    fn mix_name() -> &'static str {
+        if cfg!(target_os = "windows") {
+            "mix.bat"
+        } else {
+            "mix"
+        }
+    }
+
+    #[test]
+    fn test_find_mix_in_path() {
+        let bin = TempDir::new();
+        let other = TempDir::new();
+        fs::write(bin.path().join(mix_name()), "").unwrap();
+        let paths = env::join_paths([other.path(), bin.path()]).unwrap();
+        assert_eq!(
+            MixProject::find_mix_in(&paths),
+            Some(bin.path().join(mix_name()))
+        );
+        let paths = env::join_paths([other.path()]).unwrap();
+        assert_eq!(MixProject::find_mix_in(&paths), None);
+    }
+
+    #[test]
+    fn test_discover_mix_project() {
+        let dir = TempDir::new();
+        let root = AbsPathBuf::assert_utf8(dir.path().to_path_buf());
+        let mix = PathBuf::from("/usr/bin/mix");
+        assert_eq!(
+            MixProject::discover_with_mix(&root, Some(mix.clone())),
+            None
+        );
+
+        fs::write(dir.path().join(MIX_FILE), "").unwrap();
+        assert_eq!(
+            MixProject::discover_with_mix(&root, Some(mix.clone())),
+            Some(MixProject {
+                root: root.clone(),
+                mix,
+            })
+        );
+        assert_eq!(MixProject::discover_with_mix(&root, None), None);
+    }
+}
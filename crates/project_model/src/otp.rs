@@ -157,6 +157,17 @@ impl Otp {
         Ok(val)
     }
 
+    /// The release of this OTP installation, e.g. `"27"`, found from the
+    /// `releases/<release>/OTP_VERSION` file next to its lib dir.
+    pub fn release(&self) -> Option<String> {
+        let releases = self.lib_dir.parent()?.join("releases");
+        fs::read_dir(releases)
+            .ok()?
+            .filter_map(|entry| entry.ok())
+            .filter(|entry| entry.path().join("OTP_VERSION").is_file())
+            .find_map(|entry| entry.file_name().into_string().ok())
+    }
+
     pub fn discover(path: Utf8PathBuf) -> (Otp, Vec<ProjectAppData>) {
         let apps = Self::discover_otp_apps(&path);
         (
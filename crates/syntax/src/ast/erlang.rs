@@ -208,56 +208,59 @@ pub fn is_erlang_fun(f: &str, a: usize) -> bool {
     erlang_funs().contains(&(f, a))
 }
 
-pub fn is_erlang_type(f: &str, a: usize) -> bool {
-    match (f, a) {
-        // This part is from https://www.erlang.org/doc/reference_manual/typespec.html
-        ("any", 0) => true,
-        ("atom", 0) => true,
-        ("float", 0) => true,
-        ("fun", 1) => true,
-        ("fun", 0) => true,
-        ("integer", 0) => true,
-        ("list", 1) => true,
-        ("maybe_improper_list", 2) => true,
-        ("none", 0) => true,
-        ("nonempty_improper_list", 2) => true,
-        ("nonempty_list", 1) => true,
-        ("pid", 0) => true,
-        ("port", 0) => true,
-        ("reference", 0) => true,
-        ("tuple", 0) => true,
-
-        // This part is from table 7.1 of https://www.erlang.org/doc/reference_manual/typespec.html
-        ("term", 0) => true,
-        ("binary", 0) => true,
-        ("nonempty_binary", 0) => true,
-        ("bitstring", 0) => true,
-        ("nonempty_bitstring", 0) => true,
-        ("boolean", 0) => true,
-        ("byte", 0) => true,
-        ("char", 0) => true,
-        ("nil", 0) => true,
-        ("number", 0) => true,
-        ("list", 0) => true,
-        ("maybe_improper_list", 0) => true,
-        ("nonempty_list", 0) => true,
-        ("string", 0) => true,
-        ("nonempty_string", 0) => true,
-        ("iodata", 0) => true,
-        ("iolist", 0) => true,
-        ("map", 0) => true,
-        ("function", 0) => true,
-        ("module", 0) => true,
-        ("mfa", 0) => true,
-        ("arity", 0) => true,
-        ("identifier", 0) => true,
-        ("node", 0) => true,
-        ("timeout", 0) => true,
-        ("no_return", 0) => true,
+/// The built-in types, which need no definition.
+pub const ERLANG_TYPES: &[(&str, usize)] = &[
+    // This part is from https://www.erlang.org/doc/reference_manual/typespec.html
+    ("any", 0),
+    ("atom", 0),
+    ("float", 0),
+    ("fun", 1),
+    ("fun", 0),
+    ("integer", 0),
+    ("list", 1),
+    ("maybe_improper_list", 2),
+    ("none", 0),
+    ("nonempty_improper_list", 2),
+    ("nonempty_list", 1),
+    ("pid", 0),
+    ("port", 0),
+    ("reference", 0),
+    ("tuple", 0),
+    // This part is from table 7.1 of https://www.erlang.org/doc/reference_manual/typespec.html
+    ("term", 0),
+    ("binary", 0),
+    ("nonempty_binary", 0),
+    ("bitstring", 0),
+    ("nonempty_bitstring", 0),
+    ("boolean", 0),
+    ("byte", 0),
+    ("char", 0),
+    ("nil", 0),
+    ("number", 0),
+    ("neg_integer", 0),
+    ("non_neg_integer", 0),
+    ("pos_integer", 0),
+    ("list", 0),
+    ("maybe_improper_list", 0),
+    ("nonempty_list", 0),
+    ("string", 0),
+    ("nonempty_string", 0),
+    ("iodata", 0),
+    ("iolist", 0),
+    ("map", 0),
+    ("function", 0),
+    ("module", 0),
+    ("mfa", 0),
+    ("arity", 0),
+    ("identifier", 0),
+    ("node", 0),
+    ("timeout", 0),
+    ("no_return", 0),
+    ("dynamic", 0),
+];
 
-        ("dynamic", 0) => true,
-        _ => false,
-    }
+pub fn is_erlang_type(f: &str, a: usize) -> bool {
+    ERLANG_TYPES.contains(&(f, a))
 }
 
 #[cfg(test)]
@@ -269,4 +272,13 @@ mod tests {
         assert!(is_erlang_fun("abs", 1));
         assert!(!is_erlang_fun("foo", 1));
     }
+
+    #[test]
+    fn test_is_erlang_type() {
+        assert!(is_erlang_type("integer", 0));
+        assert!(is_erlang_type("pos_integer", 0));
+        assert!(is_erlang_type("non_neg_integer", 0));
+        assert!(is_erlang_type("neg_integer", 0));
+        assert!(!is_erlang_type("pos_integer", 1));
+    }
 }
@@ -0,0 +1,6 @@
+-module(fix_all).
+
+-export([nested_last/1]).
+
+nested_last(L) ->
+    hd(lists:reverse(hd(lists:reverse(L)))).